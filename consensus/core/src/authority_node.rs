@@ -395,6 +395,7 @@ where
             let observer_service = Arc::new(ObserverService::new(
                 context.clone(),
                 dag_state.clone(),
+                store.clone(),
                 signals_receivers.accepted_block_broadcast_receiver(),
             ));
             network_manager
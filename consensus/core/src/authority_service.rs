@@ -558,47 +558,7 @@ impl<C: CoreThreadDispatcher> ValidatorNetworkService for AuthorityService<C> {
     ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
         fail_point_async!("consensus-rpc-response");
 
-        // Compute an inclusive end index and bound the maximum number of commits scanned.
-        let inclusive_end = commit_range.end().min(
-            commit_range.start() + self.context.parameters.commit_sync_batch_size as CommitIndex
-                - 1,
-        );
-        let mut commits = self
-            .store
-            .scan_commits((commit_range.start()..=inclusive_end).into())?;
-        let mut certifier_block_refs = vec![];
-        'commit: while let Some(c) = commits.last() {
-            let index = c.index();
-            let votes = self.store.read_commit_votes(index)?;
-            let mut stake_aggregator = StakeAggregator::<QuorumThreshold>::new();
-            for v in &votes {
-                stake_aggregator.add(v.author, &self.context.committee);
-            }
-            if stake_aggregator.reached_threshold(&self.context.committee) {
-                certifier_block_refs = votes;
-                break 'commit;
-            } else {
-                debug!(
-                    "Commit {} votes did not reach quorum to certify, {} < {}, skipping",
-                    index,
-                    stake_aggregator.stake(),
-                    stake_aggregator.threshold(&self.context.committee)
-                );
-                self.context
-                    .metrics
-                    .node_metrics
-                    .commit_sync_fetch_commits_handler_uncertified_skipped
-                    .inc();
-                commits.pop();
-            }
-        }
-        let certifier_blocks = self
-            .store
-            .read_blocks(&certifier_block_refs)?
-            .into_iter()
-            .flatten()
-            .collect();
-        Ok((commits, certifier_blocks))
+        read_certified_commits(&self.context, self.store.as_ref(), commit_range)
     }
 
     async fn handle_fetch_latest_blocks(
@@ -702,6 +662,54 @@ impl<C: CoreThreadDispatcher> ObserverNetworkService for AuthorityService<C> {
         ))
     }
 }
+
+/// Reads up to `commit_sync_batch_size` commits from the start of `commit_range`, together with
+/// the blocks voting for the last returned commit. Trailing commits without a quorum of votes
+/// are dropped, so the returned commits can be verified by the requesting peer.
+pub(crate) fn read_certified_commits(
+    context: &Context,
+    store: &dyn Store,
+    commit_range: CommitRange,
+) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
+    // Compute an inclusive end index and bound the maximum number of commits scanned.
+    let inclusive_end = commit_range
+        .end()
+        .min(commit_range.start() + context.parameters.commit_sync_batch_size as CommitIndex - 1);
+    let mut commits = store.scan_commits((commit_range.start()..=inclusive_end).into())?;
+    let mut certifier_block_refs = vec![];
+    'commit: while let Some(c) = commits.last() {
+        let index = c.index();
+        let votes = store.read_commit_votes(index)?;
+        let mut stake_aggregator = StakeAggregator::<QuorumThreshold>::new();
+        for v in &votes {
+            stake_aggregator.add(v.author, &context.committee);
+        }
+        if stake_aggregator.reached_threshold(&context.committee) {
+            certifier_block_refs = votes;
+            break 'commit;
+        } else {
+            debug!(
+                "Commit {} votes did not reach quorum to certify, {} < {}, skipping",
+                index,
+                stake_aggregator.stake(),
+                stake_aggregator.threshold(&context.committee)
+            );
+            context
+                .metrics
+                .node_metrics
+                .commit_sync_fetch_commits_handler_uncertified_skipped
+                .inc();
+            commits.pop();
+        }
+    }
+    let certifier_blocks = store
+        .read_blocks(&certifier_block_refs)?
+        .into_iter()
+        .flatten()
+        .collect();
+    Ok((commits, certifier_blocks))
+}
+
 struct Counter {
     count: usize,
     subscriptions_by_peer: BTreeMap<PeerId, usize>,
@@ -176,14 +176,14 @@ impl TrustedCommit {
         Self::new_trusted(commit, serialized)
     }
 
-    pub(crate) fn reference(&self) -> CommitRef {
+    pub fn reference(&self) -> CommitRef {
         CommitRef {
             index: self.index(),
             digest: self.digest(),
         }
     }
 
-    pub(crate) fn digest(&self) -> CommitDigest {
+    pub fn digest(&self) -> CommitDigest {
         self.digest
     }
 
@@ -800,84 +800,109 @@ impl<VC: ValidatorNetworkClient, OC: ObserverNetworkClient> Inner<VC, OC> {
         serialized_commits: Vec<Bytes>,
         serialized_vote_blocks: Vec<Bytes>,
     ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
-        // Parse and verify commits.
-        let mut commits = Vec::new();
-        for serialized in &serialized_commits {
-            let commit: Commit =
-                bcs::from_bytes(serialized).map_err(ConsensusError::MalformedCommit)?;
-            let digest = TrustedCommit::compute_digest(serialized);
-            if commits.is_empty() {
-                // start is inclusive, so first commit must be at the start index.
-                if commit.index() != commit_range.start() {
-                    return Err(ConsensusError::UnexpectedStartCommit {
-                        peer,
-                        start: commit_range.start(),
-                        commit: Box::new(commit),
-                    });
-                }
-            } else {
-                // Verify next commit increments index and references the previous digest.
-                let (last_commit_digest, last_commit): &(CommitDigest, Commit) =
-                    commits.last().unwrap();
-                if commit.index() != last_commit.index() + 1
-                    || &commit.previous_digest() != last_commit_digest
-                {
-                    return Err(ConsensusError::UnexpectedCommitSequence {
-                        peer,
-                        prev_commit: Box::new(last_commit.clone()),
-                        curr_commit: Box::new(commit),
-                    });
-                }
-            }
-            // Do not process more commits past the end index.
-            if commit.index() > commit_range.end() {
-                break;
-            }
-            commits.push((digest, commit));
-        }
-        let Some((end_commit_digest, end_commit)) = commits.last() else {
-            return Err(ConsensusError::NoCommitReceived { peer });
-        };
+        verify_certified_commits(
+            &self.context,
+            self.block_verifier.as_ref(),
+            Some(&self.transaction_certifier),
+            peer,
+            commit_range,
+            serialized_commits,
+            serialized_vote_blocks,
+        )
+    }
+}
 
-        // Parse and verify blocks. Then accumulate votes on the end commit.
-        let end_commit_ref = CommitRef::new(end_commit.index(), *end_commit_digest);
-        let mut stake_aggregator = StakeAggregator::<QuorumThreshold>::new();
-        let mut vote_blocks = Vec::new();
-        for serialized in serialized_vote_blocks {
-            let block: SignedBlock =
-                bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
-            // Only block signatures need to be verified, to verify commit votes.
-            // But the blocks will be sent to Core, so they need to be fully verified.
-            let (block, reject_transaction_votes) =
-                self.block_verifier.verify_and_vote(block, serialized)?;
-            if self.context.protocol_config.transaction_voting_enabled() {
-                self.transaction_certifier
-                    .add_voted_blocks(vec![(block.clone(), reject_transaction_votes)]);
+/// Verifies commits fetched from `peer` and certifies them using the provided vote blocks for the
+/// last commit. Returns the trusted commits and the votes as verified blocks.
+///
+/// When a transaction certifier is provided, the transaction votes in the vote blocks are added to it.
+pub(crate) fn verify_certified_commits(
+    context: &Context,
+    block_verifier: &dyn BlockVerifier,
+    transaction_certifier: Option<&TransactionCertifier>,
+    peer: AuthorityIndex,
+    commit_range: CommitRange,
+    serialized_commits: Vec<Bytes>,
+    serialized_vote_blocks: Vec<Bytes>,
+) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
+    // Parse and verify commits.
+    let mut commits = Vec::new();
+    for serialized in &serialized_commits {
+        let commit: Commit =
+            bcs::from_bytes(serialized).map_err(ConsensusError::MalformedCommit)?;
+        let digest = TrustedCommit::compute_digest(serialized);
+        if commits.is_empty() {
+            // start is inclusive, so first commit must be at the start index.
+            if commit.index() != commit_range.start() {
+                return Err(ConsensusError::UnexpectedStartCommit {
+                    peer,
+                    start: commit_range.start(),
+                    commit: Box::new(commit),
+                });
             }
-            for vote in block.commit_votes() {
-                if *vote == end_commit_ref {
-                    stake_aggregator.add(block.author(), &self.context.committee);
-                }
+        } else {
+            // Verify next commit increments index and references the previous digest.
+            let (last_commit_digest, last_commit): &(CommitDigest, Commit) =
+                commits.last().unwrap();
+            if commit.index() != last_commit.index() + 1
+                || &commit.previous_digest() != last_commit_digest
+            {
+                return Err(ConsensusError::UnexpectedCommitSequence {
+                    peer,
+                    prev_commit: Box::new(last_commit.clone()),
+                    curr_commit: Box::new(commit),
+                });
             }
-            vote_blocks.push(block);
         }
+        // Do not process more commits past the end index.
+        if commit.index() > commit_range.end() {
+            break;
+        }
+        commits.push((digest, commit));
+    }
+    let Some((end_commit_digest, end_commit)) = commits.last() else {
+        return Err(ConsensusError::NoCommitReceived { peer });
+    };
 
-        // Check if the end commit has enough votes.
-        if !stake_aggregator.reached_threshold(&self.context.committee) {
-            return Err(ConsensusError::NotEnoughCommitVotes {
-                stake: stake_aggregator.stake(),
-                peer,
-                commit: Box::new(end_commit.clone()),
-            });
+    // Parse and verify blocks. Then accumulate votes on the end commit.
+    let end_commit_ref = CommitRef::new(end_commit.index(), *end_commit_digest);
+    let mut stake_aggregator = StakeAggregator::<QuorumThreshold>::new();
+    let mut vote_blocks = Vec::new();
+    for serialized in serialized_vote_blocks {
+        let block: SignedBlock =
+            bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+        // Only block signatures need to be verified, to verify commit votes.
+        // But the blocks will be sent to Core, so they need to be fully verified.
+        let (block, reject_transaction_votes) =
+            block_verifier.verify_and_vote(block, serialized)?;
+        if let Some(transaction_certifier) = transaction_certifier
+            && context.protocol_config.transaction_voting_enabled()
+        {
+            transaction_certifier.add_voted_blocks(vec![(block.clone(), reject_transaction_votes)]);
         }
+        for vote in block.commit_votes() {
+            if *vote == end_commit_ref {
+                stake_aggregator.add(block.author(), &context.committee);
+            }
+        }
+        vote_blocks.push(block);
+    }
 
-        let trusted_commits = commits
-            .into_iter()
-            .zip_debug_eq(serialized_commits)
-            .map(|((_d, c), s)| TrustedCommit::new_trusted(c, s))
-            .collect();
-        Ok((trusted_commits, vote_blocks))
+    // Check if the end commit has enough votes.
+    if !stake_aggregator.reached_threshold(&context.committee) {
+        return Err(ConsensusError::NotEnoughCommitVotes {
+            stake: stake_aggregator.stake(),
+            peer,
+            commit: Box::new(end_commit.clone()),
+        });
     }
+
+    let trusted_commits = commits
+        .into_iter()
+        .zip_debug_eq(serialized_commits)
+        .map(|((_d, c), s)| TrustedCommit::new_trusted(c, s))
+        .collect();
+    Ok((trusted_commits, vote_blocks))
 }
 
 #[cfg(test)]
@@ -1,11 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::SystemTime};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::SystemTime,
+};
 
 use consensus_config::{AuthorityIndex, Committee, ConsensusProtocolConfig, Parameters};
 use consensus_config::{NetworkKeyPair, ProtocolKeyPair};
 use consensus_types::block::BlockTimestampMs;
+use sui_macros::fail_point_arg;
 use tempfile::TempDir;
 use tokio::time::Instant;
 
@@ -128,6 +135,8 @@ pub struct Clock {
     initial_system_time: SystemTime,
     // `clock_drift` should be used only for testing
     clock_drift: BlockTimestampMs,
+    // When set, timestamps are read from this simulated time instead of the system time.
+    simulated_time_ms: Option<Arc<AtomicU64>>,
 }
 
 impl Default for Clock {
@@ -136,6 +145,7 @@ impl Default for Clock {
             initial_instant: Instant::now(),
             initial_system_time: SystemTime::now(),
             clock_drift: 0,
+            simulated_time_ms: None,
         }
     }
 }
@@ -146,6 +156,18 @@ impl Clock {
             initial_instant: Instant::now(),
             initial_system_time: SystemTime::now(),
             clock_drift,
+            simulated_time_ms: None,
+        }
+    }
+
+    /// Creates a clock returning the UNIX timestamp stored in `time_ms`, which is advanced by a
+    /// simulation instead of the system time.
+    pub(crate) fn new_simulated(time_ms: Arc<AtomicU64>) -> Self {
+        Self {
+            initial_instant: Instant::now(),
+            initial_system_time: SystemTime::now(),
+            clock_drift: 0,
+            simulated_time_ms: Some(time_ms),
         }
     }
 
@@ -160,6 +182,19 @@ impl Clock {
             );
         }
 
+        #[allow(unused_mut)]
+        let mut timestamp_ms = match &self.simulated_time_ms {
+            Some(time_ms) => time_ms.load(Ordering::Relaxed) + self.clock_drift,
+            None => self.system_timestamp_ms() + self.clock_drift,
+        };
+        // Lets simulation tests skew the clocks of individual authorities while they run.
+        fail_point_arg!("consensus-clock-skew", |skew_ms: i64| {
+            timestamp_ms = timestamp_ms.saturating_add_signed(skew_ms);
+        });
+        timestamp_ms
+    }
+
+    fn system_timestamp_ms(&self) -> BlockTimestampMs {
         let now: Instant = Instant::now();
         let monotonic_system_time = self
             .initial_system_time
@@ -183,6 +218,5 @@ impl Clock {
                 )
             })
             .as_millis() as BlockTimestampMs
-            + self.clock_drift
     }
 }
@@ -0,0 +1,614 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    ops::RangeInclusive,
+    sync::Arc,
+};
+
+use consensus_config::{AuthorityIndex, Committee, ConsensusProtocolConfig, Parameters};
+use consensus_types::block::{BlockRef, Round};
+use parking_lot::RwLock;
+use prometheus::Registry;
+
+use crate::{
+    block::{BlockAPI as _, GENESIS_ROUND, Slot, VerifiedBlock},
+    commit::{
+        CommitAPI as _, CommitIndex, CommitRange, CommittedSubDag, DecidedLeader, TrustedCommit,
+    },
+    context::{Clock, Context},
+    dag_state::DagState,
+    error::ConsensusResult,
+    leader_schedule::LeaderSchedule,
+    linearizer::Linearizer,
+    metrics::initialise_metrics,
+    storage::{Store, mem_store::MemStore},
+    universal_committer::{
+        UniversalCommitter, universal_committer_builder::UniversalCommitterBuilder,
+    },
+};
+
+/// Number of rounds of blocks read from the source store at once.
+const LOAD_ROUNDS_BATCH: Round = 100;
+
+/// Decision of the commit rule for a leader slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LeaderDecision {
+    /// The leader block was committed in the commit at `commit_index`.
+    Commit {
+        block: BlockRef,
+        direct: bool,
+        commit_index: CommitIndex,
+    },
+    /// The leader slot was skipped.
+    Skip,
+    /// The leader slot is not decided yet by the replayed blocks.
+    Undecided,
+}
+
+/// A leader slot and its decision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayedLeader {
+    pub round: Round,
+    pub authority: AuthorityIndex,
+    pub decision: LeaderDecision,
+}
+
+/// Re-runs the commit rule and the linearizer of a validator offline, over blocks loaded from a
+/// consensus store or built for a test.
+///
+/// Blocks are added round by round, and the leader schedule is updated with reputation scores
+/// in the same way as in `Core`, so the resulting commits are expected to match the commits of
+/// the validators of the epoch. Replays start from the beginning of the epoch, and all replayed
+/// blocks are kept in memory.
+pub struct DagReplay {
+    context: Arc<Context>,
+    dag_state: Arc<RwLock<DagState>>,
+    store: Arc<MemStore>,
+    leader_schedule: Arc<LeaderSchedule>,
+    committer: UniversalCommitter,
+    linearizer: Linearizer,
+    last_decided_leader: Slot,
+    // Decisions of all decided leader slots, by round and authority.
+    decisions: BTreeMap<(Round, AuthorityIndex), LeaderDecision>,
+}
+
+impl DagReplay {
+    pub fn new(
+        epoch_start_timestamp_ms: u64,
+        committee: Committee,
+        mut parameters: Parameters,
+        protocol_config: ConsensusProtocolConfig,
+    ) -> Self {
+        // The replay is not an authority of the committee, but DagState requires an own index.
+        // Blocks of that authority are checked for equivocations, which must be replayed as is.
+        parameters.internal.skip_equivocation_validation = true;
+        let context = Arc::new(Context::new(
+            epoch_start_timestamp_ms,
+            AuthorityIndex::ZERO,
+            committee,
+            parameters,
+            protocol_config,
+            initialise_metrics(Registry::new()),
+            Arc::new(Clock::default()),
+        ));
+        Self::with_context(context)
+    }
+
+    pub(crate) fn with_context(context: Arc<Context>) -> Self {
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let leader_schedule = Arc::new(LeaderSchedule::from_store(
+            context.clone(),
+            dag_state.clone(),
+        ));
+        let number_of_leaders = context.protocol_config.num_leaders_per_round().unwrap_or(1);
+        let committer = UniversalCommitterBuilder::new(
+            context.clone(),
+            leader_schedule.clone(),
+            dag_state.clone(),
+        )
+        .with_number_of_leaders(number_of_leaders)
+        .with_pipeline(true)
+        .build();
+        let linearizer = Linearizer::new(context.clone(), dag_state.clone());
+        let last_decided_leader = dag_state.read().last_commit_leader();
+        Self {
+            context,
+            dag_state,
+            store,
+            leader_schedule,
+            committer,
+            linearizer,
+            last_decided_leader,
+            decisions: BTreeMap::new(),
+        }
+    }
+
+    pub fn committee(&self) -> &Committee {
+        &self.context.committee
+    }
+
+    /// Highest round of the replayed blocks.
+    pub fn highest_round(&self) -> Round {
+        self.dag_state.read().highest_accepted_round()
+    }
+
+    /// Replays the blocks of `store` above the highest replayed round, up to `end_round`.
+    pub fn load_from_store(&mut self, store: &dyn Store, end_round: Round) -> ConsensusResult<()> {
+        let mut start_round = self.highest_round() + 1;
+        while start_round <= end_round {
+            let batch_end_round = end_round.min(start_round + LOAD_ROUNDS_BATCH - 1);
+            let mut blocks_by_round: BTreeMap<Round, Vec<VerifiedBlock>> = BTreeMap::new();
+            for (authority, _) in self.context.committee.authorities() {
+                for block in store.scan_blocks_by_author_in_range(
+                    authority,
+                    start_round,
+                    batch_end_round + 1,
+                    usize::MAX,
+                )? {
+                    blocks_by_round
+                        .entry(block.round())
+                        .or_default()
+                        .push(block);
+                }
+            }
+            for (_, blocks) in blocks_by_round {
+                self.add_blocks(blocks);
+            }
+            start_round = batch_end_round + 1;
+        }
+        Ok(())
+    }
+
+    /// Adds blocks to the replayed DAG and runs the commit rule, returning the new commits.
+    /// The ancestors of the blocks must have been added before.
+    pub fn add_blocks(&mut self, blocks: Vec<VerifiedBlock>) -> Vec<CommittedSubDag> {
+        self.dag_state.write().accept_blocks(blocks);
+        let committed_sub_dags = self.try_commit();
+        self.dag_state.write().flush();
+        committed_sub_dags
+    }
+
+    // Follows `Core::try_commit()`, without certified commits.
+    fn try_commit(&mut self) -> Vec<CommittedSubDag> {
+        let mut committed_sub_dags = vec![];
+        loop {
+            let mut commits_until_update = self
+                .leader_schedule
+                .commits_until_leader_schedule_update(self.dag_state.clone());
+            if commits_until_update == 0 {
+                self.leader_schedule
+                    .update_leader_schedule_v2(&self.dag_state);
+                commits_until_update = self
+                    .leader_schedule
+                    .commits_until_leader_schedule_update(self.dag_state.clone());
+            }
+
+            let mut decided_leaders = self.committer.try_decide(self.last_decided_leader);
+            if decided_leaders.len() >= commits_until_update {
+                let _ = decided_leaders.split_off(commits_until_update);
+            }
+            let Some(last_decided) = decided_leaders.last() else {
+                break;
+            };
+            self.last_decided_leader = last_decided.slot();
+
+            let mut sequenced_leaders = vec![];
+            for leader in decided_leaders {
+                let slot = leader.slot();
+                let decision = match leader {
+                    DecidedLeader::Commit(block, direct) => {
+                        let decision = LeaderDecision::Commit {
+                            block: block.reference(),
+                            direct,
+                            // Set below, after the leader is linearized.
+                            commit_index: 0,
+                        };
+                        sequenced_leaders.push(block);
+                        decision
+                    }
+                    DecidedLeader::Skip(_) => LeaderDecision::Skip,
+                };
+                self.decisions
+                    .insert((slot.round, slot.authority), decision);
+            }
+            if sequenced_leaders.is_empty() {
+                break;
+            }
+
+            let sub_dags = self.linearizer.handle_commit(sequenced_leaders);
+            for sub_dag in &sub_dags {
+                if let Some(LeaderDecision::Commit { commit_index, .. }) = self
+                    .decisions
+                    .get_mut(&(sub_dag.leader.round, sub_dag.leader.author))
+                {
+                    *commit_index = sub_dag.commit_ref.index;
+                }
+            }
+            self.dag_state.write().add_scoring_subdags(sub_dags.clone());
+            committed_sub_dags.extend(sub_dags);
+        }
+        committed_sub_dags
+    }
+
+    /// Reads the replayed commits in `range`.
+    pub fn commits(&self, range: CommitRange) -> ConsensusResult<Vec<TrustedCommit>> {
+        self.store.scan_commits(range)
+    }
+
+    /// Returns the replayed blocks in `rounds`, ordered by round and author.
+    pub fn blocks(&self, rounds: RangeInclusive<Round>) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut blocks = vec![];
+        for (authority, _) in self.context.committee.authorities() {
+            blocks.extend(self.store.scan_blocks_by_author_in_range(
+                authority,
+                *rounds.start(),
+                rounds.end().saturating_add(1),
+                usize::MAX,
+            )?);
+        }
+        blocks.sort_by_key(|block| (block.round(), block.author()));
+        Ok(blocks)
+    }
+
+    /// Returns the leader slots in `rounds` up to the highest replayed round, with their
+    /// decisions. Leaders of undecided rounds are elected with the current leader schedule.
+    pub fn leaders(&self, rounds: RangeInclusive<Round>) -> Vec<ReplayedLeader> {
+        let start_round = (*rounds.start()).max(GENESIS_ROUND + 1);
+        let end_round = (*rounds.end()).min(self.highest_round());
+        let mut leaders = vec![];
+        for round in start_round..=end_round {
+            let decided = self
+                .decisions
+                .range((round, AuthorityIndex::ZERO)..=(round, AuthorityIndex::MAX))
+                .map(|((round, authority), decision)| ReplayedLeader {
+                    round: *round,
+                    authority: *authority,
+                    decision: decision.clone(),
+                })
+                .collect::<Vec<_>>();
+            if !decided.is_empty() || round <= self.last_decided_leader.round {
+                leaders.extend(decided);
+                continue;
+            }
+            leaders.extend(
+                self.committer
+                    .get_leaders(round)
+                    .into_iter()
+                    .map(|authority| ReplayedLeader {
+                        round,
+                        authority,
+                        decision: LeaderDecision::Undecided,
+                    }),
+            );
+        }
+        leaders
+    }
+
+    /// Renders the replayed DAG in `rounds` in the Graphviz DOT format. Blocks are grouped by
+    /// round, and leader slots are colored by their decision: green when committed directly,
+    /// light green when committed indirectly, red when skipped and grey when undecided.
+    pub fn render_dot(&self, rounds: RangeInclusive<Round>) -> ConsensusResult<String> {
+        let blocks = self.blocks(rounds.clone())?;
+        let committed = self.committed_blocks(&rounds)?;
+        let leaders = self
+            .leaders(rounds.clone())
+            .into_iter()
+            .map(|leader| ((leader.round, leader.authority), leader.decision))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut dot = String::new();
+        writeln!(dot, "digraph dag {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [shape=box, style=rounded];").unwrap();
+
+        let mut blocks_by_round: BTreeMap<Round, Vec<&VerifiedBlock>> = BTreeMap::new();
+        for block in &blocks {
+            blocks_by_round
+                .entry(block.round())
+                .or_default()
+                .push(block);
+        }
+        let mut rendered_leaders = BTreeSet::new();
+        for (round, round_blocks) in &blocks_by_round {
+            writeln!(dot, "    subgraph round_{round} {{").unwrap();
+            writeln!(dot, "        rank=same;").unwrap();
+            for block in round_blocks {
+                let block_ref = block.reference();
+                let hostname = &self.context.committee.authority(block.author()).hostname;
+                let mut label = format!("{hostname}\\nround {round}");
+                if let Some(commit_index) = committed.get(&block_ref) {
+                    write!(label, "\\ncommit {commit_index}").unwrap();
+                }
+                let mut attributes = vec![];
+                if let Some(decision) = leaders.get(&(block.round(), block.author())) {
+                    rendered_leaders.insert((block.round(), block.author()));
+                    let color = match decision {
+                        LeaderDecision::Commit {
+                            block: leader,
+                            direct,
+                            ..
+                        } if *leader == block_ref => {
+                            label.push_str(if *direct { "\\ndirect" } else { "\\nindirect" });
+                            if *direct { "green" } else { "palegreen" }
+                        }
+                        LeaderDecision::Commit { .. } => "white",
+                        LeaderDecision::Skip => {
+                            label.push_str("\\nskipped");
+                            "salmon"
+                        }
+                        LeaderDecision::Undecided => "lightgrey",
+                    };
+                    attributes.push(format!("style=\"rounded,filled,bold\", fillcolor={color}"));
+                } else if !committed.contains_key(&block_ref) {
+                    attributes.push("style=\"rounded,dashed\"".to_string());
+                }
+                attributes.push(format!("label=\"{label}\""));
+                writeln!(
+                    dot,
+                    "        \"{}\" [{}];",
+                    block_ref,
+                    attributes.join(", ")
+                )
+                .unwrap();
+            }
+            // Leader slots without blocks.
+            for ((leader_round, authority), decision) in
+                leaders.range((*round, AuthorityIndex::ZERO)..=(*round, AuthorityIndex::MAX))
+            {
+                if rendered_leaders.contains(&(*leader_round, *authority)) {
+                    continue;
+                }
+                let hostname = &self.context.committee.authority(*authority).hostname;
+                let status = if *decision == LeaderDecision::Skip {
+                    "skipped"
+                } else {
+                    "undecided"
+                };
+                writeln!(
+                    dot,
+                    "        \"{}\" [style=\"rounded,dashed\", label=\"{hostname}\\nround {round}\\nmissing leader, {status}\"];",
+                    Slot::new(*leader_round, *authority)
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+
+        // Edges to ancestors outside of the rendered rounds are omitted.
+        for block in &blocks {
+            for ancestor in block.ancestors() {
+                if rounds.contains(&ancestor.round) {
+                    writeln!(dot, "    \"{}\" -> \"{}\";", ancestor, block.reference()).unwrap();
+                }
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+        Ok(dot)
+    }
+
+    /// Renders the replayed DAG in `rounds` in the format of the DAG parser used by tests, so the
+    /// DAG can be turned into a test. Rounds below `rounds` are rendered as fully connected.
+    ///
+    /// The format describes the DAG at the level of slots, with authorities named by letters,
+    /// so it cannot describe the stake of authorities, and blocks of equivocating authorities
+    /// are rendered as a single block.
+    ///
+    /// Panics if the committee has more than 26 authorities.
+    pub fn render_dag_dsl(&self, rounds: RangeInclusive<Round>) -> ConsensusResult<String> {
+        let committee_size = self.context.committee.size();
+        assert!(
+            committee_size <= 26,
+            "The DAG format supports up to 26 authorities"
+        );
+        let start_round = (*rounds.start()).max(GENESIS_ROUND + 1);
+        let blocks = self.blocks(start_round..=*rounds.end())?;
+
+        let mut dsl = String::new();
+        writeln!(dsl, "DAG {{").unwrap();
+        writeln!(dsl, "    Round 0 : {{ {committee_size} }},").unwrap();
+        for round in GENESIS_ROUND + 1..start_round {
+            writeln!(dsl, "    Round {round} : {{ * }},").unwrap();
+        }
+
+        // Ancestors of each slot, merged over the blocks of equivocating authorities.
+        let mut ancestors_by_round: BTreeMap<
+            Round,
+            BTreeMap<AuthorityIndex, BTreeSet<(Round, AuthorityIndex)>>,
+        > = BTreeMap::new();
+        for block in &blocks {
+            ancestors_by_round
+                .entry(block.round())
+                .or_default()
+                .entry(block.author())
+                .or_default()
+                .extend(
+                    block
+                        .ancestors()
+                        .iter()
+                        .map(|ancestor| (ancestor.round, ancestor.author)),
+                );
+        }
+
+        let all_authorities = self
+            .context
+            .committee
+            .authorities()
+            .map(|(index, _)| index)
+            .collect::<BTreeSet<_>>();
+        let mut previous_authorities = all_authorities.clone();
+        for round in start_round..=*rounds.end() {
+            let slots = ancestors_by_round.remove(&round).unwrap_or_default();
+            let mut lines = vec![];
+            for (authority, ancestors) in &slots {
+                let previous = ancestors
+                    .iter()
+                    .filter(|(ancestor_round, _)| ancestor_round + 1 == round)
+                    .map(|(_, authority)| *authority)
+                    .collect::<BTreeSet<_>>();
+                let mut connections = vec![];
+                if !previous.is_empty() && previous == previous_authorities {
+                    connections.push("*".to_string());
+                } else if previous.len() + 1 == previous_authorities.len()
+                    && previous.is_subset(&previous_authorities)
+                {
+                    // The parser only supports excluding a single slot.
+                    let excluded = previous_authorities.difference(&previous).next().unwrap();
+                    connections.push(format!("-{}", dsl_slot(round - 1, *excluded)));
+                } else {
+                    connections.extend(previous.iter().map(|a| dsl_slot(round - 1, *a)));
+                }
+                connections.extend(
+                    ancestors
+                        .iter()
+                        .filter(|(ancestor_round, _)| ancestor_round + 1 < round)
+                        .map(|(ancestor_round, authority)| dsl_slot(*ancestor_round, *authority)),
+                );
+                lines.push((dsl_authority(*authority), connections));
+            }
+
+            let authorities = slots.keys().copied().collect::<BTreeSet<_>>();
+            if authorities == all_authorities
+                && lines
+                    .iter()
+                    .all(|(_, connections)| connections.len() == 1 && connections[0] == "*")
+            {
+                writeln!(dsl, "    Round {round} : {{ * }},").unwrap();
+            } else {
+                writeln!(dsl, "    Round {round} : {{").unwrap();
+                for (author, connections) in lines {
+                    writeln!(dsl, "        {author} -> [{}],", connections.join(", ")).unwrap();
+                }
+                writeln!(dsl, "    }},").unwrap();
+            }
+            previous_authorities = authorities;
+        }
+        writeln!(dsl, "}}").unwrap();
+        Ok(dsl)
+    }
+
+    // Returns the index of the commit of each committed block in `rounds`.
+    fn committed_blocks(
+        &self,
+        rounds: &RangeInclusive<Round>,
+    ) -> ConsensusResult<BTreeMap<BlockRef, CommitIndex>> {
+        let last_commit_index = self.dag_state.read().last_commit_index();
+        let mut committed = BTreeMap::new();
+        for commit in self.commits((1..=last_commit_index).into())? {
+            // Blocks in the rounds can only be committed by leaders of higher rounds.
+            if commit.leader().round < *rounds.start() {
+                continue;
+            }
+            for block_ref in commit.blocks() {
+                if rounds.contains(&block_ref.round) {
+                    committed.insert(*block_ref, commit.index());
+                }
+            }
+        }
+        Ok(committed)
+    }
+}
+
+fn dsl_authority(authority: AuthorityIndex) -> char {
+    (b'A' + authority.value() as u8) as char
+}
+
+fn dsl_slot(round: Round, authority: AuthorityIndex) -> String {
+    format!("{}{round}", dsl_authority(authority))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_dag_builder::DagBuilder, test_dag_parser::parse_dag};
+
+    #[tokio::test]
+    async fn test_replay_and_render() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+
+        // The leader of round 5 is not voted for, so it is skipped directly.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=5).build();
+        dag_builder.layer(6).no_leader_link(5, vec![]);
+        dag_builder.layers(7..=10).build();
+
+        let mut replay = DagReplay::with_context(context);
+        let mut commits = vec![];
+        for round in 1..=10 {
+            commits.extend(replay.add_blocks(dag_builder.blocks(round..=round)));
+        }
+        assert_eq!(replay.highest_round(), 10);
+
+        let leaders = replay.leaders(1..=10);
+        assert_eq!(leaders.len(), 10);
+        let mut commit_index = 0;
+        for leader in &leaders {
+            match leader.round {
+                5 => assert_eq!(leader.decision, LeaderDecision::Skip),
+                9 | 10 => assert_eq!(leader.decision, LeaderDecision::Undecided),
+                _ => {
+                    commit_index += 1;
+                    let LeaderDecision::Commit {
+                        block,
+                        direct,
+                        commit_index: index,
+                    } = &leader.decision
+                    else {
+                        panic!("Expected leader {leader:?} to be committed");
+                    };
+                    assert_eq!(block.author, leader.authority);
+                    assert!(*direct);
+                    assert_eq!(*index, commit_index);
+                }
+            }
+        }
+        assert_eq!(commits.len(), 7);
+        assert_eq!(
+            replay
+                .commits((1..=7).into())
+                .unwrap()
+                .iter()
+                .map(|commit| commit.reference())
+                .collect::<Vec<_>>(),
+            commits
+                .iter()
+                .map(|sub_dag| sub_dag.commit_ref)
+                .collect::<Vec<_>>()
+        );
+
+        let dot = replay.render_dot(4..=6).unwrap();
+        assert_eq!(dot.matches("subgraph").count(), 3);
+        assert_eq!(dot.matches("salmon").count(), 1);
+
+        // The rendered DAG is parsed into the same DAG at the level of slots.
+        let dsl = replay.render_dag_dsl(4..=10).unwrap();
+        let (_, parsed) = parse_dag(&dsl).expect("Rendered DAG should be parsed");
+        let slots = |blocks: Vec<VerifiedBlock>| {
+            blocks
+                .iter()
+                .map(|block| {
+                    (
+                        Slot::from(block.reference()),
+                        block
+                            .ancestors()
+                            .iter()
+                            .map(|ancestor| Slot::from(*ancestor))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut expected = slots(dag_builder.blocks(4..=10));
+        let mut actual = slots(parsed.blocks(4..=10));
+        for (_, ancestors) in expected.iter_mut().chain(actual.iter_mut()) {
+            ancestors.sort_by_key(|slot| (slot.round, slot.authority));
+        }
+        expected.sort_by_key(|(slot, _)| (slot.round, slot.authority));
+        actual.sort_by_key(|(slot, _)| (slot.round, slot.authority));
+        assert_eq!(actual, expected);
+    }
+}
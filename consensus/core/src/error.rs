@@ -190,6 +190,9 @@ pub enum ConsensusError {
 
     #[error("Consensus has shut down!")]
     Shutdown,
+
+    #[error("Invalid simulation config: {0}")]
+    InvalidSimulationConfig(String),
 }
 
 impl ConsensusError {
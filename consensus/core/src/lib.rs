@@ -18,6 +18,7 @@ mod commit_vote_monitor;
 mod context;
 mod core;
 mod core_thread;
+mod dag_replay;
 mod dag_state;
 mod error;
 mod leader_schedule;
@@ -26,10 +27,12 @@ mod leader_timeout;
 mod linearizer;
 mod metrics;
 mod network;
+mod observer_client;
 mod observer_service;
 mod proposed_block_handler;
 mod round_prober;
 mod round_tracker;
+mod simulator;
 mod stake_aggregator;
 pub mod storage;
 mod subscriber;
@@ -61,11 +64,21 @@ pub use block::{TestBlock, Transaction, VerifiedBlock};
 pub use commit::{CommitAPI, CommitDigest, CommitIndex, CommitRange, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumerArgs, CommitConsumerMonitor};
 pub use context::Clock;
+pub use dag_replay::{DagReplay, LeaderDecision, ReplayedLeader};
 pub use metrics::Metrics;
+pub use simulator::{
+    AuthorityBehavior, AuthorityReport, LatencyStats, NetworkConfig, ReputationSnapshot,
+    SimulationConfig, SimulationReport, Simulator,
+};
 pub use transaction::{
     BlockStatus, ClientError, TransactionClient, TransactionVerifier, ValidationError,
 };
 
+/// Exported API for observers.
+pub use commit::TrustedCommit;
+pub use error::{ConsensusError, ConsensusResult};
+pub use observer_client::{ObservedBlock, ObservedBlockStream, ObserverClient};
+
 // Exported API for benchmarking
 pub use block_verifier::{BlockVerifier, NoopBlockVerifier};
 pub use commit_finalizer::CommitFinalizer;
@@ -13,14 +13,17 @@ use parking_lot::RwLock;
 use tokio_stream::Iter;
 use tonic::{Request, Response, Streaming};
 use tower_http::trace::{DefaultMakeSpan, DefaultOnFailure, TraceLayer};
-use tracing::{debug, trace};
+use tracing::{debug, info, trace, warn};
 
 use crate::{
     CommitRange, Context,
     error::{ConsensusError, ConsensusResult},
     network::{
-        ObserverBlockStream, ObserverNetworkClient, PeerId, metrics_layer::MetricsCallbackMaker,
-        observer::block_stream_request::Command, to_host_port_str, tonic_network::Channel,
+        ObserverBlockStream, ObserverNetworkClient, PeerId,
+        metrics_layer::MetricsCallbackMaker,
+        observer::block_stream_request::Command,
+        to_host_port_str,
+        tonic_network::{Channel, MAX_FETCH_RESPONSE_BYTES, chunk_blocks},
         tonic_tls::certificate_server_name,
     },
 };
@@ -94,7 +97,6 @@ pub(crate) struct FetchCommitsResponse {
 /// Information about an observer peer connection, set in request extensions by the server.
 #[derive(Clone, Debug)]
 pub(crate) struct ObserverPeerInfo {
-    #[allow(unused)]
     pub(crate) public_key: NetworkPublicKey,
 }
 
@@ -227,7 +229,6 @@ impl TonicObserverClient {
         }
     }
 
-    #[allow(unused)]
     async fn get_client(
         &self,
         peer: PeerId,
@@ -295,26 +296,92 @@ impl ObserverNetworkClient for TonicObserverClient {
 
     async fn fetch_blocks(
         &self,
-        _peer: PeerId,
-        _block_refs: Vec<BlockRef>,
-        _timeout: Duration,
+        peer: PeerId,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
     ) -> ConsensusResult<Vec<Bytes>> {
-        // TODO: Implement block fetching for observers
-        Err(ConsensusError::NetworkRequest(
-            "fetch_blocks not yet implemented".to_string(),
-        ))
+        let mut client = self.get_client(peer, timeout).await?;
+        let mut request = Request::new(FetchBlocksRequest {
+            block_refs: block_refs
+                .iter()
+                .filter_map(|r| match bcs::to_bytes(r) {
+                    Ok(serialized) => Some(serialized),
+                    Err(e) => {
+                        debug!("Failed to serialize block ref {:?}: {e:?}", r);
+                        None
+                    }
+                })
+                .collect(),
+        });
+        request.set_timeout(timeout);
+
+        let mut stream = client
+            .fetch_blocks(request)
+            .await
+            .map_err(|e| {
+                if e.code() == tonic::Code::DeadlineExceeded {
+                    ConsensusError::NetworkRequestTimeout(format!("fetch_blocks failed: {e:?}"))
+                } else {
+                    ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}"))
+                }
+            })?
+            .into_inner();
+
+        // Allow twice the max total size of transactions in the fetched blocks.
+        let max_allowed_bytes = block_refs.len()
+            * self
+                .context
+                .protocol_config
+                .max_transactions_in_block_bytes() as usize
+            * 2;
+        let mut blocks = vec![];
+        let mut total_fetched_bytes = 0;
+        loop {
+            match stream.message().await {
+                Ok(Some(response)) => {
+                    total_fetched_bytes += response.blocks.iter().map(|b| b.len()).sum::<usize>();
+                    blocks.extend(response.blocks);
+                    if total_fetched_bytes > max_allowed_bytes {
+                        info!(
+                            "fetch_blocks() fetched bytes exceeded limit: {} > {}, terminating stream.",
+                            total_fetched_bytes, max_allowed_bytes,
+                        );
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    if blocks.is_empty() {
+                        return Err(ConsensusError::NetworkRequest(format!(
+                            "fetch_blocks failed mid-stream: {e:?}"
+                        )));
+                    }
+                    warn!("fetch_blocks failed mid-stream: {e:?}");
+                    break;
+                }
+            }
+        }
+        Ok(blocks)
     }
 
     async fn fetch_commits(
         &self,
-        _peer: PeerId,
-        _commit_range: CommitRange,
-        _timeout: Duration,
+        peer: PeerId,
+        commit_range: CommitRange,
+        timeout: Duration,
     ) -> ConsensusResult<(Vec<Bytes>, Vec<Bytes>)> {
-        // TODO: Implement commit fetching for observers
-        Err(ConsensusError::NetworkRequest(
-            "fetch_commits not yet implemented".to_string(),
-        ))
+        let mut client = self.get_client(peer, timeout).await?;
+        let mut request = Request::new(FetchCommitsRequest {
+            start: commit_range.start(),
+            end: commit_range.end(),
+        });
+        request.set_timeout(timeout);
+        let response = client
+            .fetch_commits(request)
+            .await
+            .map_err(|e| ConsensusError::NetworkRequest(format!("fetch_commits failed: {e:?}")))?;
+        let response = response.into_inner();
+        Ok((response.commits, response.certifier_blocks))
     }
 }
 
@@ -353,15 +420,7 @@ impl<S: ObserverNetworkService> ObserverService for ObserverServiceProxy<S> {
         &self,
         request: Request<Streaming<BlockStreamRequest>>,
     ) -> Result<Response<Self::StreamBlocksStream>, tonic::Status> {
-        let peer_id = request
-            .extensions()
-            .get::<ObserverPeerInfo>()
-            .map(|info| info.public_key.clone())
-            .ok_or_else(|| {
-                tonic::Status::unauthenticated(
-                    "Observer peer info not found in request. TLS authentication required.",
-                )
-            })?;
+        let peer_id = observer_peer_id(&request)?;
 
         let mut request_stream = request.into_inner();
         let first_request = match request_stream.next().await {
@@ -404,25 +463,74 @@ impl<S: ObserverNetworkService> ObserverService for ObserverServiceProxy<S> {
 
     async fn fetch_blocks(
         &self,
-        _request: Request<FetchBlocksRequest>,
+        request: Request<FetchBlocksRequest>,
     ) -> Result<Response<Self::FetchBlocksStream>, tonic::Status> {
-        // TODO: Implement fetch_blocks for observer nodes
-        Err(tonic::Status::unimplemented(
-            "fetch_blocks not yet implemented for observers",
-        ))
+        let peer_id = observer_peer_id(&request)?;
+        let block_refs = request
+            .into_inner()
+            .block_refs
+            .into_iter()
+            .filter_map(|serialized| match bcs::from_bytes(&serialized) {
+                Ok(r) => Some(r),
+                Err(e) => {
+                    debug!("Failed to deserialize block ref {:?}: {e:?}", serialized);
+                    None
+                }
+            })
+            .collect();
+        let blocks = self
+            .service
+            .handle_fetch_blocks(peer_id, block_refs)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let responses: std::vec::IntoIter<Result<FetchBlocksResponse, tonic::Status>> =
+            chunk_blocks(blocks, MAX_FETCH_RESPONSE_BYTES)
+                .into_iter()
+                .map(|blocks| Ok(FetchBlocksResponse { blocks }))
+                .collect::<Vec<_>>()
+                .into_iter();
+        Ok(Response::new(tokio_stream::iter(responses)))
     }
 
     async fn fetch_commits(
         &self,
-        _request: Request<FetchCommitsRequest>,
+        request: Request<FetchCommitsRequest>,
     ) -> Result<Response<FetchCommitsResponse>, tonic::Status> {
-        // TODO: Implement fetch_commits for observer nodes
-        Err(tonic::Status::unimplemented(
-            "fetch_commits not yet implemented for observers",
-        ))
+        let peer_id = observer_peer_id(&request)?;
+        let request = request.into_inner();
+        let (commits, certifier_blocks) = self
+            .service
+            .handle_fetch_commits(peer_id, (request.start..=request.end).into())
+            .await
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let commits = commits
+            .into_iter()
+            .map(|c| c.serialized().clone())
+            .collect();
+        let certifier_blocks = certifier_blocks
+            .into_iter()
+            .map(|b| b.serialized().clone())
+            .collect();
+        Ok(Response::new(FetchCommitsResponse {
+            commits,
+            certifier_blocks,
+        }))
     }
 }
 
+/// Returns the public key of the observer peer authenticated with TLS.
+fn observer_peer_id<T>(request: &Request<T>) -> Result<NetworkPublicKey, tonic::Status> {
+    request
+        .extensions()
+        .get::<ObserverPeerInfo>()
+        .map(|info| info.public_key.clone())
+        .ok_or_else(|| {
+            tonic::Status::unauthenticated(
+                "Observer peer info not found in request. TLS authentication required.",
+            )
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use consensus_config::{
+    AuthorityIndex, Committee, ConsensusProtocolConfig, NetworkKeyPair, Parameters,
+};
+use consensus_types::block::{BlockRef, Round, TransactionIndex};
+use futures::{Stream, StreamExt as _};
+use prometheus::Registry;
+
+use crate::{
+    block::{SignedBlock, VerifiedBlock},
+    block_verifier::{BlockVerifier, SignedBlockVerifier},
+    commit::{CommitIndex, CommitRange, TrustedCommit},
+    commit_syncer::verify_certified_commits,
+    context::{Clock, Context},
+    error::{ConsensusError, ConsensusResult},
+    metrics::initialise_metrics,
+    network::{ObserverNetworkClient as _, PeerId, observer::TonicObserverClient},
+    transaction::{TransactionVerifier, ValidationError},
+};
+
+/// A block accepted by the observed validator, with the highest commit index produced by the
+/// validator when the block was sent.
+#[derive(Clone, Debug)]
+pub struct ObservedBlock {
+    pub block: VerifiedBlock,
+    pub highest_commit_index: CommitIndex,
+}
+
+/// A stream of verified blocks from an observer server.
+pub type ObservedBlockStream = Pin<Box<dyn Stream<Item = ConsensusResult<ObservedBlock>> + Send>>;
+
+/// Client of the observer servers of validators, for nodes outside of the committee.
+///
+/// All blocks and commits returned by the client are verified against the committee: blocks must
+/// be correctly signed by their authors, and the last fetched commit must be voted by a quorum.
+/// The validator to connect to must be configured in `parameters.tonic.observer_peers`.
+pub struct ObserverClient {
+    context: Arc<Context>,
+    block_verifier: Arc<SignedBlockVerifier>,
+    network_client: TonicObserverClient,
+}
+
+impl ObserverClient {
+    pub fn new(
+        epoch_start_timestamp_ms: u64,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ConsensusProtocolConfig,
+        network_keypair: NetworkKeyPair,
+        registry: Registry,
+    ) -> Self {
+        // Observers are not part of the committee, so they use an index outside of it.
+        let context = Arc::new(Context::new(
+            epoch_start_timestamp_ms,
+            AuthorityIndex::MAX,
+            committee,
+            parameters,
+            protocol_config,
+            initialise_metrics(registry),
+            Arc::new(Clock::default()),
+        ));
+        let block_verifier = Arc::new(SignedBlockVerifier::new(
+            context.clone(),
+            Arc::new(ObserverTransactionVerifier),
+        ));
+        let network_client = TonicObserverClient::new(context.clone(), network_keypair);
+        Self {
+            context,
+            block_verifier,
+            network_client,
+        }
+    }
+
+    pub fn committee(&self) -> &Committee {
+        &self.context.committee
+    }
+
+    /// Streams the blocks accepted by `peer` after `highest_round_per_authority`. The stream ends
+    /// after the first block failing verification, with the verification error.
+    pub async fn stream_blocks(
+        &self,
+        peer: AuthorityIndex,
+        highest_round_per_authority: Vec<Round>,
+        timeout: Duration,
+    ) -> ConsensusResult<ObservedBlockStream> {
+        let stream = self
+            .network_client
+            .stream_blocks(
+                PeerId::Validator(peer),
+                highest_round_per_authority
+                    .into_iter()
+                    .map(|round| round as u64)
+                    .collect(),
+                timeout,
+            )
+            .await?;
+        let block_verifier = self.block_verifier.clone();
+        let stream = stream
+            .map(move |item| {
+                let block = verify_block(block_verifier.as_ref(), item.block)?;
+                Ok(ObservedBlock {
+                    block,
+                    highest_commit_index: item.highest_commit_index as CommitIndex,
+                })
+            })
+            .scan(false, |failed, result| {
+                let item = (!*failed).then(|| {
+                    *failed = result.is_err();
+                    result
+                });
+                futures::future::ready(item)
+            });
+        Ok(Box::pin(stream))
+    }
+
+    /// Fetches blocks from `peer`. Only the requested blocks are returned, in no particular
+    /// order, and blocks that `peer` does not have are omitted.
+    pub async fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let serialized_blocks = self
+            .network_client
+            .fetch_blocks(PeerId::Validator(peer), block_refs.clone(), timeout)
+            .await?;
+        let mut blocks = Vec::with_capacity(serialized_blocks.len());
+        for serialized in serialized_blocks {
+            let block = verify_block(self.block_verifier.as_ref(), serialized)?;
+            if !block_refs.contains(&block.reference()) {
+                return Err(ConsensusError::UnexpectedFetchedBlock {
+                    index: peer,
+                    block_ref: block.reference(),
+                });
+            }
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Fetches certified commits in `commit_range` from `peer`, along with the blocks voting for
+    /// the last returned commit. Fewer commits than requested can be returned.
+    pub async fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+        timeout: Duration,
+    ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
+        let (serialized_commits, serialized_vote_blocks) = self
+            .network_client
+            .fetch_commits(PeerId::Validator(peer), commit_range.clone(), timeout)
+            .await?;
+        verify_certified_commits(
+            &self.context,
+            self.block_verifier.as_ref(),
+            None,
+            peer,
+            commit_range,
+            serialized_commits,
+            serialized_vote_blocks,
+        )
+    }
+}
+
+fn verify_block(
+    block_verifier: &dyn BlockVerifier,
+    serialized: Bytes,
+) -> ConsensusResult<VerifiedBlock> {
+    let signed_block: SignedBlock =
+        bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+    let (block, _) = block_verifier.verify_and_vote(signed_block, serialized)?;
+    Ok(block)
+}
+
+/// Observers do not vote on transactions, so transactions are accepted as is.
+struct ObserverTransactionVerifier;
+
+impl TransactionVerifier for ObserverTransactionVerifier {
+    fn verify_batch(&self, _batch: &[&[u8]]) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    fn verify_and_vote_batch(
+        &self,
+        _block_ref: &BlockRef,
+        _batch: &[&[u8]],
+    ) -> Result<Vec<TransactionIndex>, ValidationError> {
+        Ok(vec![])
+    }
+}
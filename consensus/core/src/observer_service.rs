@@ -5,13 +5,13 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use consensus_types::block::BlockRef;
+use consensus_types::block::{BlockRef, GENESIS_ROUND};
 use futures::{StreamExt as _, stream};
 use parking_lot::RwLock;
 use tokio::sync::broadcast;
 
 use crate::{
-    authority_service::{BroadcastStream, SubscriptionCounter},
+    authority_service::{BroadcastStream, SubscriptionCounter, read_certified_commits},
     block::{BlockAPI as _, VerifiedBlock},
     commit::{CommitIndex, CommitRange, TrustedCommit},
     context::Context,
@@ -20,6 +20,7 @@ use crate::{
     network::{
         NodeId, ObserverBlockStream, ObserverBlockStreamItem, ObserverNetworkService, PeerId,
     },
+    storage::Store,
 };
 
 /// Serves observer requests from observer or validator peers. It is the server-side
@@ -27,6 +28,7 @@ use crate::{
 pub(crate) struct ObserverService {
     context: Arc<Context>,
     dag_state: Arc<RwLock<DagState>>,
+    store: Arc<dyn Store>,
     rx_accepted_block_broadcast: broadcast::Receiver<(VerifiedBlock, CommitIndex)>,
     subscription_counter: Arc<SubscriptionCounter>,
 }
@@ -35,12 +37,14 @@ impl ObserverService {
     pub(crate) fn new(
         context: Arc<Context>,
         dag_state: Arc<RwLock<DagState>>,
+        store: Arc<dyn Store>,
         rx_accepted_block_broadcast: broadcast::Receiver<(VerifiedBlock, CommitIndex)>,
     ) -> Self {
         let subscription_counter = Arc::new(SubscriptionCounter::new(context.clone()));
         Self {
             context,
             dag_state,
+            store,
             rx_accepted_block_broadcast,
             subscription_counter,
         }
@@ -103,24 +107,40 @@ impl ObserverNetworkService for ObserverService {
     async fn handle_fetch_blocks(
         &self,
         _peer: NodeId,
-        _block_refs: Vec<BlockRef>,
+        mut block_refs: Vec<BlockRef>,
     ) -> ConsensusResult<Vec<Bytes>> {
-        // TODO: implement observer fetch blocks, similar to validator fetch_blocks but
-        // without highest_accepted_rounds.
-        Err(ConsensusError::NetworkRequest(
-            "Observer fetch blocks not yet implemented".to_string(),
-        ))
+        // Observers have no DAG to sync missing ancestors against, so only the requested blocks
+        // are returned.
+        block_refs.truncate(self.context.parameters.max_blocks_per_fetch);
+        for block_ref in &block_refs {
+            if !self.context.committee.is_valid_index(block_ref.author) {
+                return Err(ConsensusError::InvalidAuthorityIndex {
+                    index: block_ref.author,
+                    max: self.context.committee.size(),
+                });
+            }
+            if block_ref.round == GENESIS_ROUND {
+                return Err(ConsensusError::UnexpectedGenesisBlockRequested);
+            }
+        }
+
+        let blocks = self
+            .dag_state
+            .read()
+            .get_blocks(&block_refs)
+            .into_iter()
+            .flatten()
+            .map(|block| block.serialized().clone())
+            .collect();
+        Ok(blocks)
     }
 
     async fn handle_fetch_commits(
         &self,
         _peer: NodeId,
-        _commit_range: CommitRange,
+        commit_range: CommitRange,
     ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
-        // TODO: implement observer fetch commits, similar to validator fetch_commits.
-        Err(ConsensusError::NetworkRequest(
-            "Observer fetch commits not yet implemented".to_string(),
-        ))
+        read_certified_commits(&self.context, self.store.as_ref(), commit_range)
     }
 }
 
@@ -135,8 +155,10 @@ mod tests {
     use super::*;
     use crate::{
         block::{TestBlock, VerifiedBlock},
+        commit::{CommitDigest, CommitRef},
         context::Context,
-        storage::mem_store::MemStore,
+        storage::{WriteBatch, mem_store::MemStore},
+        test_dag_builder::DagBuilder,
     };
 
     #[tokio::test]
@@ -146,12 +168,13 @@ mod tests {
         let context = Arc::new(context);
 
         let store = Arc::new(MemStore::new());
-        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
 
         let (tx_accepted_block, rx_accepted_block) =
             broadcast::channel::<(VerifiedBlock, CommitIndex)>(100);
 
-        let observer_service = ObserverService::new(context.clone(), dag_state, rx_accepted_block);
+        let observer_service =
+            ObserverService::new(context.clone(), dag_state, store, rx_accepted_block);
 
         // Observer starts with no blocks seen
         let highest_round_per_authority = vec![0u64; context.committee.size()];
@@ -201,12 +224,13 @@ mod tests {
         let context = Arc::new(context);
 
         let store = Arc::new(MemStore::new());
-        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
 
         let (_tx_accepted_block, rx_accepted_block) =
             broadcast::channel::<(VerifiedBlock, CommitIndex)>(100);
 
-        let observer_service = ObserverService::new(context.clone(), dag_state, rx_accepted_block);
+        let observer_service =
+            ObserverService::new(context.clone(), dag_state, store, rx_accepted_block);
 
         let peer = keys[0].0.public().clone();
 
@@ -228,4 +252,125 @@ mod tests {
             Ok(_) => panic!("Expected error, got Ok"),
         }
     }
+
+    #[tokio::test]
+    async fn test_observer_fetch_blocks() {
+        telemetry_subscribers::init_for_testing();
+        let (mut context, keys) = Context::new_for_test(4);
+        context.parameters.max_blocks_per_fetch = 3;
+        let context = Arc::new(context);
+
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let (_tx_accepted_block, rx_accepted_block) = broadcast::channel(100);
+        let observer_service =
+            ObserverService::new(context.clone(), dag_state.clone(), store, rx_accepted_block);
+        let peer = keys[0].0.public().clone();
+
+        // GIVEN: 5 rounds of blocks, flushed to the store.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder
+            .layers(1..=5)
+            .build()
+            .persist_layers(dag_state.clone());
+        dag_state.write().flush();
+
+        // WHEN: requesting more blocks than allowed per fetch.
+        let requested = dag_builder
+            .blocks(4..=5)
+            .iter()
+            .map(|b| b.reference())
+            .collect::<Vec<_>>();
+        let blocks = observer_service
+            .handle_fetch_blocks(peer.clone(), requested.clone())
+            .await
+            .unwrap();
+
+        // THEN: only the first requested blocks are returned, and no ancestors.
+        let received = blocks
+            .iter()
+            .map(|b| {
+                let signed = bcs::from_bytes(b).unwrap();
+                VerifiedBlock::new_verified(signed, b.clone()).reference()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(received, requested[..3]);
+
+        // Genesis blocks cannot be requested.
+        let genesis = dag_builder.genesis_block_refs();
+        assert!(matches!(
+            observer_service.handle_fetch_blocks(peer, genesis).await,
+            Err(ConsensusError::UnexpectedGenesisBlockRequested)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_observer_fetch_commits() {
+        telemetry_subscribers::init_for_testing();
+        let (context, keys) = Context::new_for_test(4);
+        let context = Arc::new(context);
+
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let (_tx_accepted_block, rx_accepted_block) = broadcast::channel(100);
+        let observer_service =
+            ObserverService::new(context.clone(), dag_state, store.clone(), rx_accepted_block);
+        let peer = keys[0].0.public().clone();
+
+        // GIVEN: 3 chained commits, where only commit 2 is voted by a quorum of blocks.
+        let leader = VerifiedBlock::new_for_test(TestBlock::new(1, 0).build());
+        let mut commits = vec![];
+        let mut previous_digest = CommitDigest::MIN;
+        for index in 1..=3 {
+            let commit = TrustedCommit::new_for_test(
+                index,
+                previous_digest,
+                index as u64,
+                leader.reference(),
+                vec![leader.reference()],
+            );
+            previous_digest = commit.digest();
+            commits.push(commit);
+        }
+        let vote = CommitRef::new(2, commits[1].digest());
+        let voting_blocks = (0..3)
+            .map(|author| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(3, author)
+                        .set_commit_votes(vec![vote])
+                        .build(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut blocks = voting_blocks.clone();
+        blocks.push(leader);
+        store
+            .write(WriteBatch::new(blocks, commits.clone(), vec![], vec![]))
+            .unwrap();
+
+        // WHEN: fetching all commits.
+        let (fetched, certifier_blocks) = observer_service
+            .handle_fetch_commits(peer, CommitRange::new(1..=3))
+            .await
+            .unwrap();
+
+        // THEN: the uncertified trailing commit is dropped, and the votes are returned.
+        assert_eq!(
+            fetched.iter().map(|c| c.reference()).collect::<Vec<_>>(),
+            commits[..2]
+                .iter()
+                .map(|c| c.reference())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            certifier_blocks
+                .iter()
+                .map(|b| b.reference())
+                .collect::<Vec<_>>(),
+            voting_blocks
+                .iter()
+                .map(|b| b.reference())
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,1089 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use consensus_config::{
+    AuthorityIndex, ConsensusProtocolConfig, Parameters, ProtocolKeyPair, Stake,
+    local_committee_and_keys_with_test_options,
+};
+use consensus_types::block::{BlockRef, Round};
+use mysten_common::ZipDebugEqIteratorExt as _;
+use mysten_metrics::monitored_mpsc::UnboundedReceiver;
+use parking_lot::RwLock;
+use prometheus::Registry;
+use rand::{Rng as _, SeedableRng as _, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{
+    broadcast::{self, error::TryRecvError},
+    watch,
+};
+
+use crate::{
+    CommitConsumerArgs,
+    block::{Block, BlockAPI as _, BlockV1, BlockV2, ExtendedBlock, SignedBlock, VerifiedBlock},
+    block_manager::BlockManager,
+    block_verifier::NoopBlockVerifier,
+    commit::{CommitAPI as _, CommitIndex, CommittedSubDag},
+    commit_observer::CommitObserver,
+    context::{Clock, Context},
+    core::{Core, CoreSignals, CoreSignalsReceivers},
+    dag_state::DagState,
+    error::{ConsensusError, ConsensusResult},
+    leader_schedule::LeaderSchedule,
+    metrics::initialise_metrics,
+    round_tracker::RoundTracker,
+    storage::{Store as _, mem_store::MemStore},
+    transaction::{TransactionClient, TransactionConsumer},
+    transaction_certifier::TransactionCertifier,
+};
+
+/// UNIX timestamp of the start of simulated time, so block timestamps are deterministic.
+const SIMULATION_START_MS: u64 = 1_700_000_000_000;
+/// Time after which a lost or unanswered fetch request is retried with another peer.
+const FETCH_TIMEOUT_MS: u64 = 2_000;
+
+/// Configuration of a simulated committee and its network, shared by all parameter sets
+/// evaluated against it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SimulationConfig {
+    /// Stake of each authority. The committee size is the number of stakes.
+    pub stakes: Vec<Stake>,
+    /// Length of the simulation in simulated time.
+    pub duration_ms: u64,
+    /// Seed of the randomness of latencies and packet losses.
+    pub seed: u64,
+    /// Number of commits between leader schedule updates.
+    pub commits_per_schedule: u64,
+    pub network: NetworkConfig,
+    /// Behaviors of authorities by index. Authorities without a behavior are honest.
+    pub behaviors: BTreeMap<usize, AuthorityBehavior>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            stakes: vec![1; 4],
+            duration_ms: 60_000,
+            seed: 0,
+            commits_per_schedule: 300,
+            network: NetworkConfig::default(),
+            behaviors: BTreeMap::new(),
+        }
+    }
+}
+
+/// Latency and reliability of the links between authorities.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// One way latency of every link.
+    pub latency_ms: u64,
+    /// Maximum random latency added to each message.
+    pub jitter_ms: u64,
+    /// Latency added to every link of an authority, e.g. for an authority in a remote region.
+    pub authority_latency_ms: BTreeMap<usize, u64>,
+    /// Probability of losing each message. Lost blocks are fetched by peers once they are
+    /// referenced by later blocks.
+    pub packet_loss: f64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: 50,
+            jitter_ms: 10,
+            authority_latency_ms: BTreeMap::new(),
+            packet_loss: 0.0,
+        }
+    }
+}
+
+/// Behavior of a simulated authority.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorityBehavior {
+    #[default]
+    Honest,
+    /// Stops at `at_ms` and never recovers.
+    Crash { at_ms: u64 },
+    /// Sends its own blocks to peers with an additional delay.
+    SlowBroadcast { delay_ms: u64 },
+    /// Signs a second block for every proposed round, and sends it to half of the peers.
+    Equivocate,
+}
+
+/// Distribution of latencies measured during a simulation.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LatencyStats {
+    pub samples: u64,
+    pub mean_ms: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencyStats {
+    fn new(mut latencies: Vec<u64>) -> Self {
+        if latencies.is_empty() {
+            return Self::default();
+        }
+        latencies.sort_unstable();
+        let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+        Self {
+            samples: latencies.len() as u64,
+            mean_ms: latencies.iter().sum::<u64>() as f64 / latencies.len() as f64,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            max_ms: *latencies.last().unwrap(),
+        }
+    }
+}
+
+/// Activity of an authority during a simulation.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuthorityReport {
+    pub hostname: String,
+    pub behavior: AuthorityBehavior,
+    /// Number of blocks proposed by the authority, excluding equivocating copies.
+    pub proposed_blocks: u64,
+    /// Number of leader slots of the authority that were committed.
+    pub committed_leaders: u64,
+    /// Number of leader slots of the authority that were skipped.
+    pub skipped_leaders: u64,
+}
+
+/// Reputation scores of a leader schedule update.
+#[derive(Clone, Debug, Serialize)]
+pub struct ReputationSnapshot {
+    /// Simulated time when the reference authority updated its leader schedule.
+    pub time_ms: u64,
+    /// Last commit index of the commits the scores were computed from.
+    pub commit_index: CommitIndex,
+    /// Score of each authority.
+    pub scores: Vec<u64>,
+    /// Authorities swapped out of the leader schedule for their low scores.
+    pub bad_nodes: Vec<AuthorityIndex>,
+}
+
+/// Results of a simulation.
+///
+/// Commits, leader decisions and reputation scores are observed by a reference authority, which
+/// is the first honest authority of the committee. Commit latencies are observed by all honest
+/// authorities, from the proposal of a block to its commit.
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulationReport {
+    pub duration_ms: u64,
+    pub reference_authority: AuthorityIndex,
+    /// Highest round of the DAG of the reference authority.
+    pub highest_round: Round,
+    pub commits: u64,
+    pub committed_blocks: u64,
+    /// Latency to commit leader blocks.
+    pub leader_commit_latency: LatencyStats,
+    /// Latency to commit any block.
+    pub block_commit_latency: LatencyStats,
+    /// Fraction of decided leader slots that were skipped.
+    pub leader_skip_rate: f64,
+    pub authorities: Vec<AuthorityReport>,
+    /// Reputation scores after each leader schedule update.
+    pub reputation: Vec<ReputationSnapshot>,
+}
+
+impl SimulationReport {
+    pub fn rounds_per_second(&self) -> f64 {
+        self.highest_round as f64 * 1000.0 / self.duration_ms.max(1) as f64
+    }
+
+    pub fn commits_per_second(&self) -> f64 {
+        self.commits as f64 * 1000.0 / self.duration_ms.max(1) as f64
+    }
+
+    pub fn committed_blocks_per_second(&self) -> f64 {
+        self.committed_blocks as f64 * 1000.0 / self.duration_ms.max(1) as f64
+    }
+}
+
+/// Runs a committee of `Core`s over a simulated network and simulated time, to evaluate consensus
+/// parameters under configurable latencies, packet losses and faulty authorities.
+///
+/// Events are processed one at a time in simulated time order, and all randomness comes from the
+/// configured seed, so a simulation is deterministic. The leader timeout and the round prober are
+/// emulated by the simulator with the same logic as their tasks. Missing ancestors are fetched
+/// from the peer that sent the block, and retried with a random peer after a timeout.
+pub struct Simulator {
+    config: SimulationConfig,
+    parameters: Parameters,
+    protocol_config: ConsensusProtocolConfig,
+}
+
+impl Simulator {
+    pub fn new(
+        config: SimulationConfig,
+        parameters: Parameters,
+        protocol_config: ConsensusProtocolConfig,
+    ) -> ConsensusResult<Self> {
+        let size = config.stakes.len();
+        if let Some(index) = config
+            .behaviors
+            .keys()
+            .chain(config.network.authority_latency_ms.keys())
+            .find(|index| **index >= size)
+        {
+            return Err(ConsensusError::InvalidSimulationConfig(format!(
+                "Authority {index} is not in the committee of {size} authorities"
+            )));
+        }
+        if size < 2 {
+            return Err(ConsensusError::InvalidSimulationConfig(
+                "At least 2 authorities are needed".to_string(),
+            ));
+        }
+        if !(0.0..1.0).contains(&config.network.packet_loss) {
+            return Err(ConsensusError::InvalidSimulationConfig(format!(
+                "Packet loss {} is not in [0, 1)",
+                config.network.packet_loss
+            )));
+        }
+        Ok(Self {
+            config,
+            parameters,
+            protocol_config,
+        })
+    }
+
+    pub async fn run(self) -> ConsensusResult<SimulationReport> {
+        let mut simulation = Simulation::new(self).await;
+        simulation.run()?;
+        Ok(simulation.report())
+    }
+}
+
+enum Event {
+    DeliverBlock {
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+        block: ExtendedBlock,
+    },
+    FetchRequest {
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+    },
+    FetchResponse {
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        blocks: Vec<VerifiedBlock>,
+    },
+    FetchRetry {
+        authority: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+    },
+    LeaderTimeout {
+        authority: AuthorityIndex,
+        round: Round,
+        force: bool,
+    },
+    Probe {
+        authority: AuthorityIndex,
+    },
+    Crash {
+        authority: AuthorityIndex,
+    },
+}
+
+struct SimulatedAuthority {
+    core: Core,
+    dag_state: Arc<RwLock<DagState>>,
+    store: Arc<MemStore>,
+    leader_schedule: Arc<LeaderSchedule>,
+    transaction_certifier: TransactionCertifier,
+    round_tracker: Arc<RwLock<RoundTracker>>,
+    // Keeps the signal channels open.
+    _signal_receivers: CoreSignalsReceivers,
+    block_receiver: broadcast::Receiver<ExtendedBlock>,
+    new_round_receiver: watch::Receiver<Round>,
+    // Committed sub dags are not consumed, they are observed from the store instead.
+    _commit_receiver: UnboundedReceiver<CommittedSubDag>,
+    // Signs equivocating blocks.
+    block_signer: ProtocolKeyPair,
+    behavior: AuthorityBehavior,
+    crashed: bool,
+    // Blocks being fetched, to avoid fetching the same blocks concurrently.
+    fetching: BTreeSet<BlockRef>,
+    last_commit_index: CommitIndex,
+}
+
+impl SimulatedAuthority {
+    fn add_blocks(&mut self, blocks: Vec<VerifiedBlock>) -> ConsensusResult<BTreeSet<BlockRef>> {
+        self.transaction_certifier
+            .add_voted_blocks(blocks.iter().map(|b| (b.clone(), vec![])).collect());
+        self.core.add_blocks(blocks)
+    }
+}
+
+struct Simulation {
+    config: SimulationConfig,
+    context: Arc<Context>,
+    time_ms: Arc<AtomicU64>,
+    now: u64,
+    rng: StdRng,
+    // Pending events by time and insertion sequence.
+    events: BTreeMap<(u64, u64), Event>,
+    next_sequence: u64,
+    authorities: Vec<SimulatedAuthority>,
+    reference_authority: AuthorityIndex,
+    // Simulated time of the proposal of each block.
+    proposal_times: BTreeMap<BlockRef, u64>,
+    proposed_blocks: Vec<u64>,
+    committed_leaders: Vec<u64>,
+    skipped_leaders: Vec<u64>,
+    // Last leader slot committed by the reference authority, as round and leader offset.
+    last_committed_slot: (Round, u32),
+    committed_blocks: u64,
+    leader_latencies: Vec<u64>,
+    block_latencies: Vec<u64>,
+    reputation: Vec<ReputationSnapshot>,
+}
+
+impl Simulation {
+    async fn new(simulator: Simulator) -> Self {
+        let Simulator {
+            config,
+            mut parameters,
+            protocol_config,
+        } = simulator;
+        let (committee, keys) =
+            local_committee_and_keys_with_test_options(0, config.stakes.clone(), false);
+        // Keys are generated deterministically, so a second set is used to sign equivocations.
+        let (_, equivocation_keys) =
+            local_committee_and_keys_with_test_options(0, config.stakes.clone(), false);
+        // Equivocations are simulated, and should not fail the equivocating authority.
+        parameters.internal.skip_equivocation_validation = true;
+
+        let time_ms = Arc::new(AtomicU64::new(SIMULATION_START_MS));
+        let context = Arc::new(Context::new(
+            SIMULATION_START_MS,
+            AuthorityIndex::ZERO,
+            committee,
+            parameters,
+            protocol_config,
+            initialise_metrics(Registry::new()),
+            Arc::new(Clock::new_simulated(time_ms.clone())),
+        ));
+
+        let mut authorities = vec![];
+        for (((index, _), (_, block_signer)), (_, equivocation_signer)) in context
+            .committee
+            .authorities()
+            .zip_debug_eq(keys)
+            .zip_debug_eq(equivocation_keys)
+        {
+            let behavior = config
+                .behaviors
+                .get(&index.value())
+                .cloned()
+                .unwrap_or_default();
+            authorities.push(
+                Self::new_authority(
+                    Arc::new((*context).clone().with_authority_index(index)),
+                    config.commits_per_schedule,
+                    block_signer,
+                    equivocation_signer,
+                    behavior,
+                )
+                .await,
+            );
+        }
+        let reference_authority = authorities
+            .iter()
+            .position(|a| a.behavior == AuthorityBehavior::Honest)
+            .map(|i| context.committee.to_authority_index(i).unwrap())
+            .unwrap_or(AuthorityIndex::ZERO);
+
+        let size = context.committee.size();
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            context,
+            time_ms,
+            now: 0,
+            events: BTreeMap::new(),
+            next_sequence: 0,
+            authorities,
+            reference_authority,
+            proposal_times: BTreeMap::new(),
+            proposed_blocks: vec![0; size],
+            committed_leaders: vec![0; size],
+            skipped_leaders: vec![0; size],
+            last_committed_slot: (0, 0),
+            committed_blocks: 0,
+            leader_latencies: vec![],
+            block_latencies: vec![],
+            reputation: vec![],
+        }
+    }
+
+    // Wires a `Core` in the same way as `CoreTextFixture`, with stores in memory.
+    async fn new_authority(
+        context: Arc<Context>,
+        commits_per_schedule: u64,
+        block_signer: ProtocolKeyPair,
+        equivocation_signer: ProtocolKeyPair,
+        behavior: AuthorityBehavior,
+    ) -> SimulatedAuthority {
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let block_manager = BlockManager::new(context.clone(), dag_state.clone());
+        let leader_schedule = Arc::new(
+            LeaderSchedule::from_store(context.clone(), dag_state.clone())
+                .with_num_commits_per_schedule(commits_per_schedule),
+        );
+        let (_transaction_client, tx_receiver) = TransactionClient::new(context.clone());
+        let transaction_consumer = TransactionConsumer::new(tx_receiver, context.clone());
+        let transaction_certifier = TransactionCertifier::new(
+            context.clone(),
+            Arc::new(NoopBlockVerifier {}),
+            dag_state.clone(),
+        );
+        let (signals, signal_receivers) = CoreSignals::new(context.clone());
+        let block_receiver = signal_receivers.block_broadcast_receiver();
+        let new_round_receiver = signal_receivers.new_round_receiver();
+
+        let (commit_consumer, commit_receiver) = CommitConsumerArgs::new(0, 0);
+        let commit_observer = CommitObserver::new(
+            context.clone(),
+            commit_consumer,
+            dag_state.clone(),
+            transaction_certifier.clone(),
+            leader_schedule.clone(),
+        )
+        .await;
+        let round_tracker = Arc::new(RwLock::new(RoundTracker::new(context.clone(), vec![])));
+
+        let core = Core::new(
+            context,
+            leader_schedule.clone(),
+            transaction_consumer,
+            transaction_certifier.clone(),
+            block_manager,
+            commit_observer,
+            signals,
+            block_signer,
+            dag_state.clone(),
+            false,
+            round_tracker.clone(),
+        );
+
+        SimulatedAuthority {
+            core,
+            dag_state,
+            store,
+            leader_schedule,
+            transaction_certifier,
+            round_tracker,
+            _signal_receivers: signal_receivers,
+            block_receiver,
+            new_round_receiver,
+            _commit_receiver: commit_receiver,
+            block_signer: equivocation_signer,
+            behavior,
+            crashed: false,
+            fetching: BTreeSet::new(),
+            last_commit_index: 0,
+        }
+    }
+
+    fn run(&mut self) -> ConsensusResult<()> {
+        let probe_interval_ms = self.context.parameters.round_prober_interval_ms;
+        for (index, _) in self.context.committee.authorities() {
+            if let AuthorityBehavior::Crash { at_ms } = self.authorities[index].behavior {
+                self.schedule(at_ms, Event::Crash { authority: index });
+            }
+            self.schedule(probe_interval_ms, Event::Probe { authority: index });
+            // Cores propose their first block during recovery.
+            self.after_core(index)?;
+        }
+
+        while let Some(entry) = self.events.first_entry() {
+            let (time_ms, _) = *entry.key();
+            if time_ms > self.config.duration_ms {
+                break;
+            }
+            let event = entry.remove();
+            self.now = time_ms;
+            self.time_ms
+                .store(SIMULATION_START_MS + time_ms, Ordering::Relaxed);
+            self.process(event)?;
+        }
+        Ok(())
+    }
+
+    fn schedule(&mut self, delay_ms: u64, event: Event) {
+        self.events
+            .insert((self.now + delay_ms, self.next_sequence), event);
+        self.next_sequence += 1;
+    }
+
+    fn latency_ms(&mut self, from: AuthorityIndex, to: AuthorityIndex) -> u64 {
+        let network = &self.config.network;
+        let extra = |authority: AuthorityIndex| {
+            network
+                .authority_latency_ms
+                .get(&authority.value())
+                .copied()
+                .unwrap_or_default()
+        };
+        network.latency_ms + extra(from) + extra(to) + self.rng.gen_range(0..=network.jitter_ms)
+    }
+
+    fn is_lost(&mut self) -> bool {
+        self.rng.gen_bool(self.config.network.packet_loss)
+    }
+
+    fn process(&mut self, event: Event) -> ConsensusResult<()> {
+        match event {
+            Event::DeliverBlock { from, to, block } => {
+                if self.authorities[to].crashed {
+                    return Ok(());
+                }
+                let authority = &mut self.authorities[to];
+                authority
+                    .round_tracker
+                    .write()
+                    .update_from_verified_block(&block);
+                let missing = authority.add_blocks(vec![block.block])?;
+                self.after_core(to)?;
+                self.fetch(to, from, missing);
+            }
+            Event::FetchRequest {
+                from,
+                to,
+                block_refs,
+            } => {
+                if self.authorities[to].crashed || self.is_lost() {
+                    self.schedule(
+                        FETCH_TIMEOUT_MS,
+                        Event::FetchRetry {
+                            authority: from,
+                            block_refs,
+                        },
+                    );
+                    return Ok(());
+                }
+                let blocks = self.authorities[to]
+                    .dag_state
+                    .read()
+                    .get_blocks(&block_refs)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                let latency = self.latency_ms(to, from);
+                self.schedule(
+                    latency,
+                    Event::FetchResponse {
+                        from: to,
+                        to: from,
+                        block_refs,
+                        blocks,
+                    },
+                );
+            }
+            Event::FetchResponse {
+                from,
+                to,
+                block_refs,
+                blocks,
+            } => {
+                let authority = &mut self.authorities[to];
+                if authority.crashed {
+                    return Ok(());
+                }
+                for block_ref in &block_refs {
+                    authority.fetching.remove(block_ref);
+                }
+                let unavailable = block_refs
+                    .into_iter()
+                    .filter(|block_ref| !blocks.iter().any(|b| b.reference() == *block_ref))
+                    .collect::<Vec<_>>();
+                let missing = authority.add_blocks(blocks)?;
+                self.after_core(to)?;
+                self.fetch(to, from, missing);
+                if !unavailable.is_empty() {
+                    self.schedule(
+                        0,
+                        Event::FetchRetry {
+                            authority: to,
+                            block_refs: unavailable,
+                        },
+                    );
+                }
+            }
+            Event::FetchRetry {
+                authority,
+                block_refs,
+            } => {
+                let simulated = &mut self.authorities[authority];
+                if simulated.crashed {
+                    return Ok(());
+                }
+                for block_ref in &block_refs {
+                    simulated.fetching.remove(block_ref);
+                }
+                let missing = simulated.core.get_missing_blocks();
+                let block_refs = block_refs
+                    .into_iter()
+                    .filter(|block_ref| missing.contains(block_ref))
+                    .collect();
+                let peer = self.random_peer(authority);
+                self.fetch(authority, peer, block_refs);
+            }
+            Event::LeaderTimeout {
+                authority,
+                round,
+                force,
+            } => {
+                if self.authorities[authority].crashed {
+                    return Ok(());
+                }
+                self.authorities[authority].core.new_block(round, force)?;
+                self.after_core(authority)?;
+            }
+            Event::Probe { authority } => {
+                if self.authorities[authority].crashed {
+                    return Ok(());
+                }
+                self.probe(authority)?;
+                self.after_core(authority)?;
+                self.schedule(
+                    self.context.parameters.round_prober_interval_ms,
+                    Event::Probe { authority },
+                );
+            }
+            Event::Crash { authority } => {
+                self.authorities[authority].crashed = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn random_peer(&mut self, authority: AuthorityIndex) -> AuthorityIndex {
+        let size = self.context.committee.size();
+        let offset = self.rng.gen_range(1..size);
+        self.context
+            .committee
+            .to_authority_index((authority.value() + offset) % size)
+            .unwrap()
+    }
+
+    fn fetch(
+        &mut self,
+        authority: AuthorityIndex,
+        peer: AuthorityIndex,
+        block_refs: BTreeSet<BlockRef>,
+    ) {
+        let fetching = &mut self.authorities[authority].fetching;
+        let block_refs = block_refs
+            .into_iter()
+            .filter(|block_ref| fetching.insert(*block_ref))
+            .collect::<Vec<_>>();
+        if block_refs.is_empty() {
+            return;
+        }
+        if self.is_lost() {
+            self.schedule(
+                FETCH_TIMEOUT_MS,
+                Event::FetchRetry {
+                    authority,
+                    block_refs,
+                },
+            );
+            return;
+        }
+        let latency = self.latency_ms(authority, peer);
+        self.schedule(
+            latency,
+            Event::FetchRequest {
+                from: authority,
+                to: peer,
+                block_refs,
+            },
+        );
+    }
+
+    // Same computation as `RoundProber::probe()`, with the latest rounds of peers read directly.
+    // Probes of crashed peers and lost probes fail, and count as rounds 0.
+    fn probe(&mut self, authority: AuthorityIndex) -> ConsensusResult<()> {
+        let size = self.context.committee.size();
+        let mut highest_received_rounds = vec![vec![0; size]; size];
+        let mut highest_accepted_rounds = vec![vec![0; size]; size];
+        for (peer, _) in self.context.committee.authorities() {
+            if peer != authority && (self.authorities[peer].crashed || self.is_lost()) {
+                continue;
+            }
+            let simulated = &self.authorities[peer];
+            highest_received_rounds[peer] = simulated
+                .round_tracker
+                .read()
+                .local_highest_received_rounds();
+            highest_accepted_rounds[peer] = simulated
+                .dag_state
+                .read()
+                .get_last_cached_block_per_authority(Round::MAX)
+                .into_iter()
+                .map(|(block, _)| block.round())
+                .collect();
+        }
+        let last_proposed_round = highest_accepted_rounds[authority][authority];
+        highest_received_rounds[authority][authority] = last_proposed_round;
+
+        let simulated = &mut self.authorities[authority];
+        simulated
+            .round_tracker
+            .write()
+            .update_from_probe(highest_accepted_rounds, highest_received_rounds);
+        let propagation_delay = simulated
+            .round_tracker
+            .read()
+            .calculate_propagation_delay(last_proposed_round);
+
+        // Same as the core thread, propose again if proposals were stopped by the delay.
+        let should_propose_before = simulated.core.should_propose();
+        simulated.core.set_propagation_delay(propagation_delay);
+        if !should_propose_before && simulated.core.should_propose() {
+            simulated.core.new_block(Round::MAX, true)?;
+        }
+        Ok(())
+    }
+
+    // Broadcasts blocks proposed by the authority, schedules leader timeouts of new rounds, and
+    // records new commits.
+    fn after_core(&mut self, authority: AuthorityIndex) -> ConsensusResult<()> {
+        loop {
+            match self.authorities[authority].block_receiver.try_recv() {
+                Ok(block) => self.broadcast(authority, block),
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+
+        let new_round_receiver = &mut self.authorities[authority].new_round_receiver;
+        if new_round_receiver.has_changed().unwrap_or(false) {
+            let round = *new_round_receiver.borrow_and_update();
+            // Same timers as `LeaderTimeoutTask`.
+            let parameters = &self.context.parameters;
+            let (min_round_delay, leader_timeout) = (
+                parameters.min_round_delay.as_millis() as u64,
+                parameters.leader_timeout.as_millis() as u64,
+            );
+            self.schedule(
+                min_round_delay,
+                Event::LeaderTimeout {
+                    authority,
+                    round,
+                    force: false,
+                },
+            );
+            self.schedule(
+                leader_timeout,
+                Event::LeaderTimeout {
+                    authority,
+                    round,
+                    force: true,
+                },
+            );
+        }
+
+        self.record_commits(authority)
+    }
+
+    fn broadcast(&mut self, authority: AuthorityIndex, block: ExtendedBlock) {
+        self.proposal_times
+            .insert(block.block.reference(), self.now);
+        self.proposed_blocks[authority] += 1;
+
+        let behavior = self.authorities[authority].behavior.clone();
+        let equivocation = (behavior == AuthorityBehavior::Equivocate).then(|| {
+            let equivocation = self.equivocate(authority, &block.block);
+            self.proposal_times
+                .insert(equivocation.reference(), self.now);
+            ExtendedBlock {
+                block: equivocation,
+                excluded_ancestors: block.excluded_ancestors.clone(),
+            }
+        });
+        let extra_delay = match behavior {
+            AuthorityBehavior::SlowBroadcast { delay_ms } => delay_ms,
+            _ => 0,
+        };
+
+        let size = self.context.committee.size();
+        for (peer, _) in self.context.committee.authorities() {
+            if peer == authority || self.is_lost() {
+                continue;
+            }
+            // Peers in the other half of the committee receive the equivocating block.
+            let block = match &equivocation {
+                Some(equivocation)
+                    if (peer.value() + size - authority.value()) % size > size / 2 =>
+                {
+                    equivocation.clone()
+                }
+                _ => block.clone(),
+            };
+            let latency = self.latency_ms(authority, peer) + extra_delay;
+            self.schedule(
+                latency,
+                Event::DeliverBlock {
+                    from: authority,
+                    to: peer,
+                    block,
+                },
+            );
+        }
+    }
+
+    // Signs a block with the same ancestors and votes as `block` but a different timestamp.
+    fn equivocate(&self, authority: AuthorityIndex, block: &VerifiedBlock) -> VerifiedBlock {
+        let timestamp_ms = block.timestamp_ms() + 1;
+        let equivocation = if self.context.protocol_config.transaction_voting_enabled() {
+            Block::V2(BlockV2::new(
+                block.epoch(),
+                block.round(),
+                block.author(),
+                timestamp_ms,
+                block.ancestors().to_vec(),
+                block.transactions().to_vec(),
+                block.commit_votes().to_vec(),
+                block.transaction_votes().to_vec(),
+                block.misbehavior_reports().to_vec(),
+            ))
+        } else {
+            Block::V1(BlockV1::new(
+                block.epoch(),
+                block.round(),
+                block.author(),
+                timestamp_ms,
+                block.ancestors().to_vec(),
+                block.transactions().to_vec(),
+                block.commit_votes().to_vec(),
+                block.misbehavior_reports().to_vec(),
+            ))
+        };
+        let signed = SignedBlock::new(equivocation, &self.authorities[authority].block_signer)
+            .expect("Block signing failed.");
+        let serialized = signed.serialize().expect("Block serialization failed.");
+        VerifiedBlock::new_verified(signed, serialized)
+    }
+
+    fn record_commits(&mut self, authority: AuthorityIndex) -> ConsensusResult<()> {
+        let simulated = &self.authorities[authority];
+        let last_commit_index = simulated.dag_state.read().last_commit_index();
+        if last_commit_index == simulated.last_commit_index {
+            return Ok(());
+        }
+        simulated.dag_state.write().flush();
+        let commits = simulated
+            .store
+            .scan_commits((simulated.last_commit_index + 1..=last_commit_index).into())?;
+        self.authorities[authority].last_commit_index = last_commit_index;
+
+        if self.authorities[authority].behavior == AuthorityBehavior::Honest {
+            for commit in &commits {
+                let latency = |block_ref: &BlockRef| {
+                    self.proposal_times
+                        .get(block_ref)
+                        .map(|proposed| self.now - proposed)
+                };
+                self.leader_latencies.extend(latency(&commit.leader()));
+                if authority == self.reference_authority {
+                    let latencies = commit
+                        .blocks()
+                        .iter()
+                        .filter_map(latency)
+                        .collect::<Vec<_>>();
+                    self.block_latencies.extend(latencies);
+                }
+            }
+        }
+        if authority != self.reference_authority {
+            return Ok(());
+        }
+
+        let leader_schedule = self.authorities[authority].leader_schedule.clone();
+        let num_leaders = self
+            .context
+            .protocol_config
+            .num_leaders_per_round()
+            .unwrap_or(1) as u32;
+        for commit in &commits {
+            self.committed_blocks += commit.blocks().len() as u64;
+            let leader = commit.leader();
+            // Slots between committed leaders were skipped. They are attributed with the current
+            // leader schedule, which can differ from the schedule that decided them around
+            // schedule updates.
+            let offset = (0..num_leaders)
+                .find(|offset| leader_schedule.elect_leader(leader.round, *offset) == leader.author)
+                .unwrap_or_default();
+            let (mut round, mut next_offset) = self.last_committed_slot;
+            loop {
+                next_offset += 1;
+                if round == 0 || next_offset == num_leaders {
+                    round += 1;
+                    next_offset = 0;
+                }
+                if (round, next_offset) >= (leader.round, offset) {
+                    break;
+                }
+                self.skipped_leaders[leader_schedule.elect_leader(round, next_offset)] += 1;
+            }
+            self.committed_leaders[leader.author] += 1;
+            self.last_committed_slot = (leader.round, offset);
+        }
+
+        let swap_table = leader_schedule.leader_swap_table.read();
+        let scores = &swap_table.reputation_scores;
+        if !scores.scores_per_authority.is_empty()
+            && self
+                .reputation
+                .last()
+                .is_none_or(|snapshot| snapshot.commit_index != scores.commit_range.end())
+        {
+            self.reputation.push(ReputationSnapshot {
+                time_ms: self.now,
+                commit_index: scores.commit_range.end(),
+                scores: scores.scores_per_authority.clone(),
+                bad_nodes: swap_table.bad_nodes.keys().copied().collect(),
+            });
+        }
+        Ok(())
+    }
+
+    fn report(&self) -> SimulationReport {
+        let reference = &self.authorities[self.reference_authority];
+        let committed_leaders = self.committed_leaders.iter().sum::<u64>();
+        let skipped_leaders = self.skipped_leaders.iter().sum::<u64>();
+        SimulationReport {
+            duration_ms: self.config.duration_ms,
+            reference_authority: self.reference_authority,
+            highest_round: reference.dag_state.read().highest_accepted_round(),
+            commits: reference.last_commit_index as u64,
+            committed_blocks: self.committed_blocks,
+            leader_commit_latency: LatencyStats::new(self.leader_latencies.clone()),
+            block_commit_latency: LatencyStats::new(self.block_latencies.clone()),
+            leader_skip_rate: skipped_leaders as f64
+                / (committed_leaders + skipped_leaders).max(1) as f64,
+            authorities: self
+                .context
+                .committee
+                .authorities()
+                .map(|(index, authority)| AuthorityReport {
+                    hostname: authority.hostname.clone(),
+                    behavior: self.authorities[index].behavior.clone(),
+                    proposed_blocks: self.proposed_blocks[index],
+                    committed_leaders: self.committed_leaders[index],
+                    skipped_leaders: self.skipped_leaders[index],
+                })
+                .collect(),
+            reputation: self.reputation.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn protocol_config() -> ConsensusProtocolConfig {
+        let mut protocol_config = ConsensusProtocolConfig::for_testing();
+        protocol_config.set_bad_nodes_stake_threshold_for_testing(33);
+        protocol_config
+    }
+
+    #[tokio::test]
+    async fn test_simulation_with_crashed_authority() {
+        let config = SimulationConfig {
+            duration_ms: 20_000,
+            commits_per_schedule: 50,
+            network: NetworkConfig {
+                packet_loss: 0.01,
+                ..Default::default()
+            },
+            behaviors: BTreeMap::from([(3, AuthorityBehavior::Crash { at_ms: 5_000 })]),
+            ..Default::default()
+        };
+        let parameters = Parameters {
+            leader_timeout: Duration::from_millis(500),
+            ..Default::default()
+        };
+
+        let report = Simulator::new(config.clone(), parameters.clone(), protocol_config())
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(report.reference_authority, AuthorityIndex::ZERO);
+        assert!(report.commits > 50, "{report:?}");
+        assert!(report.leader_commit_latency.samples > 0);
+        assert!(report.leader_skip_rate > 0.0);
+        // Leader slots of the crashed authority are skipped until it is swapped out of the
+        // leader schedule.
+        let crashed = &report.authorities[3];
+        assert!(crashed.skipped_leaders > 0);
+        let last_reputation = report.reputation.last().unwrap();
+        assert_eq!(
+            last_reputation.bad_nodes,
+            vec![AuthorityIndex::new_for_test(3)]
+        );
+
+        // Simulations are deterministic.
+        let rerun = Simulator::new(config, parameters, protocol_config())
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(rerun.commits, report.commits);
+        assert_eq!(rerun.highest_round, report.highest_round);
+        assert_eq!(
+            rerun.leader_commit_latency.mean_ms,
+            report.leader_commit_latency.mean_ms
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulation_with_equivocating_authority() {
+        let config = SimulationConfig {
+            duration_ms: 5_000,
+            behaviors: BTreeMap::from([(1, AuthorityBehavior::Equivocate)]),
+            ..Default::default()
+        };
+        let report = Simulator::new(config, Parameters::default(), protocol_config())
+            .unwrap()
+            .run()
+            .await
+            .unwrap();
+        assert!(report.commits > 0, "{report:?}");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = SimulationConfig {
+            behaviors: BTreeMap::from([(4, AuthorityBehavior::Equivocate)]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Simulator::new(config, Parameters::default(), protocol_config()),
+            Err(ConsensusError::InvalidSimulationConfig(_))
+        ));
+    }
+}
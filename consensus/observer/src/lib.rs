@@ -0,0 +1,573 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Follows consensus of a committee from outside of it, by connecting to the observer server of
+//! a validator. Accepted blocks and certified commits are verified against the committee and
+//! written to a local consensus store, which can be inspected with the same tools as a
+//! validator's consensus store.
+
+mod stats;
+
+use std::{collections::BTreeSet, future::Future, sync::Arc, time::Duration};
+
+use consensus_config::{AuthorityIndex, Committee};
+use consensus_core::{
+    BlockAPI as _, CommitAPI as _, CommitIndex, CommitRange, CommitRef, ConsensusError,
+    ConsensusResult, ObservedBlock, ObservedBlockStream, ObserverClient, TrustedCommit,
+    VerifiedBlock,
+    storage::{Store, WriteBatch},
+};
+use consensus_types::block::{BlockRef, Round};
+use futures::StreamExt as _;
+use mysten_common::ZipDebugEqIteratorExt as _;
+use parking_lot::RwLock;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+pub use stats::{AuthorityStats, ObserverStats};
+
+/// Timeout of requests to the observed validator.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Delay before reconnecting to the observed validator after the block stream ends.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Delay before retrying to fetch commits, when the latest commits are not certified yet.
+const COMMIT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Requests made by the observer to the observed validator. Implemented by `ObserverClient`.
+pub trait ObserverNetwork: Send + Sync {
+    fn committee(&self) -> &Committee;
+
+    fn stream_blocks(
+        &self,
+        peer: AuthorityIndex,
+        highest_round_per_authority: Vec<Round>,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<ObservedBlockStream>> + Send;
+
+    fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<Vec<VerifiedBlock>>> + Send;
+
+    fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)>> + Send;
+}
+
+impl ObserverNetwork for ObserverClient {
+    fn committee(&self) -> &Committee {
+        ObserverClient::committee(self)
+    }
+
+    fn stream_blocks(
+        &self,
+        peer: AuthorityIndex,
+        highest_round_per_authority: Vec<Round>,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<ObservedBlockStream>> + Send {
+        ObserverClient::stream_blocks(self, peer, highest_round_per_authority, timeout)
+    }
+
+    fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<Vec<VerifiedBlock>>> + Send {
+        ObserverClient::fetch_blocks(self, peer, block_refs, timeout)
+    }
+
+    fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+        timeout: Duration,
+    ) -> impl Future<Output = ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)>> + Send
+    {
+        ObserverClient::fetch_commits(self, peer, commit_range, timeout)
+    }
+}
+
+/// Streams accepted blocks and fetches certified commits from a validator, and writes them to
+/// a store.
+pub struct ConsensusObserver<N = ObserverClient> {
+    client: N,
+    peer: AuthorityIndex,
+    store: Arc<dyn Store>,
+    stats: RwLock<ObserverStats>,
+    // Reference of the last commit written to the store.
+    last_commit: RwLock<CommitRef>,
+    // Highest commit index produced by the peer.
+    highest_commit_index: watch::Sender<CommitIndex>,
+}
+
+impl<N: ObserverNetwork> ConsensusObserver<N> {
+    /// Creates an observer of `peer`, which resumes from the blocks and commits in `store`.
+    pub fn new(client: N, peer: AuthorityIndex, store: Arc<dyn Store>) -> ConsensusResult<Self> {
+        let committee = client.committee();
+        if !committee.is_valid_index(peer) {
+            return Err(ConsensusError::InvalidAuthorityIndex {
+                index: peer,
+                max: committee.size() - 1,
+            });
+        }
+
+        let mut stats = ObserverStats::new(committee);
+        for (authority, _) in committee.authorities() {
+            let blocks = store.scan_last_blocks_by_author(authority, 1, None)?;
+            stats.record_stored_blocks(&blocks);
+        }
+        let mut last_commit = CommitRef::default();
+        if let Some(commit) = store.read_last_commit()? {
+            last_commit = commit.reference();
+            stats.record_stored_commit(commit.index(), commit.leader());
+        }
+        info!(
+            "Observing validator {} from round {} and commit {}",
+            committee.authority(peer).hostname,
+            stats.highest_round(),
+            last_commit.index
+        );
+
+        let (highest_commit_index, _) = watch::channel(last_commit.index);
+        Ok(Self {
+            client,
+            peer,
+            store,
+            stats: RwLock::new(stats),
+            last_commit: RwLock::new(last_commit),
+            highest_commit_index,
+        })
+    }
+
+    pub fn stats(&self) -> ObserverStats {
+        self.stats.read().clone()
+    }
+
+    /// Observes the validator until a commit does not extend the commits in the store, or the
+    /// store fails. Connection and verification errors are retried.
+    pub async fn run(&self) -> ConsensusResult<()> {
+        tokio::select! {
+            result = self.observe_blocks() => result,
+            result = self.sync_commits() => result,
+        }
+    }
+
+    async fn observe_blocks(&self) -> ConsensusResult<()> {
+        loop {
+            let highest_round_per_authority = self.stats.read().highest_round_per_authority();
+            match self
+                .client
+                .stream_blocks(self.peer, highest_round_per_authority, REQUEST_TIMEOUT)
+                .await
+            {
+                Ok(mut stream) => {
+                    // Missing ancestors are only fetched above the round of the first block in
+                    // the stream, to avoid walking back the whole DAG.
+                    let mut lowest_round = None;
+                    while let Some(result) = stream.next().await {
+                        match result {
+                            Ok(observed) => {
+                                let lowest_round =
+                                    *lowest_round.get_or_insert(observed.block.round());
+                                self.process_block(observed, lowest_round).await?;
+                            }
+                            Err(e) => {
+                                warn!("Received an invalid block, reconnecting: {e}");
+                                break;
+                            }
+                        }
+                    }
+                    info!("Block stream ended, reconnecting");
+                }
+                Err(e) => {
+                    warn!("Failed to stream blocks: {e}");
+                }
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn process_block(
+        &self,
+        observed: ObservedBlock,
+        lowest_round: Round,
+    ) -> ConsensusResult<()> {
+        let ObservedBlock {
+            block,
+            highest_commit_index,
+        } = observed;
+        self.stats
+            .write()
+            .record_highest_commit_index(highest_commit_index);
+        self.highest_commit_index.send_if_modified(|index| {
+            if highest_commit_index > *index {
+                *index = highest_commit_index;
+                true
+            } else {
+                false
+            }
+        });
+
+        if self.store.contains_blocks(&[block.reference()])?[0] {
+            return Ok(());
+        }
+
+        // Blocks are streamed after they are accepted by the peer, so their ancestors are
+        // normally received first. Ancestors can be missing when the stream lags behind.
+        let ancestors = block
+            .ancestors()
+            .iter()
+            .filter(|ancestor| ancestor.round > lowest_round)
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let missing_ancestors = ancestors
+            .iter()
+            .zip_debug_eq(self.store.contains_blocks(&ancestors)?)
+            .filter_map(|(ancestor, found)| (!found).then_some(*ancestor))
+            .collect::<Vec<BlockRef>>();
+        let mut fetched = vec![];
+        if !missing_ancestors.is_empty() {
+            debug!("Fetching missing ancestors: {missing_ancestors:?}");
+            match self
+                .client
+                .fetch_blocks(self.peer, missing_ancestors, REQUEST_TIMEOUT)
+                .await
+            {
+                Ok(blocks) => fetched = blocks,
+                Err(e) => warn!("Failed to fetch missing ancestors: {e}"),
+            }
+        }
+
+        let num_fetched = fetched.len();
+        let blocks: Vec<VerifiedBlock> = std::iter::once(block).chain(fetched).collect();
+        self.store
+            .write(WriteBatch::new(blocks.clone(), vec![], vec![], vec![]))?;
+        self.stats.write().record_blocks(&blocks, num_fetched);
+        Ok(())
+    }
+
+    async fn sync_commits(&self) -> ConsensusResult<()> {
+        let mut highest_commit_index = self.highest_commit_index.subscribe();
+        loop {
+            let last_commit = *self.last_commit.read();
+            let target = *highest_commit_index.borrow_and_update();
+            if target <= last_commit.index {
+                // The sender is owned by self, so it cannot be dropped.
+                let _ = highest_commit_index.changed().await;
+                continue;
+            }
+
+            let range = CommitRange::new(last_commit.index + 1..=target);
+            let (commits, vote_blocks) = match self
+                .client
+                .fetch_commits(self.peer, range, REQUEST_TIMEOUT)
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    debug!("Failed to fetch commits after {}: {e}", last_commit.index);
+                    tokio::time::sleep(COMMIT_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+
+            // Fetched commits are verified to be chained, but the first commit also needs to
+            // extend the last commit in the store.
+            let first_commit = &commits[0];
+            if last_commit.index > 0 && first_commit.previous_digest() != last_commit.digest {
+                let stored_commit = self
+                    .store
+                    .scan_commits((last_commit.index..=last_commit.index).into())?
+                    .pop()
+                    .expect("Last commit must be in the store");
+                return Err(ConsensusError::UnexpectedCommitSequence {
+                    peer: self.peer,
+                    prev_commit: Box::new((*stored_commit).clone()),
+                    curr_commit: Box::new((**first_commit).clone()),
+                });
+            }
+
+            let last_fetched = commits
+                .last()
+                .expect("Verified commits are not empty")
+                .reference();
+            let leaders = commits
+                .iter()
+                .map(|commit| (commit.index(), commit.leader()))
+                .collect::<Vec<_>>();
+            self.store
+                .write(WriteBatch::new(vote_blocks, commits, vec![], vec![]))?;
+
+            *self.last_commit.write() = last_fetched;
+            let mut stats = self.stats.write();
+            for (index, leader) in leaders {
+                stats.record_commit(index, leader);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use consensus_core::{Context, DagBuilder, MemStore};
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    const NUM_AUTHORITIES: usize = 4;
+    const PEER: u32 = 0;
+    const MAX_COMMITS_PER_FETCH: usize = 3;
+
+    /// A validator serving the blocks and commits of a DAG. Each connection streams the next
+    /// scripted list of blocks, and then stays open without sending anything.
+    struct FakeValidator {
+        committee: Committee,
+        blocks: Vec<VerifiedBlock>,
+        commits: Vec<TrustedCommit>,
+        streams: Mutex<VecDeque<Vec<ConsensusResult<ObservedBlock>>>>,
+        stream_requests: Mutex<Vec<Vec<Round>>>,
+    }
+
+    impl FakeValidator {
+        fn new(committee: Committee, dag_builder: &mut DagBuilder, num_commits: u32) -> Self {
+            let commits = dag_builder
+                .get_sub_dag_and_commits(1..=num_commits)
+                .into_iter()
+                .map(|(_, commit)| commit)
+                .collect();
+            Self {
+                committee,
+                blocks: dag_builder.all_blocks(),
+                commits,
+                streams: Mutex::new(VecDeque::new()),
+                stream_requests: Mutex::new(vec![]),
+            }
+        }
+
+        fn add_stream(&self, blocks: Vec<VerifiedBlock>, highest_commit_index: CommitIndex) {
+            self.streams.lock().push_back(
+                blocks
+                    .into_iter()
+                    .map(|block| {
+                        Ok(ObservedBlock {
+                            block,
+                            highest_commit_index,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    impl ObserverNetwork for FakeValidator {
+        fn committee(&self) -> &Committee {
+            &self.committee
+        }
+
+        async fn stream_blocks(
+            &self,
+            peer: AuthorityIndex,
+            highest_round_per_authority: Vec<Round>,
+            _timeout: Duration,
+        ) -> ConsensusResult<ObservedBlockStream> {
+            assert_eq!(peer, AuthorityIndex::new_for_test(PEER));
+            self.stream_requests
+                .lock()
+                .push(highest_round_per_authority);
+            let stream: ObservedBlockStream = match self.streams.lock().pop_front() {
+                Some(items) => Box::pin(futures::stream::iter(items)),
+                None => Box::pin(futures::stream::pending()),
+            };
+            Ok(stream)
+        }
+
+        async fn fetch_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            block_refs: Vec<BlockRef>,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<VerifiedBlock>> {
+            Ok(self
+                .blocks
+                .iter()
+                .filter(|block| block_refs.contains(&block.reference()))
+                .cloned()
+                .collect())
+        }
+
+        async fn fetch_commits(
+            &self,
+            _peer: AuthorityIndex,
+            commit_range: CommitRange,
+            _timeout: Duration,
+        ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
+            let commits: Vec<_> = self
+                .commits
+                .iter()
+                .filter(|commit| {
+                    commit.index() >= commit_range.start() && commit.index() <= commit_range.end()
+                })
+                .take(MAX_COMMITS_PER_FETCH)
+                .cloned()
+                .collect();
+            if commits.is_empty() {
+                return Err(ConsensusError::NetworkRequest(format!(
+                    "No commits in {commit_range:?}"
+                )));
+            }
+            Ok((commits, vec![]))
+        }
+    }
+
+    fn build_dag(num_rounds: Round, num_transactions: u32) -> (Committee, DagBuilder) {
+        let (context, _) = Context::new_for_test(NUM_AUTHORITIES);
+        let committee = context.committee.clone();
+        let mut dag_builder = DagBuilder::new(Arc::new(context));
+        dag_builder
+            .layers(1..=num_rounds)
+            .num_transactions(num_transactions)
+            .build();
+        (committee, dag_builder)
+    }
+
+    /// Runs the observer until `done` returns true.
+    async fn run_until(observer: &ConsensusObserver<FakeValidator>, done: impl Fn() -> bool) {
+        let wait = async {
+            while !done() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(30), async {
+            tokio::select! {
+                result = observer.run() => panic!("Observer stopped: {result:?}"),
+                _ = wait => {}
+            }
+        })
+        .await
+        .expect("Observer did not catch up");
+    }
+
+    #[tokio::test]
+    async fn test_observe_blocks_and_sync_commits() {
+        let (committee, mut dag_builder) = build_dag(10, 0);
+        let validator = FakeValidator::new(committee, &mut dag_builder, 8);
+        // The first connection fails after round 5, with commits up to 2.
+        validator.add_stream(dag_builder.blocks(1..=5), 2);
+        validator
+            .streams
+            .lock()
+            .back_mut()
+            .unwrap()
+            .push(Err(ConsensusError::Shutdown));
+        // After reconnecting, the blocks of round 7 are not streamed and have to be fetched.
+        let mut blocks = dag_builder.blocks(6..=6);
+        blocks.extend(dag_builder.blocks(8..=10));
+        validator.add_stream(blocks, 8);
+
+        let store = Arc::new(MemStore::new());
+        let observer =
+            ConsensusObserver::new(validator, AuthorityIndex::new_for_test(PEER), store.clone())
+                .unwrap();
+        let all_blocks: Vec<_> = dag_builder
+            .all_blocks()
+            .iter()
+            .map(|block| block.reference())
+            .collect();
+        run_until(&observer, || {
+            observer.stats().last_commit_index == 8
+                && store
+                    .contains_blocks(&all_blocks)
+                    .unwrap()
+                    .into_iter()
+                    .all(|found| found)
+        })
+        .await;
+
+        // The second connection resumed from the last round of the first one.
+        let stream_requests = observer.client.stream_requests.lock().clone();
+        assert_eq!(
+            stream_requests[..2],
+            [vec![0; NUM_AUTHORITIES], vec![5; NUM_AUTHORITIES]]
+        );
+
+        let stats = observer.stats();
+        assert_eq!(stats.missing_blocks_fetched, NUM_AUTHORITIES as u64);
+        assert_eq!(stats.highest_commit_index, 8);
+        assert_eq!(stats.highest_round(), 10);
+        for authority in &stats.authorities {
+            assert_eq!(authority.blocks, 10);
+        }
+        let stored_commits = store.scan_commits((1..=8).into()).unwrap();
+        assert_eq!(stored_commits, observer.client.commits);
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_store() {
+        let (committee, mut dag_builder) = build_dag(10, 0);
+        let validator = FakeValidator::new(committee, &mut dag_builder, 8);
+        validator.add_stream(dag_builder.blocks(6..=10), 8);
+
+        // The store already has the first 5 rounds and 4 commits.
+        let store = Arc::new(MemStore::new());
+        store
+            .write(WriteBatch::new(
+                dag_builder.blocks(1..=5),
+                validator.commits[..4].to_vec(),
+                vec![],
+                vec![],
+            ))
+            .unwrap();
+        let observer =
+            ConsensusObserver::new(validator, AuthorityIndex::new_for_test(PEER), store.clone())
+                .unwrap();
+        assert_eq!(observer.stats().last_commit_index, 4);
+
+        run_until(&observer, || observer.stats().last_commit_index == 8).await;
+        assert_eq!(
+            observer.client.stream_requests.lock()[0],
+            vec![5; NUM_AUTHORITIES]
+        );
+        assert_eq!(observer.stats().missing_blocks_fetched, 0);
+        assert_eq!(
+            store.scan_commits((1..=8).into()).unwrap(),
+            observer.client.commits
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diverging_commits() {
+        let (committee, mut dag_builder) = build_dag(10, 0);
+        let validator = FakeValidator::new(committee, &mut dag_builder, 8);
+        validator.add_stream(dag_builder.blocks(1..=10), 8);
+
+        // The store has a first commit of another DAG.
+        let (_, mut other_dag_builder) = build_dag(3, 1);
+        let other_commit = other_dag_builder.get_sub_dag_and_commits(1..=1)[0]
+            .1
+            .clone();
+        let store = Arc::new(MemStore::new());
+        store
+            .write(WriteBatch::new(vec![], vec![other_commit], vec![], vec![]))
+            .unwrap();
+
+        let observer =
+            ConsensusObserver::new(validator, AuthorityIndex::new_for_test(PEER), store).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(30), observer.run())
+            .await
+            .expect("Observer did not stop");
+        assert!(
+            matches!(result, Err(ConsensusError::UnexpectedCommitSequence { .. })),
+            "{result:?}"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus_config::{AuthorityIndex, Committee};
+use consensus_core::{BlockAPI as _, CommitIndex, VerifiedBlock};
+use consensus_types::block::{BlockRef, Round};
+
+/// Consensus progress seen by an observer. Block counts are since the observer started, while
+/// rounds and commit indices include the data already in the store.
+#[derive(Clone, Debug)]
+pub struct ObserverStats {
+    pub authorities: Vec<AuthorityStats>,
+    /// Number of blocks that were not streamed by the peer, and had to be fetched.
+    pub missing_blocks_fetched: u64,
+    /// Highest commit index produced by the peer. Commits up to this index might not be
+    /// certified yet.
+    pub highest_commit_index: CommitIndex,
+    /// Index of the last certified commit written to the store.
+    pub last_commit_index: CommitIndex,
+    /// Round of the leader of the last commit written to the store.
+    pub last_committed_leader_round: Round,
+    /// Number of rounds between committed leaders without a committed leader, since the
+    /// observer started.
+    pub rounds_without_committed_leader: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthorityStats {
+    pub hostname: String,
+    /// Highest round of the authority's blocks in the store.
+    pub highest_round: Round,
+    /// Number of blocks received from the authority.
+    pub blocks: u64,
+    /// Number of commits led by a block of the authority.
+    pub committed_leaders: u64,
+}
+
+impl ObserverStats {
+    pub(crate) fn new(committee: &Committee) -> Self {
+        Self {
+            authorities: committee
+                .authorities()
+                .map(|(_, authority)| AuthorityStats {
+                    hostname: authority.hostname.clone(),
+                    highest_round: 0,
+                    blocks: 0,
+                    committed_leaders: 0,
+                })
+                .collect(),
+            missing_blocks_fetched: 0,
+            highest_commit_index: 0,
+            last_commit_index: 0,
+            last_committed_leader_round: 0,
+            rounds_without_committed_leader: 0,
+        }
+    }
+
+    /// Highest round across all authorities.
+    pub fn highest_round(&self) -> Round {
+        self.authorities
+            .iter()
+            .map(|a| a.highest_round)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn highest_round_per_authority(&self) -> Vec<Round> {
+        self.authorities.iter().map(|a| a.highest_round).collect()
+    }
+
+    pub fn authority(&self, index: AuthorityIndex) -> &AuthorityStats {
+        &self.authorities[index.value()]
+    }
+
+    pub(crate) fn record_stored_blocks(&mut self, blocks: &[VerifiedBlock]) {
+        for block in blocks {
+            let authority = &mut self.authorities[block.author().value()];
+            authority.highest_round = authority.highest_round.max(block.round());
+        }
+    }
+
+    pub(crate) fn record_stored_commit(&mut self, index: CommitIndex, leader: BlockRef) {
+        self.last_commit_index = index;
+        self.last_committed_leader_round = leader.round;
+        self.highest_commit_index = self.highest_commit_index.max(index);
+    }
+
+    pub(crate) fn record_blocks(
+        &mut self,
+        blocks: &[VerifiedBlock],
+        missing_blocks_fetched: usize,
+    ) {
+        for block in blocks {
+            let authority = &mut self.authorities[block.author().value()];
+            authority.highest_round = authority.highest_round.max(block.round());
+            authority.blocks += 1;
+        }
+        self.missing_blocks_fetched += missing_blocks_fetched as u64;
+    }
+
+    pub(crate) fn record_highest_commit_index(&mut self, commit_index: CommitIndex) {
+        self.highest_commit_index = self.highest_commit_index.max(commit_index);
+    }
+
+    pub(crate) fn record_commit(&mut self, index: CommitIndex, leader: BlockRef) {
+        // Multiple leaders of the same round can be committed.
+        if self.last_committed_leader_round > 0 && leader.round > self.last_committed_leader_round {
+            self.rounds_without_committed_leader +=
+                (leader.round - self.last_committed_leader_round - 1) as u64;
+        }
+        self.authorities[leader.author.value()].committed_leaders += 1;
+        self.last_commit_index = index;
+        self.last_committed_leader_round = leader.round;
+        self.highest_commit_index = self.highest_commit_index.max(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use consensus_config::local_committee_and_keys;
+    use consensus_core::{TestBlock, VerifiedBlock};
+    use consensus_types::block::BlockDigest;
+
+    use super::*;
+
+    #[test]
+    fn test_record_blocks_and_commits() {
+        let (committee, _) = local_committee_and_keys(0, vec![1; 4]);
+        let mut stats = ObserverStats::new(&committee);
+
+        let blocks = [(1, 0), (2, 0), (2, 3)]
+            .into_iter()
+            .map(|(round, author)| {
+                VerifiedBlock::new_for_test(TestBlock::new(round, author).build())
+            })
+            .collect::<Vec<_>>();
+        stats.record_blocks(&blocks, 1);
+        assert_eq!(stats.highest_round_per_authority(), vec![2, 0, 0, 2]);
+        assert_eq!(stats.authority(AuthorityIndex::new_for_test(0)).blocks, 2);
+        assert_eq!(stats.missing_blocks_fetched, 1);
+
+        let leader = |round, author| {
+            BlockRef::new(
+                round,
+                AuthorityIndex::new_for_test(author),
+                BlockDigest::MIN,
+            )
+        };
+        stats.record_commit(1, leader(3, 1));
+        stats.record_commit(2, leader(4, 2));
+        stats.record_commit(3, leader(4, 3));
+        // Rounds 5 and 6 have no committed leader.
+        stats.record_commit(4, leader(7, 1));
+
+        assert_eq!(stats.last_commit_index, 4);
+        assert_eq!(stats.last_committed_leader_round, 7);
+        assert_eq!(stats.rounds_without_committed_leader, 2);
+        assert_eq!(
+            stats
+                .authority(AuthorityIndex::new_for_test(1))
+                .committed_leaders,
+            2
+        );
+        assert_eq!(stats.highest_commit_index, 4);
+    }
+}
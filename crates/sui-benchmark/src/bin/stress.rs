@@ -178,6 +178,8 @@ async fn main() -> Result<()> {
                     let benchmark_table = benchmark_stats.to_table();
                     eprintln!("Benchmark Report:");
                     eprintln!("{}", benchmark_table);
+                    eprintln!("Workload Report:");
+                    eprintln!("{}", benchmark_stats.to_workload_table());
 
                     if stress_stat_collection {
                         eprintln!("Stress Performance Report:");
@@ -55,7 +55,7 @@ use tokio::{time, time::Instant};
 use tracing::{debug, error, info, warn};
 
 use super::Interval;
-use super::{BenchmarkStats, StressStats};
+use super::{BenchmarkStats, StressStats, WorkloadStats};
 
 /// Randomly partitions a list of transactions into groups for soft bundle submission.
 /// Each group will be submitted as a separate soft bundle.
@@ -118,6 +118,7 @@ pub struct BenchMetrics {
     pub validators_in_effects_cert: IntCounterVec,
     pub cpu_usage: GaugeVec,
     pub num_success_cmds: IntCounterVec,
+    pub gas_used: IntCounterVec,
 }
 
 impl BenchMetrics {
@@ -207,6 +208,13 @@ impl BenchMetrics {
                 registry,
             )
             .unwrap(),
+            gas_used: register_int_counter_vec_with_registry!(
+                "gas_used",
+                "Total gas used by executed transactions",
+                &["workload", "client_type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -470,6 +478,7 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                 latency_ms: HistogramWrapper {
                     histogram: hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap(),
                 },
+                workload_stats: BTreeMap::new(),
             };
             let mut stat_collection: BTreeMap<usize, Stats> = BTreeMap::new();
             let mut counter = 0;
@@ -800,6 +809,7 @@ async fn run_bench_worker(
     let mut worker_gas_used = 0;
 
     let mut latency_histogram = hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+    let mut workload_stats: BTreeMap<String, WorkloadStats> = BTreeMap::new();
     let mut request_interval = time::interval(Duration::from_micros(request_delay_micros));
     request_interval.set_missed_tick_behavior(time::MissedTickBehavior::Burst);
     let mut stat_interval = time::interval(Duration::from_micros(stat_delay_micros));
@@ -852,6 +862,11 @@ async fn run_bench_worker(
                 let num_commands =
                     transaction.data().transaction_data().kind().num_commands() as u16;
 
+                metrics
+                    .gas_used
+                    .with_label_values(&[&payload.to_string(), &client_type.to_string()])
+                    .inc_by(effects.gas_used());
+
                 if effects.is_ok() {
                     metrics
                         .num_success
@@ -997,7 +1012,8 @@ async fn run_bench_worker(
                             latency_ms:HistogramWrapper{
                                 histogram:latency_histogram.clone()
                             },
-                            total_gas_used: worker_gas_used
+                            total_gas_used: worker_gas_used,
+                            workload_stats: std::mem::take(&mut workload_stats),
                         },
                     })
                     .is_err()
@@ -1179,6 +1195,7 @@ async fn run_bench_worker(
                         num_success_cmds += num_commands as u64;
                         num_in_flight -= 1;
                         worker_gas_used += gas_used;
+                        workload_stats.entry(payload.to_string()).or_default().record(latency, gas_used);
                         free_pool.push_back(payload);
                         latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
 
@@ -1211,6 +1228,7 @@ async fn run_bench_worker(
                 latency_ms: HistogramWrapper {
                     histogram: latency_histogram,
                 },
+                workload_stats,
             },
         })
         .is_err()
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use duration_str::parse;
+use std::collections::BTreeMap;
 use std::fmt::Formatter;
 use std::{str::FromStr, time::Duration};
 
@@ -133,6 +134,49 @@ pub struct BenchmarkStats {
     /// Total gas used
     pub total_gas_used: u64,
     pub latency_ms: HistogramWrapper,
+    /// Statistics of the transactions that executed successfully, by payload name
+    #[serde(default)]
+    pub workload_stats: BTreeMap<String, WorkloadStats>,
+}
+
+/// Statistics of the successful transactions of a single payload kind, e.g. a template of a
+/// scripted workload.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct WorkloadStats {
+    pub num_success_txes: u64,
+    pub total_gas_used: u64,
+    pub latency_ms: HistogramWrapper,
+}
+
+impl Default for WorkloadStats {
+    fn default() -> Self {
+        Self {
+            num_success_txes: 0,
+            total_gas_used: 0,
+            latency_ms: HistogramWrapper {
+                histogram: Histogram::new_with_max(120_000, 3).unwrap(),
+            },
+        }
+    }
+}
+
+impl WorkloadStats {
+    pub fn record(&mut self, latency: Duration, gas_used: u64) {
+        self.num_success_txes += 1;
+        self.total_gas_used += gas_used;
+        self.latency_ms
+            .histogram
+            .saturating_record(latency.as_millis().try_into().unwrap_or(u64::MAX));
+    }
+
+    pub fn update(&mut self, sample_stat: &WorkloadStats) {
+        self.num_success_txes += sample_stat.num_success_txes;
+        self.total_gas_used += sample_stat.total_gas_used;
+        self.latency_ms
+            .histogram
+            .add(&sample_stat.latency_ms.histogram)
+            .unwrap();
+    }
 }
 
 impl BenchmarkStats {
@@ -147,6 +191,12 @@ impl BenchmarkStats {
             .histogram
             .add(&sample_stat.latency_ms.histogram)
             .unwrap();
+        for (name, stats) in &sample_stat.workload_stats {
+            self.workload_stats
+                .entry(name.clone())
+                .or_default()
+                .update(stats);
+        }
     }
     pub fn to_table(&self) -> Table {
         let mut table = Table::new();
@@ -200,6 +250,46 @@ impl BenchmarkStats {
         table.add_row(row);
         table
     }
+    /// Breakdown of the successful transactions by payload name.
+    pub fn to_workload_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "workload",
+                "tps",
+                "latency (p50)",
+                "latency (p99)",
+                "gas used/tx (MIST avg.)",
+                "gas used (MIST total)",
+            ]);
+        let duration_secs = self.duration.as_secs_f64().max(1.0);
+        for (name, stats) in &self.workload_stats {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name));
+            row.add_cell(Cell::new(format!(
+                "{:.2}",
+                stats.num_success_txes as f64 / duration_secs
+            )));
+            row.add_cell(Cell::new(stats.latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(
+                stats.latency_ms.histogram.value_at_quantile(0.99),
+            ));
+            row.add_cell(Cell::new(format_num_with_separators(
+                stats.total_gas_used / stats.num_success_txes.max(1),
+                3,
+                ",",
+            )));
+            row.add_cell(Cell::new(format_num_with_separators(
+                stats.total_gas_used,
+                3,
+                ",",
+            )));
+            table.add_row(row);
+        }
+        table
+    }
 }
 
 /// A comparison between an old and a new benchmark.
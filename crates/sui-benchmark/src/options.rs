@@ -6,6 +6,7 @@ use clap::*;
 use strum_macros::EnumString;
 
 use crate::drivers::Interval;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Parser)]
@@ -196,8 +197,14 @@ pub enum RunSpec {
         // relative weight of composite transactions in the benchmark workload
         #[clap(long, num_args(1..), value_delimiter = ',', default_values_t = [0])]
         composite: Vec<u32>,
+        // relative weight of transactions from the workload script in the benchmark workload
+        #[clap(long, num_args(1..), value_delimiter = ',', default_values_t = [0])]
+        scripted: Vec<u32>,
 
         // --- workload-specific options --- (TODO: use subcommands or similar)
+        // YAML script of the scripted workload, see `workloads::scripted` for the format
+        #[clap(long)]
+        workload_script: Option<PathBuf>,
         // 100 for max hotness i.e all requests target
         // just the same shared counter, 0 for no hotness
         // i.e. all requests target a different shared
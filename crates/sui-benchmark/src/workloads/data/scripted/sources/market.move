@@ -0,0 +1,42 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Example package of the scripted workload, see `workload.yaml`.
+module scripted::market {
+    /// A market shared by all the accounts.
+    public struct Pool has key {
+        id: UID,
+        volume: u64,
+        trades: u64,
+    }
+
+    /// An account owned by a single sender.
+    public struct Account has key {
+        id: UID,
+        balance: u64,
+    }
+
+    public fun create_pool(ctx: &mut TxContext) {
+        transfer::share_object(Pool { id: object::new(ctx), volume: 0, trades: 0 })
+    }
+
+    public fun open_account(ctx: &mut TxContext) {
+        transfer::transfer(Account { id: object::new(ctx), balance: 0 }, ctx.sender())
+    }
+
+    public fun deposit(account: &mut Account, amount: u64) {
+        account.balance = account.balance + amount;
+    }
+
+    /// Trades up to `amount` of the balance of `account` in `pool`.
+    public fun trade(pool: &mut Pool, account: &mut Account, amount: u64) {
+        let amount = amount.min(account.balance);
+        account.balance = account.balance - amount;
+        pool.volume = pool.volume + amount;
+        pool.trades = pool.trades + 1;
+    }
+
+    public fun volume(pool: &Pool): u64 {
+        pool.volume
+    }
+}
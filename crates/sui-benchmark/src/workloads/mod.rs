@@ -10,6 +10,7 @@ pub mod party;
 pub mod payload;
 pub mod randomized_transaction;
 pub mod randomness;
+pub mod scripted;
 pub mod shared_counter;
 pub mod shared_object_deletion;
 pub mod slow;
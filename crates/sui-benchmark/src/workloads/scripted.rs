@@ -0,0 +1,752 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A workload defined by a script instead of code, to benchmark a user Move package. The script
+//! names the package to publish, pools of objects created by the package, and templates of
+//! programmable transactions picked at random according to their weights:
+//!
+//! ```yaml
+//! package: ../my_package
+//! pools:
+//!   - name: markets
+//!     kind: shared
+//!     constructor: { call: market::create }
+//!     count: 10
+//!   - name: accounts
+//!     kind: owned
+//!     constructor: { call: market::open_account }
+//! templates:
+//!   - name: trade
+//!     weight: 3
+//!     commands:
+//!       - call: market::trade
+//!         args: [{ object: markets }, { object: accounts }, { u64: 100 }]
+//!   - name: hot_trade
+//!     hot_objects: 1
+//!     commands:
+//!       - call: market::trade
+//!         args: [{ object: markets }, { object: accounts }, { u64: 100 }]
+//! ```
+//!
+//! Each template is reported separately by the driver, as `scripted:<template name>`.
+
+use crate::drivers::Interval;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{MAX_GAS_FOR_TESTING, Workload, WorkloadBuilder};
+use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::{ExecutionEffects, ValidatorProxy};
+use anyhow::{Context, anyhow, bail, ensure};
+use async_trait::async_trait;
+use futures::future::join_all;
+use mysten_common::ZipDebugEqIteratorExt;
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::get_key_pair;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, CallArg, ObjectArg, SharedObjectMutability, Transaction};
+use sui_types::{Identifier, TypeTag, parse_sui_type_tag};
+use tracing::{error, info};
+
+/// Maximum number of objects of a shared pool created by a single transaction.
+const MAX_CONSTRUCTORS_PER_TX: u64 = 100;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadScript {
+    /// Path of the Move package to publish, relative to the script.
+    pub package: PathBuf,
+    #[serde(default)]
+    pub pools: Vec<ObjectPoolSpec>,
+    pub templates: Vec<TemplateSpec>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolKind {
+    /// Objects shared by all payloads.
+    Shared,
+    /// One object per payload, owned by the account of the payload.
+    Owned,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ObjectPoolSpec {
+    pub name: String,
+    pub kind: PoolKind,
+    /// Call creating one object of the pool. It must share the object for shared pools, and
+    /// transfer it to the sender for owned pools.
+    pub constructor: MoveCallSpec,
+    /// Number of objects of a shared pool. Owned pools have one object per payload.
+    #[serde(default = "default_pool_count")]
+    pub count: u64,
+}
+
+fn default_pool_count() -> u64 {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateSpec {
+    pub name: String,
+    #[serde(default = "default_template_weight")]
+    pub weight: u32,
+    /// Only use the first `hot_objects` objects of each shared pool, to raise the contention on
+    /// them. All the objects of the pools are used when unset.
+    #[serde(default)]
+    pub hot_objects: Option<usize>,
+    #[serde(default)]
+    pub gas_budget: Option<u64>,
+    pub commands: Vec<MoveCallSpec>,
+}
+
+fn default_template_weight() -> u32 {
+    1
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveCallSpec {
+    /// `module::function` of the package of the script, or `address::module::function`.
+    pub call: String,
+    /// Type arguments, where `$package` stands for the address of the package of the script.
+    #[serde(default)]
+    pub type_args: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<ArgSpec>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgSpec {
+    Bool(bool),
+    U8(u8),
+    U64(u64),
+    /// An address, or `sender` for the sender of the transaction.
+    Address(String),
+    String(String),
+    /// A random object of the pool, or the object of the payload for owned pools.
+    Object(String),
+    /// Like `object`, but takes shared objects by immutable reference.
+    ObjectRead(String),
+    /// The `0x6` clock, by immutable reference.
+    Clock,
+    /// The gas coin of the transaction.
+    Gas,
+    /// The result of an earlier command of the template.
+    Result(u16),
+    NestedResult(u16, u16),
+}
+
+impl WorkloadScript {
+    /// Loads and validates a YAML workload script.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workload script {}", path.display()))?;
+        let mut script: Self = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse workload script {}", path.display()))?;
+        if let Some(dir) = path.parent() {
+            script.package = dir.join(&script.package);
+        }
+        script.validate()?;
+        Ok(script)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut pools = HashMap::new();
+        for pool in &self.pools {
+            ensure!(
+                pools.insert(pool.name.as_str(), pool.kind).is_none(),
+                "Duplicate object pool {}",
+                pool.name
+            );
+            ensure!(pool.count > 0, "Object pool {} is empty", pool.name);
+            resolve_call(&pool.constructor, ObjectID::ZERO)
+                .with_context(|| format!("Invalid constructor of pool {}", pool.name))?;
+            for arg in &pool.constructor.args {
+                ensure!(
+                    !matches!(
+                        arg,
+                        ArgSpec::Object(_)
+                            | ArgSpec::ObjectRead(_)
+                            | ArgSpec::Gas
+                            | ArgSpec::Result(_)
+                            | ArgSpec::NestedResult(..)
+                    ),
+                    "The constructor of pool {} can only take pure arguments and the clock",
+                    pool.name
+                );
+            }
+        }
+
+        ensure!(!self.templates.is_empty(), "The script has no templates");
+        ensure!(
+            self.templates.iter().any(|t| t.weight > 0),
+            "All the templates have a weight of 0"
+        );
+        let mut names = HashSet::new();
+        for template in &self.templates {
+            ensure!(
+                names.insert(template.name.as_str()),
+                "Duplicate template {}",
+                template.name
+            );
+            ensure!(
+                !template.commands.is_empty(),
+                "Template {} has no commands",
+                template.name
+            );
+            ensure!(
+                template.hot_objects != Some(0),
+                "Template {} has no hot objects",
+                template.name
+            );
+            for (i, command) in template.commands.iter().enumerate() {
+                resolve_call(command, ObjectID::ZERO).with_context(|| {
+                    format!("Invalid command {i} of template {}", template.name)
+                })?;
+                for arg in &command.args {
+                    match arg {
+                        ArgSpec::Object(pool) | ArgSpec::ObjectRead(pool) => ensure!(
+                            pools.contains_key(pool.as_str()),
+                            "Template {} uses unknown pool {pool}",
+                            template.name
+                        ),
+                        ArgSpec::Result(j) | ArgSpec::NestedResult(j, _) => ensure!(
+                            (*j as usize) < i,
+                            "Command {i} of template {} uses the result of command {j}",
+                            template.name
+                        ),
+                        ArgSpec::Address(address) if address != "sender" => {
+                            SuiAddress::from_str(address)
+                                .map_err(|e| anyhow!("Invalid address {address}: {e}"))?;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ResolvedCall {
+    package: ObjectID,
+    module: Identifier,
+    function: Identifier,
+    type_args: Vec<TypeTag>,
+    args: Vec<ArgSpec>,
+}
+
+fn resolve_call(spec: &MoveCallSpec, package_id: ObjectID) -> anyhow::Result<ResolvedCall> {
+    let parts: Vec<_> = spec.call.split("::").collect();
+    let (package, module, function) = match parts[..] {
+        [module, function] => (package_id, module, function),
+        [address, module, function] => (
+            ObjectID::from_hex_literal(address)
+                .map_err(|e| anyhow!("Invalid package address {address}: {e}"))?,
+            module,
+            function,
+        ),
+        _ => bail!(
+            "Invalid call {}, expected module::function or address::module::function",
+            spec.call
+        ),
+    };
+    let type_args = spec
+        .type_args
+        .iter()
+        .map(|t| parse_sui_type_tag(&t.replace("$package", &package_id.to_string())))
+        .collect::<anyhow::Result<_>>()?;
+    Ok(ResolvedCall {
+        package,
+        module: Identifier::new(module)?,
+        function: Identifier::new(function)?,
+        type_args,
+        args: spec.args.clone(),
+    })
+}
+
+#[derive(Debug)]
+struct Template {
+    name: String,
+    hot_objects: Option<usize>,
+    gas_budget: Option<u64>,
+    commands: Vec<ResolvedCall>,
+}
+
+/// The templates and shared objects of the script, shared by all the payloads.
+#[derive(Debug)]
+struct ScriptState {
+    templates: Vec<Template>,
+    weights: WeightedIndex<u32>,
+    shared_pools: HashMap<String, Vec<(ObjectID, SequenceNumber)>>,
+}
+
+#[derive(Debug)]
+pub struct ScriptedPayload {
+    state: Arc<ScriptState>,
+    gas: Gas,
+    owned_objects: HashMap<String, ObjectRef>,
+    /// Template of the latest transaction of the payload.
+    template: usize,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for ScriptedPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "scripted:{}", self.state.templates[self.template].name)
+    }
+}
+
+impl ScriptedPayload {
+    fn argument(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        template: &Template,
+        arg: &ArgSpec,
+    ) -> Argument {
+        let shared_object = |pool: &str, mutability| {
+            let objects = &self.state.shared_pools[pool];
+            let candidates = template
+                .hot_objects
+                .map_or(objects.len(), |n| n.min(objects.len()));
+            let (id, initial_shared_version) = objects[rand::thread_rng().gen_range(0..candidates)];
+            ObjectArg::SharedObject {
+                id,
+                initial_shared_version,
+                mutability,
+            }
+        };
+        match arg {
+            ArgSpec::Bool(value) => builder.pure(*value).unwrap(),
+            ArgSpec::U8(value) => builder.pure(*value).unwrap(),
+            ArgSpec::U64(value) => builder.pure(*value).unwrap(),
+            ArgSpec::Address(address) => {
+                let address = if address == "sender" {
+                    self.gas.1
+                } else {
+                    SuiAddress::from_str(address).unwrap()
+                };
+                builder.pure(address).unwrap()
+            }
+            ArgSpec::String(value) => builder.pure(value).unwrap(),
+            ArgSpec::Object(pool) | ArgSpec::ObjectRead(pool) => {
+                let object = match self.owned_objects.get(pool) {
+                    Some(object) => ObjectArg::ImmOrOwnedObject(*object),
+                    None if matches!(arg, ArgSpec::Object(_)) => {
+                        shared_object(pool, SharedObjectMutability::Mutable)
+                    }
+                    None => shared_object(pool, SharedObjectMutability::Immutable),
+                };
+                builder.obj(object).unwrap()
+            }
+            ArgSpec::Clock => builder.input(CallArg::CLOCK_IMM).unwrap(),
+            ArgSpec::Gas => Argument::GasCoin,
+            ArgSpec::Result(i) => Argument::Result(*i),
+            ArgSpec::NestedResult(i, j) => Argument::NestedResult(*i, *j),
+        }
+    }
+}
+
+impl Payload for ScriptedPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Scripted tx failed... Status: {:?}", effects.status());
+        }
+        self.gas.0 = effects.gas_object().0;
+        for (object, _) in effects.mutated() {
+            if let Some(owned) = self
+                .owned_objects
+                .values_mut()
+                .find(|owned| owned.0 == object.0)
+            {
+                *owned = object;
+            }
+        }
+    }
+
+    fn make_transaction(&mut self) -> Transaction {
+        self.template = self.state.weights.sample(&mut rand::thread_rng());
+        let template = &self.state.templates[self.template];
+        let rgp = self
+            .system_state_observer
+            .state
+            .borrow()
+            .reference_gas_price;
+        let mut tx_builder = TestTransactionBuilder::new(self.gas.1, self.gas.0, rgp);
+        let builder = tx_builder.ptb_builder_mut();
+        for command in &template.commands {
+            let args = command
+                .args
+                .iter()
+                .map(|arg| self.argument(builder, template, arg))
+                .collect();
+            builder.programmable_move_call(
+                command.package,
+                command.module.clone(),
+                command.function.clone(),
+                command.type_args.clone(),
+                args,
+            );
+        }
+        if let Some(gas_budget) = template.gas_budget {
+            tx_builder = tx_builder.with_gas_budget(gas_budget);
+        }
+        tx_builder.build_and_sign(self.gas.2.as_ref())
+    }
+}
+
+#[derive(Debug)]
+pub struct ScriptedWorkloadBuilder {
+    script: Arc<WorkloadScript>,
+    num_payloads: u64,
+}
+
+impl ScriptedWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        script: Arc<WorkloadScript>,
+        duration: Interval,
+        group: u32,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = (workload_weight * target_qps as f32).ceil() as u64;
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+                duration,
+                group,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(ScriptedWorkloadBuilder {
+                    script,
+                    num_payloads: max_ops,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for ScriptedWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Gas coin for publishing the package and creating the shared pools
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount: MAX_GAS_FOR_TESTING,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount: MAX_GAS_FOR_TESTING,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(ScriptedWorkload {
+            script: self.script.clone(),
+            state: None,
+            init_gas: init_gas.pop().unwrap(),
+            owned_objects: vec![HashMap::new(); payload_gas.len()],
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct ScriptedWorkload {
+    script: Arc<WorkloadScript>,
+    state: Option<Arc<ScriptState>>,
+    init_gas: Gas,
+    payload_gas: Vec<Gas>,
+    /// Objects of the owned pools, for each payload.
+    owned_objects: Vec<HashMap<String, ObjectRef>>,
+}
+
+/// Executes a transaction calling each of `calls`, returning the effects.
+async fn execute_calls(
+    execution_proxy: &Arc<dyn ValidatorProxy + Sync + Send>,
+    gas: &mut Gas,
+    gas_price: u64,
+    calls: &[&ResolvedCall],
+) -> ExecutionEffects {
+    let mut tx_builder = TestTransactionBuilder::new(gas.1, gas.0, gas_price);
+    let builder = tx_builder.ptb_builder_mut();
+    for call in calls {
+        let args = call
+            .args
+            .iter()
+            .map(|arg| match arg {
+                ArgSpec::Bool(value) => builder.pure(*value).unwrap(),
+                ArgSpec::U8(value) => builder.pure(*value).unwrap(),
+                ArgSpec::U64(value) => builder.pure(*value).unwrap(),
+                ArgSpec::Address(address) if address == "sender" => builder.pure(gas.1).unwrap(),
+                ArgSpec::Address(address) => builder
+                    .pure(SuiAddress::from_str(address).unwrap())
+                    .unwrap(),
+                ArgSpec::String(value) => builder.pure(value).unwrap(),
+                ArgSpec::Clock => builder.input(CallArg::CLOCK_IMM).unwrap(),
+                _ => unreachable!("constructors only take pure arguments and the clock"),
+            })
+            .collect();
+        builder.programmable_move_call(
+            call.package,
+            call.module.clone(),
+            call.function.clone(),
+            call.type_args.clone(),
+            args,
+        );
+    }
+    let transaction = tx_builder.build_and_sign(gas.2.as_ref());
+    let (_, execution_result) = execution_proxy.execute_transaction_block(transaction).await;
+    let effects = execution_result.unwrap();
+    assert!(
+        effects.is_ok(),
+        "Scripted workload setup failed: {}",
+        effects.status()
+    );
+    gas.0 = effects.gas_object().0;
+    effects
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for ScriptedWorkload {
+    async fn init(
+        &mut self,
+        execution_proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _fullnode_proxies: Vec<Arc<dyn ValidatorProxy + Sync + Send>>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.state.is_some() {
+            return;
+        }
+        let gas_price = system_state_observer.state.borrow().reference_gas_price;
+
+        info!("Publishing package {}", self.script.package.display());
+        let gas = &mut self.init_gas;
+        let transaction = TestTransactionBuilder::new(gas.1, gas.0, gas_price)
+            .publish_async(self.script.package.clone())
+            .await
+            .build_and_sign(gas.2.as_ref());
+        let (_, execution_result) = execution_proxy.execute_transaction_block(transaction).await;
+        let effects = execution_result.unwrap();
+        gas.0 = effects.gas_object().0;
+        let package_id = effects
+            .created()
+            .iter()
+            .find(|(_, owner)| matches!(owner, Owner::Immutable))
+            .map(|(package, _)| package.0)
+            .expect("Publishing the package of the script must create it");
+        info!("Script package id {package_id:?}");
+
+        let mut shared_pools = HashMap::new();
+        for pool in &self.script.pools {
+            let constructor = resolve_call(&pool.constructor, package_id).unwrap();
+            if pool.kind == PoolKind::Owned {
+                let futures = self
+                    .payload_gas
+                    .iter_mut()
+                    .zip_debug_eq(self.owned_objects.iter_mut())
+                    .map(|(gas, owned_objects)| {
+                        let execution_proxy = &execution_proxy;
+                        let constructor = &constructor;
+                        async move {
+                            let sender = gas.1;
+                            let effects =
+                                execute_calls(execution_proxy, gas, gas_price, &[constructor])
+                                    .await;
+                            let created: Vec<_> = effects
+                                .created()
+                                .into_iter()
+                                .filter(|(_, owner)| *owner == Owner::AddressOwner(sender))
+                                .collect();
+                            assert_eq!(
+                                created.len(),
+                                1,
+                                "The constructor of pool {} must create one owned object",
+                                pool.name
+                            );
+                            owned_objects.insert(pool.name.clone(), created[0].0);
+                        }
+                    });
+                join_all(futures).await;
+                continue;
+            }
+
+            info!("Creating {} objects of pool {}", pool.count, pool.name);
+            let mut objects = vec![];
+            let mut remaining = pool.count;
+            while remaining > 0 {
+                let batch = remaining.min(MAX_CONSTRUCTORS_PER_TX);
+                let calls = vec![&constructor; batch as usize];
+                let effects = execute_calls(&execution_proxy, gas, gas_price, &calls).await;
+                let created: Vec<_> = effects
+                    .created()
+                    .into_iter()
+                    .filter_map(|(object, owner)| match owner {
+                        Owner::Shared {
+                            initial_shared_version,
+                        } => Some((object.0, initial_shared_version)),
+                        _ => None,
+                    })
+                    .collect();
+                assert_eq!(
+                    created.len() as u64,
+                    batch,
+                    "The constructor of pool {} must create one shared object",
+                    pool.name
+                );
+                objects.extend(created);
+                remaining -= batch;
+            }
+            shared_pools.insert(pool.name.clone(), objects);
+        }
+
+        let templates = self
+            .script
+            .templates
+            .iter()
+            .map(|template| Template {
+                name: template.name.clone(),
+                hot_objects: template.hot_objects,
+                gas_budget: template.gas_budget,
+                commands: template
+                    .commands
+                    .iter()
+                    .map(|command| resolve_call(command, package_id).unwrap())
+                    .collect(),
+            })
+            .collect();
+        let weights = WeightedIndex::new(self.script.templates.iter().map(|t| t.weight)).unwrap();
+        self.state = Some(Arc::new(ScriptState {
+            templates,
+            weights,
+            shared_pools,
+        }));
+    }
+
+    async fn make_test_payloads(
+        &self,
+        _execution_proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _fullnode_proxies: Vec<Arc<dyn ValidatorProxy + Sync + Send>>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        let state = self.state.clone().expect("Workload must be initialized");
+        self.payload_gas
+            .iter()
+            .zip_debug_eq(&self.owned_objects)
+            .map(|(gas, owned_objects)| {
+                Box::new(ScriptedPayload {
+                    state: state.clone(),
+                    gas: gas.clone(),
+                    owned_objects: owned_objects.clone(),
+                    template: 0,
+                    system_state_observer: system_state_observer.clone(),
+                }) as Box<dyn Payload>
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "Scripted"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> anyhow::Result<WorkloadScript> {
+        let script: WorkloadScript = serde_yaml::from_str(yaml)?;
+        script.validate()?;
+        Ok(script)
+    }
+
+    #[test]
+    fn example_script_is_valid() {
+        let mut path = crate::workloads::benchmark_move_base_dir();
+        path.push("src/workloads/data/scripted/workload.yaml");
+        let script = WorkloadScript::load(&path).unwrap();
+        assert!(script.package.ends_with("src/workloads/data/scripted"));
+        assert_eq!(script.pools[1].kind, PoolKind::Owned);
+        assert!(matches!(
+            script.templates[0].commands[0].args[..],
+            [ArgSpec::Object(_), ArgSpec::U64(10)]
+        ));
+        assert_eq!(script.templates[2].hot_objects, Some(1));
+    }
+
+    #[test]
+    fn invalid_scripts() {
+        let template = "templates: [{name: t, commands: [{call: m::f}]}]";
+        assert!(parse(&format!("package: .\n{template}")).is_ok());
+        // Unknown pool.
+        assert!(
+            parse(
+                "package: .\ntemplates: [{name: t, commands: [{call: m::f, args: [{object: p}]}]}]"
+            )
+            .is_err()
+        );
+        // Result of a later command.
+        assert!(
+            parse(
+                "package: .\ntemplates: [{name: t, commands: [{call: m::f, args: [{result: 0}]}]}]"
+            )
+            .is_err()
+        );
+        // Malformed call.
+        assert!(parse("package: .\ntemplates: [{name: t, commands: [{call: f}]}]").is_err());
+        // Objects can't be passed to constructors.
+        assert!(
+            parse(&format!(
+                "package: .\npools: [{{name: p, kind: shared, constructor: {{call: m::new, args: [{{object: p}}]}}}}]\n{template}"
+            ))
+            .is_err()
+        );
+        // No template can be picked.
+        assert!(
+            parse("package: .\ntemplates: [{name: t, weight: 0, commands: [{call: m::f}]}]")
+                .is_err()
+        );
+    }
+}
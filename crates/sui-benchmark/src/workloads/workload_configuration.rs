@@ -8,11 +8,12 @@ use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::batch_payment::BatchPaymentWorkloadBuilder;
 use crate::workloads::delegation::DelegationWorkloadBuilder;
 use crate::workloads::party::PartyWorkloadBuilder;
+use crate::workloads::scripted::{ScriptedWorkloadBuilder, WorkloadScript};
 use crate::workloads::shared_counter::SharedCounterWorkloadBuilder;
 use crate::workloads::slow::SlowWorkloadBuilder;
 use crate::workloads::transfer_object::TransferObjectWorkloadBuilder;
 use crate::workloads::{ExpectedFailureType, GroupID, WorkloadBuilderInfo, WorkloadInfo};
-use anyhow::Result;
+use anyhow::{Result, bail};
 use futures::future::join_all;
 use mysten_common::ZipDebugEqIteratorExt;
 use std::collections::BTreeMap;
@@ -42,6 +43,7 @@ pub struct WorkloadWeights {
     pub party: u32,
     pub conflicting_transfer: u32,
     pub composite: u32,
+    pub scripted: u32,
 }
 
 pub struct WorkloadConfig {
@@ -61,6 +63,7 @@ pub struct WorkloadConfig {
     pub in_flight_ratio: u64,
     pub duration: Interval,
     pub composite_config: Option<super::composite::CompositeWorkloadConfig>,
+    pub scripted_config: Option<Arc<WorkloadScript>>,
 }
 pub struct WorkloadConfiguration;
 
@@ -89,6 +92,8 @@ impl WorkloadConfiguration {
                 party,
                 conflicting_transfer,
                 composite,
+                scripted,
+                workload_script,
                 shared_counter_hotness_factor,
                 num_shared_counters,
                 shared_counter_max_tip,
@@ -105,6 +110,14 @@ impl WorkloadConfiguration {
                     "Number of benchmark groups to run: {}",
                     num_of_benchmark_groups
                 );
+                let scripted_config = if scripted.iter().any(|w| *w > 0) {
+                    let Some(path) = workload_script else {
+                        bail!("--workload-script is required by the scripted workload");
+                    };
+                    Some(Arc::new(WorkloadScript::load(&path)?))
+                } else {
+                    None
+                };
 
                 // Creating the workload builders for each benchmark group. The workloads for each
                 // benchmark group will run in the same time for the same duration.
@@ -128,6 +141,7 @@ impl WorkloadConfiguration {
                             party: party[i],
                             conflicting_transfer: conflicting_transfer[i],
                             composite: composite[i],
+                            scripted: scripted[i],
                         },
                         adversarial_cfg: AdversarialPayloadCfg::from_str(&adversarial_cfg[i])
                             .unwrap(),
@@ -149,6 +163,7 @@ impl WorkloadConfiguration {
                         } else {
                             None
                         },
+                        scripted_config: scripted_config.clone(),
                     };
                     let builders =
                         Self::create_workload_builders(config, system_state_observer.clone()).await;
@@ -232,6 +247,7 @@ impl WorkloadConfiguration {
             in_flight_ratio,
             duration,
             composite_config,
+            scripted_config,
         }: WorkloadConfig,
         system_state_observer: Arc<SystemStateObserver>,
     ) -> Vec<Option<WorkloadBuilderInfo>> {
@@ -254,7 +270,8 @@ impl WorkloadConfiguration {
             + weights.slow
             + weights.party
             + weights.conflicting_transfer
-            + weights.composite;
+            + weights.composite
+            + weights.scripted;
         let reference_gas_price = system_state_observer.state.borrow().reference_gas_price;
         let mut workload_builders = vec![];
         let shared_workload = SharedCounterWorkloadBuilder::from(
@@ -384,6 +401,18 @@ impl WorkloadConfiguration {
             );
             workload_builders.push(composite_workload);
         }
+        if let Some(script) = scripted_config {
+            let scripted_workload = ScriptedWorkloadBuilder::from(
+                weights.scripted as f32 / total_weight as f32,
+                target_qps,
+                num_workers,
+                in_flight_ratio,
+                script,
+                duration,
+                group,
+            );
+            workload_builders.push(scripted_workload);
+        }
         workload_builders
     }
 }
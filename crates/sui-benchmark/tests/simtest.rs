@@ -16,11 +16,13 @@ mod test {
     use std::time::{Duration, Instant};
     use sui_benchmark::BenchmarkProxyMetrics;
     use sui_benchmark::bank::BenchmarkBank;
+    use sui_benchmark::drivers::BenchmarkStats;
     use sui_benchmark::system_state_observer::SystemStateObserver;
     use sui_benchmark::workloads::adversarial::AdversarialPayloadCfg;
     use sui_benchmark::workloads::benchmark_move_base_dir;
     use sui_benchmark::workloads::composite::CompositeWorkloadConfig;
     use sui_benchmark::workloads::expected_failure::ExpectedFailurePayloadCfg;
+    use sui_benchmark::workloads::scripted::WorkloadScript;
     use sui_benchmark::workloads::workload::ExpectedFailureType;
     use sui_benchmark::workloads::workload_configuration::{
         WorkloadConfig, WorkloadConfiguration, WorkloadWeights,
@@ -1069,6 +1071,8 @@ mod test {
         num_contested_objects: u64,
         composite_weight: u32,
         composite_config: Option<CompositeWorkloadConfig>,
+        scripted_weight: u32,
+        scripted_config: Option<Arc<WorkloadScript>>,
     }
 
     impl Default for SimulatedLoadConfig {
@@ -1099,6 +1103,8 @@ mod test {
                 num_contested_objects: 2,
                 composite_weight: 1,
                 composite_config: Some(CompositeWorkloadConfig::balanced()),
+                scripted_weight: 0,
+                scripted_config: None,
             }
         }
     }
@@ -1120,6 +1126,24 @@ mod test {
                 ..Default::default()
             }
         }
+
+        fn scripted_only(script: WorkloadScript) -> Self {
+            Self {
+                scripted_weight: 1,
+                scripted_config: Some(Arc::new(script)),
+                shared_counter_weight: 0,
+                randomness_weight: 0,
+                transfer_object_weight: 0,
+                delegation_weight: 0,
+                batch_payment_weight: 0,
+                shared_deletion_weight: 0,
+                slow_weight: 0,
+                randomized_transaction_weight: 0,
+                composite_weight: 0,
+                composite_config: None,
+                ..Default::default()
+            }
+        }
     }
 
     async fn test_simulated_load(test_cluster: Arc<TestCluster>, test_duration_secs: u64) {
@@ -1143,7 +1167,8 @@ mod test {
         num_workers: Option<u64>,
         pre_load_setup: Option<F>,
         enable_surfer: bool,
-    ) where
+    ) -> (BenchmarkStats, prometheus::Registry)
+    where
         F: FnOnce(Arc<TestCluster>) -> Fut + Send,
         Fut: std::future::Future<Output = ()> + Send,
     {
@@ -1243,6 +1268,7 @@ mod test {
             party: config.party_weight,
             conflicting_transfer: config.conflicting_transfer_weight,
             composite: config.composite_weight,
+            scripted: config.scripted_weight,
         };
 
         let workload_config = WorkloadConfig {
@@ -1262,6 +1288,7 @@ mod test {
             in_flight_ratio,
             duration,
             composite_config: config.composite_config,
+            scripted_config: config.scripted_config,
         };
 
         let workloads_builders = WorkloadConfiguration::create_workload_builders(
@@ -1314,6 +1341,7 @@ mod test {
             // TODO: make this stricter (== 0) when we have reliable error retrying on the client.
             tracing::info!("end of test {:?}", benchmark_stats);
             assert!(benchmark_stats.num_error_txes < 100);
+            (benchmark_stats, registry)
         });
 
         if enable_surfer {
@@ -1344,10 +1372,11 @@ mod test {
                 assert!(!results.unique_move_functions_called.is_empty());
             });
 
-            let _ = futures::join!(bench_task, surfer_task);
+            let (bench_result, _) = futures::join!(bench_task, surfer_task);
+            bench_result.unwrap()
         } else {
             info!("Surfer disabled, running only benchmark task");
-            bench_task.await.unwrap();
+            bench_task.await.unwrap()
         }
     }
 
@@ -1727,6 +1756,72 @@ mod test {
         assert!(shared_plus_randomness_cancellations > 0);
     }
 
+    #[sim_test(config = "test_config()")]
+    async fn test_scripted_workload() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = build_test_cluster(4, 10000, 1).await;
+
+        let mut script_path = benchmark_move_base_dir();
+        script_path.push("src/workloads/data/scripted/workload.yaml");
+        let script = WorkloadScript::load(&script_path).unwrap();
+        let templates: Vec<_> = script
+            .templates
+            .iter()
+            .map(|template| format!("scripted:{}", template.name))
+            .collect();
+
+        let (benchmark_stats, registry) = test_simulated_load_with_test_config(
+            test_cluster,
+            60,
+            SimulatedLoadConfig::scripted_only(script),
+            None,
+            None,
+            None::<fn(Arc<TestCluster>) -> std::future::Ready<()>>,
+            false,
+        )
+        .await;
+
+        let mut gas_used_metric = BTreeMap::<String, f64>::new();
+        for family in registry.gather() {
+            if family.name() != "gas_used" {
+                continue;
+            }
+            for metric in family.get_metric() {
+                let workload = metric
+                    .get_label()
+                    .iter()
+                    .find(|label| label.name() == "workload")
+                    .unwrap()
+                    .value();
+                *gas_used_metric.entry(workload.to_string()).or_default() += metric.counter.value();
+            }
+        }
+
+        // Every template of the script ran and is reported on its own.
+        for template in &templates {
+            let stats = benchmark_stats
+                .workload_stats
+                .get(template)
+                .unwrap_or_else(|| panic!("no stats for {template}"));
+            info!(
+                "{template}: {} transactions, {} gas",
+                stats.num_success_txes, stats.total_gas_used
+            );
+            assert!(stats.num_success_txes > 0, "{template} never succeeded");
+            assert!(stats.total_gas_used > 0);
+            assert!(gas_used_metric.get(template).copied().unwrap_or_default() > 0.0);
+        }
+        assert_eq!(benchmark_stats.workload_stats.len(), templates.len());
+        assert_eq!(
+            benchmark_stats
+                .workload_stats
+                .values()
+                .map(|stats| stats.num_success_txes)
+                .sum::<u64>(),
+            benchmark_stats.num_success_txes
+        );
+    }
+
     /// Tests that async post-processing produces consistent indexes even when
     /// the node crashes after indexing but before notifying the checkpoint executor.
     /// Uses a fail point to simulate this crash scenario under load, then verifies
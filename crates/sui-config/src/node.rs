@@ -258,6 +258,12 @@ pub struct NodeConfig {
     /// When set, enables per-commit binary logs of congestion tracker state.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub congestion_log: Option<CongestionLogConfig>,
+
+    /// Runs the node as a read replica of a fullnode on the same machine.
+    /// When set, the node opens the database of the fullnode as a RocksDB secondary and only
+    /// serves rpc reads from it; it does not sync, execute or index any checkpoints itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_replica: Option<ReadReplicaConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -309,6 +315,25 @@ fn default_congestion_log_max_files() -> u32 {
     10
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReadReplicaConfig {
+    /// The `db-path` of the fullnode whose database is served. The replica keeps the files of its
+    /// secondary RocksDB instances under its own `db-path`.
+    pub primary_db_path: PathBuf,
+
+    /// How often the replica catches up with the writes of the fullnode.
+    ///
+    /// If unspecified, this will default to `200` milliseconds.
+    pub catch_up_interval_ms: Option<u64>,
+}
+
+impl ReadReplicaConfig {
+    pub fn catch_up_interval(&self) -> Duration {
+        Duration::from_millis(self.catch_up_interval_ms.unwrap_or(200))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum ForkCrashBehavior {
@@ -1178,6 +1203,28 @@ pub struct AuthorityStorePruningConfig {
     pub smooth: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_epochs_to_retain_for_indexes: Option<u64>,
+    /// rules that retain old versions of matching objects, and the transactions that touched
+    /// them, for longer than the rest of the store. Rejected at startup when using tidehunter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retention_rules: Vec<PruningRetentionRule>,
+}
+
+/// Retains old versions of the objects matching all of the given criteria, and the transactions
+/// that touched them, for `num_epochs_to_retain` epochs. Retention rules can only extend the
+/// retention configured for the rest of the store.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PruningRetentionRule {
+    /// Move package, module or type of matching objects, e.g. `0x2`, `0x2::coin` or
+    /// `0x2::coin::Coin<0x2::sui::SUI>`. A type without type parameters matches all of its
+    /// instantiations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+    /// Address that owns matching objects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<SuiAddress>,
+    /// number of epochs to retain old versions of matching objects and their transactions for
+    pub num_epochs_to_retain: u64,
 }
 
 fn default_num_latest_epoch_dbs_to_retain() -> usize {
@@ -1218,6 +1265,7 @@ impl Default for AuthorityStorePruningConfig {
             killswitch_tombstone_pruning: false,
             smooth: true,
             num_epochs_to_retain_for_indexes: None,
+            retention_rules: vec![],
         }
     }
 }
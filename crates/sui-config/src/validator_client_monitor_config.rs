@@ -66,6 +66,19 @@
 //!   - Increase for latency-sensitive applications
 //!   - Individual operation weights can be tuned separately
 //!
+//! ### Validator Selection
+//!
+//! - `selection-strategy`: How validators are ranked as targets of requests
+//!   - Default: `shuffled-preferred`, which shuffles the validators within
+//!     `selection-latency-delta` of the lowest latency to balance load among them
+//!   - `lowest-latency` always prefers the validator with the lowest adjusted latency
+//!   - `random` ignores client-observed performance
+//!
+//! - `hedge-delay`: Delay before a full effects request is also sent to the second best
+//!   validator when the best one has not responded yet
+//!   - Default: unset, no hedged requests are sent
+//!   - Set around the p90 of `validator_client_observed_latency{operation="effects"}`
+//!
 //! # Example Configurations
 //!
 //! ## Low Latency Priority
@@ -128,6 +141,37 @@ pub struct ValidatorClientMonitorConfig {
     /// Size of the moving window for reliability measurements
     #[serde(default = "default_reliability_moving_window_size")]
     pub reliability_moving_window_size: usize,
+
+    /// Strategy used to rank validators as targets of requests.
+    #[serde(default)]
+    pub selection_strategy: ValidatorSelectionStrategyConfig,
+
+    /// Validators with latencies within this fraction of the lowest latency are shuffled by the
+    /// `shuffled-preferred` selection strategy.
+    #[serde(
+        default = "default_selection_latency_delta",
+        deserialize_with = "deserialize_selection_latency_delta"
+    )]
+    pub selection_latency_delta: f64,
+
+    /// When set, full effects requests are also sent to the second best validator if the best
+    /// validator has not responded within this delay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hedge_delay: Option<Duration>,
+}
+
+/// Strategies to rank validators by their client-observed performance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValidatorSelectionStrategyConfig {
+    /// Validators are sorted by latency, with the ones within `selection_latency_delta` of the
+    /// lowest latency shuffled.
+    #[default]
+    ShuffledPreferred,
+    /// Validators are sorted by latency.
+    LowestLatency,
+    /// Validators are shuffled regardless of their latency.
+    Random,
 }
 
 impl Default for ValidatorClientMonitorConfig {
@@ -138,6 +182,9 @@ impl Default for ValidatorClientMonitorConfig {
             reliability_weight: default_reliability_weight(),
             latency_moving_window_size: default_latency_moving_window_size(),
             reliability_moving_window_size: default_reliability_moving_window_size(),
+            selection_strategy: ValidatorSelectionStrategyConfig::default(),
+            selection_latency_delta: default_selection_latency_delta(),
+            hedge_delay: None,
         }
     }
 }
@@ -162,3 +209,28 @@ fn default_latency_moving_window_size() -> usize {
 fn default_reliability_moving_window_size() -> usize {
     20
 }
+
+fn default_selection_latency_delta() -> f64 {
+    0.02
+}
+
+/// Checks that `delta` can be used as `selection_latency_delta`, which must be finite and not
+/// negative.
+pub fn validate_selection_latency_delta(delta: f64) -> Result<f64, String> {
+    if delta.is_finite() && delta >= 0.0 {
+        Ok(delta)
+    } else {
+        Err(format!(
+            "selection-latency-delta must be a finite, non-negative number, got {delta}"
+        ))
+    }
+}
+
+fn deserialize_selection_latency_delta<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    validate_selection_latency_delta(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+}
@@ -10,8 +10,10 @@ use crate::accumulators::transaction_rewriting::rewrite_transaction_for_coin_res
 use crate::accumulators::{self, AccumulatorSettlementTxBuilder};
 use crate::checkpoints::CheckpointBuilderError;
 use crate::checkpoints::CheckpointBuilderResult;
+use crate::checkpoints::checkpoint_executor::control::CheckpointExecutionControl;
 use crate::congestion_tracker::CongestionTracker;
 use crate::consensus_adapter::ConsensusOverloadChecker;
+use crate::db_checkpoint_handler::ON_DEMAND_DB_CHECKPOINT_PREFIX;
 use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::execution_cache::writeback_cache::WritebackCache;
@@ -255,10 +257,12 @@ pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
 pub mod congestion_log;
+pub mod congestion_simulator;
 pub mod consensus_tx_status_cache;
 pub(crate) mod epoch_marker_key;
 pub mod epoch_start_configuration;
 pub mod execution_time_estimator;
+pub mod pruner_retention_rules;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
 pub mod submitted_transaction_cache;
@@ -936,6 +940,9 @@ pub struct AuthorityState {
 
     pub(crate) congestion_tracker: Arc<CongestionTracker>,
 
+    /// Operator controls of the checkpoint executor, which outlive the executor of each epoch.
+    pub checkpoint_execution_control: Arc<CheckpointExecutionControl>,
+
     /// Consumed by gasless tx rate limiter.
     pub(crate) consensus_gasless_counter: Arc<ConsensusGaslessCounter>,
 
@@ -3782,7 +3789,7 @@ impl AuthorityState {
         policy_config: Option<PolicyConfig>,
         firewall_config: Option<RemoteFirewallConfig>,
         pruner_watermarks: Arc<PrunerWatermarks>,
-    ) -> Arc<Self> {
+    ) -> anyhow::Result<Arc<Self>> {
         Self::check_protocol_version(supported_protocol_versions, epoch_store.protocol_version());
 
         let metrics = Arc::new(AuthorityMetrics::new(prometheus_registry));
@@ -3815,7 +3822,7 @@ impl AuthorityState {
             epoch_store.epoch_start_state().epoch_duration_ms(),
             prometheus_registry,
             pruner_watermarks,
-        );
+        )?;
         let input_loader =
             TransactionInputLoader::new(execution_cache_trait_pointers.object_cache_reader.clone());
         let epoch = epoch_store.epoch();
@@ -3843,6 +3850,9 @@ impl AuthorityState {
             execution_cache_trait_pointers.child_object_resolver.clone(),
         ));
 
+        let highest_executed_checkpoint =
+            checkpoint_store.get_highest_executed_checkpoint_seq_number()?;
+
         let object_funds_checker_metrics =
             Arc::new(ObjectFundsCheckerMetrics::new(prometheus_registry));
         let state = Arc::new(AuthorityState {
@@ -3868,6 +3878,9 @@ impl AuthorityState {
             overload_info: AuthorityOverloadInfo::default(),
             chain_identifier,
             congestion_tracker: Arc::new(CongestionTracker::new()),
+            checkpoint_execution_control: Arc::new(CheckpointExecutionControl::new(
+                highest_executed_checkpoint,
+            )),
             consensus_gasless_counter: Arc::new(ConsensusGaslessCounter::default()),
             traffic_controller,
             fork_recovery_state,
@@ -3919,7 +3932,7 @@ impl AuthorityState {
             }
         }
 
-        state
+        Ok(state)
     }
 
     async fn init_object_funds_checker(&self) {
@@ -4211,7 +4224,12 @@ impl AuthorityState {
                 .unwrap_or(false);
             let current_epoch = cur_epoch_store.epoch();
             let epoch_checkpoint_path = checkpoint_path.join(format!("epoch_{}", current_epoch));
-            self.checkpoint_all_dbs(&epoch_checkpoint_path, cur_epoch_store, checkpoint_indexes)?;
+            self.checkpoint_all_dbs(
+                &epoch_checkpoint_path,
+                cur_epoch_store,
+                checkpoint_indexes,
+                false,
+            )?;
         }
 
         self.get_reconfig_api()
@@ -4567,6 +4585,7 @@ impl AuthorityState {
         checkpoint_path: &Path,
         cur_epoch_store: &AuthorityPerEpochStore,
         checkpoint_indexes: bool,
+        checkpoint_epoch_db: bool,
     ) -> SuiResult {
         let _metrics_guard = self.metrics.db_checkpoint_latency.start_timer();
         let current_epoch = cur_epoch_store.epoch();
@@ -4594,6 +4613,11 @@ impl AuthorityState {
         self.checkpoint_store
             .checkpoint_db(&checkpoint_path_tmp.join("checkpoints"))?;
 
+        // The epoch db is needed to resume from a db checkpoint taken in the middle of an epoch.
+        if checkpoint_epoch_db {
+            cur_epoch_store.checkpoint_db(&store_checkpoint_path_tmp)?;
+        }
+
         self.get_reconfig_api()
             .checkpoint_db(&store_checkpoint_path_tmp.join("perpetual"))?;
 
@@ -4609,6 +4633,43 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Takes a db checkpoint of the node outside of epoch boundaries, in a directory under the
+    /// configured db checkpoint path named after the highest executed checkpoint. The checkpoint
+    /// db is captured first, so the restored node resumes execution from a checkpoint at or
+    /// after the returned one and re-executes any checkpoints already applied to the store.
+    pub fn checkpoint_all_dbs_on_demand(
+        &self,
+        cur_epoch_store: &AuthorityPerEpochStore,
+    ) -> SuiResult<(CheckpointSequenceNumber, PathBuf)> {
+        let Some(checkpoint_root) = &self.db_checkpoint_config.checkpoint_path else {
+            return Err(SuiErrorKind::UnsupportedFeatureError {
+                error: "db checkpoint path is not configured".to_string(),
+            }
+            .into());
+        };
+        let checkpoint_seq = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?
+            .unwrap_or_default();
+        let checkpoint_path =
+            checkpoint_root.join(format!("{ON_DEMAND_DB_CHECKPOINT_PREFIX}{checkpoint_seq}"));
+        // `checkpoint_all_dbs` skips existing db checkpoints, which would report success
+        // without taking a new one.
+        if checkpoint_path.exists() {
+            return Err(SuiErrorKind::FileIOError(format!(
+                "db checkpoint already exists at {}",
+                checkpoint_path.display()
+            ))
+            .into());
+        }
+        let checkpoint_indexes = self
+            .db_checkpoint_config
+            .perform_index_db_checkpoints_at_epoch_end
+            .unwrap_or(false);
+        self.checkpoint_all_dbs(&checkpoint_path, cur_epoch_store, checkpoint_indexes, true)?;
+        Ok((checkpoint_seq, checkpoint_path))
+    }
+
     /// Load the current epoch store. This can change during reconfiguration. To ensure that
     /// we never end up accessing different epoch stores in a single task, we need to make sure
     /// that this is called once per task. Each call needs to be carefully audited to ensure it is
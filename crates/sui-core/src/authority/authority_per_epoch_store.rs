@@ -89,7 +89,10 @@ use typed_store::rocksdb::Options;
 use super::authority_store_tables::ENV_VAR_LOCKS_BLOCK_CACHE_SIZE;
 use super::consensus_tx_status_cache::{ConsensusTxStatus, ConsensusTxStatusCache};
 use super::epoch_start_configuration::EpochStartConfigTrait;
-use super::execution_time_estimator::{ConsensusObservations, ExecutionTimeEstimator};
+use super::execution_time_estimator::{
+    ConsensusObservations, ExecutionTimeEstimatesDump, ExecutionTimeEstimator,
+    ExecutionTimeObserverSnapshot,
+};
 use super::shared_object_congestion_tracker::{
     CongestionPerObjectDebt, SharedObjectCongestionTracker,
 };
@@ -444,6 +447,8 @@ pub struct AuthorityPerEpochStore {
     pub(crate) execution_time_estimator: tokio::sync::Mutex<ExecutionTimeEstimator>,
     tx_local_execution_time: OnceCell<mpsc::Sender<LocalExecutionTimeData>>,
     pub(crate) tx_object_debts: OnceCell<mpsc::Sender<Vec<ObjectID>>>,
+    tx_execution_time_observer_snapshot:
+        OnceCell<mpsc::Sender<oneshot::Sender<ExecutionTimeObserverSnapshot>>>,
     // Saved at end of epoch for propagating observations to the next.
     pub(crate) end_of_epoch_execution_time_observations: OnceCell<StoredExecutionTimeObservations>,
 
@@ -906,6 +911,11 @@ impl AuthorityEpochTables {
         parent_path.join(format!("{}{}", EPOCH_DB_PREFIX, epoch))
     }
 
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        // This checkpoints the entire db and not just the reconfig state table
+        self.reconfig_state.checkpoint_db(path).map_err(Into::into)
+    }
+
     fn load_reconfig_state(&self) -> SuiResult<ReconfigState> {
         let state = self
             .reconfig_state
@@ -1266,6 +1276,7 @@ impl AuthorityPerEpochStore {
             execution_time_estimator: tokio::sync::Mutex::new(execution_time_estimator),
             tx_local_execution_time: OnceCell::new(),
             tx_object_debts: OnceCell::new(),
+            tx_execution_time_observer_snapshot: OnceCell::new(),
             end_of_epoch_execution_time_observations: OnceCell::new(),
             consensus_tx_status_cache,
             tx_reject_reason_cache,
@@ -1286,6 +1297,13 @@ impl AuthorityPerEpochStore {
         }
     }
 
+    /// Takes a db checkpoint of the tables of this epoch in `store_path`, next to the perpetual
+    /// store, in the same layout as the db directory of the node.
+    pub fn checkpoint_db(&self, store_path: &Path) -> SuiResult {
+        self.tables()?
+            .checkpoint_db(&AuthorityEpochTables::path(self.epoch(), store_path))
+    }
+
     // Ideally the epoch tables handle should have the same lifetime as the outer AuthorityPerEpochStore,
     // and this function should be unnecessary. But unfortunately, Arc<AuthorityPerEpochStore> outlives the
     // epoch significantly right now, so we need to manually release the tables to release its memory usage.
@@ -1648,6 +1666,7 @@ impl AuthorityPerEpochStore {
             u64, // gas_price
         )>,
         tx_object_debts: mpsc::Sender<Vec<ObjectID>>,
+        tx_snapshot_requests: mpsc::Sender<oneshot::Sender<ExecutionTimeObserverSnapshot>>,
     ) {
         if let Err(e) = self.tx_local_execution_time.set(tx_local_execution_time) {
             debug_fatal!(
@@ -1657,6 +1676,14 @@ impl AuthorityPerEpochStore {
         if let Err(e) = self.tx_object_debts.set(tx_object_debts) {
             debug_fatal!("failed to set tx_object_debts channel on AuthorityPerEpochStore: {e:?}");
         }
+        if let Err(e) = self
+            .tx_execution_time_observer_snapshot
+            .set(tx_snapshot_requests)
+        {
+            debug_fatal!(
+                "failed to set tx_execution_time_observer_snapshot channel on AuthorityPerEpochStore: {e:?}"
+            );
+        }
     }
 
     pub fn record_local_execution_time(
@@ -3826,6 +3853,27 @@ impl AuthorityPerEpochStore {
             .get_observations()
     }
 
+    /// Only used by admin API
+    pub async fn get_execution_time_estimates_dump(&self) -> ExecutionTimeEstimatesDump {
+        // Local observations are only available if this node runs an ExecutionTimeObserver.
+        let observer = match self.tx_execution_time_observer_snapshot.get() {
+            Some(tx_snapshot_requests) => {
+                let (tx, rx) = oneshot::channel();
+                if tx_snapshot_requests.send(tx).await.is_ok() {
+                    rx.await.unwrap_or_default()
+                } else {
+                    ExecutionTimeObserverSnapshot::default()
+                }
+            }
+            None => ExecutionTimeObserverSnapshot::default(),
+        };
+        ExecutionTimeEstimatesDump::new(
+            self.epoch(),
+            &*self.execution_time_estimator.lock().await,
+            observer,
+        )
+    }
+
     /// Whether this node is a validator in this epoch.
     pub fn is_validator(&self) -> bool {
         self.committee.authority_exists(&self.name)
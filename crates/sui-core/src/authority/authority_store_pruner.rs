@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::authority_store_tables::AuthorityPerpetualTables;
+use super::pruner_retention_rules::RetentionRules;
 use crate::checkpoints::{CheckpointStore, CheckpointWatermark};
 use crate::jsonrpc_index::IndexStore;
 use crate::rpc_index::RpcIndexStore;
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use mysten_metrics::{monitored_scope, spawn_monitored_task};
 use once_cell::sync::Lazy;
 use prometheus::{
@@ -15,7 +16,7 @@ use prometheus::{
 #[cfg(tidehunter)]
 use serde::de::DeserializeOwned;
 use std::cmp::{max, min};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::sync::Mutex;
 use std::sync::atomic::AtomicU64;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -72,6 +73,11 @@ pub struct AuthorityStorePruningMetrics {
     pub last_pruned_indexes_transaction: IntGauge,
     pub num_epochs_to_retain_for_objects: IntGauge,
     pub num_epochs_to_retain_for_checkpoints: IntGauge,
+    pub num_retention_rules: IntGauge,
+    pub num_retained_objects: IntCounter,
+    pub num_retained_transactions: IntCounter,
+    pub num_pruned_retained_objects: IntCounter,
+    pub num_pruned_retained_transactions: IntCounter,
 }
 
 impl AuthorityStorePruningMetrics {
@@ -119,6 +125,36 @@ impl AuthorityStorePruningMetrics {
                 registry
             )
             .unwrap(),
+            num_retention_rules: register_int_gauge_with_registry!(
+                "pruner_num_retention_rules",
+                "Number of configured pruning retention rules",
+                registry
+            )
+            .unwrap(),
+            num_retained_objects: register_int_counter_with_registry!(
+                "pruner_num_retained_objects",
+                "Number of object versions retained past their pruning point by retention rules",
+                registry
+            )
+            .unwrap(),
+            num_retained_transactions: register_int_counter_with_registry!(
+                "pruner_num_retained_transactions",
+                "Number of transactions retained past their pruning point by retention rules",
+                registry
+            )
+            .unwrap(),
+            num_pruned_retained_objects: register_int_counter_with_registry!(
+                "pruner_num_pruned_retained_objects",
+                "Number of retained object versions pruned after their retention expired",
+                registry
+            )
+            .unwrap(),
+            num_pruned_retained_transactions: register_int_counter_with_registry!(
+                "pruner_num_pruned_retained_transactions",
+                "Number of retained transactions pruned after their retention expired",
+                registry
+            )
+            .unwrap(),
         };
         Arc::new(this)
     }
@@ -134,7 +170,95 @@ pub enum PruningMode {
     Checkpoints,
 }
 
+/// Object versions and transactions that retention rules keep past their regular pruning point,
+/// with the first epoch in which they may be pruned.
+#[cfg(not(tidehunter))]
+#[derive(Default)]
+struct RetainedData {
+    objects: HashMap<ObjectKey, EpochId>,
+    transactions: HashMap<TransactionDigest, EpochId>,
+}
+
 impl AuthorityStorePruner {
+    /// Finds the object versions superseded by `transaction_effects`, and the transactions
+    /// themselves, that retention rules keep past `current_epoch`. The objects written by the
+    /// transactions are still in the store, as they can only be superseded by later transactions.
+    #[cfg(not(tidehunter))]
+    fn apply_retention_rules(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        transaction_effects: &[TransactionEffects],
+        retention_rules: &RetentionRules,
+        current_epoch: EpochId,
+    ) -> anyhow::Result<RetainedData> {
+        let mut retained = RetainedData::default();
+        let Some(max_num_epochs_to_retain) = retention_rules.max_num_epochs_to_retain() else {
+            return Ok(retained);
+        };
+
+        for effects in transaction_effects {
+            let epoch = effects.executed_epoch();
+            // No rule retains anything from this long ago.
+            if epoch.saturating_add(max_num_epochs_to_retain) <= current_epoch {
+                continue;
+            }
+
+            // Only objects whose owner could match a rule are loaded. The owners of input
+            // versions are only recorded from effects v2 onwards.
+            let input_keys: Vec<_> = match effects {
+                TransactionEffects::V1(_) => effects
+                    .modified_at_versions()
+                    .into_iter()
+                    .map(|(object_id, version)| ObjectKey(object_id, version))
+                    .collect(),
+                TransactionEffects::V2(_) => effects
+                    .old_object_metadata()
+                    .into_iter()
+                    .filter(|(_, owner)| retention_rules.may_match_owner(owner))
+                    .map(|((object_id, version, _), _)| ObjectKey(object_id, version))
+                    .collect(),
+            };
+            let output_keys: Vec<_> = effects
+                .all_changed_objects()
+                .into_iter()
+                .filter(|(_, owner, _)| retention_rules.may_match_owner(owner))
+                .map(|((object_id, version, _), _, _)| ObjectKey(object_id, version))
+                .collect();
+            if input_keys.is_empty() && output_keys.is_empty() {
+                continue;
+            }
+
+            let keys: Vec<_> = input_keys.iter().chain(&output_keys).copied().collect();
+            let objects = perpetual_db.objects.multi_get(&keys)?;
+
+            let mut transaction_retained_until = None;
+            for (i, (key, object)) in keys.iter().zip(objects).enumerate() {
+                let Some(object) = object else {
+                    continue;
+                };
+                let Some(object) = perpetual_db.object(key, object)? else {
+                    continue;
+                };
+                let Some(retained_until) =
+                    retention_rules.retained_until(&object, epoch, current_epoch)
+                else {
+                    continue;
+                };
+                // Only the input versions are superseded, and would otherwise be pruned.
+                if i < input_keys.len() {
+                    retained.objects.insert(*key, retained_until);
+                }
+                transaction_retained_until = max(transaction_retained_until, Some(retained_until));
+            }
+
+            if let Some(retained_until) = transaction_retained_until {
+                retained
+                    .transactions
+                    .insert(*effects.transaction_digest(), retained_until);
+            }
+        }
+        Ok(retained)
+    }
+
     /// prunes old versions of objects based on transaction effects
     #[cfg(not(tidehunter))]
     async fn prune_objects_and_indexes(
@@ -145,16 +269,28 @@ impl AuthorityStorePruner {
         checkpoint_content_to_prune: Vec<CheckpointContents>,
         rpc_index: Option<&RpcIndexStore>,
         enable_pruning_tombstones: bool,
+        retention_rules: &RetentionRules,
+        current_epoch: EpochId,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
 
+        let retained = Self::apply_retention_rules(
+            perpetual_db,
+            &transaction_effects,
+            retention_rules,
+            current_epoch,
+        )?;
+
         // Collect objects keys that need to be deleted from `transaction_effects`.
         let mut live_object_keys_to_prune = vec![];
         let mut object_tombstones_to_prune = vec![];
         for effects in &transaction_effects {
             for (object_id, seq_number) in effects.modified_at_versions() {
-                live_object_keys_to_prune.push(ObjectKey(object_id, seq_number));
+                let object_key = ObjectKey(object_id, seq_number);
+                if !retained.objects.contains_key(&object_key) {
+                    live_object_keys_to_prune.push(object_key);
+                }
             }
 
             if enable_pruning_tombstones {
@@ -172,8 +308,20 @@ impl AuthorityStorePruner {
             .num_pruned_tombstones
             .inc_by(object_tombstones_to_prune.len() as u64);
 
+        // Versions of objects that have retained versions in this batch are deleted one by one, so
+        // that the retained versions aren't covered by a range delete.
+        let objects_with_retained_versions: HashSet<ObjectID> = retained
+            .objects
+            .keys()
+            .map(|ObjectKey(id, _)| *id)
+            .collect();
+        let mut object_keys_to_delete = vec![];
         let mut updates: HashMap<ObjectID, (VersionNumber, VersionNumber)> = HashMap::new();
         for ObjectKey(object_id, seq_number) in live_object_keys_to_prune {
+            if objects_with_retained_versions.contains(&object_id) {
+                object_keys_to_delete.push(ObjectKey(object_id, seq_number));
+                continue;
+            }
             updates
                 .entry(object_id)
                 .and_modify(|range| *range = (min(range.0, seq_number), max(range.1, seq_number)))
@@ -189,6 +337,7 @@ impl AuthorityStorePruner {
             let end_range = ObjectKey(object_id, (max_version.value() + 1).into());
             wb.schedule_delete_range(&perpetual_db.objects, &start_range, &end_range)?;
         }
+        wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
 
         // When enable_pruning_tombstones is enabled, instead of using range deletes, we need to do a scan of all the keys
         // for the deleted objects and then do point deletes to delete all the existing keys. This is because to improve read
@@ -204,13 +353,53 @@ impl AuthorityStorePruner {
                 ) {
                     let (object_key, _) = result?;
                     assert_eq!(object_key.0, object_id);
-                    object_keys_to_delete.push(object_key);
+                    if !retained.objects.contains_key(&object_key) {
+                        object_keys_to_delete.push(object_key);
+                    }
                 }
             }
 
+            // Keep the versions retained while pruning earlier batches.
+            if !retention_rules.is_empty() {
+                let previously_retained = perpetual_db
+                    .pruner_retained_objects
+                    .multi_contains_keys(&object_keys_to_delete)?;
+                object_keys_to_delete = object_keys_to_delete
+                    .into_iter()
+                    .zip(previously_retained)
+                    .filter_map(|(key, retained)| (!retained).then_some(key))
+                    .collect();
+            }
+
             wb.delete_batch(&perpetual_db.objects, object_keys_to_delete)?;
         }
 
+        metrics
+            .num_retained_objects
+            .inc_by(retained.objects.len() as u64);
+        metrics
+            .num_retained_transactions
+            .inc_by(retained.transactions.len() as u64);
+        wb.insert_batch(
+            &perpetual_db.pruner_retained_objects_by_expiry,
+            retained
+                .objects
+                .iter()
+                .map(|(key, retained_until)| ((*retained_until, *key), ())),
+        )?;
+        wb.insert_batch(
+            &perpetual_db.pruner_retained_transactions_by_expiry,
+            retained
+                .transactions
+                .iter()
+                .map(|(digest, retained_until)| ((*retained_until, *digest), ())),
+        )?;
+        wb.insert_batch(&perpetual_db.pruner_retained_objects, retained.objects)?;
+        wb.insert_batch(
+            &perpetual_db.pruner_retained_transactions,
+            retained.transactions,
+        )?;
+
         if let Some(rpc_index) = rpc_index {
             rpc_index.prune(checkpoint_number, &checkpoint_content_to_prune)?;
         }
@@ -231,6 +420,8 @@ impl AuthorityStorePruner {
         checkpoint_content_to_prune: Vec<CheckpointContents>,
         rpc_index: Option<&RpcIndexStore>,
         _: bool,
+        _: &RetentionRules,
+        _: EpochId,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("ObjectsLivePruner");
         let mut wb = perpetual_db.objects.batch();
@@ -269,6 +460,8 @@ impl AuthorityStorePruner {
         checkpoint_content_to_prune: Vec<CheckpointContents>,
         effects_to_prune: &Vec<TransactionEffects>,
         metrics: Arc<AuthorityStorePruningMetrics>,
+        retention_rules: &RetentionRules,
+        current_epoch: EpochId,
     ) -> anyhow::Result<()> {
         let _scope = monitored_scope("EffectsLivePruner");
 
@@ -278,6 +471,28 @@ impl AuthorityStorePruner {
             .flat_map(|content| content.iter().map(|tx| tx.transaction))
             .collect();
 
+        // Transactions retained by the objects pruner are pruned once their retention expires.
+        let retained_transactions: HashSet<TransactionDigest> = if retention_rules.is_empty() {
+            HashSet::new()
+        } else {
+            transactions
+                .iter()
+                .zip(
+                    perpetual_db
+                        .pruner_retained_transactions
+                        .multi_get(&transactions)?,
+                )
+                .filter(|(_, retained_until)| {
+                    retained_until.is_some_and(|retained_until| retained_until > current_epoch)
+                })
+                .map(|(digest, _)| *digest)
+                .collect()
+        };
+        let transactions: Vec<_> = transactions
+            .into_iter()
+            .filter(|digest| !retained_transactions.contains(digest))
+            .collect();
+
         perpetual_batch.delete_batch(&perpetual_db.transactions, transactions.iter())?;
         perpetual_batch.delete_batch(&perpetual_db.executed_effects, transactions.iter())?;
         perpetual_batch.delete_batch(
@@ -287,6 +502,9 @@ impl AuthorityStorePruner {
 
         let mut effect_digests = vec![];
         for effects in effects_to_prune {
+            if retained_transactions.contains(effects.transaction_digest()) {
+                continue;
+            }
             let effects_digest = effects.digest();
             debug!("Pruning effects {:?}", effects_digest);
             effect_digests.push(effects_digest);
@@ -461,6 +679,7 @@ impl AuthorityStorePruner {
             .get_highest_executed_checkpoint()?
             .map(|c| c.epoch())
             .unwrap_or_default();
+        let retention_rules = RetentionRules::new(&config.retention_rules)?;
 
         let mut checkpoints_to_prune = vec![];
         let mut checkpoint_content_to_prune = vec![];
@@ -516,6 +735,8 @@ impl AuthorityStorePruner {
                             checkpoint_content_to_prune,
                             rpc_index,
                             !config.killswitch_tombstone_pruning,
+                            &retention_rules,
+                            current_epoch,
                         )
                         .await?
                     }
@@ -527,6 +748,8 @@ impl AuthorityStorePruner {
                         checkpoint_content_to_prune,
                         &effects_to_prune,
                         metrics.clone(),
+                        &retention_rules,
+                        current_epoch,
                     )?,
                 };
                 checkpoints_to_prune = vec![];
@@ -548,6 +771,8 @@ impl AuthorityStorePruner {
                         checkpoint_content_to_prune,
                         rpc_index,
                         !config.killswitch_tombstone_pruning,
+                        &retention_rules,
+                        current_epoch,
                     )
                     .await?
                 }
@@ -559,12 +784,119 @@ impl AuthorityStorePruner {
                     checkpoint_content_to_prune,
                     &effects_to_prune,
                     metrics.clone(),
+                    &retention_rules,
+                    current_epoch,
                 )?,
             };
         }
         Ok(())
     }
 
+    /// Prunes the object versions and transactions kept by retention rules once their retention
+    /// expires. Runs at most once per epoch.
+    #[cfg(not(tidehunter))]
+    fn prune_expired_retained_data(
+        perpetual_db: &Arc<AuthorityPerpetualTables>,
+        checkpoint_store: &Arc<CheckpointStore>,
+        config: &AuthorityStorePruningConfig,
+        metrics: &AuthorityStorePruningMetrics,
+        last_pruned_epoch: &mut Option<EpochId>,
+    ) -> anyhow::Result<()> {
+        let _scope = monitored_scope("PruneExpiredRetainedData");
+        let current_epoch = checkpoint_store
+            .get_highest_executed_checkpoint()?
+            .map(|c| c.epoch())
+            .unwrap_or_default();
+        if *last_pruned_epoch == Some(current_epoch) {
+            return Ok(());
+        }
+
+        // Everything retained until an epoch up to and including the current one has expired.
+        let expiry_end = current_epoch.saturating_add(1);
+        let mut expired_object_entries = vec![];
+        for item in perpetual_db
+            .pruner_retained_objects_by_expiry
+            .safe_range_iter((0, ObjectKey::ZERO)..(expiry_end, ObjectKey::ZERO))
+        {
+            let (entry, _) = item?;
+            expired_object_entries.push(entry);
+        }
+        let expired_objects: Vec<_> = expired_object_entries
+            .iter()
+            .map(|(_, object_key)| *object_key)
+            .collect();
+
+        // A retained transaction is pruned here only if the checkpoints pruner has already moved
+        // past it. Otherwise the checkpoints pruner prunes it once it gets there.
+        let checkpoints_pruning_enabled = config.num_epochs_to_retain_for_checkpoints().is_some();
+        let highest_pruned_checkpoint =
+            checkpoint_store.get_highest_pruned_checkpoint_seq_number()?;
+        let mut expired_transactions = vec![];
+        let mut transactions_to_prune = vec![];
+        for item in perpetual_db
+            .pruner_retained_transactions_by_expiry
+            .safe_range_iter((0, TransactionDigest::ZERO)..(expiry_end, TransactionDigest::ZERO))
+        {
+            let ((retained_until, digest), _) = item?;
+            if let Some((_, checkpoint)) = perpetual_db
+                .executed_transactions_to_checkpoint
+                .get(&digest)?
+                && checkpoints_pruning_enabled
+            {
+                if highest_pruned_checkpoint.is_none_or(|highest| checkpoint > highest) {
+                    continue;
+                }
+                transactions_to_prune.push(digest);
+            }
+            expired_transactions.push((retained_until, digest));
+        }
+
+        let mut wb = perpetual_db.objects.batch();
+        wb.delete_batch(&perpetual_db.objects, &expired_objects)?;
+        wb.delete_batch(&perpetual_db.pruner_retained_objects, &expired_objects)?;
+        wb.delete_batch(
+            &perpetual_db.pruner_retained_objects_by_expiry,
+            &expired_object_entries,
+        )?;
+
+        let effects_digests: Vec<_> = perpetual_db
+            .executed_effects
+            .multi_get(&transactions_to_prune)?
+            .into_iter()
+            .flatten()
+            .collect();
+        wb.delete_batch(&perpetual_db.transactions, &transactions_to_prune)?;
+        wb.delete_batch(&perpetual_db.executed_effects, &transactions_to_prune)?;
+        wb.delete_batch(
+            &perpetual_db.executed_transactions_to_checkpoint,
+            &transactions_to_prune,
+        )?;
+        wb.delete_batch(&perpetual_db.events_2, &transactions_to_prune)?;
+        wb.delete_batch(
+            &perpetual_db.unchanged_loaded_runtime_objects,
+            &transactions_to_prune,
+        )?;
+        wb.delete_batch(&perpetual_db.effects, effects_digests)?;
+        wb.delete_batch(
+            &perpetual_db.pruner_retained_transactions,
+            expired_transactions.iter().map(|(_, digest)| digest),
+        )?;
+        wb.delete_batch(
+            &perpetual_db.pruner_retained_transactions_by_expiry,
+            &expired_transactions,
+        )?;
+        wb.write()?;
+
+        metrics
+            .num_pruned_retained_objects
+            .inc_by(expired_objects.len() as u64);
+        metrics
+            .num_pruned_retained_transactions
+            .inc_by(transactions_to_prune.len() as u64);
+        *last_pruned_epoch = Some(current_epoch);
+        Ok(())
+    }
+
     fn prune_indexes(
         indexes: Option<&IndexStore>,
         config: &AuthorityStorePruningConfig,
@@ -837,6 +1169,9 @@ impl AuthorityStorePruner {
                 .num_epochs_to_retain_for_checkpoints
                 .unwrap_or_default() as i64,
         );
+        metrics
+            .num_retention_rules
+            .set(config.retention_rules.len() as i64);
 
         #[cfg(tidehunter)]
         {
@@ -890,12 +1225,16 @@ impl AuthorityStorePruner {
                 });
             }
             tokio::task::spawn(async move {
+                let mut retained_data_pruned_epoch = None;
                 loop {
                     tokio::select! {
                         _ = objects_prune_interval.tick(), if config.num_epochs_to_retain != u64::MAX => {
                             if let Err(err) = Self::prune_objects_for_eligible_epochs(&perpetual_db, &checkpoint_store, rpc_index.as_deref(), config.clone(), metrics.clone(), epoch_duration_ms).await {
                                 error!("Failed to prune objects: {:?}", err);
                             }
+                            if let Err(err) = Self::prune_expired_retained_data(&perpetual_db, &checkpoint_store, &config, &metrics, &mut retained_data_pruned_epoch) {
+                                error!("Failed to prune expired retained data: {:?}", err);
+                            }
                             if let Err(err) = Self::prune_executed_tx_digests(&perpetual_db, &checkpoint_store).await {
                                 error!("Failed to prune executed_tx_digests: {:?}", err);
                             }
@@ -928,7 +1267,14 @@ impl AuthorityStorePruner {
         epoch_duration_ms: u64,
         registry: &Registry,
         pruner_watermarks: Arc<PrunerWatermarks>, // used by tidehunter relocation filters
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
+        RetentionRules::new(&pruning_config.retention_rules)
+            .context("Invalid pruning retention rules")?;
+        // Tidehunter prunes through relocation filters, which can't tell retained data apart.
+        #[cfg(tidehunter)]
+        if !pruning_config.retention_rules.is_empty() {
+            anyhow::bail!("Pruning retention rules are not supported with tidehunter");
+        }
         if pruning_config.num_epochs_to_retain > 0 && pruning_config.num_epochs_to_retain < u64::MAX
         {
             warn!(
@@ -942,7 +1288,7 @@ impl AuthorityStorePruner {
                 warn!("Consider using an aggressive pruner (num_epochs_to_retain = 0)");
             }
         }
-        AuthorityStorePruner {
+        Ok(AuthorityStorePruner {
             _objects_pruner_cancel_handle: Self::setup_pruning(
                 pruning_config,
                 epoch_duration_ms,
@@ -953,7 +1299,7 @@ impl AuthorityStorePruner {
                 AuthorityStorePruningMetrics::new(registry),
                 pruner_watermarks,
             ),
-        }
+        })
     }
 
     pub fn compact(perpetual_db: &Arc<AuthorityPerpetualTables>) -> Result<(), TypedStoreError> {
@@ -1006,7 +1352,8 @@ mod tests {
         StoreObject, StoreObjectWrapper, get_store_object,
     };
     use prometheus::Registry;
-    use sui_types::base_types::ObjectDigest;
+    use sui_config::node::PruningRetentionRule;
+    use sui_types::base_types::{ObjectDigest, SuiAddress};
     use sui_types::effects::TransactionEffects;
     use sui_types::effects::TransactionEffectsAPI;
     use sui_types::{
@@ -1018,6 +1365,7 @@ mod tests {
     use typed_store::rocks::{DBMap, MetricConf, ReadWriteOptions, default_db_options};
 
     use super::AuthorityStorePruner;
+    use crate::authority::pruner_retention_rules::RetentionRules;
 
     fn get_keys_after_pruning(path: &Path) -> anyhow::Result<HashSet<ObjectKey>> {
         let perpetual_db_path = path.join(Path::new("perpetual"));
@@ -1147,6 +1495,8 @@ mod tests {
                 vec![],
                 None,
                 true,
+                &RetentionRules::default(),
+                0,
             )
             .await
             .unwrap();
@@ -1184,6 +1534,80 @@ mod tests {
         assert_eq!(get_keys_after_pruning(&path).unwrap().len(), 0);
     }
 
+    // Tests that old versions of objects matching a retention rule are kept.
+    #[cfg(not(tidehunter))]
+    #[tokio::test]
+    async fn test_pruning_retention_rules() {
+        let path = tempfile::tempdir().unwrap().keep();
+        let retained_owner = SuiAddress::random_for_testing_only();
+        let retention_rules = RetentionRules::new(&[PruningRetentionRule {
+            object_type: None,
+            owner: Some(retained_owner),
+            num_epochs_to_retain: 5,
+        }])
+        .unwrap();
+
+        let (retained, pruned) = {
+            let db = Arc::new(AuthorityPerpetualTables::open(&path, None, None));
+            let (mut retained, mut pruned) = (vec![], vec![]);
+            let mut effects = TransactionEffects::default();
+            let mut batch = db.objects.batch();
+            for owner in [retained_owner, SuiAddress::random_for_testing_only()] {
+                let id = ObjectID::random();
+                for version in 0..3 {
+                    let object_key = ObjectKey(id, SequenceNumber::from_u64(version));
+                    let obj = get_store_object(Object::with_id_owner_for_testing(id, owner));
+                    batch
+                        .insert_batch(&db.objects, [(object_key, obj)])
+                        .unwrap();
+                    // All but the latest version are pruned, unless retained.
+                    if version < 2 {
+                        effects.unsafe_add_deleted_live_object_for_testing((
+                            id,
+                            object_key.1,
+                            ObjectDigest::MIN,
+                        ));
+                        if owner == retained_owner {
+                            retained.push(object_key);
+                        } else {
+                            pruned.push(object_key);
+                        }
+                    }
+                }
+            }
+            batch.write().unwrap();
+
+            AuthorityStorePruner::prune_objects_and_indexes(
+                vec![effects],
+                &db,
+                0,
+                AuthorityStorePruningMetrics::new_for_test(),
+                vec![],
+                None,
+                true,
+                &retention_rules,
+                0,
+            )
+            .await
+            .unwrap();
+
+            for object_key in &retained {
+                assert_eq!(db.pruner_retained_objects.get(object_key).unwrap(), Some(5));
+                assert!(
+                    db.pruner_retained_objects_by_expiry
+                        .contains_key(&(5, *object_key))
+                        .unwrap()
+                );
+            }
+            (retained, pruned)
+        };
+        tokio::time::sleep(Duration::from_secs(3)).await;
+
+        let keys = get_keys_after_pruning(&path).unwrap();
+        assert!(retained.iter().all(|key| keys.contains(key)));
+        assert!(pruned.iter().all(|key| !keys.contains(key)));
+    }
+
     #[cfg(not(target_env = "msvc"))]
     #[tokio::test]
     async fn test_db_size_after_compaction() -> Result<(), anyhow::Error> {
@@ -1245,6 +1669,8 @@ mod tests {
             vec![],
             None,
             true,
+            &RetentionRules::default(),
+            0,
         )
         .await;
         info!("Total pruned keys = {:?}", total_pruned);
@@ -141,6 +141,22 @@ pub struct AuthorityPerpetualTables {
     /// Used to support address balance gas payments feature.
     /// This table uses epoch-prefixed keys to support efficient pruning via range delete.
     pub(crate) executed_transaction_digests: DBMap<(EpochId, TransactionDigest), ()>,
+
+    /// Old object versions that the pruner retains for longer because of retention rules, with
+    /// the first epoch in which they may be pruned.
+    pub(crate) pruner_retained_objects: DBMap<ObjectKey, EpochId>,
+
+    /// Transactions that the pruner retains for longer because of retention rules, with the
+    /// first epoch in which they may be pruned.
+    pub(crate) pruner_retained_transactions: DBMap<TransactionDigest, EpochId>,
+
+    /// `pruner_retained_objects` keyed by the first epoch in which they may be pruned, so that
+    /// expired versions are found with a range scan.
+    pub(crate) pruner_retained_objects_by_expiry: DBMap<(EpochId, ObjectKey), ()>,
+
+    /// `pruner_retained_transactions` keyed by the first epoch in which they may be pruned, so
+    /// that expired transactions are found with a range scan.
+    pub(crate) pruner_retained_transactions_by_expiry: DBMap<(EpochId, TransactionDigest), ()>,
 }
 
 impl AuthorityPerpetualTables {
@@ -407,6 +423,47 @@ impl AuthorityPerpetualTables {
                     ),
                 ),
             ),
+            // The pruner's retention tables stay empty, as retention rules are rejected when
+            // running on tidehunter.
+            (
+                "pruner_retained_objects".to_string(),
+                ThConfig::new_with_config_indexing(
+                    KeyIndexing::fixed(32 + 8),
+                    mutexes,
+                    KeyType::uniform(default_cells_per_mutex()),
+                    KeySpaceConfig::default(),
+                ),
+            ),
+            (
+                "pruner_retained_transactions".to_string(),
+                ThConfig::new_with_rm_prefix(
+                    32,
+                    mutexes,
+                    uniform_key,
+                    KeySpaceConfig::default(),
+                    digest_prefix.clone(),
+                ),
+            ),
+            (
+                "pruner_retained_objects_by_expiry".to_string(),
+                ThConfig::new_with_config_indexing(
+                    // EpochId + ObjectKey
+                    KeyIndexing::fixed(8 + (32 + 8)),
+                    mutexes,
+                    KeyType::uniform(default_cells_per_mutex()),
+                    KeySpaceConfig::default(),
+                ),
+            ),
+            (
+                "pruner_retained_transactions_by_expiry".to_string(),
+                ThConfig::new_with_config_indexing(
+                    // EpochId + (TransactionDigest)
+                    KeyIndexing::fixed(8 + (32 + 8)),
+                    transaction_mutexes,
+                    epoch_tx_digest_prefix_key,
+                    KeySpaceConfig::default(),
+                ),
+            ),
         ];
         Self::open_tables_read_write(
             Self::path(parent_path),
@@ -431,6 +488,18 @@ impl AuthorityPerpetualTables {
         Self::open(parent_path, None, None)
     }
 
+    /// Opens the perpetual tables of a node running in another process as a RocksDB secondary,
+    /// keeping the files of the secondary instance under `secondary_path`.
+    #[cfg(not(tidehunter))]
+    pub fn open_as_secondary(parent_path: &Path, secondary_path: &Path) -> Self {
+        Self::open_tables_as_secondary(
+            Self::path(parent_path),
+            secondary_path.to_path_buf(),
+            MetricConf::new("perpetual_secondary"),
+            None,
+        )
+    }
+
     #[cfg(tidehunter)]
     pub fn force_rebuild_control_region(&self) -> anyhow::Result<()> {
         self.objects.db.force_rebuild_control_region()
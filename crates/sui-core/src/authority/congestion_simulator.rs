@@ -0,0 +1,552 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sui_protocol_config::{
+    ConsensusTransactionOrdering, ExecutionTimeEstimateParams, PerObjectCongestionControlMode,
+    ProtocolConfig,
+};
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::execution::ExecutionTimeObservationKey;
+use sui_types::messages_consensus::{AuthorityIndex, Round};
+use sui_types::transaction::{TransactionDataAPI, TransactionKind};
+
+use super::authority_per_epoch_store::AuthorityEpochTables;
+use super::execution_time_estimator::ExecutionTimeEstimator;
+use super::shared_object_congestion_tracker::SharedObjectCongestionTracker;
+use super::transaction_deferral::{DeferralKey, transaction_deferral_within_limit};
+use crate::consensus_handler::{CommitIntervalObserver, ConsensusCommitInfo};
+
+/// Number of objects with the most deferrals included in a `CongestionSimulationReport`.
+const NUM_REPORTED_CONGESTED_OBJECTS: usize = 20;
+
+/// Congestion control parameters to simulate. Defaults to those of a `ProtocolConfig`, with any
+/// of them overridable to evaluate alternatives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CongestionSimulationParams {
+    pub execution_time_estimate: ExecutionTimeEstimateParams,
+    pub max_deferral_rounds_for_congestion_control: u64,
+    pub max_accumulated_txn_cost_per_object_in_commit: u64,
+    pub max_accumulated_randomness_txn_cost_per_object_in_commit: u64,
+    pub order_by_gas_price: bool,
+    pub commit_rate_estimation_window_size: u32,
+    pub min_checkpoint_interval_ms: u64,
+}
+
+impl CongestionSimulationParams {
+    pub fn from_protocol_config(protocol_config: &ProtocolConfig) -> anyhow::Result<Self> {
+        let PerObjectCongestionControlMode::ExecutionTimeEstimate(execution_time_estimate) =
+            protocol_config.per_object_congestion_control_mode()
+        else {
+            anyhow::bail!(
+                "congestion control mode of protocol version {} is not ExecutionTimeEstimate",
+                protocol_config.version.as_u64()
+            );
+        };
+        let max_accumulated_txn_cost_per_object_in_commit = protocol_config
+            .max_accumulated_txn_cost_per_object_in_mysticeti_commit_as_option()
+            .unwrap_or(0);
+        Ok(Self {
+            execution_time_estimate,
+            max_deferral_rounds_for_congestion_control: protocol_config
+                .max_deferral_rounds_for_congestion_control(),
+            max_accumulated_txn_cost_per_object_in_commit,
+            max_accumulated_randomness_txn_cost_per_object_in_commit: protocol_config
+                .max_accumulated_randomness_txn_cost_per_object_in_mysticeti_commit_as_option()
+                .unwrap_or(max_accumulated_txn_cost_per_object_in_commit),
+            order_by_gas_price: matches!(
+                protocol_config.consensus_transaction_ordering(),
+                ConsensusTransactionOrdering::ByGasPrice
+            ),
+            commit_rate_estimation_window_size: protocol_config
+                .get_consensus_commit_rate_estimation_window_size(),
+            min_checkpoint_interval_ms: protocol_config.min_checkpoint_interval_ms(),
+        })
+    }
+}
+
+/// The user transactions sequenced in a single consensus commit.
+pub struct SimulatedCommit {
+    pub round: Round,
+    pub timestamp_ms: u64,
+    pub transactions: Vec<VerifiedExecutableTransaction>,
+}
+
+/// Outcome of a congestion control simulation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CongestionSimulationReport {
+    pub num_commits: u64,
+    /// Number of transactions with shared inputs submitted to the simulation.
+    pub num_transactions: u64,
+    /// Number of transactions which were deferred at least once.
+    pub num_deferred_transactions: u64,
+    /// Total number of deferrals, counting every commit a transaction was deferred in.
+    pub num_deferrals: u64,
+    /// Number of transactions cancelled after being deferred for too many rounds.
+    pub num_cancelled_transactions: u64,
+    /// Number of transactions still deferred at the end of an epoch or of the simulation.
+    pub num_pending_transactions: u64,
+    /// Objects which caused the most deferrals and cancellations, with how many they caused.
+    pub most_congested_objects: Vec<(ObjectID, u64)>,
+}
+
+struct DeferredTransaction {
+    transaction: VerifiedExecutableTransaction,
+    deferral_key: DeferralKey,
+}
+
+/// Execution time observations shared by a validator, as sequenced by consensus.
+pub struct SequencedObservations {
+    pub source: AuthorityIndex,
+    pub generation: u64,
+    pub observations: Vec<(ExecutionTimeObservationKey, Duration)>,
+}
+
+/// Replays consensus commits and execution time observations through an
+/// `ExecutionTimeEstimator` and the shared object congestion trackers, to estimate how many
+/// transactions would have been deferred or cancelled under a set of congestion control
+/// parameters.
+///
+/// Unlike the consensus handler, transactions are not deferred waiting for randomness, and
+/// deferred transactions are retried in the next simulated commit.
+pub struct CongestionSimulator {
+    estimator: ExecutionTimeEstimator,
+    params: CongestionSimulationParams,
+    commit_interval_observer: CommitIntervalObserver,
+    object_debts: HashMap<ObjectID, (Round, u64)>,
+    randomness_object_debts: HashMap<ObjectID, (Round, u64)>,
+    deferred: Vec<DeferredTransaction>,
+    deferred_digests: HashSet<TransactionDigest>,
+    congested_objects: HashMap<ObjectID, u64>,
+    report: CongestionSimulationReport,
+}
+
+impl CongestionSimulator {
+    /// Creates a simulator starting in the epoch of `committee`. Commands in `initial_estimates`
+    /// are estimated as if every member of the committee had observed the given duration,
+    /// standing in for the observations stored at the end of the previous epoch.
+    pub fn new(
+        committee: Arc<Committee>,
+        params: CongestionSimulationParams,
+        initial_estimates: &[(ExecutionTimeObservationKey, Duration)],
+    ) -> Self {
+        let num_members = committee.num_members();
+        let estimator = ExecutionTimeEstimator::new(
+            committee,
+            params.execution_time_estimate,
+            initial_estimates.iter().flat_map(|(key, duration)| {
+                (0..num_members)
+                    .map(move |index| (index.try_into().unwrap(), None, key.clone(), *duration))
+            }),
+        );
+        Self {
+            estimator,
+            params,
+            commit_interval_observer: CommitIntervalObserver::new(
+                params.commit_rate_estimation_window_size,
+            ),
+            object_debts: HashMap::new(),
+            randomness_object_debts: HashMap::new(),
+            deferred: Vec::new(),
+            deferred_digests: HashSet::new(),
+            congested_objects: HashMap::new(),
+            report: CongestionSimulationReport::default(),
+        }
+    }
+
+    /// Moves the simulation to the epoch of `committee`. As on a node, object debts are reset,
+    /// transactions still deferred are dropped and counted as pending, and the estimator of the
+    /// new epoch starts from the observations of the previous one.
+    pub fn start_epoch(&mut self, committee: Arc<Committee>, params: CongestionSimulationParams) {
+        self.report.num_pending_transactions += self.deferred.len() as u64;
+        self.deferred.clear();
+        self.object_debts.clear();
+        self.randomness_object_debts.clear();
+
+        let observations = self.estimator.take_observations().unwrap_v1();
+        self.estimator = ExecutionTimeEstimator::new(
+            committee.clone(),
+            params.execution_time_estimate,
+            observations
+                .into_iter()
+                .flat_map(move |(key, observations)| {
+                    let committee = committee.clone();
+                    observations
+                        .into_iter()
+                        .filter_map(move |(authority, duration)| {
+                            committee
+                                .authority_index(&authority)
+                                .map(|index| (index, None, key.clone(), duration))
+                        })
+                }),
+        );
+        self.params = params;
+        self.commit_interval_observer =
+            CommitIntervalObserver::new(params.commit_rate_estimation_window_size);
+    }
+
+    /// Updates the estimator with observations sequenced by consensus before the next commit.
+    pub fn process_observations(&mut self, observations: &SequencedObservations) {
+        self.estimator.process_observations_from_consensus(
+            observations.source,
+            Some(observations.generation),
+            &observations.observations,
+        );
+    }
+
+    pub fn process_commit(&mut self, commit: SimulatedCommit) {
+        self.commit_interval_observer
+            .observe_commit_timestamp(commit.timestamp_ms);
+        let estimated_commit_period = self
+            .commit_interval_observer
+            .commit_interval_estimate()
+            .unwrap_or(std::time::Duration::from_millis(
+                self.params.min_checkpoint_interval_ms,
+            ));
+        let commit_info = ConsensusCommitInfo::new_for_congestion_test(
+            commit.round,
+            commit.timestamp_ms,
+            estimated_commit_period,
+        );
+        self.report.num_commits += 1;
+
+        // Previously deferred transactions are scheduled along with the new ones.
+        let mut previously_deferred = HashMap::new();
+        let mut transactions = Vec::new();
+        let mut randomness_transactions = Vec::new();
+        for deferred in std::mem::take(&mut self.deferred) {
+            previously_deferred.insert(*deferred.transaction.digest(), deferred.deferral_key);
+            Self::partition(
+                deferred.transaction,
+                &mut transactions,
+                &mut randomness_transactions,
+            );
+        }
+        for transaction in commit.transactions {
+            if transaction.shared_input_objects().next().is_none() {
+                continue;
+            }
+            self.report.num_transactions += 1;
+            Self::partition(transaction, &mut transactions, &mut randomness_transactions);
+        }
+        if self.params.order_by_gas_price {
+            for transactions in [&mut transactions, &mut randomness_transactions] {
+                transactions.sort_by_key(|tx| std::cmp::Reverse(tx.transaction_data().gas_price()));
+            }
+        }
+
+        self.object_debts = self.schedule(&commit_info, transactions, &previously_deferred, false);
+        self.randomness_object_debts = self.schedule(
+            &commit_info,
+            randomness_transactions,
+            &previously_deferred,
+            true,
+        );
+    }
+
+    pub fn finish(mut self) -> CongestionSimulationReport {
+        self.report.num_pending_transactions += self.deferred.len() as u64;
+        let mut congested_objects: Vec<_> = self.congested_objects.into_iter().collect();
+        congested_objects.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+        congested_objects.truncate(NUM_REPORTED_CONGESTED_OBJECTS);
+        self.report.most_congested_objects = congested_objects;
+        self.report
+    }
+
+    fn partition(
+        transaction: VerifiedExecutableTransaction,
+        transactions: &mut Vec<VerifiedExecutableTransaction>,
+        randomness_transactions: &mut Vec<VerifiedExecutableTransaction>,
+    ) {
+        if transaction.transaction_data().uses_randomness() {
+            randomness_transactions.push(transaction);
+        } else {
+            transactions.push(transaction);
+        }
+    }
+
+    /// Schedules `transactions` in the commit, deferring those using congested objects, and
+    /// returns the object debts accumulated by the commit.
+    fn schedule(
+        &mut self,
+        commit_info: &ConsensusCommitInfo,
+        transactions: Vec<VerifiedExecutableTransaction>,
+        previously_deferred: &HashMap<TransactionDigest, DeferralKey>,
+        for_randomness: bool,
+    ) -> HashMap<ObjectID, (Round, u64)> {
+        let (debts, per_commit_budget) = if for_randomness {
+            (
+                &self.randomness_object_debts,
+                self.params
+                    .max_accumulated_randomness_txn_cost_per_object_in_commit,
+            )
+        } else {
+            (
+                &self.object_debts,
+                self.params.max_accumulated_txn_cost_per_object_in_commit,
+            )
+        };
+        // Debts are reduced by the budget of any rounds without a commit, as in
+        // `ConsensusOutputQuarantine::load_initial_object_debts`.
+        let initial_debts: Vec<_> = debts
+            .iter()
+            .map(|(id, (round, debt))| {
+                let num_rounds = commit_info.round.saturating_sub(*round + 1);
+                (*id, debt.saturating_sub(per_commit_budget * num_rounds))
+            })
+            .collect();
+        let mut tracker = SharedObjectCongestionTracker::new(
+            initial_debts,
+            self.params.execution_time_estimate,
+            for_randomness,
+            false,
+        );
+
+        for transaction in transactions {
+            let tx_cost = self
+                .estimator
+                .get_estimate(transaction.transaction_data())
+                .as_micros()
+                .try_into()
+                .unwrap_or(u64::MAX);
+            let Some((deferral_key, congested_objects)) = tracker
+                .should_defer_due_to_object_congestion(
+                    &transaction,
+                    previously_deferred,
+                    commit_info,
+                )
+            else {
+                tracker.bump_object_execution_cost(tx_cost, &transaction);
+                continue;
+            };
+
+            for object_id in congested_objects {
+                *self.congested_objects.entry(object_id).or_default() += 1;
+            }
+            if !transaction_deferral_within_limit(
+                &deferral_key,
+                self.params.max_deferral_rounds_for_congestion_control,
+            ) {
+                self.report.num_cancelled_transactions += 1;
+                continue;
+            }
+
+            self.report.num_deferrals += 1;
+            if self.deferred_digests.insert(*transaction.digest()) {
+                self.report.num_deferred_transactions += 1;
+            }
+            self.deferred.push(DeferredTransaction {
+                transaction,
+                deferral_key,
+            });
+        }
+
+        tracker
+            .finish_commit(commit_info)
+            .accumulated_debts
+            .into_iter()
+            .map(|(id, debt)| (id, (commit_info.round, debt)))
+            .collect()
+    }
+}
+
+/// Returns the execution time observations sequenced by consensus in `epoch`, as recorded in
+/// the epoch db under `store_path`, ordered by generation. Returns `None` if the epoch db does
+/// not exist, e.g. because it was already pruned.
+pub fn load_sequenced_observations(
+    store_path: &Path,
+    epoch: EpochId,
+) -> anyhow::Result<Option<Vec<SequencedObservations>>> {
+    if !AuthorityEpochTables::path(epoch, store_path).exists() {
+        return Ok(None);
+    }
+    let tables = AuthorityEpochTables::open_readonly(epoch, store_path);
+    let mut sequenced = tables
+        .execution_time_observations
+        .safe_iter()
+        .map(|item| {
+            item.map(
+                |((generation, source), observations)| SequencedObservations {
+                    source,
+                    generation,
+                    observations,
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    sequenced.sort_by_key(|observations| (observations.generation, observations.source));
+    Ok(Some(sequenced))
+}
+
+/// Returns the round and timestamp of the consensus commit that `kind` is the prologue of.
+pub fn consensus_commit_prologue_round_and_timestamp(
+    kind: &TransactionKind,
+) -> Option<(Round, u64)> {
+    match kind {
+        TransactionKind::ConsensusCommitPrologue(prologue) => {
+            Some((prologue.round, prologue.commit_timestamp_ms))
+        }
+        TransactionKind::ConsensusCommitPrologueV2(prologue) => {
+            Some((prologue.round, prologue.commit_timestamp_ms))
+        }
+        TransactionKind::ConsensusCommitPrologueV3(prologue) => {
+            Some((prologue.round, prologue.commit_timestamp_ms))
+        }
+        TransactionKind::ConsensusCommitPrologueV4(prologue) => {
+            Some((prologue.round, prologue.commit_timestamp_ms))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_test_transaction_builder::TestTransactionBuilder;
+    use sui_types::base_types::{SequenceNumber, random_object_ref};
+    use sui_types::crypto::{AccountKeyPair, get_key_pair};
+    use sui_types::transaction::{CallArg, ObjectArg, SharedObjectMutability, VerifiedTransaction};
+
+    fn build_transaction(package: ObjectID, object: ObjectID) -> VerifiedExecutableTransaction {
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        VerifiedExecutableTransaction::new_system(
+            VerifiedTransaction::new_unchecked(
+                TestTransactionBuilder::new(sender, random_object_ref(), 1000)
+                    .move_call(
+                        package,
+                        "module",
+                        "function",
+                        vec![CallArg::Object(ObjectArg::SharedObject {
+                            id: object,
+                            initial_shared_version: SequenceNumber::new(),
+                            mutability: SharedObjectMutability::Mutable,
+                        })],
+                    )
+                    .build_and_sign(&keypair),
+            ),
+            0,
+        )
+    }
+
+    fn params(max_deferral_rounds: u64) -> CongestionSimulationParams {
+        CongestionSimulationParams {
+            execution_time_estimate: ExecutionTimeEstimateParams {
+                target_utilization: 100,
+                allowed_txn_cost_overage_burst_limit_us: 0,
+                max_estimate_us: u64::MAX,
+                randomness_scalar: 100,
+                ..Default::default()
+            },
+            max_deferral_rounds_for_congestion_control: max_deferral_rounds,
+            max_accumulated_txn_cost_per_object_in_commit: 0,
+            max_accumulated_randomness_txn_cost_per_object_in_commit: 0,
+            order_by_gas_price: true,
+            commit_rate_estimation_window_size: 10,
+            min_checkpoint_interval_ms: 1,
+        }
+    }
+
+    fn observation_key(package: ObjectID) -> ExecutionTimeObservationKey {
+        ExecutionTimeObservationKey::MoveEntryPoint {
+            package,
+            module: "module".to_string(),
+            function: "function".to_string(),
+            type_arguments: vec![],
+        }
+    }
+
+    fn commits(package: ObjectID, object: ObjectID) -> Vec<SimulatedCommit> {
+        vec![
+            SimulatedCommit {
+                round: 1,
+                timestamp_ms: 0,
+                transactions: (0..3).map(|_| build_transaction(package, object)).collect(),
+            },
+            SimulatedCommit {
+                round: 2,
+                timestamp_ms: 2,
+                transactions: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_simulate_congestion() {
+        let package = ObjectID::random();
+        let object = ObjectID::random();
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let committee = Arc::new(committee);
+        let estimates = [(observation_key(package), Duration::from_millis(1))];
+
+        // With a budget of 1ms per commit, the third transaction is deferred to the next commit.
+        let mut simulator = CongestionSimulator::new(committee.clone(), params(10), &estimates);
+        for commit in commits(package, object) {
+            simulator.process_commit(commit);
+        }
+        assert_eq!(
+            simulator.finish(),
+            CongestionSimulationReport {
+                num_commits: 2,
+                num_transactions: 3,
+                num_deferred_transactions: 1,
+                num_deferrals: 1,
+                num_cancelled_transactions: 0,
+                num_pending_transactions: 0,
+                most_congested_objects: vec![(object, 1)],
+            }
+        );
+
+        // If transactions may not be deferred, it is cancelled instead.
+        let mut simulator = CongestionSimulator::new(committee, params(0), &estimates);
+        for commit in commits(package, object) {
+            simulator.process_commit(commit);
+        }
+        let report = simulator.finish();
+        assert_eq!(report.num_deferrals, 0);
+        assert_eq!(report.num_cancelled_transactions, 1);
+        assert_eq!(report.most_congested_objects, vec![(object, 1)]);
+    }
+
+    #[test]
+    fn test_simulate_congestion_with_observations() {
+        let package = ObjectID::random();
+        let object = ObjectID::random();
+        let (committee, _) = Committee::new_simple_test_committee_of_size(1);
+        let committee = Arc::new(committee);
+        let mut simulator = CongestionSimulator::new(
+            committee.clone(),
+            params(10),
+            &[(observation_key(package), Duration::from_millis(1))],
+        );
+
+        // Once the command is observed to take 2ms, only one transaction fits in a commit.
+        simulator.process_observations(&SequencedObservations {
+            source: 0,
+            generation: 1,
+            observations: vec![(observation_key(package), Duration::from_millis(2))],
+        });
+        for commit in commits(package, object) {
+            simulator.process_commit(commit);
+        }
+
+        // Observations are carried over to the next epoch, while deferred transactions are
+        // dropped.
+        simulator.start_epoch(committee, params(10));
+        simulator.process_commit(SimulatedCommit {
+            round: 1,
+            timestamp_ms: 4,
+            transactions: (0..2).map(|_| build_transaction(package, object)).collect(),
+        });
+        let report = simulator.finish();
+        assert_eq!(report.num_transactions, 5);
+        assert_eq!(report.num_deferred_transactions, 3);
+        assert_eq!(report.num_pending_transactions, 2);
+    }
+}
@@ -27,7 +27,7 @@ use sui_config::node::ExecutionTimeObserverConfig;
 use sui_protocol_config::{ExecutionTimeEstimateParams, PerObjectCongestionControlMode};
 use sui_types::{
     base_types::ObjectID,
-    committee::Committee,
+    committee::{Committee, EpochId},
     error::SuiErrorKind,
     execution::{ExecutionTimeObservationKey, ExecutionTiming},
     messages_consensus::{AuthorityIndex, ConsensusTransaction, ExecutionTimeObservation},
@@ -194,7 +194,12 @@ impl ExecutionTimeObserver {
             mpsc::channel(config.observation_channel_capacity().into());
         let (tx_object_debts, mut rx_object_debts) =
             mpsc::channel(config.object_debt_channel_capacity().into());
-        epoch_store.set_local_execution_time_channels(tx_local_execution_time, tx_object_debts);
+        let (tx_snapshot_requests, mut rx_snapshot_requests) = mpsc::channel(8);
+        epoch_store.set_local_execution_time_channels(
+            tx_local_execution_time,
+            tx_object_debts,
+            tx_snapshot_requests,
+        );
 
         // TODO: pre-populate local observations with stored data from prior epoch.
         let mut observer = Self {
@@ -228,6 +233,9 @@ impl ExecutionTimeObserver {
                         observer
                             .record_local_observations(&tx, &timings, total_duration, gas_price);
                     }
+                    Some(response) = rx_snapshot_requests.recv() => {
+                        let _ = response.send(observer.snapshot());
+                    }
                     else => { break }
                 }
             }
@@ -625,6 +633,36 @@ impl ExecutionTimeObserver {
         }
     }
 
+    fn snapshot(&self) -> ExecutionTimeObserverSnapshot {
+        let mut local_estimates: Vec<_> = self
+            .local_observations
+            .iter()
+            .map(|(key, observations)| (key.clone(), observations.get_average()))
+            .collect();
+        local_estimates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut object_utilization: Vec<_> = self
+            .object_utilization_tracker
+            .iter()
+            .map(|(id, utilization)| ObjectUtilizationSummary {
+                object_id: *id,
+                excess_execution_time_us: utilization.excess_execution_time.as_micros() as u64,
+                overutilized: utilization.overutilized(&self.config),
+                indebted: self.indebted_objects.binary_search(id).is_ok(),
+            })
+            .collect();
+        object_utilization.sort_by(|a, b| {
+            b.excess_execution_time_us
+                .cmp(&a.excess_execution_time_us)
+                .then(a.object_id.cmp(&b.object_id))
+        });
+
+        ExecutionTimeObserverSnapshot {
+            local_estimates,
+            object_utilization,
+        }
+    }
+
     fn update_indebted_objects(&mut self, mut object_debts: Vec<ObjectID>) {
         let _scope = monitored_scope("ExecutionTimeObserver::update_indebted_objects");
 
@@ -643,6 +681,99 @@ impl ExecutionTimeObserver {
     }
 }
 
+/// Local state of the `ExecutionTimeObserver`, for inspection by operators.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTimeObserverSnapshot {
+    pub local_estimates: Vec<(ExecutionTimeObservationKey, Duration)>,
+    pub object_utilization: Vec<ObjectUtilizationSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectUtilizationSummary {
+    pub object_id: ObjectID,
+    /// Execution time spent on the object above the utilization target.
+    pub excess_execution_time_us: u64,
+    /// Whether observations for transactions using the object are currently being shared.
+    pub overutilized: bool,
+    /// Whether the object accumulated congestion debt in a recent consensus commit.
+    pub indebted: bool,
+}
+
+/// Execution time estimates and object utilization of a node, as dumped by the admin API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionTimeEstimatesDump {
+    pub epoch: EpochId,
+    pub protocol_params: ExecutionTimeEstimateParams,
+    pub commands: Vec<CommandEstimate>,
+    pub object_utilization: Vec<ObjectUtilizationSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandEstimate {
+    pub key: ExecutionTimeObservationKey,
+    /// Per-command estimate used for congestion control: the stake-weighted median of the
+    /// observations received via consensus, or the default if not enough stake reported one.
+    pub estimate_us: u64,
+    /// Whether `estimate_us` is the default duration for the command.
+    pub is_default: bool,
+    /// Number of validators that shared an observation for the command.
+    pub num_observations: usize,
+    /// Moving average of local execution times of the command on this node.
+    pub local_average_us: Option<u64>,
+}
+
+impl ExecutionTimeEstimatesDump {
+    pub fn new(
+        epoch: EpochId,
+        estimator: &ExecutionTimeEstimator,
+        observer: ExecutionTimeObserverSnapshot,
+    ) -> Self {
+        let mut local_estimates: HashMap<_, _> = observer.local_estimates.into_iter().collect();
+        let mut commands: Vec<_> = estimator
+            .consensus_observations
+            .iter()
+            .map(|(key, observations)| {
+                let local_average = local_estimates.remove(key);
+                (key.clone(), Some(observations), local_average)
+            })
+            .collect();
+        // Include commands only executed locally, which are estimated with their default.
+        commands.extend(
+            local_estimates
+                .into_iter()
+                .map(|(key, local_average)| (key, None, Some(local_average))),
+        );
+
+        let mut commands: Vec<_> = commands
+            .into_iter()
+            .map(|(key, observations, local_average)| {
+                let median = observations.and_then(|obs| obs.stake_weighted_median);
+                CommandEstimate {
+                    estimate_us: median.unwrap_or_else(|| key.default_duration()).as_micros()
+                        as u64,
+                    is_default: median.is_none(),
+                    num_observations: observations.map_or(0, |obs| {
+                        obs.observations
+                            .iter()
+                            .filter(|(_, duration)| duration.is_some())
+                            .count()
+                    }),
+                    local_average_us: local_average.map(|d| d.as_micros() as u64),
+                    key,
+                }
+            })
+            .collect();
+        commands.sort_by(|a, b| b.estimate_us.cmp(&a.estimate_us).then(a.key.cmp(&b.key)));
+
+        Self {
+            epoch,
+            protocol_params: estimator.protocol_params,
+            commands,
+            object_utilization: observer.object_utilization,
+        }
+    }
+}
+
 // Key used to save StoredExecutionTimeObservations in the Sui system state object's
 // `extra_fields` Bag.
 pub const EXTRA_FIELD_EXECUTION_TIME_ESTIMATES_KEY: u64 = 0;
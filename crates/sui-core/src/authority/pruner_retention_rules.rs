@@ -0,0 +1,261 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, bail};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use sui_config::node::PruningRetentionRule;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::EpochId;
+use sui_types::object::{Object, Owner};
+use sui_types::{parse_sui_address, parse_sui_module_id, parse_sui_struct_tag};
+
+/// Retention rules of the authority store pruner, parsed from `PruningRetentionRule`s.
+#[derive(Debug, Default)]
+pub struct RetentionRules {
+    rules: Vec<RetentionRule>,
+}
+
+#[derive(Debug)]
+struct RetentionRule {
+    object_type: Option<TypeMatcher>,
+    owner: Option<SuiAddress>,
+    num_epochs_to_retain: u64,
+}
+
+#[derive(Debug)]
+enum TypeMatcher {
+    Package(AccountAddress),
+    Module(ModuleId),
+    /// A struct type. Matches all instantiations if it has no type parameters.
+    Type(StructTag),
+}
+
+impl RetentionRules {
+    pub fn new(rules: &[PruningRetentionRule]) -> anyhow::Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                RetentionRule::new(rule).with_context(|| format!("invalid retention rule {i}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The longest retention of any rule, if there are any rules.
+    pub fn max_num_epochs_to_retain(&self) -> Option<u64> {
+        self.rules
+            .iter()
+            .map(|rule| rule.num_epochs_to_retain)
+            .max()
+    }
+
+    /// Whether an object with `owner` could match any rule, before looking at its type.
+    pub fn may_match_owner(&self, owner: &Owner) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.owner
+                .is_none_or(|o| owner.get_owner_address().ok() == Some(o))
+        })
+    }
+
+    /// Returns the number of epochs that `object` must be retained for, if it matches any rule.
+    pub fn num_epochs_to_retain(&self, object: &Object) -> Option<u64> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(object))
+            .map(|rule| rule.num_epochs_to_retain)
+            .max()
+    }
+
+    /// Returns the first epoch in which `object`, superseded in `epoch`, may be pruned, if it
+    /// must be retained past `current_epoch`.
+    pub fn retained_until(
+        &self,
+        object: &Object,
+        epoch: EpochId,
+        current_epoch: EpochId,
+    ) -> Option<EpochId> {
+        self.num_epochs_to_retain(object)
+            .map(|num_epochs| epoch.saturating_add(num_epochs))
+            .filter(|retained_until| *retained_until > current_epoch)
+    }
+}
+
+impl RetentionRule {
+    fn new(rule: &PruningRetentionRule) -> anyhow::Result<Self> {
+        if rule.object_type.is_none() && rule.owner.is_none() {
+            bail!("a retention rule must match on an object type, an owner, or both");
+        }
+
+        let object_type = rule
+            .object_type
+            .as_deref()
+            .map(TypeMatcher::parse)
+            .transpose()?;
+
+        Ok(Self {
+            object_type,
+            owner: rule.owner,
+            num_epochs_to_retain: rule.num_epochs_to_retain,
+        })
+    }
+
+    fn matches(&self, object: &Object) -> bool {
+        if let Some(owner) = &self.owner
+            && object.owner.get_owner_address().ok().as_ref() != Some(owner)
+        {
+            return false;
+        }
+
+        if let Some(object_type) = &self.object_type {
+            let Some(struct_tag) = object.struct_tag() else {
+                // Packages never match a type.
+                return false;
+            };
+            if !object_type.matches(&struct_tag) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl TypeMatcher {
+    fn parse(s: &str) -> anyhow::Result<Self> {
+        let path = s.split('<').next().unwrap_or_default();
+        match path.matches("::").count() {
+            0 => Ok(Self::Package(
+                parse_sui_address(s)
+                    .with_context(|| format!("invalid package {s}"))?
+                    .into(),
+            )),
+            1 => Ok(Self::Module(
+                parse_sui_module_id(s).with_context(|| format!("invalid module {s}"))?,
+            )),
+            _ => Ok(Self::Type(
+                parse_sui_struct_tag(s).with_context(|| format!("invalid type {s}"))?,
+            )),
+        }
+    }
+
+    fn matches(&self, struct_tag: &StructTag) -> bool {
+        match self {
+            Self::Package(address) => struct_tag.address == *address,
+            Self::Module(module) => {
+                struct_tag.address == *module.address()
+                    && struct_tag.module.as_ident_str() == module.name()
+            }
+            Self::Type(tag) if tag.type_params.is_empty() => {
+                struct_tag.address == tag.address
+                    && struct_tag.module == tag.module
+                    && struct_tag.name == tag.name
+            }
+            Self::Type(tag) => struct_tag == tag,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::ObjectID;
+    use sui_types::gas_coin::GasCoin;
+
+    fn rule(object_type: Option<&str>, owner: Option<SuiAddress>) -> PruningRetentionRule {
+        PruningRetentionRule {
+            object_type: object_type.map(str::to_owned),
+            owner,
+            num_epochs_to_retain: 10,
+        }
+    }
+
+    fn gas_coin(owner: SuiAddress) -> Object {
+        Object::with_id_owner_for_testing(ObjectID::random(), owner)
+    }
+
+    #[test]
+    fn test_match_object_type() {
+        let owner = SuiAddress::random_for_testing_only();
+        let object = gas_coin(owner);
+        assert_eq!(object.struct_tag(), Some(GasCoin::type_()));
+
+        for object_type in [
+            "0x2",
+            "0x2::coin",
+            "0x2::coin::Coin",
+            "0x2::coin::Coin<0x2::sui::SUI>",
+        ] {
+            let rules = RetentionRules::new(&[rule(Some(object_type), None)]).unwrap();
+            assert_eq!(
+                rules.num_epochs_to_retain(&object),
+                Some(10),
+                "{object_type}"
+            );
+        }
+
+        for object_type in [
+            "0x3",
+            "0x2::balance",
+            "0x2::coin::TreasuryCap",
+            "0x2::coin::Coin<0x3::staking_pool::StakedSui>",
+        ] {
+            let rules = RetentionRules::new(&[rule(Some(object_type), None)]).unwrap();
+            assert_eq!(rules.num_epochs_to_retain(&object), None, "{object_type}");
+        }
+    }
+
+    #[test]
+    fn test_match_owner() {
+        let owner = SuiAddress::random_for_testing_only();
+        let object = gas_coin(owner);
+
+        let rules = RetentionRules::new(&[rule(None, Some(owner))]).unwrap();
+        assert_eq!(rules.num_epochs_to_retain(&object), Some(10));
+
+        let rules = RetentionRules::new(&[rule(None, Some(SuiAddress::random_for_testing_only()))])
+            .unwrap();
+        assert_eq!(rules.num_epochs_to_retain(&object), None);
+
+        // All criteria of a rule must match.
+        let rules = RetentionRules::new(&[rule(Some("0x3"), Some(owner))]).unwrap();
+        assert_eq!(rules.num_epochs_to_retain(&object), None);
+        assert!(rules.may_match_owner(&Owner::AddressOwner(owner)));
+        assert!(
+            !rules.may_match_owner(&Owner::AddressOwner(SuiAddress::random_for_testing_only()))
+        );
+
+        // Rules without an owner may match objects of any owner.
+        let rules = RetentionRules::new(&[rule(Some("0x3"), None)]).unwrap();
+        assert!(rules.may_match_owner(&Owner::Immutable));
+    }
+
+    #[test]
+    fn test_longest_retention_wins() {
+        let owner = SuiAddress::random_for_testing_only();
+        let object = gas_coin(owner);
+
+        let mut longer = rule(None, Some(owner));
+        longer.num_epochs_to_retain = 20;
+        let rules = RetentionRules::new(&[rule(Some("0x2"), None), longer]).unwrap();
+        assert_eq!(rules.num_epochs_to_retain(&object), Some(20));
+        assert_eq!(rules.max_num_epochs_to_retain(), Some(20));
+        assert_eq!(RetentionRules::default().max_num_epochs_to_retain(), None);
+
+        // Superseded in epoch 5, retained until epoch 25.
+        assert_eq!(rules.retained_until(&object, 5, 10), Some(25));
+        assert_eq!(rules.retained_until(&object, 5, 25), None);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(RetentionRules::new(&[rule(None, None)]).is_err());
+        assert!(RetentionRules::new(&[rule(Some("not a type"), None)]).is_err());
+        assert!(RetentionRules::new(&[rule(Some("0x2::coin::Coin<"), None)]).is_err());
+    }
+}
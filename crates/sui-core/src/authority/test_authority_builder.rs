@@ -425,7 +425,8 @@ impl<'a> TestAuthorityBuilder<'a> {
             firewall_config,
             Arc::new(PrunerWatermarks::default()),
         )
-        .await;
+        .await
+        .unwrap();
 
         // Set up randomness with no-op consensus (DKG will not complete).
         if epoch_store.randomness_state_enabled() {
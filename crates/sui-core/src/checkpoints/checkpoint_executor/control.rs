@@ -0,0 +1,318 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Operator controls to pause the checkpoint executor of a running node and step through
+//! checkpoint execution, for debugging incidents.
+//!
+//! Pausing only holds back the execution of checkpoints: state sync keeps syncing and storing
+//! checkpoints while the executor is paused, and they are executed once execution resumes.
+//! A checkpoint is either released for execution as a whole, or released for transaction
+//! stepping, in which case each of its unexecuted transactions is only executed once it was
+//! stepped over. Checkpoints which are already executing when the executor is paused run to
+//! completion.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use sui_types::base_types::TransactionDigest;
+use sui_types::effects::TransactionEffects;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use tokio::sync::watch;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CheckpointExecutionControlState {
+    /// Checkpoints with a sequence number greater or equal to `pause_at` are not executed.
+    pub pause_at: Option<CheckpointSequenceNumber>,
+    /// The highest checkpoint released for execution.
+    pub highest_released: Option<CheckpointSequenceNumber>,
+    /// The checkpoint whose transactions are executed one at a time, if any.
+    pub stepping: Option<TransactionStepping>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TransactionStepping {
+    pub checkpoint: CheckpointSequenceNumber,
+    /// The number of unexecuted transactions of the checkpoint released for execution.
+    pub released_transactions: usize,
+}
+
+/// The next transaction to be executed while stepping through the transactions of a checkpoint.
+#[derive(Clone, Debug, Serialize)]
+pub struct NextTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Index of the transaction among the unexecuted transactions of the checkpoint.
+    pub index: usize,
+    pub digest: TransactionDigest,
+    pub expected_effects: TransactionEffects,
+    pub input_objects: Vec<Object>,
+}
+
+pub struct CheckpointExecutionControl {
+    state: watch::Sender<CheckpointExecutionControlState>,
+    next_transaction: Mutex<Option<NextTransaction>>,
+}
+
+impl CheckpointExecutionControl {
+    /// Creates the controls of a node whose highest executed checkpoint is `highest_executed`.
+    /// Executed checkpoints count as released, so that pausing and stepping act on the next
+    /// checkpoint to execute.
+    pub fn new(highest_executed: Option<CheckpointSequenceNumber>) -> Self {
+        Self {
+            state: watch::channel(CheckpointExecutionControlState {
+                highest_released: highest_executed,
+                ..Default::default()
+            })
+            .0,
+            next_transaction: Mutex::new(None),
+        }
+    }
+
+    pub fn state(&self) -> CheckpointExecutionControlState {
+        self.state.borrow().clone()
+    }
+
+    pub fn next_transaction(&self) -> Option<NextTransaction> {
+        self.next_transaction.lock().clone()
+    }
+
+    /// Stops releasing checkpoints for execution from `checkpoint` on, or from the next
+    /// checkpoint if `checkpoint` is not set.
+    pub fn pause(&self, checkpoint: Option<CheckpointSequenceNumber>) {
+        self.state.send_modify(|state| {
+            state.pause_at = Some(checkpoint.unwrap_or_else(|| next_checkpoint(state)));
+        });
+    }
+
+    /// Releases the next checkpoint for execution, and pauses again after it.
+    pub fn step_checkpoint(&self) {
+        self.state.send_modify(|state| {
+            state.pause_at = Some(next_checkpoint(state) + 1);
+        });
+    }
+
+    /// Releases the next transaction of the checkpoint being stepped through for execution. If no
+    /// checkpoint is being stepped through, the next checkpoint is released for transaction
+    /// stepping, without executing any of its transactions.
+    pub fn step_transaction(&self) {
+        self.state.send_modify(|state| match &mut state.stepping {
+            Some(stepping) => stepping.released_transactions += 1,
+            None => {
+                let checkpoint = next_checkpoint(state);
+                state.pause_at = Some(checkpoint + 1);
+                state.stepping = Some(TransactionStepping {
+                    checkpoint,
+                    released_transactions: 0,
+                });
+            }
+        });
+    }
+
+    /// Releases all checkpoints for execution.
+    pub fn resume(&self) {
+        self.state.send_modify(|state| {
+            state.pause_at = None;
+            state.stepping = None;
+        });
+        *self.next_transaction.lock() = None;
+    }
+
+    /// Waits until `checkpoint` is released for execution.
+    pub(crate) async fn wait_for_checkpoint_release(&self, checkpoint: CheckpointSequenceNumber) {
+        let mut receiver = self.state.subscribe();
+        receiver
+            .wait_for(|state| state.pause_at.is_none_or(|pause_at| checkpoint < pause_at))
+            .await
+            .expect("sender is owned by self");
+        self.state.send_if_modified(|state| {
+            if state
+                .highest_released
+                .is_none_or(|highest| highest < checkpoint)
+            {
+                state.highest_released = Some(checkpoint);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    pub(crate) fn is_stepping_transactions(&self, checkpoint: CheckpointSequenceNumber) -> bool {
+        self.state
+            .borrow()
+            .stepping
+            .as_ref()
+            .is_some_and(|stepping| stepping.checkpoint == checkpoint)
+    }
+
+    /// Publishes `next` as the next transaction and waits until it is released for execution.
+    pub(crate) async fn wait_for_transaction_release(&self, next: NextTransaction) {
+        let (checkpoint, index) = (next.checkpoint, next.index);
+        *self.next_transaction.lock() = Some(next);
+        let mut receiver = self.state.subscribe();
+        receiver
+            .wait_for(|state| match &state.stepping {
+                Some(stepping) if stepping.checkpoint == checkpoint => {
+                    index < stepping.released_transactions
+                }
+                _ => true,
+            })
+            .await
+            .expect("sender is owned by self");
+    }
+
+    /// Ends transaction stepping once the stepped checkpoint is executed.
+    pub(crate) fn checkpoint_executed(&self, checkpoint: CheckpointSequenceNumber) {
+        let modified = self.state.send_if_modified(|state| {
+            if state
+                .stepping
+                .as_ref()
+                .is_some_and(|stepping| stepping.checkpoint == checkpoint)
+            {
+                state.stepping = None;
+                true
+            } else {
+                false
+            }
+        });
+        if modified {
+            *self.next_transaction.lock() = None;
+        }
+    }
+}
+
+fn next_checkpoint(state: &CheckpointExecutionControlState) -> CheckpointSequenceNumber {
+    let next_unreleased = state.highest_released.map(|c| c + 1).unwrap_or(0);
+    // The executor may not have reached `pause_at` yet, in which case the next checkpoint is the
+    // next one to be released.
+    state
+        .pause_at
+        .map_or(next_unreleased, |pause_at| pause_at.min(next_unreleased))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn test_pause_and_step_checkpoints() {
+        let control = CheckpointExecutionControl::new(None);
+        control.wait_for_checkpoint_release(0).await;
+        control.wait_for_checkpoint_release(1).await;
+
+        control.pause(Some(3));
+        control.wait_for_checkpoint_release(2).await;
+        assert!(
+            control
+                .wait_for_checkpoint_release(3)
+                .now_or_never()
+                .is_none()
+        );
+
+        control.step_checkpoint();
+        control.wait_for_checkpoint_release(3).await;
+        assert!(
+            control
+                .wait_for_checkpoint_release(4)
+                .now_or_never()
+                .is_none()
+        );
+        assert_eq!(control.state().highest_released, Some(3));
+
+        control.resume();
+        control.wait_for_checkpoint_release(4).await;
+        control.wait_for_checkpoint_release(5).await;
+    }
+
+    #[tokio::test]
+    async fn test_pause_at_next_checkpoint() {
+        let control = CheckpointExecutionControl::new(None);
+        control.wait_for_checkpoint_release(0).await;
+
+        control.pause(None);
+        assert_eq!(control.state().pause_at, Some(1));
+        assert!(
+            control
+                .wait_for_checkpoint_release(1)
+                .now_or_never()
+                .is_none()
+        );
+
+        // Pausing a paused executor does not release any checkpoint.
+        control.pause(None);
+        assert_eq!(control.state().pause_at, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_pause_after_highest_executed_checkpoint() {
+        let control = CheckpointExecutionControl::new(Some(41));
+
+        control.pause(None);
+        assert_eq!(control.state().pause_at, Some(42));
+
+        control.step_checkpoint();
+        assert_eq!(control.state().pause_at, Some(43));
+        control.wait_for_checkpoint_release(42).await;
+        assert!(
+            control
+                .wait_for_checkpoint_release(43)
+                .now_or_never()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_step_transactions() {
+        let control = CheckpointExecutionControl::new(None);
+        control.pause(Some(0));
+
+        control.step_transaction();
+        control.wait_for_checkpoint_release(0).await;
+        assert!(control.is_stepping_transactions(0));
+        assert!(
+            control
+                .wait_for_checkpoint_release(1)
+                .now_or_never()
+                .is_none()
+        );
+
+        let next = |index| NextTransaction {
+            checkpoint: 0,
+            index,
+            digest: TransactionDigest::random(),
+            expected_effects: TransactionEffects::default(),
+            input_objects: vec![],
+        };
+        assert!(
+            control
+                .wait_for_transaction_release(next(0))
+                .now_or_never()
+                .is_none()
+        );
+        assert_eq!(control.next_transaction().unwrap().index, 0);
+
+        control.step_transaction();
+        control.wait_for_transaction_release(next(0)).await;
+        assert!(
+            control
+                .wait_for_transaction_release(next(1))
+                .now_or_never()
+                .is_none()
+        );
+
+        control.checkpoint_executed(0);
+        assert!(!control.is_stepping_transactions(0));
+        assert!(control.next_transaction().is_none());
+        assert!(
+            control
+                .wait_for_checkpoint_release(1)
+                .now_or_never()
+                .is_none()
+        );
+
+        // Stepping again steps through the transactions of the next checkpoint.
+        control.step_transaction();
+        control.wait_for_checkpoint_release(1).await;
+        assert!(control.is_stepping_transactions(1));
+    }
+}
@@ -21,13 +21,15 @@
 use futures::StreamExt;
 use mysten_common::{ZipDebugEqIteratorExt, debug_fatal, fatal, izip_debug_eq};
 use parking_lot::Mutex;
+use std::collections::BTreeMap;
 use std::{sync::Arc, time::Instant};
 use sui_types::SUI_ACCUMULATOR_ROOT_OBJECT_ID;
-use sui_types::base_types::SequenceNumber;
+use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::crypto::RandomnessRound;
 use sui_types::inner_temporary_store::PackageStoreWithFallback;
 use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSequenceNumber};
-use sui_types::transaction::{TransactionDataAPI, TransactionKind};
+use sui_types::object::Object;
+use sui_types::transaction::{InputObjectKind, TransactionDataAPI, TransactionKind};
 
 use sui_config::node::{CheckpointExecutorConfig, RunWithRange};
 use sui_macros::fail_point;
@@ -56,10 +58,14 @@ use crate::{
     execution_cache::{ObjectCacheRead, TransactionCacheRead},
 };
 
+pub mod control;
 mod data_ingestion_handler;
 pub mod metrics;
+#[cfg(test)]
+mod tests;
 pub(crate) mod utils;
 
+use control::NextTransaction;
 use data_ingestion_handler::{load_checkpoint, store_checkpoint_locally};
 use metrics::CheckpointExecutorMetrics;
 use utils::*;
@@ -322,6 +328,10 @@ impl CheckpointExecutor {
             let this = this.clone();
             let pipeline_handle = pipeline_stages.handle(*checkpoint.sequence_number());
             async move {
+                this.state
+                    .checkpoint_execution_control
+                    .wait_for_checkpoint_release(*checkpoint.sequence_number())
+                    .await;
                 let pipeline_handle = pipeline_handle.await;
                 tokio::spawn(this.execute_checkpoint(checkpoint, pipeline_handle))
                     .await
@@ -491,6 +501,9 @@ impl CheckpointExecutor {
         fail_point!("crash");
 
         self.bump_highest_executed_checkpoint(&ckpt_state.data.checkpoint);
+        self.state
+            .checkpoint_execution_control
+            .checkpoint_executed(seq);
 
         finish_stage!(pipeline_handle, BumpHighestExecutedCheckpoint);
 
@@ -585,13 +598,28 @@ impl CheckpointExecutor {
         pipeline_handle: &mut PipelineHandle,
     ) -> CheckpointExecutionState {
         let sequence_number = checkpoint.sequence_number;
-        let (mut ckpt_state, tx_data, unexecuted_tx_digests) = {
+        let (mut ckpt_state, tx_data, unexecuted_txns) = {
             let _scope =
                 mysten_metrics::monitored_scope("CheckpointExecutor::execute_transactions");
             let (ckpt_state, tx_data) = self.load_checkpoint_transactions(checkpoint);
-            let unexecuted_tx_digests = self.schedule_transaction_execution(&ckpt_state, &tx_data);
-            (ckpt_state, tx_data, unexecuted_tx_digests)
+            let unexecuted_txns = self.prepare_transaction_execution(&ckpt_state, &tx_data);
+            (ckpt_state, tx_data, unexecuted_txns)
         };
+        let unexecuted_tx_digests: Vec<_> = unexecuted_txns
+            .iter()
+            .map(|(txn, _)| *txn.digest())
+            .collect();
+        if self
+            .state
+            .checkpoint_execution_control
+            .is_stepping_transactions(sequence_number)
+        {
+            self.step_transaction_execution(sequence_number, unexecuted_txns, &tx_data)
+                .await;
+        } else {
+            self.execution_scheduler
+                .enqueue_transactions(unexecuted_txns, &self.epoch_store);
+        }
 
         finish_stage!(pipeline_handle, ExecuteTransactions);
 
@@ -851,84 +879,162 @@ impl CheckpointExecutor {
         }
     }
 
-    // Schedule all unexecuted transactions in the checkpoint for execution
+    // Prepare all unexecuted transactions in the checkpoint for execution
     #[instrument(level = "info", skip_all)]
-    fn schedule_transaction_execution(
+    fn prepare_transaction_execution(
         &self,
         ckpt_state: &CheckpointExecutionState,
         tx_data: &CheckpointTransactionData,
-    ) -> Vec<TransactionDigest> {
+    ) -> Vec<(VerifiedExecutableTransaction, ExecutionEnv)> {
         let mut barrier_deps_builder = BarrierDependencyBuilder::new();
 
         // Find unexecuted transactions and their expected effects digests
-        let (unexecuted_tx_digests, unexecuted_txns): (Vec<_>, Vec<_>) = itertools::multiunzip(
-            izip_debug_eq!(
-                tx_data.transactions.iter(),
-                ckpt_state.data.tx_digests.iter(),
-                ckpt_state.data.fx_digests.iter(),
-                tx_data.effects.iter(),
-                tx_data.executed_fx_digests.iter(),
-                tx_data.accumulator_versions.iter()
-            )
-            .filter_map(
-                |(
-                    txn,
-                    tx_digest,
-                    expected_fx_digest,
-                    effects,
-                    executed_fx_digest,
-                    accumulator_version,
-                )| {
-                    let barrier_deps =
-                        barrier_deps_builder.process_tx(*tx_digest, txn.transaction_data());
-
-                    if let Some(executed_fx_digest) = executed_fx_digest {
-                        assert_not_forked(
-                            &ckpt_state.data.checkpoint,
-                            tx_digest,
-                            expected_fx_digest,
-                            executed_fx_digest,
-                            &*self.transaction_cache_reader,
-                        );
-                        None
-                    } else if txn.transaction_data().is_end_of_epoch_tx() {
-                        None
-                    } else {
-                        let assigned_versions = self
-                            .epoch_store
-                            .acquire_shared_version_assignments_from_effects(
-                                txn,
-                                effects,
-                                *accumulator_version,
-                                &*self.object_cache_reader,
-                            )
-                            .expect("failed to acquire shared version assignments");
-
-                        let mut env = ExecutionEnv::new()
-                            .with_assigned_versions(assigned_versions)
-                            .with_expected_effects_digest(*expected_fx_digest)
-                            .with_barrier_dependencies(barrier_deps);
-
-                        // Check if the expected effects indicate insufficient balance
-                        if let &ExecutionStatus::Failure(ExecutionFailure {
-                            error: ExecutionErrorKind::InsufficientFundsForWithdraw,
-                            ..
-                        }) = effects.status()
-                        {
-                            env = env.with_insufficient_funds();
-                        }
-
-                        Some((tx_digest, (txn.clone(), env)))
+        izip_debug_eq!(
+            tx_data.transactions.iter(),
+            ckpt_state.data.tx_digests.iter(),
+            ckpt_state.data.fx_digests.iter(),
+            tx_data.effects.iter(),
+            tx_data.executed_fx_digests.iter(),
+            tx_data.accumulator_versions.iter()
+        )
+        .filter_map(
+            |(
+                txn,
+                tx_digest,
+                expected_fx_digest,
+                effects,
+                executed_fx_digest,
+                accumulator_version,
+            )| {
+                let barrier_deps =
+                    barrier_deps_builder.process_tx(*tx_digest, txn.transaction_data());
+
+                if let Some(executed_fx_digest) = executed_fx_digest {
+                    assert_not_forked(
+                        &ckpt_state.data.checkpoint,
+                        tx_digest,
+                        expected_fx_digest,
+                        executed_fx_digest,
+                        &*self.transaction_cache_reader,
+                    );
+                    None
+                } else if txn.transaction_data().is_end_of_epoch_tx() {
+                    None
+                } else {
+                    let assigned_versions = self
+                        .epoch_store
+                        .acquire_shared_version_assignments_from_effects(
+                            txn,
+                            effects,
+                            *accumulator_version,
+                            &*self.object_cache_reader,
+                        )
+                        .expect("failed to acquire shared version assignments");
+
+                    let mut env = ExecutionEnv::new()
+                        .with_assigned_versions(assigned_versions)
+                        .with_expected_effects_digest(*expected_fx_digest)
+                        .with_barrier_dependencies(barrier_deps);
+
+                    // Check if the expected effects indicate insufficient balance
+                    if let &ExecutionStatus::Failure(ExecutionFailure {
+                        error: ExecutionErrorKind::InsufficientFundsForWithdraw,
+                        ..
+                    }) = effects.status()
+                    {
+                        env = env.with_insufficient_funds();
                     }
-                },
-            ),
-        );
 
-        // Enqueue unexecuted transactions with their expected effects digests
-        self.execution_scheduler
-            .enqueue_transactions(unexecuted_txns, &self.epoch_store);
+                    Some((txn.clone(), env))
+                }
+            },
+        )
+        .collect()
+    }
+
+    /// Executes the unexecuted transactions of a checkpoint one at a time, waiting for each of
+    /// them to be released through the checkpoint execution control.
+    async fn step_transaction_execution(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        unexecuted_txns: Vec<(VerifiedExecutableTransaction, ExecutionEnv)>,
+        tx_data: &CheckpointTransactionData,
+    ) {
+        let control = &self.state.checkpoint_execution_control;
+        let mut unexecuted_txns = unexecuted_txns.into_iter().enumerate().peekable();
+        while let Some((index, (txn, env))) = unexecuted_txns.next() {
+            let digest = *txn.digest();
+            let expected_effects = tx_data
+                .effects
+                .iter()
+                .find(|effects| *effects.transaction_digest() == digest)
+                .expect("checkpoint must contain effects of its transactions")
+                .clone();
+            let input_objects = self.load_input_objects(&txn, &expected_effects);
+            info!(?digest, index, "waiting for transaction to be stepped over");
+            control
+                .wait_for_transaction_release(NextTransaction {
+                    checkpoint: sequence_number,
+                    index,
+                    digest,
+                    expected_effects,
+                    input_objects,
+                })
+                .await;
+
+            self.execution_scheduler
+                .enqueue_transactions(vec![(txn, env)], &self.epoch_store);
+            if !control.is_stepping_transactions(sequence_number) {
+                // Execution was resumed, execute the remaining transactions at once.
+                self.execution_scheduler.enqueue_transactions(
+                    unexecuted_txns.map(|(_, txn)| txn).collect(),
+                    &self.epoch_store,
+                );
+                return;
+            }
+            self.transaction_cache_reader
+                .notify_read_executed_effects_digests(
+                    "CheckpointExecutor::step_transaction_execution",
+                    &[digest],
+                )
+                .await;
+        }
+    }
 
-        unexecuted_tx_digests
+    /// Loads the objects read by a transaction, at the versions it is expected to read them.
+    fn load_input_objects(
+        &self,
+        txn: &VerifiedExecutableTransaction,
+        effects: &TransactionEffects,
+    ) -> Vec<Object> {
+        let mut keys: BTreeMap<ObjectID, Option<SequenceNumber>> = effects
+            .modified_at_versions()
+            .into_iter()
+            .chain(
+                effects
+                    .input_consensus_objects()
+                    .iter()
+                    .map(|object| object.id_and_version()),
+            )
+            .map(|(id, version)| (id, Some(version)))
+            .collect();
+        for input in txn.transaction_data().input_objects().unwrap_or_default() {
+            match input {
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    keys.entry(id).or_insert(Some(version));
+                }
+                InputObjectKind::MovePackage(id) => {
+                    keys.entry(id).or_insert(None);
+                }
+                InputObjectKind::SharedMoveObject { .. } => {}
+            }
+        }
+        keys.into_iter()
+            .filter_map(|(id, version)| match version {
+                Some(version) => self.object_cache_reader.get_object_by_key(&id, version),
+                None => self.object_cache_reader.get_object(&id),
+            })
+            .collect()
     }
 
     // Execute the change epoch txn
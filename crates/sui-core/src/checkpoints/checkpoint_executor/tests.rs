@@ -0,0 +1,96 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::authority::test_authority_builder::TestAuthorityBuilder;
+use std::time::Duration;
+use sui_swarm_config::test_utils::CommitteeFixture;
+
+/// Stores `number_of_checkpoints` empty checkpoints as synced, starting from the genesis checkpoint.
+fn sync_empty_checkpoints(
+    checkpoint_store: &CheckpointStore,
+    committee: &CommitteeFixture,
+    number_of_checkpoints: usize,
+) {
+    let (checkpoints, contents, _, _) =
+        committee.make_empty_checkpoints(number_of_checkpoints, None);
+    for (checkpoint, contents) in checkpoints.iter().zip_debug_eq(contents) {
+        checkpoint_store
+            .insert_verified_checkpoint_contents(checkpoint, contents)
+            .unwrap();
+        checkpoint_store
+            .insert_verified_checkpoint(checkpoint)
+            .unwrap();
+        checkpoint_store
+            .update_highest_synced_checkpoint(checkpoint)
+            .unwrap();
+    }
+}
+
+async fn wait_for_executed_checkpoint(
+    checkpoint_store: &CheckpointStore,
+    sequence_number: CheckpointSequenceNumber,
+) {
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        checkpoint_store.notify_read_executed_checkpoint(sequence_number),
+    )
+    .await
+    .expect("checkpoint was not executed");
+}
+
+/// Waits for a while, and returns the highest executed checkpoint.
+async fn settled_highest_executed_checkpoint(
+    checkpoint_store: &CheckpointStore,
+) -> Option<CheckpointSequenceNumber> {
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    checkpoint_store
+        .get_highest_executed_checkpoint_seq_number()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_pause_step_and_resume_checkpoint_execution() {
+    let state = TestAuthorityBuilder::new().build().await;
+    let checkpoint_store = state.get_checkpoint_store().clone();
+    let control = state.checkpoint_execution_control.clone();
+
+    // Nothing was executed yet, so pausing holds back the genesis checkpoint.
+    control.pause(None);
+    assert_eq!(control.state().pause_at, Some(0));
+
+    let committee = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4);
+    sync_empty_checkpoints(&checkpoint_store, &committee, 5);
+    let executor = CheckpointExecutor::new_for_tests(
+        state.epoch_store_for_testing().clone(),
+        checkpoint_store.clone(),
+        state.clone(),
+        Arc::new(GlobalStateHasher::new_for_tests(
+            state.get_global_state_hash_store().clone(),
+        )),
+    );
+    let executor_handle = tokio::spawn(executor.run_epoch(None));
+    assert_eq!(
+        settled_highest_executed_checkpoint(&checkpoint_store).await,
+        None
+    );
+
+    // Each step executes exactly one more checkpoint.
+    control.step_checkpoint();
+    wait_for_executed_checkpoint(&checkpoint_store, 0).await;
+    control.step_checkpoint();
+    wait_for_executed_checkpoint(&checkpoint_store, 1).await;
+    assert_eq!(
+        settled_highest_executed_checkpoint(&checkpoint_store).await,
+        Some(1)
+    );
+    assert_eq!(control.state().highest_released, Some(1));
+    assert_eq!(control.state().pause_at, Some(2));
+
+    // Resuming executes all synced checkpoints.
+    control.resume();
+    wait_for_executed_checkpoint(&checkpoint_store, 4).await;
+    assert_eq!(control.state().pause_at, None);
+
+    executor_handle.abort();
+}
@@ -342,6 +342,16 @@ impl CheckpointStoreTables {
     pub fn open_readonly(path: &Path) -> Self {
         Self::new(path, "checkpoint", Arc::new(PrunerWatermarks::default()))
     }
+
+    #[cfg(not(tidehunter))]
+    pub fn open_as_secondary(path: &Path, secondary_path: &Path) -> Self {
+        Self::open_tables_as_secondary(
+            path.to_path_buf(),
+            secondary_path.to_path_buf(),
+            MetricConf::new("checkpoint_secondary"),
+            None,
+        )
+    }
 }
 
 pub struct CheckpointStore {
@@ -365,6 +375,23 @@ impl CheckpointStore {
         CheckpointStore::new(ckpt_dir.path(), Arc::new(PrunerWatermarks::default()))
     }
 
+    /// Opens the checkpoint store of a node running in another process as a RocksDB secondary.
+    /// The store only observes checkpoints written by the node after `try_catch_up_with_primary`.
+    #[cfg(not(tidehunter))]
+    pub fn new_as_secondary(path: &Path, secondary_path: &Path) -> Arc<Self> {
+        let tables = CheckpointStoreTables::open_as_secondary(path, secondary_path);
+        Arc::new(Self {
+            tables,
+            synced_checkpoint_notify_read: NotifyRead::new(),
+            executed_checkpoint_notify_read: NotifyRead::new(),
+        })
+    }
+
+    #[cfg(not(tidehunter))]
+    pub fn try_catch_up_with_primary(&self) -> Result<(), TypedStoreError> {
+        self.tables.try_catch_up_with_primary_all()
+    }
+
     pub fn new_for_db_checkpoint_handler(path: &Path) -> Arc<Self> {
         let tables = CheckpointStoreTables::new(
             path,
@@ -3811,6 +3838,33 @@ mod tests {
         }
     }
 
+    #[cfg(not(tidehunter))]
+    #[tokio::test]
+    async fn test_secondary_catch_up() {
+        let primary_dir = mysten_common::tempdir().unwrap();
+        let secondary_dir = mysten_common::tempdir().unwrap();
+        let primary =
+            CheckpointStore::new(primary_dir.path(), Arc::new(PrunerWatermarks::default()));
+        let secondary = CheckpointStore::new_as_secondary(primary_dir.path(), secondary_dir.path());
+        assert!(secondary.get_checkpoint_fork_detected().unwrap().is_none());
+
+        let digest = CheckpointDigest::random();
+        primary.record_checkpoint_fork_detected(42, digest).unwrap();
+
+        // Writes of the primary are only observed after catching up.
+        assert!(secondary.get_checkpoint_fork_detected().unwrap().is_none());
+        secondary.try_catch_up_with_primary().unwrap();
+        assert_eq!(
+            secondary.get_checkpoint_fork_detected().unwrap(),
+            Some((42, digest))
+        );
+        assert!(
+            secondary
+                .record_checkpoint_fork_detected(43, digest)
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_fork_detection_storage() {
         let store = CheckpointStore::new_for_tests();
@@ -3,12 +3,13 @@
 
 use moka::ops::compute::Op;
 use moka::sync::Cache;
-use std::collections::HashMap;
 use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use sui_types::base_types::ObjectID;
 use sui_types::effects::{InputConsensusObject, TransactionEffects, TransactionEffectsAPI};
 use sui_types::execution_status::CongestedObjects;
 use sui_types::messages_checkpoint::{CheckpointTimestamp, VerifiedCheckpoint};
+use sui_types::storage::CongestionSample;
 use sui_types::transaction::{TransactionData, TransactionDataAPI};
 
 use crate::execution_cache::TransactionCacheRead;
@@ -54,8 +55,13 @@ impl CongestionInfo {
     }
 }
 
+/// Number of checkpoints with congestion on an object that are kept in its history.
+const MAX_CONGESTION_HISTORY: usize = 32;
+
 pub struct CongestionTracker {
     pub congestion_clearing_prices: Cache<ObjectID, CongestionInfo>,
+    /// Per-object congestion observed in recent checkpoints, oldest first.
+    congestion_history: Cache<ObjectID, VecDeque<CongestionSample>>,
 }
 
 impl Default for CongestionTracker {
@@ -68,6 +74,7 @@ impl CongestionTracker {
     pub fn new() -> Self {
         Self {
             congestion_clearing_prices: Cache::new(10_000),
+            congestion_history: Cache::new(10_000),
         }
     }
 
@@ -126,6 +133,14 @@ impl CongestionTracker {
                 .map(|id| id.id),
         )
     }
+
+    /// Congestion observed on `object_id` in the most recent checkpoints that it was congested
+    /// in (or cleared after being congested), oldest first.
+    pub fn get_congestion_history(&self, object_id: &ObjectID) -> Option<Vec<CongestionSample>> {
+        self.congestion_history
+            .get(object_id)
+            .map(|history| history.into_iter().collect())
+    }
 }
 
 impl CongestionTracker {
@@ -137,6 +152,7 @@ impl CongestionTracker {
     ) {
         let congestion_info_map =
             self.compute_per_checkpoint_congestion_info(now, congestion_events, cleared_events);
+        self.record_congestion_history(now, &congestion_info_map);
         self.process_checkpoint_congestion(congestion_info_map);
     }
 
@@ -244,6 +260,36 @@ impl CongestionTracker {
         }
     }
 
+    fn record_congestion_history(
+        &self,
+        now: CheckpointTimestamp,
+        congestion_info_map: &HashMap<ObjectID, CongestionInfo>,
+    ) {
+        for (object_id, info) in congestion_info_map {
+            // Only the prices observed in this checkpoint are recorded: the info for an object that
+            // was only cleared in this checkpoint carries its previous cancellation.
+            let sample = CongestionSample {
+                checkpoint_timestamp_ms: now,
+                highest_cancelled_gas_price: (info.last_cancellation_time == now)
+                    .then_some(info.highest_cancelled_gas_price),
+                lowest_executed_gas_price: info
+                    .lowest_executed_gas_price
+                    .filter(|_| info.last_success_time == Some(now)),
+            };
+
+            self.congestion_history
+                .entry(*object_id)
+                .and_compute_with(|maybe_entry| {
+                    let mut history = maybe_entry.map(|e| e.into_value()).unwrap_or_default();
+                    if history.len() == MAX_CONGESTION_HISTORY {
+                        history.pop_front();
+                    }
+                    history.push_back(sample);
+                    Op::Put(history)
+                });
+        }
+    }
+
     fn get_congestion_info(&self, object_id: ObjectID) -> Option<CongestionInfo> {
         self.congestion_clearing_prices.get(&object_id)
     }
@@ -330,4 +376,53 @@ mod tests {
             Some(150)
         );
     }
+
+    #[test]
+    fn test_congestion_history() {
+        let tracker = CongestionTracker::new();
+        let obj = ObjectID::random();
+        let uncongested = ObjectID::random();
+
+        // Objects that have not been congested have no history
+        tracker.process_per_checkpoint_events(1000, &[], &[(100, vec![uncongested])]);
+        assert_eq!(tracker.get_congestion_history(&uncongested), None);
+
+        tracker.process_per_checkpoint_events(1000, &[(100, vec![obj]), (75, vec![obj])], &[]);
+        tracker.process_per_checkpoint_events(2000, &[], &[(150, vec![obj])]);
+        tracker.process_per_checkpoint_events(
+            3000,
+            &[(100, vec![obj])],
+            &[(175, vec![obj]), (125, vec![obj])],
+        );
+
+        assert_eq!(
+            tracker.get_congestion_history(&obj),
+            Some(vec![
+                CongestionSample {
+                    checkpoint_timestamp_ms: 1000,
+                    highest_cancelled_gas_price: Some(100),
+                    lowest_executed_gas_price: None,
+                },
+                CongestionSample {
+                    checkpoint_timestamp_ms: 2000,
+                    highest_cancelled_gas_price: None,
+                    lowest_executed_gas_price: Some(150),
+                },
+                CongestionSample {
+                    checkpoint_timestamp_ms: 3000,
+                    highest_cancelled_gas_price: Some(100),
+                    lowest_executed_gas_price: Some(125),
+                },
+            ])
+        );
+
+        // Only the most recent checkpoints are kept
+        for i in 0..MAX_CONGESTION_HISTORY as u64 {
+            tracker.process_per_checkpoint_events(4000 + i, &[(100, vec![obj])], &[]);
+        }
+
+        let history = tracker.get_congestion_history(&obj).unwrap();
+        assert_eq!(history.len(), MAX_CONGESTION_HISTORY);
+        assert_eq!(history[0].checkpoint_timestamp_ms, 4000);
+    }
 }
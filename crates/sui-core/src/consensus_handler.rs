@@ -3649,7 +3649,10 @@ impl CommitIntervalObserver {
     }
 
     pub fn observe_commit_time(&mut self, consensus_commit: &impl ConsensusCommitAPI) {
-        let commit_time = consensus_commit.commit_timestamp_ms();
+        self.observe_commit_timestamp(consensus_commit.commit_timestamp_ms());
+    }
+
+    pub fn observe_commit_timestamp(&mut self, commit_time: u64) {
         if self.ring_buffer.len() == self.ring_buffer.capacity() {
             self.ring_buffer.pop_front();
         }
@@ -117,7 +117,10 @@ impl AddressOverridesMap {
     }
 }
 
-fn to_consensus_protocol_config(config: &ProtocolConfig, chain: Chain) -> ConsensusProtocolConfig {
+pub fn to_consensus_protocol_config(
+    config: &ProtocolConfig,
+    chain: Chain,
+) -> ConsensusProtocolConfig {
     let chain_type = match chain {
         Chain::Mainnet => ChainType::Mainnet,
         Chain::Testnet => ChainType::Testnet,
@@ -7,23 +7,31 @@ use crate::authority::authority_store_pruner::{
 use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::checkpoints::CheckpointStore;
 use crate::rpc_index::RpcIndexStore;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use bytes::Bytes;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::hash::{Blake2b256, HashFunction};
 use futures::future::try_join_all;
+use futures::{StreamExt, TryStreamExt};
+use itertools::Itertools;
 use object_store::path::Path;
 use object_store::{DynObjectStore, ObjectStoreExt};
 use prometheus::{IntGauge, Registry, register_int_gauge_with_registry};
+use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::Read;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Component, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sui_config::node::AuthorityStorePruningConfig;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_storage::object_store::util::{
-    copy_recursively, find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs,
+    copy_recursively, find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, get,
     path_to_filesystem, put, run_manifest_update_loop, write_snapshot_manifest,
 };
+use sui_types::committee::EpochId;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use tracing::{debug, error, info};
 
 pub const SUCCESS_MARKER: &str = "_SUCCESS";
@@ -377,16 +385,318 @@ impl DBCheckpointHandler {
     }
 }
 
+/// Prefix of the directories holding on-demand db checkpoints, which are named after the
+/// highest executed checkpoint at the time they were taken. These are not picked up by the
+/// epoch based upload and garbage collection loops.
+pub const ON_DEMAND_DB_CHECKPOINT_PREFIX: &str = "checkpoint_";
+/// Number of on-demand db checkpoints kept on local disk when no db checkpoint object store is
+/// configured. Older ones are deleted whenever a new one is taken.
+pub const NUM_LOCAL_ON_DEMAND_DB_CHECKPOINTS_TO_RETAIN: usize = 2;
+/// Directory in the remote object store that incremental db checkpoints are uploaded to.
+pub const INCREMENTAL_DB_CHECKPOINT_DIR: &str = "incremental";
+/// Name of the manifest that describes a single incremental db checkpoint. It is written after
+/// all files of the db checkpoint were uploaded, so its presence marks the upload as complete.
+pub const INCREMENTAL_MANIFEST_FILENAME: &str = "MANIFEST.json";
+/// Directory under `INCREMENTAL_DB_CHECKPOINT_DIR` holding file contents, keyed by their digest.
+const INCREMENTAL_BLOBS_DIR: &str = "blobs";
+
+/// Describes a db checkpoint uploaded incrementally to a remote object store. The contents of
+/// every file are stored once under `incremental/blobs/<digest>`, and shared by all db
+/// checkpoints which contain the same file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncrementalDBCheckpointManifest {
+    pub epoch: EpochId,
+    pub checkpoint_seq: CheckpointSequenceNumber,
+    pub timestamp_ms: u64,
+    pub files: Vec<IncrementalDBCheckpointFile>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncrementalDBCheckpointFile {
+    /// Path of the file relative to the root of the db checkpoint, using `/` as separator.
+    pub path: String,
+    /// Hex encoded Blake2b256 digest of the contents of the file.
+    pub digest: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct IncrementalUploadSummary {
+    pub num_files: usize,
+    pub num_files_uploaded: usize,
+    pub bytes_uploaded: u64,
+}
+
+/// Returns the path of the manifest of the incremental db checkpoint at `checkpoint_seq`.
+pub fn incremental_manifest_path(checkpoint_seq: CheckpointSequenceNumber) -> Path {
+    Path::from(INCREMENTAL_DB_CHECKPOINT_DIR)
+        .child(format!("{ON_DEMAND_DB_CHECKPOINT_PREFIX}{checkpoint_seq}"))
+        .child(INCREMENTAL_MANIFEST_FILENAME)
+}
+
+fn incremental_blob_path(digest: &str) -> Path {
+    Path::from(INCREMENTAL_DB_CHECKPOINT_DIR)
+        .child(INCREMENTAL_BLOBS_DIR)
+        .child(digest)
+}
+
+/// Uploads the db checkpoint at `local_path` to `object_store`, skipping files whose contents
+/// are already present in the store. Most SST files are shared between consecutive db
+/// checkpoints, so only files written since the previous upload are copied.
+pub async fn upload_incremental_db_checkpoint(
+    local_path: &std::path::Path,
+    epoch: EpochId,
+    checkpoint_seq: CheckpointSequenceNumber,
+    object_store: &Arc<DynObjectStore>,
+    concurrency: NonZeroUsize,
+) -> Result<IncrementalUploadSummary> {
+    let root = local_path.to_path_buf();
+    let files = tokio::task::spawn_blocking(move || hash_local_files(&root)).await??;
+
+    let uploaded: Vec<Option<u64>> = futures::stream::iter(files.iter())
+        .map(|file| async move {
+            // Empty files are recreated from the manifest alone.
+            if file.size == 0 {
+                return Ok(None);
+            }
+            let blob_path = incremental_blob_path(&file.digest);
+            match object_store.head(&blob_path).await {
+                Ok(_) => return Ok(None),
+                Err(object_store::Error::NotFound { .. }) => {}
+                Err(err) => return Err(err.into()),
+            }
+            let bytes = tokio::fs::read(local_path.join(&file.path)).await?;
+            put(object_store, &blob_path, Bytes::from(bytes)).await?;
+            Ok::<_, anyhow::Error>(Some(file.size))
+        })
+        .buffer_unordered(concurrency.get())
+        .try_collect()
+        .await?;
+
+    let summary = IncrementalUploadSummary {
+        num_files: files.len(),
+        num_files_uploaded: uploaded.iter().flatten().count(),
+        bytes_uploaded: uploaded.iter().flatten().sum(),
+    };
+
+    let manifest = IncrementalDBCheckpointManifest {
+        epoch,
+        checkpoint_seq,
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default(),
+        files,
+    };
+    put(
+        object_store,
+        &incremental_manifest_path(checkpoint_seq),
+        Bytes::from(serde_json::to_vec(&manifest)?),
+    )
+    .await?;
+    info!(
+        "Uploaded db checkpoint at checkpoint {checkpoint_seq}: {} of {} files, {} bytes",
+        summary.num_files_uploaded, summary.num_files, summary.bytes_uploaded
+    );
+    Ok(summary)
+}
+
+/// Returns the sequence numbers of all incremental db checkpoints whose upload completed.
+pub async fn list_incremental_db_checkpoints(
+    object_store: &Arc<DynObjectStore>,
+) -> Result<Vec<CheckpointSequenceNumber>> {
+    let entries = object_store
+        .list_with_delimiter(Some(&Path::from(INCREMENTAL_DB_CHECKPOINT_DIR)))
+        .await?;
+    let mut checkpoints = vec![];
+    for entry in entries.common_prefixes {
+        let Some(checkpoint_seq) = entry
+            .filename()
+            .and_then(|name| name.strip_prefix(ON_DEMAND_DB_CHECKPOINT_PREFIX))
+            .and_then(|seq| seq.parse().ok())
+        else {
+            continue;
+        };
+        match object_store
+            .head(&incremental_manifest_path(checkpoint_seq))
+            .await
+        {
+            Ok(_) => checkpoints.push(checkpoint_seq),
+            Err(object_store::Error::NotFound { .. }) => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    checkpoints.sort();
+    Ok(checkpoints)
+}
+
+/// Downloads the incremental db checkpoint at `checkpoint_seq`, or the latest one if not
+/// specified, from `object_store` into `db_path`, verifying the digest of every file.
+pub async fn restore_incremental_db_checkpoint(
+    object_store: &Arc<DynObjectStore>,
+    checkpoint_seq: Option<CheckpointSequenceNumber>,
+    db_path: &std::path::Path,
+    concurrency: NonZeroUsize,
+) -> Result<IncrementalDBCheckpointManifest> {
+    if db_path.exists() {
+        anyhow::bail!(
+            "Cannot restore db checkpoint: {} already exists",
+            db_path.display()
+        );
+    }
+    let checkpoint_seq = match checkpoint_seq {
+        Some(checkpoint_seq) => checkpoint_seq,
+        None => *list_incremental_db_checkpoints(object_store)
+            .await?
+            .last()
+            .context("No incremental db checkpoints found in object store")?,
+    };
+    let manifest: IncrementalDBCheckpointManifest = serde_json::from_slice(
+        &get(object_store, &incremental_manifest_path(checkpoint_seq)).await?,
+    )
+    .with_context(|| format!("Failed to read manifest of db checkpoint {checkpoint_seq}"))?;
+    for file in &manifest.files {
+        manifest_file_path(&file.path)?;
+    }
+
+    // Download into a temporary directory so that an interrupted restore never leaves behind
+    // a partial db.
+    let tmp_path = db_path.with_extension("tmp");
+    if tmp_path.exists() {
+        fs::remove_dir_all(&tmp_path)?;
+    }
+    fs::create_dir_all(&tmp_path)?;
+
+    futures::stream::iter(manifest.files.iter())
+        .map(|file| {
+            let tmp_path = &tmp_path;
+            async move {
+                let bytes = if file.size == 0 {
+                    Bytes::new()
+                } else {
+                    get(object_store, &incremental_blob_path(&file.digest)).await?
+                };
+                let digest = Hex::encode(Blake2b256::digest(&bytes).digest);
+                if bytes.len() as u64 != file.size || digest != file.digest {
+                    anyhow::bail!("Digest mismatch for {}", file.path);
+                }
+                let local_path = tmp_path.join(manifest_file_path(&file.path)?);
+                if let Some(parent) = local_path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(local_path, bytes).await?;
+                Ok::<_, anyhow::Error>(())
+            }
+        })
+        .buffer_unordered(concurrency.get())
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    fs::rename(&tmp_path, db_path)?;
+    Ok(manifest)
+}
+
+/// Deletes all but the `num_to_retain` most recent on-demand db checkpoints under
+/// `checkpoint_root`, and returns the checkpoints of the deleted ones.
+pub fn garbage_collect_on_demand_db_checkpoints(
+    checkpoint_root: &std::path::Path,
+    num_to_retain: usize,
+) -> Result<Vec<CheckpointSequenceNumber>> {
+    let mut checkpoints = vec![];
+    for entry in fs::read_dir(checkpoint_root)? {
+        let path = entry?.path();
+        let Some(checkpoint_seq) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(ON_DEMAND_DB_CHECKPOINT_PREFIX))
+            .and_then(|seq| seq.parse::<CheckpointSequenceNumber>().ok())
+        else {
+            continue;
+        };
+        if path.is_dir() {
+            checkpoints.push((checkpoint_seq, path));
+        }
+    }
+    checkpoints.sort_by_key(|(checkpoint_seq, _)| *checkpoint_seq);
+    let num_to_delete = checkpoints.len().saturating_sub(num_to_retain);
+    let mut deleted = vec![];
+    for (checkpoint_seq, path) in checkpoints.into_iter().take(num_to_delete) {
+        info!("Deleting on-demand db checkpoint dir: {}", path.display());
+        fs::remove_dir_all(&path)?;
+        deleted.push(checkpoint_seq);
+    }
+    Ok(deleted)
+}
+
+/// Returns the path of a file of a manifest relative to the restore directory. Absolute paths
+/// and paths with `..` components are rejected, so that a manifest cannot write outside of it.
+fn manifest_file_path(path: &str) -> Result<&std::path::Path> {
+    let relative_path = std::path::Path::new(path);
+    if path.is_empty()
+        || !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        anyhow::bail!("Invalid path in db checkpoint manifest: {path}");
+    }
+    Ok(relative_path)
+}
+
+/// Lists all files under `root`, along with their size and digest, in a stable order.
+fn hash_local_files(root: &std::path::Path) -> Result<Vec<IncrementalDBCheckpointFile>> {
+    let mut files = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let relative_path = path
+                .strip_prefix(root)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .join("/");
+            let mut hasher = Blake2b256::default();
+            let mut reader = fs::File::open(&path)?;
+            let mut buf = vec![0u8; 1 << 20];
+            let mut size = 0;
+            loop {
+                let n = reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+                size += n as u64;
+            }
+            files.push(IncrementalDBCheckpointFile {
+                path: relative_path,
+                digest: Hex::encode(hasher.finalize().digest),
+                size,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::db_checkpoint_handler::{
-        DBCheckpointHandler, SUCCESS_MARKER, TEST_MARKER, UPLOAD_COMPLETED_MARKER,
+        DBCheckpointHandler, IncrementalDBCheckpointFile, IncrementalDBCheckpointManifest,
+        SUCCESS_MARKER, TEST_MARKER, UPLOAD_COMPLETED_MARKER,
+        garbage_collect_on_demand_db_checkpoints, incremental_manifest_path,
+        list_incremental_db_checkpoints, restore_incremental_db_checkpoint,
+        upload_incremental_db_checkpoint,
     };
+    use bytes::Bytes;
     use itertools::Itertools;
     use std::fs;
+    use std::num::NonZeroUsize;
     use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
     use sui_storage::object_store::util::{
-        find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem,
+        find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem, put,
     };
     use tempfile::TempDir;
 
@@ -714,4 +1024,174 @@ mod tests {
         assert_eq!(missing_epochs, expected_missing_epochs);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_incremental_upload() -> anyhow::Result<()> {
+        let checkpoint_dir = TempDir::new()?;
+        let remote_dir = TempDir::new()?;
+        let restore_dir = TempDir::new()?;
+        let concurrency = NonZeroUsize::new(4).unwrap();
+
+        let object_store = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote_dir.path().to_path_buf()),
+            ..Default::default()
+        }
+        .make()?;
+
+        let local_checkpoint_10 = checkpoint_dir.path().join("checkpoint_10");
+        fs::create_dir_all(local_checkpoint_10.join("store"))?;
+        fs::write(local_checkpoint_10.join("000001.sst"), b"Lorem ipsum")?;
+        fs::write(
+            local_checkpoint_10.join("store").join("000002.sst"),
+            b"dolor",
+        )?;
+        fs::write(local_checkpoint_10.join("LOCK"), b"")?;
+
+        let summary = upload_incremental_db_checkpoint(
+            &local_checkpoint_10,
+            1,
+            10,
+            &object_store,
+            concurrency,
+        )
+        .await?;
+        assert_eq!(summary.num_files, 3);
+        assert_eq!(summary.num_files_uploaded, 2);
+
+        // Only files which were not uploaded before are copied.
+        let local_checkpoint_20 = checkpoint_dir.path().join("checkpoint_20");
+        fs::create_dir_all(local_checkpoint_20.join("store"))?;
+        fs::write(local_checkpoint_20.join("000001.sst"), b"Lorem ipsum")?;
+        fs::write(
+            local_checkpoint_20.join("store").join("000002.sst"),
+            b"dolor",
+        )?;
+        fs::write(
+            local_checkpoint_20.join("store").join("000003.sst"),
+            b"sit amet",
+        )?;
+        fs::write(local_checkpoint_20.join("LOCK"), b"")?;
+
+        let summary = upload_incremental_db_checkpoint(
+            &local_checkpoint_20,
+            1,
+            20,
+            &object_store,
+            concurrency,
+        )
+        .await?;
+        assert_eq!(summary.num_files, 4);
+        assert_eq!(summary.num_files_uploaded, 1);
+        assert_eq!(summary.bytes_uploaded, 8);
+
+        assert_eq!(
+            list_incremental_db_checkpoints(&object_store).await?,
+            vec![10, 20]
+        );
+
+        // Restores the latest db checkpoint by default.
+        let db_path = restore_dir.path().join("db");
+        let manifest =
+            restore_incremental_db_checkpoint(&object_store, None, &db_path, concurrency).await?;
+        assert_eq!(manifest.checkpoint_seq, 20);
+        assert_eq!(fs::read(db_path.join("000001.sst"))?, b"Lorem ipsum");
+        assert_eq!(
+            fs::read(db_path.join("store").join("000003.sst"))?,
+            b"sit amet"
+        );
+        assert!(db_path.join("LOCK").exists());
+
+        let db_path = restore_dir.path().join("db_10");
+        restore_incremental_db_checkpoint(&object_store, Some(10), &db_path, concurrency).await?;
+        assert!(!db_path.join("store").join("000003.sst").exists());
+
+        // Restoring over an existing db is refused.
+        assert!(
+            restore_incremental_db_checkpoint(&object_store, Some(10), &db_path, concurrency)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_incremental_restore_rejects_invalid_paths() -> anyhow::Result<()> {
+        let remote_dir = TempDir::new()?;
+        let restore_dir = TempDir::new()?;
+        let concurrency = NonZeroUsize::new(4).unwrap();
+        let object_store = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(remote_dir.path().to_path_buf()),
+            ..Default::default()
+        }
+        .make()?;
+
+        for (checkpoint_seq, path) in [(10, "../escaped"), (20, "/tmp/escaped"), (30, "")] {
+            let manifest = IncrementalDBCheckpointManifest {
+                epoch: 1,
+                checkpoint_seq,
+                timestamp_ms: 0,
+                files: vec![IncrementalDBCheckpointFile {
+                    path: path.to_string(),
+                    // Blake2b256 digest of empty contents, so that only the path is invalid.
+                    digest: "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+                        .to_string(),
+                    size: 0,
+                }],
+            };
+            put(
+                &object_store,
+                &incremental_manifest_path(checkpoint_seq),
+                Bytes::from(serde_json::to_vec(&manifest)?),
+            )
+            .await?;
+
+            let db_path = restore_dir.path().join(format!("db_{checkpoint_seq}"));
+            assert!(
+                restore_incremental_db_checkpoint(
+                    &object_store,
+                    Some(checkpoint_seq),
+                    &db_path,
+                    concurrency
+                )
+                .await
+                .is_err()
+            );
+            assert!(!db_path.with_extension("tmp").exists());
+        }
+        assert!(!restore_dir.path().join("escaped").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_garbage_collect_on_demand_db_checkpoints() -> anyhow::Result<()> {
+        let checkpoint_dir = TempDir::new()?;
+        for name in [
+            "checkpoint_5",
+            "checkpoint_20",
+            "checkpoint_10",
+            "checkpoint_30.tmp",
+            "epoch_1",
+        ] {
+            fs::create_dir(checkpoint_dir.path().join(name))?;
+        }
+
+        let deleted = garbage_collect_on_demand_db_checkpoints(checkpoint_dir.path(), 2)?;
+        assert_eq!(deleted, vec![5]);
+        let remaining: Vec<_> = fs::read_dir(checkpoint_dir.path())?
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .sorted()
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                "checkpoint_10",
+                "checkpoint_20",
+                "checkpoint_30.tmp",
+                "epoch_1"
+            ]
+        );
+        Ok(())
+    }
 }
@@ -13,6 +13,7 @@ use typed_store::rocksdb::Options;
 
 use typed_store::DBMapUtils;
 use typed_store::Map;
+use typed_store::TypedStoreError;
 
 use sui_macros::nondeterministic;
 
@@ -56,6 +57,25 @@ impl CommitteeStore {
         store
     }
 
+    /// Opens the committee store of a node running in another process as a RocksDB secondary.
+    /// Committees of new epochs are only observed after `try_catch_up_with_primary`.
+    pub fn new_as_secondary(path: PathBuf, secondary_path: PathBuf) -> Self {
+        let tables = CommitteeStoreTables::open_tables_as_secondary(
+            path,
+            secondary_path,
+            MetricConf::new("committee_secondary"),
+            None,
+        );
+        Self {
+            tables,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn try_catch_up_with_primary(&self) -> Result<(), TypedStoreError> {
+        self.tables.try_catch_up_with_primary_all()
+    }
+
     pub fn new_for_testing(genesis_committee: &Committee) -> Self {
         let dir = std::env::temp_dir();
         let path = dir.join(format!("DB_{:?}", nondeterministic!(ObjectID::random())));
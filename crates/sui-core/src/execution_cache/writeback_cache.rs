@@ -389,7 +389,7 @@ impl CachedCommittedData {
         }
     }
 
-    fn clear_and_assert_empty(&self) {
+    fn clear(&self) {
         self.object_cache.invalidate_all();
         self.marker_cache.invalidate_all();
         self.transactions.invalidate_all();
@@ -398,6 +398,10 @@ impl CachedCommittedData {
         self.executed_effects_digests.invalidate_all();
         self.transaction_executed_in_last_epoch.invalidate_all();
         self._transaction_objects.invalidate_all();
+    }
+
+    fn clear_and_assert_empty(&self) {
+        self.clear();
 
         assert_empty(&self.object_cache);
         assert_empty(&self.marker_cache);
@@ -1372,6 +1376,14 @@ impl WritebackCache {
         self.store.insert_genesis_object(object).expect("db error");
     }
 
+    /// Drops all cached committed data so that it is read from the database again. Used when the
+    /// database is written by another process, in which case there is never any dirty data.
+    pub fn invalidate_committed_caches(&self) {
+        self.cached.clear();
+        self.object_by_id_cache.invalidate_all();
+        self.packages.invalidate_all();
+    }
+
     pub fn clear_caches_and_assert_empty(&self) {
         info!("clearing caches");
         self.cached.clear_and_assert_empty();
@@ -34,6 +34,8 @@ pub mod mysticeti_adapter;
 pub mod overload_monitor;
 mod par_index_live_object_set;
 pub(crate) mod post_consensus_tx_reorder;
+#[cfg(not(tidehunter))]
+pub mod read_replica;
 pub mod rpc_index;
 pub mod runtime;
 pub mod safe_client;
@@ -0,0 +1,369 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A read replica serves rpc reads from the database of a fullnode running in another process on
+//! the same machine. The databases of the fullnode are opened as RocksDB secondary instances, which
+//! only observe the writes of the fullnode when they catch up with it, so the replica periodically
+//! calls `ReadReplicaStore::catch_up`. The replica never syncs, executes or indexes checkpoints.
+
+use crate::authority::authority_store::AuthorityStore;
+use crate::authority::authority_store_tables::AuthorityPerpetualTables;
+use crate::authority::backpressure::BackpressureManager;
+use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
+use crate::checkpoints::CheckpointStore;
+use crate::epoch::committee_store::CommitteeStore;
+use crate::execution_cache::ExecutionCacheTraitPointers;
+use crate::execution_cache::WritebackCache;
+use crate::execution_cache::metrics::ExecutionCacheMetrics;
+use crate::rpc_index::RpcIndexStore;
+use crate::storage::{RocksDbStore, RpcIndexReader};
+use arc_swap::ArcSwap;
+use parking_lot::Mutex;
+use prometheus::Registry;
+use std::path::Path;
+use std::sync::Arc;
+use sui_config::ExecutionCacheConfig;
+use sui_execution::Executor;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::digests::ChainIdentifier;
+use sui_types::effects::{TransactionEffects, TransactionEvents};
+use sui_types::error::{SuiErrorKind, SuiResult};
+use sui_types::full_checkpoint_content::ObjectSet;
+use sui_types::messages_checkpoint::{
+    CheckpointContents, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
+    VerifiedCheckpoint, VersionedFullCheckpointContents,
+};
+use sui_types::object::{Object, Owner};
+use sui_types::storage::error::{Error as StorageError, Result};
+use sui_types::storage::{
+    BackingPackageStore, ChildObjectResolver, ObjectKey, ObjectStore, OverlayBackingPackageStore,
+    ReadStore, RpcIndexes, RpcStateReader,
+};
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
+use sui_types::transaction::VerifiedTransaction;
+use tracing::info;
+
+pub struct ReadReplicaStore {
+    perpetual_tables: Arc<AuthorityPerpetualTables>,
+    checkpoint_store: Arc<CheckpointStore>,
+    committee_store: Arc<CommitteeStore>,
+    rpc_index: Option<Arc<RpcIndexStore>>,
+    store: Arc<AuthorityStore>,
+    cache: Arc<WritebackCache>,
+    backing_package_store: Arc<dyn BackingPackageStore + Send + Sync>,
+    rocks: RocksDbStore,
+    indexes: RpcIndexReader,
+    chain_identifier: ChainIdentifier,
+    // Executor of the protocol version of the latest epoch observed by the replica, used to
+    // resolve type layouts.
+    executor: ArcSwap<(ProtocolVersion, Arc<dyn Executor + Send + Sync>)>,
+    // Highest checkpoint executed by the fullnode as of the last catch up. The cache is only
+    // invalidated when it changes.
+    highest_executed_checkpoint: Mutex<Option<CheckpointSequenceNumber>>,
+}
+
+impl ReadReplicaStore {
+    /// Opens the databases of the fullnode at `primary_db_path`, keeping the files of the
+    /// secondary instances under `secondary_db_path`. The rpc index is only opened if
+    /// `enable_rpc_index` is set, in which case the fullnode must maintain it.
+    pub fn open(
+        primary_db_path: &Path,
+        secondary_db_path: &Path,
+        chain_identifier: ChainIdentifier,
+        enable_rpc_index: bool,
+        cache_config: &ExecutionCacheConfig,
+        registry: &Registry,
+    ) -> SuiResult<Self> {
+        let perpetual_tables = Arc::new(AuthorityPerpetualTables::open_as_secondary(
+            &primary_db_path.join("store"),
+            &secondary_db_path.join("store"),
+        ));
+        let checkpoint_store = CheckpointStore::new_as_secondary(
+            &primary_db_path.join("checkpoints"),
+            &secondary_db_path.join("checkpoints"),
+        );
+        let committee_store = Arc::new(CommitteeStore::new_as_secondary(
+            primary_db_path.join("epochs"),
+            secondary_db_path.join("epochs"),
+        ));
+        let rpc_index = enable_rpc_index.then(|| {
+            Arc::new(RpcIndexStore::new_as_secondary(
+                primary_db_path,
+                &secondary_db_path.join("rpc-index"),
+            ))
+        });
+
+        let store = AuthorityStore::open_no_genesis(perpetual_tables.clone(), false, registry)?;
+        let cache = Arc::new(WritebackCache::new(
+            cache_config,
+            store.clone(),
+            Arc::new(ExecutionCacheMetrics::new(registry)),
+            BackpressureManager::new_from_checkpoint_store(&checkpoint_store),
+        ));
+        let cache_traits = ExecutionCacheTraitPointers::new(cache.clone());
+        let rocks = RocksDbStore::new(
+            cache_traits.clone(),
+            committee_store.clone(),
+            checkpoint_store.clone(),
+        );
+        let indexes = RpcIndexReader::new(rpc_index.clone(), rocks.clone());
+        let protocol_version = Self::current_protocol_version(&store)?;
+        let executor = Self::build_executor(protocol_version, chain_identifier)?;
+        let highest_executed_checkpoint =
+            checkpoint_store.get_highest_executed_checkpoint_seq_number()?;
+
+        Ok(Self {
+            perpetual_tables,
+            checkpoint_store,
+            committee_store,
+            rpc_index,
+            store,
+            cache,
+            backing_package_store: cache_traits.backing_package_store,
+            rocks,
+            indexes,
+            chain_identifier,
+            executor: ArcSwap::from_pointee((protocol_version, executor)),
+            highest_executed_checkpoint: Mutex::new(highest_executed_checkpoint),
+        })
+    }
+
+    /// Makes the writes of the fullnode since the last catch up visible to readers of the replica.
+    pub fn catch_up(&self) -> SuiResult {
+        // Transaction outputs are written by the fullnode before the checkpoints containing them
+        // are marked as executed and indexed. Each database is caught up to a later point in time
+        // than the ones before it, so the checkpoint store is caught up first and the stores
+        // holding the outputs last: everything a checkpoint marked as executed refers to is then
+        // visible as well. Catching up in the opposite order could serve a checkpoint executed
+        // after the outputs were caught up, while its outputs are still missing.
+        self.checkpoint_store.try_catch_up_with_primary()?;
+        self.committee_store.try_catch_up_with_primary()?;
+        self.perpetual_tables.try_catch_up_with_primary_all()?;
+        if let Some(rpc_index) = &self.rpc_index {
+            rpc_index.try_catch_up_with_primary()?;
+        }
+
+        // The cache may hold objects which were superseded, or misses for data which now exists.
+        // Reads are served as of the highest executed checkpoint, so it is only invalidated once
+        // the fullnode executed more checkpoints.
+        let highest_executed_checkpoint = self
+            .checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?;
+        let mut last_highest_executed_checkpoint = self.highest_executed_checkpoint.lock();
+        if *last_highest_executed_checkpoint != highest_executed_checkpoint {
+            self.cache.invalidate_committed_caches();
+            *last_highest_executed_checkpoint = highest_executed_checkpoint;
+        }
+
+        let protocol_version = Self::current_protocol_version(&self.store)?;
+        if protocol_version != self.executor.load().0 {
+            info!(
+                "read replica observed protocol version {}",
+                protocol_version.as_u64()
+            );
+            let executor = Self::build_executor(protocol_version, self.chain_identifier)?;
+            self.executor.store(Arc::new((protocol_version, executor)));
+        }
+        Ok(())
+    }
+
+    pub fn get_highest_executed_checkpoint_seq_number(
+        &self,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        self.checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()
+            .map_err(Into::into)
+    }
+
+    fn current_protocol_version(store: &AuthorityStore) -> SuiResult<ProtocolVersion> {
+        let epoch_start_configuration =
+            store.get_epoch_start_configuration()?.ok_or_else(|| {
+                SuiErrorKind::Unknown(
+                    "the database of the fullnode has no epoch start configuration".to_string(),
+                )
+            })?;
+        Ok(epoch_start_configuration
+            .epoch_start_state()
+            .protocol_version())
+    }
+
+    fn build_executor(
+        protocol_version: ProtocolVersion,
+        chain_identifier: ChainIdentifier,
+    ) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
+        let protocol_config =
+            ProtocolConfig::get_for_version(protocol_version, chain_identifier.chain());
+        sui_execution::executor(&protocol_config, true)
+    }
+}
+
+impl ObjectStore for ReadReplicaStore {
+    fn get_object(&self, object_id: &ObjectID) -> Option<Object> {
+        self.rocks.get_object(object_id)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: sui_types::base_types::VersionNumber,
+    ) -> Option<Object> {
+        self.rocks.get_object_by_key(object_id, version)
+    }
+}
+
+impl ReadStore for ReadReplicaStore {
+    fn get_committee(&self, epoch: EpochId) -> Option<Arc<Committee>> {
+        self.rocks.get_committee(epoch)
+    }
+
+    fn get_latest_checkpoint(&self) -> Result<VerifiedCheckpoint> {
+        self.rocks.get_latest_checkpoint()
+    }
+
+    fn get_highest_verified_checkpoint(&self) -> Result<VerifiedCheckpoint> {
+        self.rocks.get_highest_verified_checkpoint()
+    }
+
+    fn get_highest_synced_checkpoint(&self) -> Result<VerifiedCheckpoint> {
+        self.rocks.get_highest_synced_checkpoint()
+    }
+
+    fn get_lowest_available_checkpoint(&self) -> Result<CheckpointSequenceNumber> {
+        self.rocks.get_lowest_available_checkpoint()
+    }
+
+    fn get_checkpoint_by_digest(&self, digest: &CheckpointDigest) -> Option<VerifiedCheckpoint> {
+        self.rocks.get_checkpoint_by_digest(digest)
+    }
+
+    fn get_checkpoint_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<VerifiedCheckpoint> {
+        self.rocks
+            .get_checkpoint_by_sequence_number(sequence_number)
+    }
+
+    fn get_checkpoint_contents_by_digest(
+        &self,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<CheckpointContents> {
+        self.rocks.get_checkpoint_contents_by_digest(digest)
+    }
+
+    fn get_checkpoint_contents_by_sequence_number(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Option<CheckpointContents> {
+        self.rocks
+            .get_checkpoint_contents_by_sequence_number(sequence_number)
+    }
+
+    fn get_transaction(&self, digest: &TransactionDigest) -> Option<Arc<VerifiedTransaction>> {
+        self.rocks.get_transaction(digest)
+    }
+
+    fn get_transaction_effects(&self, digest: &TransactionDigest) -> Option<TransactionEffects> {
+        self.rocks.get_transaction_effects(digest)
+    }
+
+    fn get_events(&self, digest: &TransactionDigest) -> Option<TransactionEvents> {
+        self.rocks.get_events(digest)
+    }
+
+    fn get_full_checkpoint_contents(
+        &self,
+        sequence_number: Option<CheckpointSequenceNumber>,
+        digest: &CheckpointContentsDigest,
+    ) -> Option<VersionedFullCheckpointContents> {
+        self.rocks
+            .get_full_checkpoint_contents(sequence_number, digest)
+    }
+
+    fn get_unchanged_loaded_runtime_objects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Option<Vec<ObjectKey>> {
+        self.rocks.get_unchanged_loaded_runtime_objects(digest)
+    }
+
+    fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> Option<CheckpointSequenceNumber> {
+        self.rocks.get_transaction_checkpoint(digest)
+    }
+}
+
+impl ChildObjectResolver for ReadReplicaStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self.get_object(child).and_then(|o| {
+            if o.version() <= child_version_upper_bound
+                && o.owner == Owner::ObjectOwner((*parent).into())
+            {
+                Some(o)
+            } else {
+                None
+            }
+        }))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        _owner: &ObjectID,
+        _receiving_object_id: &ObjectID,
+        _receive_object_at_version: SequenceNumber,
+        _epoch_id: EpochId,
+    ) -> SuiResult<Option<Object>> {
+        Err(SuiErrorKind::UnsupportedFeatureError {
+            error: "ReadReplicaStore does not support receiving objects".to_string(),
+        }
+        .into())
+    }
+}
+
+impl RpcStateReader for ReadReplicaStore {
+    fn get_lowest_available_checkpoint_objects(&self) -> Result<CheckpointSequenceNumber> {
+        Ok(self
+            .perpetual_tables
+            .get_highest_pruned_checkpoint()?
+            .map(|cp| cp + 1)
+            .unwrap_or(0))
+    }
+
+    fn get_chain_identifier(&self) -> Result<ChainIdentifier> {
+        Ok(self.chain_identifier)
+    }
+
+    fn indexes(&self) -> Option<&dyn RpcIndexes> {
+        Some(&self.indexes)
+    }
+
+    fn get_struct_layout_with_overlay(
+        &self,
+        struct_tag: &move_core_types::language_storage::StructTag,
+        overlay: &ObjectSet,
+    ) -> Result<Option<move_core_types::annotated_value::MoveTypeLayout>> {
+        let overlay_store =
+            OverlayBackingPackageStore::new(overlay, self.backing_package_store.as_ref());
+        let executor = self.executor.load();
+        executor
+            .1
+            .type_layout_resolver(Box::new(overlay_store))
+            .get_annotated_layout(struct_tag)
+            .map(|layout| layout.into_layout())
+            .map(Some)
+            .map_err(StorageError::custom)
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/read_replica_tests.rs"]
+mod read_replica_tests;
@@ -1395,6 +1395,28 @@ impl RpcIndexStore {
         }
     }
 
+    /// Opens the index of a node running in another process as a RocksDB secondary. The index is
+    /// neither initialized nor updated by the returned store, and only observes the checkpoints
+    /// indexed by the node after `try_catch_up_with_primary`.
+    pub fn new_as_secondary(dir: &Path, secondary_path: &Path) -> Self {
+        let tables = IndexStoreTables::open_tables_as_secondary(
+            Self::db_path(dir),
+            secondary_path.to_path_buf(),
+            MetricConf::new("rpc-index-secondary"),
+            None,
+        );
+
+        Self {
+            tables,
+            pending_updates: Default::default(),
+            rpc_config: sui_config::RpcConfig::default(),
+        }
+    }
+
+    pub fn try_catch_up_with_primary(&self) -> Result<(), TypedStoreError> {
+        self.tables.try_catch_up_with_primary_all()
+    }
+
     pub fn prune(
         &self,
         pruned_checkpoint_watermark: u64,
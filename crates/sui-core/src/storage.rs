@@ -378,18 +378,17 @@ impl WriteStore for RocksDbStore {
 pub struct RestReadStore {
     state: Arc<AuthorityState>,
     rocks: RocksDbStore,
+    indexes: RpcIndexReader,
 }
 
 impl RestReadStore {
     pub fn new(state: Arc<AuthorityState>, rocks: RocksDbStore) -> Self {
-        Self { state, rocks }
-    }
-
-    fn index(&self) -> sui_types::storage::error::Result<&RpcIndexStore> {
-        self.state
-            .rpc_index
-            .as_deref()
-            .ok_or_else(|| sui_types::storage::error::Error::custom("rest index store is disabled"))
+        let indexes = RpcIndexReader::new(state.rpc_index.clone(), rocks.clone());
+        Self {
+            state,
+            rocks,
+            indexes,
+        }
     }
 }
 
@@ -546,7 +545,7 @@ impl RpcStateReader for RestReadStore {
     }
 
     fn indexes(&self) -> Option<&dyn RpcIndexes> {
-        Some(self)
+        Some(&self.indexes)
     }
 
     fn get_struct_layout_with_overlay(
@@ -566,6 +565,34 @@ impl RpcStateReader for RestReadStore {
             .map(Some)
             .map_err(StorageError::custom)
     }
+
+    fn get_congestion_history(
+        &self,
+        object_id: &ObjectID,
+    ) -> Option<Vec<sui_types::storage::CongestionSample>> {
+        self.state
+            .congestion_tracker
+            .get_congestion_history(object_id)
+    }
+}
+
+/// Serves `RpcIndexes` from the rpc index of a node, loading the events of indexed event streams
+/// from `rocks`.
+pub struct RpcIndexReader {
+    index: Option<Arc<RpcIndexStore>>,
+    rocks: RocksDbStore,
+}
+
+impl RpcIndexReader {
+    pub fn new(index: Option<Arc<RpcIndexStore>>, rocks: RocksDbStore) -> Self {
+        Self { index, rocks }
+    }
+
+    fn index(&self) -> sui_types::storage::error::Result<&RpcIndexStore> {
+        self.index
+            .as_deref()
+            .ok_or_else(|| sui_types::storage::error::Error::custom("rest index store is disabled"))
+    }
 }
 
 struct BatchedEventIterator<'a, I>
@@ -626,7 +653,7 @@ where
     }
 }
 
-impl RpcIndexes for RestReadStore {
+impl RpcIndexes for RpcIndexReader {
     fn get_epoch_info(&self, epoch: EpochId) -> Result<Option<sui_types::storage::EpochInfo>> {
         self.index()?
             .get_epoch_info(epoch)
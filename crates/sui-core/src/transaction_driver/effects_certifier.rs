@@ -40,6 +40,7 @@ use crate::{
             AggregatedEffectsDigests, TransactionDriverError, TransactionRequestError,
             aggregate_request_errors,
         },
+        lifecycle_tracker::TransactionLifecycleTracker,
         metrics::TransactionDriverMetrics,
         request_retrier::RequestRetrier,
     },
@@ -66,11 +67,18 @@ type FullEffectsResult =
 
 pub(crate) struct EffectsCertifier {
     metrics: Arc<TransactionDriverMetrics>,
+    lifecycle_tracker: Arc<TransactionLifecycleTracker>,
 }
 
 impl EffectsCertifier {
-    pub(crate) fn new(metrics: Arc<TransactionDriverMetrics>) -> Self {
-        Self { metrics }
+    pub(crate) fn new(
+        metrics: Arc<TransactionDriverMetrics>,
+        lifecycle_tracker: Arc<TransactionLifecycleTracker>,
+    ) -> Self {
+        Self {
+            metrics,
+            lifecycle_tracker,
+        }
     }
 
     #[instrument(level = "error", skip_all, err(level = "debug"))]
@@ -149,11 +157,18 @@ impl EffectsCertifier {
                 let (name, client) = retrier
                     .next_target()
                     .expect("there should be at least 1 target");
+                // With hedged requests enabled, the second best validator is also asked for
+                // full effects when the best one is slow to respond.
+                let hedge = client_monitor.hedge_delay().and_then(|delay| {
+                    let (name, client) = retrier.next_target().ok()?;
+                    Some((name, client, delay))
+                });
                 full_effects_start_time = Some(Instant::now());
                 self.get_full_effects_with_fallback(
                     authority_aggregator,
                     client,
                     name,
+                    hedge,
                     tx_digest,
                     tx_type,
                     consensus_position,
@@ -299,6 +314,9 @@ impl EffectsCertifier {
     /// we start parallel requests to validators that have already acknowledged the effects
     /// (received via the acked_validators channel from wait_for_acknowledgments).
     ///
+    /// When a `hedge` target is given, a request is also sent to it once its delay has elapsed
+    /// without a response.
+    ///
     /// This prevents slow validators from blocking the entire operation when faster
     /// validators are available, while still preferring the initial validator if it responds quickly.
     #[instrument(level = "debug", skip_all, fields(tx_digest = ?tx_digest, initial_validator = ?initial_target))]
@@ -307,6 +325,7 @@ impl EffectsCertifier {
         authority_aggregator: &Arc<AuthorityAggregator<A>>,
         initial_client: Arc<SafeClient<A>>,
         initial_target: AuthorityName,
+        mut hedge: Option<(AuthorityName, Arc<SafeClient<A>>, Duration)>,
         tx_digest: Option<TransactionDigest>,
         tx_type: TxType,
         consensus_position: Option<ConsensusPosition>,
@@ -335,6 +354,10 @@ impl EffectsCertifier {
         let mut fallback_delay = tokio::time::interval(GET_FULL_EFFECTS_FALLBACK_DELAY);
         fallback_delay.reset();
 
+        let hedge_target = hedge.as_ref().map(|(name, _, _)| *name);
+        let hedge_timer = sleep(hedge.as_ref().map_or(Duration::MAX, |(_, _, delay)| *delay));
+        tokio::pin!(hedge_timer);
+
         loop {
             tokio::select! {
                 Some((validator, result)) = pending_requests.next() => {
@@ -342,12 +365,28 @@ impl EffectsCertifier {
                     return (result, validator);
                 }
 
+                // After the hedge delay, also request full effects from the hedge target
+                _ = &mut hedge_timer, if hedge.is_some() => {
+                    let (hedge_target, client, _) = hedge.take().unwrap();
+                    tracing::debug!(?hedge_target, "Starting hedged get_full_effects request");
+                    let fut = self.get_full_effects(
+                        client,
+                        tx_digest,
+                        tx_type,
+                        consensus_position,
+                        options,
+                    );
+                    pending_requests.push(Box::pin(async move { (hedge_target, fut.await) }));
+                }
+
                 // After delay, try to start a fallback request to an acked validator
                 _ = fallback_delay.tick() => {
                     // Drain all available acked validators and pick one we haven't tried
                     while let Ok(acked_validator) = acked_validators_rx.try_recv() {
                         // We send ack requests to all validators, so skip if the acked validator was the initial target
-                        if acked_validator == initial_target {
+                        if acked_validator == initial_target
+                            || Some(acked_validator) == hedge_target
+                        {
                             continue;
                         }
 
@@ -471,6 +510,17 @@ impl EffectsCertifier {
         // accepted the transaction during voting, or the reason has been lost.
         let mut reason_not_found_aggregator = StatusAggregator::<()>::new(committee.clone());
 
+        let record_rejection = |name: &AuthorityName, error: &TransactionRequestError| {
+            if let Some(tx_digest) = &tx_digest {
+                self.lifecycle_tracker.rejected_by_validator(
+                    tx_digest,
+                    authority_aggregator.get_display_name(name),
+                    error.to_string(),
+                    error.is_submission_retriable(),
+                );
+            }
+        };
+
         // Every validator returns at most one WaitForEffectsResponse.
         while let Some((name, response)) = futures.next().await {
             match response {
@@ -484,6 +534,12 @@ impl EffectsCertifier {
                     // Using try_send since the channel is bounded by committee size and we don't
                     // want to block - if the channel is somehow full, it's fine to skip.
                     let _ = acked_validators_tx.try_send(name);
+                    if let Some(tx_digest) = &tx_digest {
+                        self.lifecycle_tracker.effects_acknowledged(
+                            tx_digest,
+                            authority_aggregator.get_display_name(&name),
+                        );
+                    }
 
                     let aggregator = effects_digest_aggregators
                         .entry(effects_digest)
@@ -520,6 +576,7 @@ impl EffectsCertifier {
                     if let Some(e) = error {
                         tracing::trace!(name = ?name.concise(), "Rejected at validator: {:?}", e);
                         let error = TransactionRequestError::RejectedAtValidator(e);
+                        record_rejection(&name, &error);
                         if error.is_submission_retriable() {
                             retriable_errors_aggregator.insert(name, error);
                         } else {
@@ -536,6 +593,7 @@ impl EffectsCertifier {
                 }
                 Ok(WaitForEffectsResponse::Expired { epoch, round }) => {
                     let error = TransactionRequestError::StatusExpired(epoch, round.unwrap_or(0));
+                    record_rejection(&name, &error);
                     // Expired status is submission retriable.
                     retriable_errors_aggregator.insert(name, error);
                     self.metrics
@@ -545,6 +603,7 @@ impl EffectsCertifier {
                 }
                 Err(error) => {
                     let error = TransactionRequestError::Aborted(error);
+                    record_rejection(&name, &error);
                     if error.is_submission_retriable() {
                         retriable_errors_aggregator.insert(name, error);
                     } else {
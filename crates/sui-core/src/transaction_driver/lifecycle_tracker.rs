@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use moka::sync::Cache;
+use sui_types::{
+    base_types::TransactionDigest,
+    error::ErrorCategory,
+    transaction_driver_types::{
+        TransactionLifecycle, TransactionLifecycleEvent, TransactionLifecycleEventKind,
+        TransactionStatus,
+    },
+};
+
+#[cfg(test)]
+#[path = "unit_tests/lifecycle_tracker_tests.rs"]
+mod lifecycle_tracker_tests;
+
+/// Approximate number of bytes of lifecycles remembered across all transactions. The least
+/// recently used lifecycles are evicted first.
+const MAX_TRACKED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Number of events remembered for each transaction. The oldest events are dropped first.
+const MAX_EVENTS_PER_TRANSACTION: usize = 64;
+
+/// Records the lifecycle of transactions submitted through the Transaction Driver: which
+/// validators accepted or rejected them and why, retries, and when effects were certified.
+pub struct TransactionLifecycleTracker {
+    lifecycles: Cache<TransactionDigest, TransactionLifecycle>,
+}
+
+impl TransactionLifecycleTracker {
+    pub fn new() -> Self {
+        Self {
+            lifecycles: Cache::builder()
+                .max_capacity(MAX_TRACKED_BYTES)
+                .weigher(|_, lifecycle| lifecycle_size(lifecycle))
+                .build(),
+        }
+    }
+
+    pub fn get(&self, tx_digest: &TransactionDigest) -> Option<TransactionLifecycle> {
+        self.lifecycles.get(tx_digest)
+    }
+
+    pub(crate) fn attempt_started(&self, tx_digest: &TransactionDigest, attempt: u32) {
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::AttemptStarted { attempt },
+            |lifecycle| {
+                lifecycle.attempts = attempt;
+                // A deferred transaction is pending again once it is retried.
+                if matches!(lifecycle.status, TransactionStatus::Deferred { .. }) {
+                    lifecycle.status = TransactionStatus::Pending;
+                }
+            },
+        );
+    }
+
+    pub(crate) fn submitted(&self, tx_digest: &TransactionDigest, validator: String) {
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::Submitted { validator },
+            |_| {},
+        );
+    }
+
+    pub(crate) fn rejected_by_validator(
+        &self,
+        tx_digest: &TransactionDigest,
+        validator: String,
+        reason: String,
+        retriable: bool,
+    ) {
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::RejectedByValidator {
+                validator,
+                reason,
+                retriable,
+            },
+            |_| {},
+        );
+    }
+
+    pub(crate) fn effects_acknowledged(&self, tx_digest: &TransactionDigest, validator: String) {
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::EffectsAcknowledged { validator },
+            |_| {},
+        );
+    }
+
+    /// An attempt failed with an error in `category`. Only failures caused by validators being
+    /// overloaded, including by congestion on the transaction's objects, defer the transaction.
+    /// Other retriable failures leave it pending, as it is retried right away.
+    pub(crate) fn attempt_failed(
+        &self,
+        tx_digest: &TransactionDigest,
+        reason: String,
+        category: ErrorCategory,
+    ) {
+        let retriable = category.is_submission_retriable();
+        let status = match category {
+            ErrorCategory::ValidatorOverloaded => Some(TransactionStatus::Deferred {
+                reason: reason.clone(),
+            }),
+            _ if retriable => None,
+            _ => Some(TransactionStatus::Rejected {
+                reason: reason.clone(),
+            }),
+        };
+
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::AttemptFailed { reason, retriable },
+            |lifecycle| {
+                if let Some(status) = status {
+                    lifecycle.status = status;
+                }
+            },
+        );
+    }
+
+    /// The transaction stopped being retried before reaching finality.
+    pub(crate) fn timed_out(&self, tx_digest: &TransactionDigest, reason: String) {
+        self.update(tx_digest, |lifecycle| {
+            lifecycle.status = TransactionStatus::Rejected { reason }
+        });
+    }
+
+    pub(crate) fn effects_certified(&self, tx_digest: &TransactionDigest) {
+        self.record(
+            tx_digest,
+            TransactionLifecycleEventKind::EffectsCertified,
+            |lifecycle| lifecycle.status = TransactionStatus::Executed,
+        );
+    }
+
+    fn record(
+        &self,
+        tx_digest: &TransactionDigest,
+        kind: TransactionLifecycleEventKind,
+        update_status: impl FnOnce(&mut TransactionLifecycle),
+    ) {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        self.update(tx_digest, |lifecycle| {
+            if lifecycle.events.len() >= MAX_EVENTS_PER_TRANSACTION {
+                lifecycle.events.remove(0);
+            }
+            lifecycle
+                .events
+                .push(TransactionLifecycleEvent { timestamp_ms, kind });
+            update_status(lifecycle);
+        });
+    }
+
+    fn update(
+        &self,
+        tx_digest: &TransactionDigest,
+        update: impl FnOnce(&mut TransactionLifecycle),
+    ) {
+        self.lifecycles.entry(*tx_digest).and_upsert_with(|entry| {
+            let mut lifecycle = entry
+                .map(|e| e.into_value())
+                .unwrap_or(TransactionLifecycle {
+                    status: TransactionStatus::Pending,
+                    attempts: 0,
+                    events: Vec::new(),
+                });
+            update(&mut lifecycle);
+            lifecycle
+        });
+    }
+}
+
+/// Approximate size of `lifecycle` in memory, in bytes, for bounding the tracker's cache.
+fn lifecycle_size(lifecycle: &TransactionLifecycle) -> u32 {
+    let status = match &lifecycle.status {
+        TransactionStatus::Deferred { reason } | TransactionStatus::Rejected { reason } => {
+            reason.len()
+        }
+        TransactionStatus::Pending | TransactionStatus::Executed => 0,
+    };
+
+    let events: usize = lifecycle
+        .events
+        .iter()
+        .map(|event| {
+            let strings = match &event.kind {
+                TransactionLifecycleEventKind::Submitted { validator }
+                | TransactionLifecycleEventKind::EffectsAcknowledged { validator } => {
+                    validator.len()
+                }
+                TransactionLifecycleEventKind::RejectedByValidator {
+                    validator, reason, ..
+                } => validator.len() + reason.len(),
+                TransactionLifecycleEventKind::AttemptFailed { reason, .. } => reason.len(),
+                TransactionLifecycleEventKind::AttemptStarted { .. }
+                | TransactionLifecycleEventKind::EffectsCertified => 0,
+            };
+            std::mem::size_of::<TransactionLifecycleEvent>() + strings
+        })
+        .sum();
+
+    (std::mem::size_of::<(TransactionDigest, TransactionLifecycle)>() + status + events)
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+impl Default for TransactionLifecycleTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,6 +3,7 @@
 
 mod effects_certifier;
 mod error;
+mod lifecycle_tracker;
 mod metrics;
 mod reconfig_observer;
 mod request_retrier;
@@ -10,6 +11,7 @@ mod transaction_submitter;
 
 /// Exports
 pub use error::TransactionDriverError;
+pub use lifecycle_tracker::TransactionLifecycleTracker;
 pub use metrics::*;
 pub use reconfig_observer::{OnsiteReconfigObserver, ReconfigObserver};
 
@@ -27,10 +29,12 @@ use parking_lot::Mutex;
 use rand::Rng;
 use sui_config::NodeConfig;
 use sui_types::{
+    base_types::TransactionDigest,
     committee::EpochId,
     error::{ErrorCategory, UserInputError},
     messages_grpc::{SubmitTxRequest, SubmitTxResult, TxType},
     transaction::TransactionDataAPI as _,
+    transaction_driver_types::TransactionLifecycle,
 };
 use tokio::{
     task::JoinSet,
@@ -90,6 +94,7 @@ pub struct TransactionDriver<A: Clone> {
     submitter: TransactionSubmitter,
     certifier: EffectsCertifier,
     client_monitor: Arc<ValidatorClientMonitor<A>>,
+    lifecycle_tracker: Arc<TransactionLifecycleTracker>,
 }
 
 impl<A> TransactionDriver<A>
@@ -119,13 +124,15 @@ where
         let client_monitor =
             ValidatorClientMonitor::new(monitor_config, client_metrics, shared_swap.clone());
 
+        let lifecycle_tracker = Arc::new(TransactionLifecycleTracker::new());
         let driver = Arc::new(Self {
             authority_aggregator: shared_swap,
             state: Mutex::new(State::new()),
             metrics: metrics.clone(),
-            submitter: TransactionSubmitter::new(metrics.clone()),
-            certifier: EffectsCertifier::new(metrics),
+            submitter: TransactionSubmitter::new(metrics.clone(), lifecycle_tracker.clone()),
+            certifier: EffectsCertifier::new(metrics, lifecycle_tracker.clone()),
             client_monitor,
+            lifecycle_tracker,
         });
 
         let driver_clone = driver.clone();
@@ -141,6 +148,21 @@ where
         &self.authority_aggregator
     }
 
+    /// Returns the monitor of client-observed validator performance, which ranks validators as
+    /// targets of requests.
+    pub fn client_monitor(&self) -> &Arc<ValidatorClientMonitor<A>> {
+        &self.client_monitor
+    }
+
+    /// Returns the lifecycle of a transaction recently submitted through this driver, if it is
+    /// still remembered.
+    pub fn transaction_lifecycle(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Option<TransactionLifecycle> {
+        self.lifecycle_tracker.get(tx_digest)
+    }
+
     /// Drives transaction to finalization.
     ///
     /// Internally, retries the attempt to finalize a transaction until:
@@ -185,6 +207,7 @@ where
             "false"
         };
         let timer = Instant::now();
+        let tx_digest = request.tx_digest();
 
         self.metrics
             .total_transactions_submitted
@@ -200,6 +223,11 @@ where
 
         let retry_loop = async {
             loop {
+                if let Some(tx_digest) = &tx_digest {
+                    self.lifecycle_tracker
+                        .attempt_started(tx_digest, attempts + 1);
+                }
+
                 // TODO(fastpath): Check local state before submitting transaction
                 match self
                     .drive_transaction_once(amplification_factor, request.clone(), &options)
@@ -224,6 +252,9 @@ where
                             .transaction_retries
                             .with_label_values(&["success", tx_type.as_str(), ping_label])
                             .observe(attempts as f64);
+                        if let Some(tx_digest) = &tx_digest {
+                            self.lifecycle_tracker.effects_certified(tx_digest);
+                        }
                         return Ok(resp);
                     }
                     Err(e) => {
@@ -235,6 +266,13 @@ where
                                 ping_label,
                             ])
                             .inc();
+                        if let Some(tx_digest) = &tx_digest {
+                            self.lifecycle_tracker.attempt_failed(
+                                tx_digest,
+                                e.to_string(),
+                                e.categorize(),
+                            );
+                        }
                         if !e.is_submission_retriable() {
                             // Record the number of retries for failed transaction
                             self.metrics
@@ -302,6 +340,9 @@ where
                                 e
                             );
                         }
+                        if let Some(tx_digest) = &tx_digest {
+                            self.lifecycle_tracker.timed_out(tx_digest, e.to_string());
+                        }
                         Err(e)
                     })
             }
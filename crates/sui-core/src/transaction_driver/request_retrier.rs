@@ -16,9 +16,6 @@ use crate::{
     validator_client_monitor::ValidatorClientMonitor,
 };
 
-/// Select validators with latencies within 2% of the lowest latency.
-const SELECT_LATENCY_DELTA: f64 = 0.02;
-
 /// Provides the next target validator to retry operations,
 /// and gathers the errors along with the operations.
 ///
@@ -27,7 +24,8 @@ const SELECT_LATENCY_DELTA: f64 = 0.02;
 /// 2. If non‑retriable errors from a quorum of validators are returned, the operation should fail permanently.
 ///
 /// When an `allowed_validators` is provided, only the validators in the list will be used to submit the transaction to.
-/// When the allowed validator list is empty, any validator can be used an then the validators are selected based on their scores.
+/// When the allowed validator list is empty, any validator can be used an then the validators are selected based on their scores,
+/// ranked by the selection strategy of the `ValidatorClientMonitor`.
 ///
 /// When a `blocked_validators` is provided, the validators in the list cannot be used to submit the transaction to.
 /// When the blocked validator list is empty, no restrictions are applied.
@@ -46,8 +44,7 @@ impl<A: Clone> RequestRetrier<A> {
         allowed_validators: Vec<String>,
         blocked_validators: Vec<String>,
     ) -> Self {
-        let ranked_validators = client_monitor
-            .select_shuffled_preferred_validators(&auth_agg.committee, SELECT_LATENCY_DELTA);
+        let ranked_validators = client_monitor.select_preferred_validators(&auth_agg.committee);
         let ranked_clients = ranked_validators
             .into_iter()
             .map(|name| (name, auth_agg.get_display_name(&name)))
@@ -25,6 +25,7 @@ use crate::{
             AggregatedEffectsDigests, TransactionDriverError, TransactionRequestError,
             aggregate_request_errors,
         },
+        lifecycle_tracker::TransactionLifecycleTracker,
         request_retrier::RequestRetrier,
     },
     validator_client_monitor::{OperationFeedback, OperationType, ValidatorClientMonitor},
@@ -43,11 +44,18 @@ const SUBMIT_TRANSACTION_BACKUP_REQUEST_DELAY: Duration = Duration::from_secs(1)
 
 pub(crate) struct TransactionSubmitter {
     metrics: Arc<TransactionDriverMetrics>,
+    lifecycle_tracker: Arc<TransactionLifecycleTracker>,
 }
 
 impl TransactionSubmitter {
-    pub(crate) fn new(metrics: Arc<TransactionDriverMetrics>) -> Self {
-        Self { metrics }
+    pub(crate) fn new(
+        metrics: Arc<TransactionDriverMetrics>,
+        lifecycle_tracker: Arc<TransactionLifecycleTracker>,
+    ) -> Self {
+        Self {
+            metrics,
+            lifecycle_tracker,
+        }
     }
 
     #[instrument(level = "debug", skip_all, err(level = "debug"))]
@@ -64,6 +72,7 @@ impl TransactionSubmitter {
         A: AuthorityAPI + Send + Sync + 'static + Clone,
     {
         let start_time = Instant::now();
+        let tx_digest = request.tx_digest();
 
         // Limit the amplification factor to [1.=committee size].
         let amplification_factor = amplification_factor
@@ -174,6 +183,9 @@ impl TransactionSubmitter {
                                 .submit_transaction_latency
                                 .with_label_values(&[tx_type.as_str(), ping_label])
                                 .observe(elapsed);
+                            if let Some(tx_digest) = &tx_digest {
+                                self.lifecycle_tracker.submitted(tx_digest, display_name);
+                            }
 
                             return Ok((name, result));
                         }
@@ -188,6 +200,14 @@ impl TransactionSubmitter {
                                     ping_label,
                                 ])
                                 .inc();
+                            if let Some(tx_digest) = &tx_digest {
+                                self.lifecycle_tracker.rejected_by_validator(
+                                    tx_digest,
+                                    display_name,
+                                    e.to_string(),
+                                    e.is_submission_retriable(),
+                                );
+                            }
 
                             retries += 1;
                             retrier.add_error(name, e)?;
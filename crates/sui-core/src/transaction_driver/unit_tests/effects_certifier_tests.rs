@@ -6,7 +6,8 @@ use crate::{
     authority_client::AuthorityAPI,
     transaction_driver::{
         SubmitTransactionOptions, effects_certifier::EffectsCertifier,
-        error::TransactionDriverError, metrics::TransactionDriverMetrics,
+        error::TransactionDriverError, lifecycle_tracker::TransactionLifecycleTracker,
+        metrics::TransactionDriverMetrics,
     },
     validator_client_monitor::ValidatorClientMonitor,
 };
@@ -203,7 +204,7 @@ async fn test_successful_certified_effects() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let effects_digest = create_test_effects_digest(1);
@@ -308,7 +309,7 @@ async fn test_transaction_rejected_non_retriable() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -377,7 +378,7 @@ async fn test_transaction_rejected_retriable() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -447,7 +448,7 @@ async fn test_transaction_rejected_with_conflicts() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -519,7 +520,7 @@ async fn test_transaction_expired() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -582,7 +583,7 @@ async fn test_mixed_rejected_and_expired() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -709,7 +710,7 @@ async fn test_mixed_rejected_reasons() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -1007,7 +1008,7 @@ async fn test_forked_execution() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -1092,7 +1093,7 @@ async fn test_aborted_with_multiple_effects() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let name = authority_aggregator
@@ -1182,7 +1183,7 @@ async fn test_full_effects_retry_loop() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let effects_digest = create_test_effects_digest(1);
@@ -1272,7 +1273,7 @@ async fn test_full_effects_digest_mismatch() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let certified_digest = create_test_effects_digest(1);
@@ -1356,7 +1357,7 @@ async fn test_request_retrier_exhaustion() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let certifier = EffectsCertifier::new(metrics);
+    let certifier = EffectsCertifier::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     let tx_digest = create_test_transaction_digest(1);
     let effects_digest = create_test_effects_digest(1);
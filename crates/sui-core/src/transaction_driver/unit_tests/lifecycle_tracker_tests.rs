@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::{
+    base_types::TransactionDigest,
+    error::ErrorCategory,
+    transaction_driver_types::{TransactionLifecycleEventKind, TransactionStatus},
+};
+
+use super::{MAX_EVENTS_PER_TRANSACTION, TransactionLifecycleTracker, lifecycle_size};
+
+#[test]
+fn test_untracked_transaction() {
+    let tracker = TransactionLifecycleTracker::new();
+    assert!(tracker.get(&TransactionDigest::random()).is_none());
+}
+
+#[test]
+fn test_successful_lifecycle() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    tracker.attempt_started(&tx_digest, 1);
+    tracker.submitted(&tx_digest, "validator-0".to_string());
+    assert_eq!(
+        tracker.get(&tx_digest).unwrap().status,
+        TransactionStatus::Pending
+    );
+
+    tracker.effects_acknowledged(&tx_digest, "validator-1".to_string());
+    tracker.effects_certified(&tx_digest);
+
+    let lifecycle = tracker.get(&tx_digest).unwrap();
+    assert_eq!(lifecycle.status, TransactionStatus::Executed);
+    assert_eq!(lifecycle.attempts, 1);
+    let kinds: Vec<_> = lifecycle.events.into_iter().map(|e| e.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TransactionLifecycleEventKind::AttemptStarted { attempt: 1 },
+            TransactionLifecycleEventKind::Submitted {
+                validator: "validator-0".to_string()
+            },
+            TransactionLifecycleEventKind::EffectsAcknowledged {
+                validator: "validator-1".to_string()
+            },
+            TransactionLifecycleEventKind::EffectsCertified,
+        ]
+    );
+}
+
+#[test]
+fn test_deferred_then_retried() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    tracker.attempt_started(&tx_digest, 1);
+    tracker.rejected_by_validator(
+        &tx_digest,
+        "validator-0".to_string(),
+        "overloaded".to_string(),
+        true,
+    );
+    tracker.attempt_failed(
+        &tx_digest,
+        "overloaded".to_string(),
+        ErrorCategory::ValidatorOverloaded,
+    );
+    assert_eq!(
+        tracker.get(&tx_digest).unwrap().status,
+        TransactionStatus::Deferred {
+            reason: "overloaded".to_string()
+        }
+    );
+
+    tracker.attempt_started(&tx_digest, 2);
+    let lifecycle = tracker.get(&tx_digest).unwrap();
+    assert_eq!(lifecycle.status, TransactionStatus::Pending);
+    assert_eq!(lifecycle.attempts, 2);
+}
+
+#[test]
+fn test_retriable_failure_is_not_deferred() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    tracker.attempt_started(&tx_digest, 1);
+    tracker.attempt_failed(
+        &tx_digest,
+        "validator unavailable".to_string(),
+        ErrorCategory::Unavailable,
+    );
+
+    let lifecycle = tracker.get(&tx_digest).unwrap();
+    assert_eq!(lifecycle.status, TransactionStatus::Pending);
+    assert_eq!(
+        lifecycle.events.last().unwrap().kind,
+        TransactionLifecycleEventKind::AttemptFailed {
+            reason: "validator unavailable".to_string(),
+            retriable: true,
+        }
+    );
+}
+
+#[test]
+fn test_rejected() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    tracker.attempt_started(&tx_digest, 1);
+    tracker.attempt_failed(
+        &tx_digest,
+        "invalid signature".to_string(),
+        ErrorCategory::InvalidTransaction,
+    );
+    assert_eq!(
+        tracker.get(&tx_digest).unwrap().status,
+        TransactionStatus::Rejected {
+            reason: "invalid signature".to_string()
+        }
+    );
+
+    let tx_digest = TransactionDigest::random();
+    tracker.attempt_started(&tx_digest, 1);
+    tracker.attempt_failed(
+        &tx_digest,
+        "overloaded".to_string(),
+        ErrorCategory::ValidatorOverloaded,
+    );
+    tracker.timed_out(&tx_digest, "timed out".to_string());
+    assert_eq!(
+        tracker.get(&tx_digest).unwrap().status,
+        TransactionStatus::Rejected {
+            reason: "timed out".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_events_are_bounded() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    let attempts = MAX_EVENTS_PER_TRANSACTION as u32 + 10;
+    for attempt in 1..=attempts {
+        tracker.attempt_started(&tx_digest, attempt);
+    }
+
+    let lifecycle = tracker.get(&tx_digest).unwrap();
+    assert_eq!(lifecycle.events.len(), MAX_EVENTS_PER_TRANSACTION);
+    assert_eq!(
+        lifecycle.events.last().unwrap().kind,
+        TransactionLifecycleEventKind::AttemptStarted { attempt: attempts }
+    );
+}
+
+#[test]
+fn test_lifecycle_size() {
+    let tracker = TransactionLifecycleTracker::new();
+    let tx_digest = TransactionDigest::random();
+
+    tracker.attempt_started(&tx_digest, 1);
+    let small = lifecycle_size(&tracker.get(&tx_digest).unwrap());
+
+    tracker.rejected_by_validator(
+        &tx_digest,
+        "validator-0".to_string(),
+        "x".repeat(1000),
+        true,
+    );
+    let large = lifecycle_size(&tracker.get(&tx_digest).unwrap());
+
+    // Reasons are counted towards the size, not just the number of events.
+    assert!(large >= small + 1000);
+}
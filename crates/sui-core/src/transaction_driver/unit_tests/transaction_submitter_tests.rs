@@ -5,7 +5,8 @@ use crate::{
     authority_aggregator::{AuthorityAggregator, AuthorityAggregatorBuilder},
     authority_client::AuthorityAPI,
     transaction_driver::{
-        SubmitTransactionOptions, error::TransactionDriverError, metrics::TransactionDriverMetrics,
+        SubmitTransactionOptions, error::TransactionDriverError,
+        lifecycle_tracker::TransactionLifecycleTracker, metrics::TransactionDriverMetrics,
         transaction_submitter::TransactionSubmitter,
     },
     validator_client_monitor::ValidatorClientMonitor,
@@ -229,7 +230,8 @@ async fn test_submit_transaction_with_amplification() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let submitter = TransactionSubmitter::new(metrics);
+    let submitter =
+        TransactionSubmitter::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     // Test 1: Transaction with 1x RGP (amplification factor = 1)
     {
@@ -465,7 +467,8 @@ async fn test_submit_transaction_invalid_input() {
         authority_aggregator.clone(),
     ));
     let metrics = Arc::new(TransactionDriverMetrics::new_for_tests());
-    let submitter = TransactionSubmitter::new(metrics);
+    let submitter =
+        TransactionSubmitter::new(metrics, Arc::new(TransactionLifecycleTracker::new()));
 
     // Transaction with 2x RGP for amplification factor = 2
     let gas_price = reference_gas_price * 2;
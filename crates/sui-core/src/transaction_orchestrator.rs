@@ -30,7 +30,7 @@ use sui_types::transaction::{Transaction, TransactionData, VerifiedTransaction};
 use sui_types::transaction_driver_types::{
     EffectsFinalityInfo, ExecuteTransactionRequestType, ExecuteTransactionRequestV3,
     ExecuteTransactionResponseV3, FinalizedEffects, IsTransactionExecutedLocally,
-    TransactionSubmissionError,
+    TransactionLifecycle, TransactionSubmissionError,
 };
 use sui_types::transaction_executor::{SimulateTransactionResult, TransactionChecks};
 use tokio::sync::broadcast::Receiver;
@@ -1174,6 +1174,10 @@ where
             .validator_state
             .simulate_transaction(transaction, checks, allow_mock_gas_coin)
     }
+
+    fn transaction_lifecycle(&self, digest: &TransactionDigest) -> Option<TransactionLifecycle> {
+        self.inner.transaction_driver.transaction_lifecycle(digest)
+    }
 }
 
 /// Keeps track of inflight transactions being submitted, and helps recover transactions
@@ -0,0 +1,63 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::ReadReplicaStore;
+use crate::authority::test_authority_builder::TestAuthorityBuilder;
+use prometheus::Registry;
+use sui_config::ExecutionCacheConfig;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::object::Object;
+use sui_types::storage::{ObjectStore, ReadStore};
+
+#[tokio::test]
+async fn test_read_replica_catches_up_with_primary() {
+    let primary_dir = mysten_common::tempdir().unwrap();
+    let secondary_dir = mysten_common::tempdir().unwrap();
+    let state = TestAuthorityBuilder::new()
+        .with_store_base_path(primary_dir.path().to_path_buf())
+        .insert_genesis_checkpoint()
+        .build()
+        .await;
+    let replica = ReadReplicaStore::open(
+        primary_dir.path(),
+        secondary_dir.path(),
+        state.get_chain_identifier(),
+        false,
+        &ExecutionCacheConfig::default(),
+        &Registry::new(),
+    )
+    .unwrap();
+    assert!(replica.get_committee(0).is_some());
+    assert!(replica.get_latest_checkpoint().is_err());
+
+    let object = Object::with_id_owner_for_testing(
+        ObjectID::random(),
+        SuiAddress::random_for_testing_only(),
+    );
+    state.insert_genesis_object(object.clone()).await;
+    let checkpoint_store = state.get_checkpoint_store();
+    let checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(0)
+        .unwrap()
+        .unwrap();
+    checkpoint_store
+        .update_highest_executed_checkpoint(&checkpoint)
+        .unwrap();
+
+    // Writes of the primary are only observed after catching up. The miss cached by this read
+    // is dropped by the catch up, as the primary executed a checkpoint in the meantime.
+    assert!(replica.get_object(&object.id()).is_none());
+    replica.catch_up().unwrap();
+
+    assert_eq!(
+        replica
+            .get_highest_executed_checkpoint_seq_number()
+            .unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        replica.get_latest_checkpoint().unwrap().digest(),
+        checkpoint.digest()
+    );
+    assert_eq!(replica.get_object(&object.id()), Some(object));
+}
@@ -3,6 +3,7 @@
 
 mod metrics;
 mod monitor;
+mod selection;
 mod stats;
 
 #[cfg(test)]
@@ -10,6 +11,12 @@ mod tests;
 
 pub use metrics::ValidatorClientMetrics;
 pub use monitor::ValidatorClientMonitor;
+pub use selection::{
+    LowestLatencySelection, RandomSelection, ShuffledPreferredSelection,
+    ValidatorSelectionStrategy, selection_strategy_from_config,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use strum::EnumIter;
 use sui_types::{base_types::AuthorityName, messages_grpc::PingType};
@@ -54,3 +61,18 @@ pub struct OperationFeedback {
     /// should not be recorded.
     pub result: Result<Duration, ()>,
 }
+
+/// Client-observed performance of a validator, as used to rank validators for requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorScore {
+    pub authority_name: AuthorityName,
+    pub display_name: String,
+    /// Latency adjusted with a penalty for unreliability, by which validators are ranked.
+    /// Lower is better. None if it has not been calculated since the validator joined.
+    pub adjusted_latency_ms: Option<f64>,
+    /// Moving average of the success rate of interactions with the validator, from 0 to 1.
+    /// None if no interaction with the validator has been recorded.
+    pub reliability: Option<f64>,
+    /// Moving average of the latency of each operation type.
+    pub operation_latencies_ms: BTreeMap<String, f64>,
+}
@@ -3,9 +3,12 @@
 
 use crate::authority_aggregator::AuthorityAggregator;
 use crate::authority_client::AuthorityAPI;
+use crate::validator_client_monitor::selection::{
+    ShuffledPreferredSelection, ValidatorSelectionStrategy, selection_strategy_from_config,
+};
 use crate::validator_client_monitor::stats::ClientObservedStats;
 use crate::validator_client_monitor::{
-    OperationFeedback, OperationType, metrics::ValidatorClientMetrics,
+    OperationFeedback, OperationType, ValidatorScore, metrics::ValidatorClientMetrics,
 };
 use arc_swap::ArcSwap;
 use parking_lot::RwLock;
@@ -15,7 +18,10 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use sui_config::validator_client_monitor_config::ValidatorClientMonitorConfig;
+use sui_config::validator_client_monitor_config::{
+    ValidatorClientMonitorConfig, ValidatorSelectionStrategyConfig,
+    validate_selection_latency_delta,
+};
 use sui_types::committee::Committee;
 use sui_types::{base_types::AuthorityName, messages_grpc::ValidatorHealthRequest};
 use tokio::{
@@ -41,6 +47,24 @@ pub struct ValidatorClientMonitor<A: Clone> {
     client_stats: RwLock<ClientObservedStats>,
     authority_aggregator: Arc<ArcSwap<AuthorityAggregator<A>>>,
     cached_latencies: RwLock<HashMap<AuthorityName, Duration>>,
+    selection_strategy: RwLock<SelectionStrategy>,
+}
+
+/// Strategy used by `select_preferred_validators`, along with the settings it was built from.
+struct SelectionStrategy {
+    strategy: ValidatorSelectionStrategyConfig,
+    latency_delta: f64,
+    ranker: Arc<dyn ValidatorSelectionStrategy>,
+}
+
+impl SelectionStrategy {
+    fn from_config(config: &ValidatorClientMonitorConfig) -> Self {
+        Self {
+            strategy: config.selection_strategy,
+            latency_delta: config.selection_latency_delta,
+            ranker: selection_strategy_from_config(config),
+        }
+    }
 }
 
 impl<A> ValidatorClientMonitor<A>
@@ -60,9 +84,10 @@ where
         let monitor = Arc::new(Self {
             config: config.clone(),
             metrics,
-            client_stats: RwLock::new(ClientObservedStats::new(config)),
+            client_stats: RwLock::new(ClientObservedStats::new(config.clone())),
             authority_aggregator,
             cached_latencies: RwLock::new(HashMap::new()),
+            selection_strategy: RwLock::new(SelectionStrategy::from_config(&config)),
         });
 
         let monitor_clone = monitor.clone();
@@ -231,12 +256,21 @@ impl<A: Clone> ValidatorClientMonitor<A> {
         client_stats.record_interaction_result(feedback);
     }
 
-    /// Select validators based on client-observed performance for the given transaction type.
+    /// Select validators based on client-observed performance, ranked by the configured
+    /// selection strategy.
     ///
     /// The current committee is passed in to ensure this function has the latest committee information.
     ///
-    /// Also the tx type is passed in so that we can select validators based on their respective latencies
-    /// for the transaction type.
+    /// Returns a vector containing all validators of the committee, from the most preferred to
+    /// the least preferred. Validators are shuffled until latencies have been calculated.
+    pub fn select_preferred_validators(&self, committee: &Committee) -> Vec<AuthorityName> {
+        let ranker = self.selection_strategy.read().ranker.clone();
+        self.select_validators_with_strategy(committee, ranker.as_ref())
+    }
+
+    /// Select validators based on client-observed performance for the given transaction type.
+    ///
+    /// The current committee is passed in to ensure this function has the latest committee information.
     ///
     /// Validators with latencies within `delta` of the lowest latency in the given transaction type
     /// are shuffled, to balance the load among the fastest validators.
@@ -249,50 +283,114 @@ impl<A: Clone> ValidatorClientMonitor<A> {
         committee: &Committee,
         delta: f64,
     ) -> Vec<AuthorityName> {
-        let mut rng = rand::thread_rng();
+        self.select_validators_with_strategy(committee, &ShuffledPreferredSelection { delta })
+    }
 
-        let cached_latencies = self.cached_latencies.read();
-        if cached_latencies.is_empty() {
-            let mut validators: Vec<_> = committee.names().cloned().collect();
-            validators.shuffle(&mut rng);
-            return validators;
+    fn select_validators_with_strategy(
+        &self,
+        committee: &Committee,
+        strategy: &dyn ValidatorSelectionStrategy,
+    ) -> Vec<AuthorityName> {
+        let validator_with_latencies: Vec<_> = {
+            let cached_latencies = self.cached_latencies.read();
+            if cached_latencies.is_empty() {
+                let mut validators: Vec<_> = committee.names().cloned().collect();
+                validators.shuffle(&mut rand::thread_rng());
+                return validators;
+            };
+
+            // Since the cached latencies are updated periodically, it is possible that it was ran on
+            // an out-of-date committee.
+            committee
+                .names()
+                .map(|v| {
+                    (
+                        *v,
+                        cached_latencies.get(v).cloned().unwrap_or(Duration::ZERO),
+                    )
+                })
+                .collect()
         };
+        strategy.rank_validators(validator_with_latencies, &self.metrics)
+    }
 
-        // Since the cached latencies are updated periodically, it is possible that it was ran on
-        // an out-of-date committee.
-        let mut validator_with_latencies: Vec<_> = committee
+    /// Replaces the strategy used to rank validators by `select_preferred_validators`.
+    pub fn set_selection_strategy(&self, strategy: Arc<dyn ValidatorSelectionStrategy>) {
+        self.selection_strategy.write().ranker = strategy;
+    }
+
+    /// Switches `select_preferred_validators` to a configured strategy. Settings which are not
+    /// given keep their current value. Returns the resulting strategy and latency delta.
+    pub fn update_selection_strategy(
+        &self,
+        strategy: Option<ValidatorSelectionStrategyConfig>,
+        latency_delta: Option<f64>,
+    ) -> Result<(ValidatorSelectionStrategyConfig, f64), String> {
+        if let Some(latency_delta) = latency_delta {
+            validate_selection_latency_delta(latency_delta)?;
+        }
+        let mut selection_strategy = self.selection_strategy.write();
+        let config = ValidatorClientMonitorConfig {
+            selection_strategy: strategy.unwrap_or(selection_strategy.strategy),
+            selection_latency_delta: latency_delta.unwrap_or(selection_strategy.latency_delta),
+            ..self.config.clone()
+        };
+        *selection_strategy = SelectionStrategy::from_config(&config);
+        Ok((
+            selection_strategy.strategy,
+            selection_strategy.latency_delta,
+        ))
+    }
+
+    /// Delay after which requests for full effects are also sent to the second best validator,
+    /// if hedged requests are enabled.
+    pub fn hedge_delay(&self) -> Option<Duration> {
+        self.config.hedge_delay
+    }
+
+    /// Returns the client-observed performance of every validator in the current committee,
+    /// from the most to the least preferred by latency.
+    pub fn validator_scores(&self) -> Vec<ValidatorScore> {
+        let authority_agg = self.authority_aggregator.load();
+        let cached_latencies = self.cached_latencies.read();
+        let client_stats = self.client_stats.read();
+        let mut scores: Vec<_> = authority_agg
+            .committee
             .names()
-            .map(|v| {
-                (
-                    *v,
-                    cached_latencies.get(v).cloned().unwrap_or(Duration::ZERO),
-                )
+            .map(|name| {
+                let (reliability, operation_latencies_ms) =
+                    match client_stats.get_validator_details(name) {
+                        Some((reliability, latencies)) => (
+                            Some(reliability),
+                            latencies
+                                .into_iter()
+                                .map(|(operation, latency)| {
+                                    (
+                                        operation.as_str().to_string(),
+                                        latency.as_secs_f64() * 1000.0,
+                                    )
+                                })
+                                .collect(),
+                        ),
+                        None => (None, Default::default()),
+                    };
+                ValidatorScore {
+                    authority_name: *name,
+                    display_name: authority_agg.get_display_name(name),
+                    adjusted_latency_ms: cached_latencies
+                        .get(name)
+                        .map(|latency| latency.as_secs_f64() * 1000.0),
+                    reliability,
+                    operation_latencies_ms,
+                }
             })
             .collect();
-        if validator_with_latencies.is_empty() {
-            return vec![];
-        }
-        // Shuffle the validators to balance the load among validators with the same latency.
-        validator_with_latencies.shuffle(&mut rng);
-        // Sort by latency in ascending order. We want to select the validators with the lowest latencies.
-        validator_with_latencies.sort_by_key(|(_, latency)| *latency);
-
-        // Shuffle the validators within delta of the lowest latency, for load balancing.
-        let lowest_latency = validator_with_latencies[0].1;
-        let threshold = lowest_latency.mul_f64(1.0 + delta);
-        let k = validator_with_latencies
-            .iter()
-            .enumerate()
-            .find(|(_, (_, latency))| *latency > threshold)
-            .map(|(i, _)| i)
-            .unwrap_or(validator_with_latencies.len());
-        validator_with_latencies[..k].shuffle(&mut rng);
-        self.metrics.shuffled_validators.observe(k as f64);
-
-        validator_with_latencies
-            .into_iter()
-            .map(|(v, _)| v)
-            .collect()
+        scores.sort_by(|a, b| {
+            let a = a.adjusted_latency_ms.unwrap_or(f64::MAX);
+            let b = b.adjusted_latency_ms.unwrap_or(f64::MAX);
+            a.total_cmp(&b)
+        });
+        scores
     }
 
     #[cfg(test)]
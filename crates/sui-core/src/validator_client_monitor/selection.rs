@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator_client_monitor::metrics::ValidatorClientMetrics;
+use rand::seq::SliceRandom;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::validator_client_monitor_config::{
+    ValidatorClientMonitorConfig, ValidatorSelectionStrategyConfig,
+};
+use sui_types::base_types::AuthorityName;
+
+/// Ranks validators as targets of requests, from the most to the least preferred.
+///
+/// Strategies are given the adjusted latency of every validator of the current committee, as
+/// computed by the `ValidatorClientMonitor` from client-observed latencies and reliability.
+/// Every validator passed in must be returned exactly once.
+pub trait ValidatorSelectionStrategy: Send + Sync {
+    fn rank_validators(
+        &self,
+        validators: Vec<(AuthorityName, Duration)>,
+        metrics: &ValidatorClientMetrics,
+    ) -> Vec<AuthorityName>;
+}
+
+pub fn selection_strategy_from_config(
+    config: &ValidatorClientMonitorConfig,
+) -> Arc<dyn ValidatorSelectionStrategy> {
+    match config.selection_strategy {
+        ValidatorSelectionStrategyConfig::ShuffledPreferred => {
+            Arc::new(ShuffledPreferredSelection {
+                delta: config.selection_latency_delta,
+            })
+        }
+        ValidatorSelectionStrategyConfig::LowestLatency => Arc::new(LowestLatencySelection),
+        ValidatorSelectionStrategyConfig::Random => Arc::new(RandomSelection),
+    }
+}
+
+/// Sorts validators by latency in ascending order, and shuffles the validators with latencies
+/// within `delta` of the lowest latency to balance the load among the fastest validators.
+pub struct ShuffledPreferredSelection {
+    pub delta: f64,
+}
+
+impl ValidatorSelectionStrategy for ShuffledPreferredSelection {
+    fn rank_validators(
+        &self,
+        mut validators: Vec<(AuthorityName, Duration)>,
+        metrics: &ValidatorClientMetrics,
+    ) -> Vec<AuthorityName> {
+        if validators.is_empty() {
+            return vec![];
+        }
+        let mut rng = rand::thread_rng();
+        // Shuffle the validators to balance the load among validators with the same latency.
+        validators.shuffle(&mut rng);
+        // Sort by latency in ascending order. We want to select the validators with the lowest latencies.
+        validators.sort_by_key(|(_, latency)| *latency);
+
+        // Shuffle the validators within delta of the lowest latency, for load balancing.
+        let lowest_latency = validators[0].1;
+        let threshold = lowest_latency.mul_f64(1.0 + self.delta);
+        let k = validators
+            .iter()
+            .position(|(_, latency)| *latency > threshold)
+            .unwrap_or(validators.len());
+        validators[..k].shuffle(&mut rng);
+        metrics.shuffled_validators.observe(k as f64);
+
+        validators.into_iter().map(|(v, _)| v).collect()
+    }
+}
+
+/// Sorts validators by latency in ascending order.
+pub struct LowestLatencySelection;
+
+impl ValidatorSelectionStrategy for LowestLatencySelection {
+    fn rank_validators(
+        &self,
+        mut validators: Vec<(AuthorityName, Duration)>,
+        _metrics: &ValidatorClientMetrics,
+    ) -> Vec<AuthorityName> {
+        validators.sort_by_key(|(name, latency)| (*latency, *name));
+        validators.into_iter().map(|(v, _)| v).collect()
+    }
+}
+
+/// Shuffles validators regardless of their latency.
+pub struct RandomSelection;
+
+impl ValidatorSelectionStrategy for RandomSelection {
+    fn rank_validators(
+        &self,
+        mut validators: Vec<(AuthorityName, Duration)>,
+        _metrics: &ValidatorClientMetrics,
+    ) -> Vec<AuthorityName> {
+        validators.shuffle(&mut rand::thread_rng());
+        validators.into_iter().map(|(v, _)| v).collect()
+    }
+}
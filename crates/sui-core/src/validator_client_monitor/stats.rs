@@ -127,6 +127,22 @@ impl ClientObservedStats {
             .collect()
     }
 
+    /// Get the reliability and the average latency of each operation type of a validator.
+    ///
+    /// Returns None if no interaction with the validator has been recorded.
+    pub fn get_validator_details(
+        &self,
+        validator: &AuthorityName,
+    ) -> Option<(f64, BTreeMap<OperationType, Duration>)> {
+        let stats = self.validator_stats.get(validator)?;
+        let latencies = stats
+            .average_latencies
+            .iter()
+            .map(|(operation, latency)| (*operation, latency.get()))
+            .collect();
+        Some((stats.reliability.get(), latencies))
+    }
+
     /// Calculate adjusted latency for a single validator.
     ///
     /// Returns the average latency for Consensus operations with reliability penalty applied.
@@ -693,6 +693,114 @@ mod client_monitor_tests {
         assert!(top_2_positions.contains(&validators[1])); // Second best
     }
 
+    #[tokio::test]
+    async fn test_validator_selection_strategy() {
+        let auth_agg = get_authority_aggregator(4);
+        let monitor = ValidatorClientMonitor::new_for_test(auth_agg.clone());
+
+        let committee = auth_agg.committee.clone();
+        let validators = committee.names().cloned().collect::<Vec<_>>();
+
+        // Validators are recorded with latencies in the reverse order of the committee.
+        for (i, validator) in validators.iter().enumerate() {
+            monitor.record_interaction_result(OperationFeedback {
+                authority_name: *validator,
+                display_name: auth_agg.get_display_name(validator),
+                operation: OperationType::SharedObjectFinality,
+                ping_type: None,
+                result: Ok(Duration::from_millis((4 - i as u64) * 50)),
+            });
+        }
+        monitor.force_update_cached_latencies(&auth_agg);
+
+        monitor.set_selection_strategy(Arc::new(LowestLatencySelection));
+        let selected = monitor.select_preferred_validators(&committee);
+        let expected: Vec<_> = validators.iter().rev().cloned().collect();
+        assert_eq!(selected, expected);
+
+        monitor.set_selection_strategy(Arc::new(RandomSelection));
+        let selected: HashSet<_> = monitor
+            .select_preferred_validators(&committee)
+            .into_iter()
+            .collect();
+        assert_eq!(selected, validators.iter().cloned().collect());
+    }
+
+    #[tokio::test]
+    async fn test_update_validator_selection_strategy() {
+        use sui_config::validator_client_monitor_config::ValidatorSelectionStrategyConfig;
+
+        let auth_agg = get_authority_aggregator(4);
+        let monitor = ValidatorClientMonitor::new_for_test(auth_agg.clone());
+
+        // Settings which are not given keep their current value.
+        assert_eq!(
+            monitor.update_selection_strategy(None, Some(0.5)),
+            Ok((ValidatorSelectionStrategyConfig::ShuffledPreferred, 0.5))
+        );
+        assert_eq!(
+            monitor.update_selection_strategy(
+                Some(ValidatorSelectionStrategyConfig::LowestLatency),
+                None
+            ),
+            Ok((ValidatorSelectionStrategyConfig::LowestLatency, 0.5))
+        );
+
+        // Deltas for which the latency threshold cannot be computed are rejected.
+        for delta in [-2.0, f64::NAN, f64::INFINITY] {
+            assert!(
+                monitor
+                    .update_selection_strategy(None, Some(delta))
+                    .is_err()
+            );
+        }
+        assert_eq!(
+            monitor.update_selection_strategy(None, None),
+            Ok((ValidatorSelectionStrategyConfig::LowestLatency, 0.5))
+        );
+        assert!(
+            serde_yaml::from_str::<ValidatorClientMonitorConfig>("selection-latency-delta: -2.0")
+                .is_err()
+        );
+        assert!(
+            serde_yaml::from_str::<ValidatorClientMonitorConfig>("selection-latency-delta: .nan")
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validator_scores() {
+        let auth_agg = get_authority_aggregator(3);
+        let monitor = ValidatorClientMonitor::new_for_test(auth_agg.clone());
+
+        let validators = auth_agg.committee.names().cloned().collect::<Vec<_>>();
+        for (i, validator) in validators.iter().take(2).enumerate() {
+            monitor.record_interaction_result(OperationFeedback {
+                authority_name: *validator,
+                display_name: auth_agg.get_display_name(validator),
+                operation: OperationType::SharedObjectFinality,
+                ping_type: None,
+                result: Ok(Duration::from_millis((2 - i as u64) * 100)),
+            });
+        }
+        monitor.force_update_cached_latencies(&auth_agg);
+
+        let scores = monitor.validator_scores();
+        assert_eq!(scores.len(), 3);
+        // Scores are sorted by adjusted latency, with the validator without stats last.
+        assert_eq!(scores[0].authority_name, validators[1]);
+        assert_eq!(scores[0].adjusted_latency_ms, Some(100.0));
+        assert_eq!(scores[0].reliability, Some(1.0));
+        assert_eq!(
+            scores[0]
+                .operation_latencies_ms
+                .get("shared_object_finality"),
+            Some(&100.0)
+        );
+        assert_eq!(scores[1].authority_name, validators[0]);
+        assert_eq!(scores[2].authority_name, validators[2]);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_stats_cleanup_on_authority_aggregator_change() {
         use arc_swap::ArcSwap;
@@ -0,0 +1,111 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(msim)]
+mod test {
+    use std::time::Duration;
+    use sui_macros::sim_test;
+    use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::sui_system_state::SuiSystemStateTrait;
+    use test_cluster::fault_scenario::{Fault, FaultScenario};
+    use test_cluster::{TestCluster, TestClusterBuilder};
+    use tracing::info;
+
+    async fn transfer(test_cluster: &TestCluster) {
+        let recipient = test_cluster.get_address_1();
+        let tx = test_cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1), recipient)
+            .build();
+        let executed = test_cluster.sign_and_execute_transaction(&tx).await;
+        assert!(executed.effects.status().is_ok());
+    }
+
+    #[sim_test]
+    async fn test_reconfig_after_crashes_and_partition() {
+        let test_cluster = TestClusterBuilder::new()
+            .with_epoch_duration_ms(10_000)
+            .build()
+            .await;
+        let validators = test_cluster.get_validator_pubkeys();
+
+        let timeline = FaultScenario::new()
+            .crash_for(
+                Duration::from_secs(2),
+                validators[0],
+                Duration::from_secs(8),
+            )
+            .partition_for(
+                Duration::from_secs(12),
+                vec![validators[1]],
+                vec![validators[2], validators[3]],
+                Duration::from_secs(6),
+            )
+            .random_crashes(
+                &validators,
+                2,
+                Duration::from_secs(20)..Duration::from_secs(30),
+                Duration::from_secs(1)..Duration::from_secs(5),
+            )
+            .run(&test_cluster)
+            .await;
+        info!("{timeline}");
+        assert_eq!(timeline.entries.len(), 8);
+
+        // Restart any validator still down from overlapping random crashes.
+        test_cluster.start_all_validators().await;
+        transfer(&test_cluster).await;
+        let epoch = test_cluster.wait_for_epoch(None).await.epoch();
+        test_cluster.wait_for_epoch_all_nodes(epoch).await;
+    }
+
+    #[sim_test]
+    async fn test_recovery_from_node_faults() {
+        let test_cluster = TestClusterBuilder::new()
+            .with_epoch_duration_ms(10_000)
+            .build()
+            .await;
+        let validators = test_cluster.get_validator_pubkeys();
+
+        let scenario = FaultScenario::new()
+            .clock_skew_for(
+                Duration::from_secs(1),
+                validators[0],
+                -5_000,
+                Duration::from_secs(10),
+            )
+            .slow_execution_for(
+                Duration::from_secs(1),
+                validators[1],
+                Duration::from_secs(2),
+                Duration::from_secs(10),
+            )
+            .disk_full_for(
+                Duration::from_secs(5),
+                validators[2],
+                Duration::from_secs(10),
+            )
+            .at(Duration::from_secs(20), Fault::Crash(validators[3]));
+        let (timeline, _) = tokio::join!(scenario.run(&test_cluster), async {
+            // Transactions keep committing while the faults are in effect.
+            for _ in 0..5 {
+                transfer(&test_cluster).await;
+                tokio::time::sleep(Duration::from_secs(3)).await;
+            }
+        });
+        info!("{timeline}");
+
+        assert!(
+            !test_cluster
+                .swarm
+                .node(&validators[3])
+                .unwrap()
+                .is_running()
+        );
+        test_cluster.start_node(&validators[3]).await;
+        transfer(&test_cluster).await;
+        let epoch = test_cluster.wait_for_epoch(None).await.epoch();
+        test_cluster.wait_for_epoch_all_nodes(epoch).await;
+    }
+}
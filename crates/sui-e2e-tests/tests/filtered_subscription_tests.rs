@@ -0,0 +1,102 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::grpc::alpha::filtered_subscription_service_proto::filtered_subscription_service_client::FilteredSubscriptionServiceClient;
+use sui_rpc_api::grpc::alpha::filtered_subscription_service_proto::{
+    EventFilter, SubscribeEventsRequest, SubscribeTransactionsRequest, TransactionFilter,
+};
+use sui_types::base_types::SuiAddress;
+use test_cluster::{TestCluster, TestClusterBuilder};
+use tokio_stream::StreamExt;
+
+async fn transfer_sui(test_cluster: &TestCluster, recipient: SuiAddress) -> String {
+    let tx_data = test_cluster
+        .test_transaction_builder()
+        .await
+        .transfer_sui(Some(1), recipient)
+        .build();
+    let response = test_cluster.sign_and_execute_transaction(&tx_data).await;
+    response.transaction.digest().to_string()
+}
+
+fn transactions_affecting(address: SuiAddress) -> SubscribeTransactionsRequest {
+    let mut filter = TransactionFilter::default();
+    filter.affected_address = Some(address.to_string());
+
+    let mut request = SubscribeTransactionsRequest::default();
+    request.filter = Some(filter);
+    request
+}
+
+#[sim_test]
+async fn test_subscribe_transactions_by_affected_address() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let mut client = FilteredSubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let mut stream = client
+        .subscribe_transactions(transactions_affecting(recipient))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let digest = transfer_sui(&test_cluster, recipient).await;
+
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.transaction.unwrap().digest, Some(digest));
+    assert!(response.cursor.unwrap().checkpoint.is_some());
+}
+
+#[sim_test]
+async fn test_subscribe_transactions_resume_from_cursor() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let mut client = FilteredSubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let mut stream = client
+        .subscribe_transactions(transactions_affecting(recipient))
+        .await
+        .unwrap()
+        .into_inner();
+
+    let first = transfer_sui(&test_cluster, recipient).await;
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.transaction.unwrap().digest, Some(first));
+    drop(stream);
+
+    // Transactions executed while disconnected are replayed after resuming.
+    let second = transfer_sui(&test_cluster, recipient).await;
+
+    let mut request = transactions_affecting(recipient);
+    request.after = response.cursor;
+    let mut stream = client
+        .subscribe_transactions(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    let response = stream.next().await.unwrap().unwrap();
+    assert_eq!(response.transaction.unwrap().digest, Some(second));
+}
+
+#[sim_test]
+async fn test_subscribe_events_invalid_filter() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut client = FilteredSubscriptionServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut filter = EventFilter::default();
+    filter.module = Some("coin".to_owned());
+    let mut request = SubscribeEventsRequest::default();
+    request.filter = Some(filter);
+
+    let err = client.subscribe_events(request).await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc_api::grpc::alpha::gas_price_service_proto::EstimateGasPriceRequest;
+use sui_rpc_api::grpc::alpha::gas_price_service_proto::gas_price_service_client::GasPriceServiceClient;
+use sui_types::SUI_CLOCK_OBJECT_ID;
+use test_cluster::TestClusterBuilder;
+
+#[sim_test]
+async fn test_estimate_gas_price_uncongested() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let reference_gas_price = test_cluster.get_reference_gas_price().await;
+
+    let mut client = GasPriceServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut request = EstimateGasPriceRequest::default();
+    request.object_ids = vec![SUI_CLOCK_OBJECT_ID.to_string()];
+    let response = client
+        .estimate_gas_price(request)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.reference_gas_price, Some(reference_gas_price));
+    assert!(!response.estimates.is_empty());
+    for estimate in &response.estimates {
+        assert_eq!(estimate.gas_price, Some(reference_gas_price));
+    }
+
+    assert_eq!(response.objects.len(), 1);
+    assert!(response.objects[0].history.is_empty());
+}
+
+#[sim_test]
+async fn test_estimate_gas_price_invalid_object_id() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut client = GasPriceServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut request = EstimateGasPriceRequest::default();
+    request.object_ids = vec!["not an object id".to_string()];
+    let err = client.estimate_gas_price(request).await.unwrap_err();
+
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_binary_format::CompiledModule;
 use sui_macros::sim_test;
 use sui_rpc::proto::sui::rpc::v2::{
     GetPackageRequest, move_package_service_client::MovePackageServiceClient,
@@ -28,6 +29,33 @@ async fn test_get_package_system() {
     validate_system_package(&package);
 }
 
+#[sim_test]
+async fn test_get_package_module_contents() {
+    let cluster = TestClusterBuilder::new()
+        .with_num_validators(1)
+        .build()
+        .await;
+    let mut service = MovePackageServiceClient::connect(cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+
+    let mut request = GetPackageRequest::default();
+    request.package_id = Some("0x2".to_string());
+
+    let response = service.get_package(request).await.unwrap();
+    let package = response.into_inner().package.unwrap();
+
+    assert!(!package.modules.is_empty());
+    for module in &package.modules {
+        let contents = module
+            .contents
+            .as_ref()
+            .expect("module contents should be set");
+        let compiled = CompiledModule::deserialize_with_defaults(contents).unwrap();
+        assert_eq!(compiled.self_id().name().as_str(), module.name());
+    }
+}
+
 #[sim_test]
 async fn test_get_package_not_found() {
     let cluster = TestClusterBuilder::new()
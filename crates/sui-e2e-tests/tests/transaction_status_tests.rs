@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_rpc::proto::sui::rpc::v2::Bcs;
+use sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest;
+use sui_rpc::proto::sui::rpc::v2::Transaction;
+use sui_rpc::proto::sui::rpc::v2::UserSignature;
+use sui_rpc_api::grpc::alpha::transaction_status_service_proto::transaction_status_service_client::TransactionStatusServiceClient;
+use sui_rpc_api::grpc::alpha::transaction_status_service_proto::{
+    GetTransactionStatusRequest, TransactionLifecycleEventKind, TransactionStatus,
+};
+use sui_test_transaction_builder::make_transfer_sui_transaction;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
+use test_cluster::TestClusterBuilder;
+
+fn get_transaction_status_request(digest: String) -> GetTransactionStatusRequest {
+    let mut request = GetTransactionStatusRequest::default();
+    request.digest = Some(digest);
+    request
+}
+
+#[sim_test]
+async fn test_execute_transaction_with_lifecycle() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let txn = make_transfer_sui_transaction(
+        &test_cluster.wallet,
+        Some(SuiAddress::random_for_testing_only()),
+        Some(1),
+    )
+    .await;
+    let digest = txn.digest().to_string();
+
+    let request = {
+        let mut message = ExecuteTransactionRequest::default();
+        message.transaction = Some({
+            let mut message = Transaction::default();
+            message.bcs = Some(Bcs::serialize(txn.transaction_data()).unwrap());
+            message
+        });
+        message.signatures = txn
+            .tx_signatures()
+            .iter()
+            .map(|s| {
+                let mut message = UserSignature::default();
+                message.bcs = Some(Bcs::from(s.as_ref().to_owned()));
+                message
+            })
+            .collect();
+        message
+    };
+
+    let mut client = TransactionStatusServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let response = client
+        .execute_transaction(request)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.response.unwrap().transaction.is_some());
+
+    let lifecycle = response.lifecycle.unwrap();
+    assert_eq!(lifecycle.attempts, Some(1));
+    assert_eq!(
+        lifecycle.events.last().unwrap().kind(),
+        TransactionLifecycleEventKind::EffectsCertified
+    );
+
+    let response = client
+        .get_transaction_status(get_transaction_status_request(digest.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.digest, Some(digest));
+    assert_eq!(response.status(), TransactionStatus::Executed);
+    assert!(response.lifecycle.is_some());
+}
+
+#[sim_test]
+async fn test_get_transaction_status_unknown() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let mut client = TransactionStatusServiceClient::connect(test_cluster.rpc_url().to_owned())
+        .await
+        .unwrap();
+    let response = client
+        .get_transaction_status(get_transaction_status_request(
+            TransactionDigest::random().to_string(),
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.status(), TransactionStatus::Unknown);
+    assert!(response.lifecycle.is_none());
+
+    let err = client
+        .get_transaction_status(get_transaction_status_request("not a digest".to_owned()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+}
@@ -3,15 +3,22 @@
 
 use crate::FaucetConfig;
 use crate::LocalFaucet;
+use crate::RequestQuotas;
 use std::sync::Arc;
 
 pub struct AppState<F = Arc<LocalFaucet>> {
     pub faucet: F,
     pub config: FaucetConfig,
+    pub quotas: RequestQuotas,
 }
 
 impl<F> AppState<F> {
     pub fn new(faucet: F, config: FaucetConfig) -> Self {
-        Self { faucet, config }
+        let quotas = RequestQuotas::new(&config);
+        Self {
+            faucet,
+            config,
+            quotas,
+        }
     }
 }
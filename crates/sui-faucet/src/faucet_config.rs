@@ -3,9 +3,14 @@
 
 use clap::Parser;
 use std::net::Ipv4Addr;
+use std::str::FromStr;
+use std::time::Duration;
+use sui_sdk::types::{TypeTag, parse_sui_type_tag};
 
 pub const DEFAULT_AMOUNT: u64 = 200_000_000_000;
 pub const DEFAULT_NUM_COINS: usize = 5;
+pub const DEFAULT_QUOTA_WINDOW_SECS: u64 = 24 * 60 * 60;
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 20;
 
 #[derive(Parser, Clone)]
 #[clap(
@@ -29,6 +34,43 @@ pub struct FaucetConfig {
 
     #[clap(long, default_value_t = 60)]
     pub wallet_client_timeout_secs: u64,
+
+    /// Maximum number of requests for a single recipient address per quota window. Unlimited if
+    /// not set.
+    #[clap(long)]
+    pub max_requests_per_address: Option<u64>,
+
+    /// Maximum number of requests from a single IP address per quota window. Unlimited if not set.
+    #[clap(long)]
+    pub max_requests_per_ip: Option<u64>,
+
+    /// Length of the window over which request quotas are counted.
+    #[clap(long, default_value_t = DEFAULT_QUOTA_WINDOW_SECS)]
+    pub quota_window_secs: u64,
+
+    /// Maximum number of queued requests dispensed by a single transaction.
+    #[clap(long, default_value_t = DEFAULT_MAX_BATCH_SIZE)]
+    pub max_batch_size: usize,
+
+    /// How long to wait for more requests to arrive before dispensing a batch. By default, only
+    /// the requests already queued are batched together.
+    #[clap(long, default_value_t = 0)]
+    pub batch_window_ms: u64,
+
+    /// Additional coin types dispensed on request, as `<coin type>=<amount>`, e.g.
+    /// `0x...::usdc::USDC=1000000`. The faucet address must hold coins of these types.
+    #[clap(long = "coin", value_name = "COIN_TYPE=AMOUNT")]
+    pub coins: Vec<DispensedCoin>,
+}
+
+impl FaucetConfig {
+    pub fn quota_window(&self) -> Duration {
+        Duration::from_secs(self.quota_window_secs)
+    }
+
+    pub fn batch_window(&self) -> Duration {
+        Duration::from_millis(self.batch_window_ms)
+    }
 }
 
 impl Default for FaucetConfig {
@@ -39,6 +81,33 @@ impl Default for FaucetConfig {
             amount: DEFAULT_AMOUNT,
             num_coins: DEFAULT_NUM_COINS,
             wallet_client_timeout_secs: 60,
+            max_requests_per_address: None,
+            max_requests_per_ip: None,
+            quota_window_secs: DEFAULT_QUOTA_WINDOW_SECS,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            batch_window_ms: 0,
+            coins: vec![],
         }
     }
 }
+
+/// A coin type other than SUI dispensed by the faucet, and the amount dispensed per request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DispensedCoin {
+    pub coin_type: TypeTag,
+    pub amount: u64,
+}
+
+impl FromStr for DispensedCoin {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (coin_type, amount) = s
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow::anyhow!("expected `<coin type>=<amount>`, got `{s}`"))?;
+        Ok(Self {
+            coin_type: parse_sui_type_tag(coin_type)?,
+            amount: amount.parse()?,
+        })
+    }
+}
@@ -5,12 +5,18 @@ mod app_state;
 mod errors;
 mod faucet_config;
 mod local_faucet;
+mod quota;
+mod request_queue;
 mod server;
 mod types;
 
 pub use app_state::AppState;
 pub use errors::FaucetError;
-pub use faucet_config::FaucetConfig;
+pub use faucet_config::{DispensedCoin, FaucetConfig};
 pub use local_faucet::LocalFaucet;
+pub use quota::RequestQuotas;
 pub use server::{create_wallet_context, start_faucet};
-pub use types::{CoinInfo, FaucetRequest, FaucetResponse, FixedAmountRequest, RequestStatus};
+pub use types::{
+    BatchFaucetResponse, BatchSendStatus, BatchStatusFaucetResponse, CoinInfo, FaucetRequest,
+    FaucetResponse, FixedAmountRequest, RequestStatus,
+};
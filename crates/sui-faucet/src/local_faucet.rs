@@ -1,26 +1,36 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
 use anyhow::bail;
+use futures::TryStreamExt;
 use sui_rpc_api::client::ExecutedTransaction;
 use sui_sdk::types::effects::TransactionEffectsAPI;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, mpsc, oneshot};
 use tokio::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
+use uuid::Uuid;
 
 use crate::FaucetConfig;
 use crate::FaucetError;
+use crate::faucet_config::DispensedCoin;
+use crate::request_queue::{
+    DispenseRequest, MAX_QUEUED_REQUESTS, QueuedRequest, TaskStatuses, run_request_queue,
+};
 
-use crate::CoinInfo;
+use crate::{BatchSendStatus, CoinInfo, FixedAmountRequest};
 use shared_crypto::intent::Intent;
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_sdk::types::{
-    base_types::{ObjectID, SuiAddress},
-    gas_coin::GasCoin,
+    TypeTag,
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    coin::Coin,
+    gas_coin::{GAS, GasCoin},
+    parse_sui_type_tag,
     transaction::{Transaction, TransactionData},
 };
 use sui_sdk::wallet_context::WalletContext;
@@ -34,6 +44,10 @@ pub struct LocalFaucet {
     coin_id: Arc<Mutex<ObjectID>>,
     coin_amount: u64,
     num_coins: usize,
+    /// Coin types dispensed on request in addition to SUI.
+    coins: Vec<DispensedCoin>,
+    queue: mpsc::Sender<QueuedRequest>,
+    statuses: std::sync::Mutex<TaskStatuses>,
 }
 
 /// We do not just derive(Debug) because WalletContext and the WriteAheadLog do not implement Debug / are also hard
@@ -55,13 +69,24 @@ impl LocalFaucet {
         let (coins, active_address) = find_gas_coins_and_address(&mut wallet, &config).await?;
         info!("Starting faucet with address: {:?}", active_address);
 
-        Ok(Arc::new(LocalFaucet {
+        let (queue, receiver) = mpsc::channel(MAX_QUEUED_REQUESTS);
+        let faucet = Arc::new(LocalFaucet {
             wallet,
             active_address,
             coin_id: Arc::new(Mutex::new(*coins[0].id())),
             coin_amount: config.amount,
             num_coins: config.num_coins,
-        }))
+            coins: config.coins.clone(),
+            queue,
+            statuses: Default::default(),
+        });
+        tokio::spawn(run_request_queue(
+            Arc::downgrade(&faucet),
+            receiver,
+            config.max_batch_size.max(1),
+            config.batch_window(),
+        ));
+        Ok(faucet)
     }
 
     /// Make transaction and execute it.
@@ -69,18 +94,243 @@ impl LocalFaucet {
         &self,
         recipient: SuiAddress,
     ) -> Result<Vec<CoinInfo>, FaucetError> {
+        let request = DispenseRequest {
+            recipient,
+            coin_types: vec![],
+        };
+        self.execute_batch(&[request])
+            .await?
+            .pop()
+            .expect("one result per request")
+    }
+
+    /// Queues a request, which is dispensed in a batch with other queued requests, and waits for
+    /// its coins to be sent.
+    pub async fn request_gas(
+        &self,
+        request: FixedAmountRequest,
+    ) -> Result<Vec<CoinInfo>, FaucetError> {
+        self.queue_gas_request(request)?.await
+    }
+
+    /// Queues a request, which is dispensed in a batch with other queued requests, and returns a
+    /// future resolving once its coins are sent. Fails without queueing the request if it is
+    /// invalid or the queue is full.
+    pub fn queue_gas_request(
+        &self,
+        request: FixedAmountRequest,
+    ) -> Result<impl Future<Output = Result<Vec<CoinInfo>, FaucetError>> + use<>, FaucetError> {
+        let (sender, receiver) = oneshot::channel();
+        self.enqueue(request, Some(sender))?;
+        Ok(async move {
+            receiver
+                .await
+                .map_err(|_| FaucetError::internal("Faucet request queue was shut down"))?
+        })
+    }
+
+    /// Queues a request, which is dispensed in a batch with other queued requests, and returns
+    /// the task to query the status of the request with.
+    pub fn submit_gas_request(&self, request: FixedAmountRequest) -> Result<Uuid, FaucetError> {
+        self.enqueue(request, None)
+    }
+
+    /// Returns the status of a queued request, if it is known.
+    pub fn request_status(&self, task: &Uuid) -> Option<BatchSendStatus> {
+        self.statuses.lock().unwrap().get(task)
+    }
+
+    fn enqueue(
+        &self,
+        request: FixedAmountRequest,
+        response: Option<oneshot::Sender<Result<Vec<CoinInfo>, FaucetError>>>,
+    ) -> Result<Uuid, FaucetError> {
+        let request = self.validate_request(request)?;
+        let task = Uuid::new_v4();
+        self.statuses
+            .lock()
+            .unwrap()
+            .set(task, BatchSendStatus::Queued);
+        let queued = QueuedRequest {
+            task,
+            request,
+            response,
+        };
+        if let Err(e) = self.queue.try_send(queued) {
+            let error = match e {
+                mpsc::error::TrySendError::Full(_) => FaucetError::TooManyRequests(
+                    "The faucet request queue is full, please try again later".to_string(),
+                ),
+                mpsc::error::TrySendError::Closed(_) => {
+                    FaucetError::internal("Faucet request queue was shut down")
+                }
+            };
+            self.statuses
+                .lock()
+                .unwrap()
+                .set(task, BatchSendStatus::Failure(error.clone()));
+            return Err(error);
+        }
+        Ok(task)
+    }
+
+    fn validate_request(
+        &self,
+        request: FixedAmountRequest,
+    ) -> Result<DispenseRequest, FaucetError> {
+        let mut coin_types: Vec<TypeTag> = vec![];
+        for coin_type in &request.coin_types {
+            let coin_type = parse_sui_type_tag(coin_type).map_err(|e| {
+                FaucetError::InvalidRequest(format!("Invalid coin type {coin_type}: {e}"))
+            })?;
+            if coin_type == GAS::type_tag() || coin_types.contains(&coin_type) {
+                continue;
+            }
+            if !self.coins.iter().any(|coin| coin.coin_type == coin_type) {
+                return Err(FaucetError::InvalidRequest(format!(
+                    "Coin type {} is not dispensed by this faucet",
+                    coin_type.to_canonical_string(true)
+                )));
+            }
+            coin_types.push(coin_type);
+        }
+        Ok(DispenseRequest {
+            recipient: request.recipient,
+            coin_types,
+        })
+    }
+
+    /// Dispenses a batch of queued requests, and records their outcome.
+    pub(crate) async fn dispense_batch(&self, batch: Vec<QueuedRequest>) {
+        let requests: Vec<_> = batch.iter().map(|queued| queued.request.clone()).collect();
+        let results = match self.execute_batch(&requests).await {
+            Ok(results) => results,
+            Err(e) if requests.len() > 1 => {
+                // The transaction of the batch failed as a whole. Dispense each request with its
+                // own transaction, so that only the requests which can't be dispensed fail.
+                warn!(
+                    "Failed to dispense a batch of {} requests, dispensing them one by one: {e}",
+                    requests.len()
+                );
+                let mut results = Vec::with_capacity(requests.len());
+                for request in requests {
+                    let result = match self.execute_batch(&[request]).await {
+                        Ok(mut results) => results.pop().expect("one result per request"),
+                        Err(e) => Err(e),
+                    };
+                    results.push(result);
+                }
+                results
+            }
+            Err(e) => vec![Err(e)],
+        };
+
+        let mut results = results.into_iter();
+        for queued in batch {
+            let result = results.next().expect("one result per request");
+            let status = match &result {
+                Ok(coins_sent) => BatchSendStatus::Success {
+                    coins_sent: coins_sent.clone(),
+                },
+                Err(e) => BatchSendStatus::Failure(e.clone()),
+            };
+            self.statuses.lock().unwrap().set(queued.task, status);
+            if let Some(response) = queued.response {
+                // The requester may have gone away.
+                let _ = response.send(result);
+            }
+        }
+    }
+
+    /// Sends the coins of all `requests` with a single transaction, and returns the coins sent for
+    /// each request. Requests for a coin type the faucet does not hold enough of fail on their
+    /// own, and are left out of the transaction. Fails as a whole if the transaction fails.
+    async fn execute_batch(
+        &self,
+        requests: &[DispenseRequest],
+    ) -> Result<Vec<Result<Vec<CoinInfo>, FaucetError>>, FaucetError> {
         let gas_price = self
             .wallet
             .get_reference_gas_price()
             .await
             .map_err(|e| FaucetError::internal(format!("Failed to get gas price: {}", e)))?;
 
+        // Coins of the other coin types are selected for all requests before building the
+        // transaction. Leaving out the requests for a coin type that can't be dispensed only
+        // lowers the amounts needed of the other coin types.
+        let mut treasury_coins = HashMap::new();
+        let mut unavailable = HashMap::new();
+        for coin in &self.coins {
+            let num_recipients = requests
+                .iter()
+                .filter(|request| request.coin_types.contains(&coin.coin_type))
+                .count();
+            if num_recipients == 0 {
+                continue;
+            }
+            let total_amount = coin.amount.saturating_mul(num_recipients as u64);
+            match self
+                .select_treasury_coins(&coin.coin_type, total_amount)
+                .await
+            {
+                Ok(coins) => {
+                    treasury_coins.insert(&coin.coin_type, coins);
+                }
+                Err(e) => {
+                    unavailable.insert(&coin.coin_type, e);
+                }
+            }
+        }
+        let mut results: Vec<Result<Vec<CoinInfo>, FaucetError>> = requests
+            .iter()
+            .map(|request| {
+                match request
+                    .coin_types
+                    .iter()
+                    .find_map(|coin_type| unavailable.get(coin_type))
+                {
+                    Some(e) => Err(e.clone()),
+                    None => Ok(vec![]),
+                }
+            })
+            .collect();
+        let dispensed: Vec<_> = requests
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| results[*i].is_ok())
+            .map(|(_, request)| request)
+            .collect();
+        if dispensed.is_empty() {
+            return Ok(results);
+        }
+
         let mut ptb = ProgrammableTransactionBuilder::new();
-        let recipients = vec![recipient; self.num_coins];
-        let amounts = vec![self.coin_amount; self.num_coins];
+        let (recipients, amounts) = dispensed
+            .iter()
+            .flat_map(|request| {
+                std::iter::repeat_n((request.recipient, self.coin_amount), self.num_coins)
+            })
+            .unzip();
         ptb.pay_sui(recipients, amounts)
             .map_err(FaucetError::internal)?;
 
+        for coin in &self.coins {
+            let recipients: Vec<_> = dispensed
+                .iter()
+                .filter(|request| request.coin_types.contains(&coin.coin_type))
+                .map(|request| request.recipient)
+                .collect();
+            if recipients.is_empty() {
+                continue;
+            }
+            let treasury_coins = treasury_coins
+                .remove(&coin.coin_type)
+                .expect("coins are selected for the coin types of all dispensed requests");
+            let amounts = vec![coin.amount; recipients.len()];
+            ptb.pay(treasury_coins, recipients, amounts)
+                .map_err(FaucetError::internal)?;
+        }
+
         let ptb = ptb.finish();
 
         let coin_id = self.coin_id.lock().await;
@@ -93,7 +343,7 @@ impl LocalFaucet {
             self.active_address,
             vec![coin_id_ref],
             ptb,
-            GAS_BUDGET,
+            GAS_BUDGET * dispensed.len() as u64,
             gas_price,
         );
 
@@ -101,19 +351,89 @@ impl LocalFaucet {
             .execute_txn_with_retries(tx_data, *coin_id, NUM_RETRIES)
             .await
             .map_err(FaucetError::internal)?;
+        if tx.effects.status().is_err() {
+            return Err(FaucetError::Transfer(format!(
+                "Transaction {} failed: {:?}",
+                tx.effects.transaction_digest(),
+                tx.effects.status()
+            )));
+        }
 
-        let coins: Vec<CoinInfo> = tx
-            .effects
-            .created()
-            .into_iter()
-            .map(|o| CoinInfo {
-                amount: self.coin_amount,
-                id: o.0.0,
-                transfer_tx_digest: *tx.effects.transaction_digest(),
-            })
-            .collect();
+        let transfer_tx_digest = *tx.effects.transaction_digest();
+        let created: Vec<_> = tx.effects.created().into_iter().map(|o| o.0.0).collect();
+        let objects = self
+            .wallet
+            .grpc_client()
+            .map_err(FaucetError::internal)?
+            .batch_get_objects(&created)
+            .await
+            .map_err(|e| FaucetError::internal(format!("Failed to get sent coins: {}", e)))?;
+
+        // Coins sent to each recipient, to be shared among the requests of the recipient.
+        let mut coins_by_recipient: HashMap<SuiAddress, Vec<CoinInfo>> = HashMap::new();
+        for object in objects {
+            let (Some(coin_type), Ok(owner)) = (
+                object.coin_type_maybe(),
+                object.owner().get_address_owner_address(),
+            ) else {
+                continue;
+            };
+            coins_by_recipient.entry(owner).or_default().push(CoinInfo {
+                amount: object.get_coin_value_unsafe(),
+                id: object.id(),
+                transfer_tx_digest,
+                coin_type: (coin_type != GAS::type_tag())
+                    .then(|| coin_type.to_canonical_string(true)),
+            });
+        }
+
+        for (i, request) in requests.iter().enumerate() {
+            let Ok(coins_sent) = &mut results[i] else {
+                continue;
+            };
+            let received = coins_by_recipient.entry(request.recipient).or_default();
+            let sui_coins = std::iter::repeat_n(None, self.num_coins);
+            let other_coins = request
+                .coin_types
+                .iter()
+                .map(|coin_type| Some(coin_type.to_canonical_string(true)));
+            *coins_sent = sui_coins
+                .chain(other_coins)
+                .filter_map(|coin_type| {
+                    let position = received
+                        .iter()
+                        .position(|coin| coin.coin_type == coin_type)?;
+                    Some(received.swap_remove(position))
+                })
+                .collect();
+        }
+        Ok(results)
+    }
 
-        Ok(coins)
+    /// Selects coins of `coin_type` held by the faucet address with a total balance of at least
+    /// `amount`.
+    async fn select_treasury_coins(
+        &self,
+        coin_type: &TypeTag,
+        amount: u64,
+    ) -> Result<Vec<ObjectRef>, FaucetError> {
+        let client = self.wallet.grpc_client().map_err(FaucetError::internal)?;
+        let mut coins = std::pin::pin!(
+            client.list_owned_objects(self.active_address, Some(Coin::type_(coin_type.clone())))
+        );
+        let mut selected = vec![];
+        let mut balance = 0u64;
+        while balance < amount {
+            let Some(coin) = coins.try_next().await.map_err(FaucetError::internal)? else {
+                return Err(FaucetError::Wallet(format!(
+                    "Insufficient balance of {} to dispense {amount}",
+                    coin_type.to_canonical_string(true)
+                )));
+            };
+            balance = balance.saturating_add(coin.get_coin_value_unsafe());
+            selected.push(coin.compute_object_reference());
+        }
+        Ok(selected)
     }
 
     async fn execute_txn(
@@ -213,8 +533,22 @@ async fn find_gas_coins_and_address(
 mod tests {
 
     use super::*;
+    use std::path::PathBuf;
+    use sui_test_transaction_builder::publish_package;
     use test_cluster::TestClusterBuilder;
 
+    async fn wait_for_status(faucet: &LocalFaucet, task: &Uuid) -> BatchSendStatus {
+        loop {
+            match faucet.request_status(task) {
+                Some(BatchSendStatus::Queued) => {
+                    tokio::time::sleep(Duration::from_millis(100)).await
+                }
+                Some(status) => return status,
+                None => panic!("unknown task {task}"),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_local_faucet_execute_txn() {
         // Setup test cluster
@@ -261,4 +595,150 @@ mod tests {
         assert!(!coins.is_empty());
         assert!(coins.iter().map(|c| c.value()).sum::<u64>() >= config.amount);
     }
+
+    #[tokio::test]
+    async fn test_batched_requests() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let client = cluster.grpc_client();
+
+        let config = FaucetConfig {
+            batch_window_ms: 1000,
+            ..Default::default()
+        };
+        let local_faucet = LocalFaucet::new(cluster.wallet, config).await.unwrap();
+
+        let recipients: Vec<_> = (0..3)
+            .map(|_| SuiAddress::random_for_testing_only())
+            .collect();
+        let tasks: Vec<_> = recipients
+            .iter()
+            .map(|recipient| {
+                let task = local_faucet
+                    .submit_gas_request(FixedAmountRequest {
+                        recipient: *recipient,
+                        coin_types: vec![],
+                    })
+                    .unwrap();
+                (*recipient, task)
+            })
+            .collect();
+
+        let mut digests = vec![];
+        for (recipient, task) in tasks {
+            let BatchSendStatus::Success { coins_sent } =
+                wait_for_status(&local_faucet, &task).await
+            else {
+                panic!("request {task} failed");
+            };
+            assert_eq!(coins_sent.len(), local_faucet.num_coins);
+            assert!(
+                coins_sent
+                    .iter()
+                    .all(|coin| coin.amount == local_faucet.coin_amount)
+            );
+            digests.extend(coins_sent.iter().map(|coin| coin.transfer_tx_digest));
+
+            let coins = client
+                .get_owned_objects(recipient, None, None, None)
+                .await
+                .unwrap();
+            assert_eq!(coins.items.len(), local_faucet.num_coins);
+        }
+
+        // All requests were dispensed by the same transaction.
+        digests.dedup();
+        assert_eq!(digests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_isolates_failed_requests() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/move/coin");
+        let package_id = publish_package(&cluster.wallet, path).await.0;
+        let coin_type = format!("{package_id}::fixed_supply::FIXED_SUPPLY");
+
+        // The faucet holds less than the amount of the coin dispensed per request.
+        let config = FaucetConfig {
+            coins: vec![format!("{coin_type}=1000000000000001").parse().unwrap()],
+            batch_window_ms: 1000,
+            ..Default::default()
+        };
+        let local_faucet = LocalFaucet::new(cluster.wallet, config).await.unwrap();
+
+        let coin_task = local_faucet
+            .submit_gas_request(FixedAmountRequest {
+                recipient: SuiAddress::random_for_testing_only(),
+                coin_types: vec![coin_type],
+            })
+            .unwrap();
+        let sui_task = local_faucet
+            .submit_gas_request(FixedAmountRequest {
+                recipient: SuiAddress::random_for_testing_only(),
+                coin_types: vec![],
+            })
+            .unwrap();
+
+        assert!(matches!(
+            wait_for_status(&local_faucet, &coin_task).await,
+            BatchSendStatus::Failure(FaucetError::Wallet(_))
+        ));
+        let BatchSendStatus::Success { coins_sent } =
+            wait_for_status(&local_faucet, &sui_task).await
+        else {
+            panic!("request {sui_task} failed");
+        };
+        assert_eq!(coins_sent.len(), local_faucet.num_coins);
+    }
+
+    #[tokio::test]
+    async fn test_dispense_coin_type() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let client = cluster.grpc_client();
+
+        // Publishing the package sends the supply of its coins to the faucet address.
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/move/coin");
+        let package_id = publish_package(&cluster.wallet, path).await.0;
+        let coin_type = format!("{package_id}::fixed_supply::FIXED_SUPPLY");
+
+        let config = FaucetConfig {
+            coins: vec![format!("{coin_type}=1000").parse().unwrap()],
+            ..Default::default()
+        };
+        let local_faucet = LocalFaucet::new(cluster.wallet, config).await.unwrap();
+
+        let recipient = SuiAddress::random_for_testing_only();
+        let coins_sent = local_faucet
+            .request_gas(FixedAmountRequest {
+                recipient,
+                coin_types: vec![coin_type.clone()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(coins_sent.len(), local_faucet.num_coins + 1);
+        let coin = coins_sent
+            .iter()
+            .find(|coin| coin.coin_type.is_some())
+            .unwrap();
+        assert_eq!(coin.amount, 1000);
+        assert_eq!(
+            parse_sui_type_tag(coin.coin_type.as_ref().unwrap()).unwrap(),
+            parse_sui_type_tag(&coin_type).unwrap()
+        );
+
+        let object = client.get_object(coin.id).await.unwrap();
+        assert_eq!(
+            object.owner().get_address_owner_address().unwrap(),
+            recipient
+        );
+        assert_eq!(object.get_coin_value_unsafe(), 1000);
+
+        // Coin types not configured in the faucet are rejected.
+        let result = local_faucet
+            .request_gas(FixedAmountRequest {
+                recipient,
+                coin_types: vec![format!("{package_id}::my_coin::MY_COIN")],
+            })
+            .await;
+        assert!(matches!(result, Err(FaucetError::InvalidRequest(_))));
+    }
 }
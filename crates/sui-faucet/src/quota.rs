@@ -0,0 +1,182 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FaucetConfig, FaucetError};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sui_sdk::types::base_types::SuiAddress;
+
+/// Per recipient address and per IP address request quotas. Requests are counted over fixed
+/// windows, and all quotas are reset when a window ends.
+pub struct RequestQuotas {
+    max_requests_per_address: Option<u64>,
+    max_requests_per_ip: Option<u64>,
+    window: Duration,
+    counts: Mutex<RequestCounts>,
+}
+
+struct RequestCounts {
+    window_start: Instant,
+    by_address: HashMap<SuiAddress, u64>,
+    by_ip: HashMap<IpAddr, u64>,
+}
+
+impl RequestQuotas {
+    pub fn new(config: &FaucetConfig) -> Self {
+        Self {
+            max_requests_per_address: config.max_requests_per_address,
+            max_requests_per_ip: config.max_requests_per_ip,
+            window: config.quota_window(),
+            counts: Mutex::new(RequestCounts {
+                window_start: Instant::now(),
+                by_address: HashMap::new(),
+                by_ip: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Counts a request for `recipient` from `ip`, unless it exceeds one of the quotas.
+    pub fn check_and_record(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+    ) -> Result<(), FaucetError> {
+        let mut counts = self.counts.lock().unwrap();
+        if counts.window_start.elapsed() >= self.window {
+            counts.window_start = Instant::now();
+            counts.by_address.clear();
+            counts.by_ip.clear();
+        }
+        let retry_in = self.window.saturating_sub(counts.window_start.elapsed());
+
+        check_quota(
+            &counts.by_address,
+            &recipient,
+            self.max_requests_per_address,
+        )
+        .map_err(|max| {
+            FaucetError::TooManyRequests(format!(
+                "recipient {recipient} reached the limit of {max} requests, retry in {}s",
+                retry_in.as_secs()
+            ))
+        })?;
+        if let Some(ip) = &ip {
+            check_quota(&counts.by_ip, ip, self.max_requests_per_ip).map_err(|max| {
+                FaucetError::TooManyRequests(format!(
+                    "{ip} reached the limit of {max} requests, retry in {}s",
+                    retry_in.as_secs()
+                ))
+            })?;
+        }
+
+        if self.max_requests_per_address.is_some() {
+            *counts.by_address.entry(recipient).or_default() += 1;
+        }
+        if let (Some(ip), Some(_)) = (ip, self.max_requests_per_ip) {
+            *counts.by_ip.entry(ip).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    /// Gives back a request counted by `check_and_record` which was not accepted after all. A
+    /// request counted in an earlier window is not given back, as that window's counts are gone.
+    pub fn release(&self, recipient: SuiAddress, ip: Option<IpAddr>) {
+        let mut counts = self.counts.lock().unwrap();
+        if counts.window_start.elapsed() >= self.window {
+            return;
+        }
+        release_quota(&mut counts.by_address, &recipient);
+        if let Some(ip) = &ip {
+            release_quota(&mut counts.by_ip, ip);
+        }
+    }
+}
+
+fn check_quota<K: Eq + Hash>(
+    counts: &HashMap<K, u64>,
+    key: &K,
+    max_requests: Option<u64>,
+) -> Result<(), u64> {
+    match max_requests {
+        Some(max) if counts.get(key).copied().unwrap_or_default() >= max => Err(max),
+        _ => Ok(()),
+    }
+}
+
+fn release_quota<K: Eq + Hash>(counts: &mut HashMap<K, u64>, key: &K) {
+    if let Some(count) = counts.get_mut(key) {
+        *count = count.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_quotas() {
+        let config = FaucetConfig {
+            max_requests_per_address: Some(2),
+            max_requests_per_ip: Some(3),
+            ..Default::default()
+        };
+        let quotas = RequestQuotas::new(&config);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let (a, b) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+
+        quotas.check_and_record(a, Some(ip)).unwrap();
+        quotas.check_and_record(a, Some(ip)).unwrap();
+        assert!(matches!(
+            quotas.check_and_record(a, Some(ip)),
+            Err(FaucetError::TooManyRequests(_))
+        ));
+
+        // Rejected requests do not count towards the quota of the IP address.
+        quotas.check_and_record(b, Some(ip)).unwrap();
+        assert!(matches!(
+            quotas.check_and_record(b, Some(ip)),
+            Err(FaucetError::TooManyRequests(_))
+        ));
+        quotas.check_and_record(b, None).unwrap();
+    }
+
+    #[test]
+    fn test_release_request_quota() {
+        let config = FaucetConfig {
+            max_requests_per_address: Some(1),
+            max_requests_per_ip: Some(1),
+            ..Default::default()
+        };
+        let quotas = RequestQuotas::new(&config);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let recipient = SuiAddress::random_for_testing_only();
+
+        quotas.check_and_record(recipient, Some(ip)).unwrap();
+        quotas.release(recipient, Some(ip));
+        quotas.check_and_record(recipient, Some(ip)).unwrap();
+        assert!(matches!(
+            quotas.check_and_record(recipient, Some(ip)),
+            Err(FaucetError::TooManyRequests(_))
+        ));
+    }
+
+    #[test]
+    fn test_request_quotas_reset_after_window() {
+        let config = FaucetConfig {
+            max_requests_per_address: Some(1),
+            quota_window_secs: 0,
+            ..Default::default()
+        };
+        let quotas = RequestQuotas::new(&config);
+        let recipient = SuiAddress::random_for_testing_only();
+        for _ in 0..3 {
+            quotas.check_and_record(recipient, None).unwrap();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BatchSendStatus, CoinInfo, FaucetError, LocalFaucet};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Weak;
+use std::time::Duration;
+use sui_sdk::types::{TypeTag, base_types::SuiAddress};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+use tracing::info;
+use uuid::Uuid;
+
+/// Maximum number of requests waiting to be dispensed.
+pub(crate) const MAX_QUEUED_REQUESTS: usize = 10_000;
+/// Maximum number of request statuses kept, the statuses of the oldest requests are dropped first.
+const MAX_TASK_STATUSES: usize = 100_000;
+
+/// A validated request for coins.
+#[derive(Clone, Debug)]
+pub(crate) struct DispenseRequest {
+    pub recipient: SuiAddress,
+    /// Coin types dispensed in addition to SUI.
+    pub coin_types: Vec<TypeTag>,
+}
+
+pub(crate) struct QueuedRequest {
+    pub task: Uuid,
+    pub request: DispenseRequest,
+    /// Notified once the coins are sent, if the requester waits for them.
+    pub response: Option<oneshot::Sender<Result<Vec<CoinInfo>, FaucetError>>>,
+}
+
+#[derive(Default)]
+pub(crate) struct TaskStatuses {
+    statuses: HashMap<Uuid, BatchSendStatus>,
+    order: VecDeque<Uuid>,
+}
+
+impl TaskStatuses {
+    pub fn get(&self, task: &Uuid) -> Option<BatchSendStatus> {
+        self.statuses.get(task).cloned()
+    }
+
+    pub fn set(&mut self, task: Uuid, status: BatchSendStatus) {
+        if self.statuses.insert(task, status).is_none() {
+            self.order.push_back(task);
+        }
+        while self.order.len() > MAX_TASK_STATUSES {
+            let oldest = self.order.pop_front().expect("order is not empty");
+            self.statuses.remove(&oldest);
+        }
+    }
+}
+
+/// Dispenses queued requests in batches of up to `max_batch_size` requests, each batch with a
+/// single transaction, until the faucet is dropped.
+pub(crate) async fn run_request_queue(
+    faucet: Weak<LocalFaucet>,
+    mut receiver: mpsc::Receiver<QueuedRequest>,
+    max_batch_size: usize,
+    batch_window: Duration,
+) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + batch_window;
+        while batch.len() < max_batch_size {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => batch.push(request),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        let Some(faucet) = faucet.upgrade() else {
+            return;
+        };
+        info!("Dispensing a batch of {} requests", batch.len());
+        faucet.dispense_batch(batch).await;
+    }
+}
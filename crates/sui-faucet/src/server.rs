@@ -6,6 +6,7 @@ use crate::{AppState, FaucetConfig, FaucetError, FaucetRequest};
 use axum::{
     BoxError, Extension, Json, Router,
     error_handling::HandleErrorLayer,
+    extract::{ConnectInfo, Path},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
@@ -23,54 +24,119 @@ use sui_sdk::wallet_context::WalletContext;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
+use uuid::Uuid;
 
 /// basic handler that responds with a static string
 async fn health() -> &'static str {
     "OK"
 }
 
+fn error_status_code(error: &FaucetError) -> StatusCode {
+    match error {
+        FaucetError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        FaucetError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+        FaucetError::Wallet(_) | FaucetError::Transfer(_) | FaucetError::Internal(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 async fn request_local_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(payload): Json<FaucetRequest>,
-    // ) -> &'static str {
 ) -> impl IntoResponse {
     let FaucetRequest::FixedAmountRequest(request) = payload;
     info!("Local request for address: {}", request.recipient);
-    let request = state
-        .faucet
-        .local_request_execute_tx(request.recipient)
-        .await;
+    let (recipient, ip) = (request.recipient, Some(addr.ip()));
+    // The request only counts towards the quotas once it is queued.
+    let result = match state.quotas.check_and_record(recipient, ip).and_then(|()| {
+        state.faucet.queue_gas_request(request).inspect_err(|_| {
+            state.quotas.release(recipient, ip);
+        })
+    }) {
+        Ok(coins_sent) => coins_sent.await,
+        Err(e) => Err(e),
+    };
 
-    if let Err(e) = request {
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
+    match result {
+        Ok(coins) => (
+            StatusCode::OK,
             Json(FaucetResponse {
-                status: RequestStatus::Failure(e),
-                coins_sent: None,
+                status: RequestStatus::Success,
+                coins_sent: Some(coins),
             }),
-        );
+        ),
+        Err(e) => (error_status_code(&e), Json(FaucetResponse::from(e))),
+    }
+}
+
+async fn batch_request_local_gas(
+    Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(payload): Json<FaucetRequest>,
+) -> impl IntoResponse {
+    let FaucetRequest::FixedAmountRequest(request) = payload;
+    info!("Local batch request for address: {}", request.recipient);
+    let (recipient, ip) = (request.recipient, Some(addr.ip()));
+    // The request only counts towards the quotas once it is queued.
+    let result = state.quotas.check_and_record(recipient, ip).and_then(|()| {
+        state
+            .faucet
+            .submit_gas_request(request)
+            .inspect_err(|_| state.quotas.release(recipient, ip))
+    });
+
+    match result {
+        Ok(task) => (
+            StatusCode::ACCEPTED,
+            Json(BatchFaucetResponse {
+                task: Some(task.to_string()),
+                error: None,
+            }),
+        ),
+        Err(e) => (
+            error_status_code(&e),
+            Json(BatchFaucetResponse {
+                task: None,
+                error: Some(e),
+            }),
+        ),
     }
+}
 
-    let Ok(coins) = request else {
+async fn request_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(task): Path<String>,
+) -> impl IntoResponse {
+    let Ok(task) = Uuid::parse_str(&task) else {
         return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(FaucetResponse {
-                status: RequestStatus::Failure(FaucetError::internal(format!(
-                    "Failed to execute transaction: {}",
-                    request.unwrap_err()
+            StatusCode::BAD_REQUEST,
+            Json(BatchStatusFaucetResponse {
+                status: None,
+                error: Some(FaucetError::InvalidRequest(format!(
+                    "Invalid task id: {task}"
                 ))),
-                coins_sent: None,
             }),
         );
     };
 
-    (
-        StatusCode::OK,
-        Json(FaucetResponse {
-            status: RequestStatus::Success,
-            coins_sent: Some(coins),
-        }),
-    )
+    match state.faucet.request_status(&task) {
+        Some(status) => (
+            StatusCode::OK,
+            Json(BatchStatusFaucetResponse {
+                status: Some(status),
+                error: None,
+            }),
+        ),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(BatchStatusFaucetResponse {
+                status: None,
+                error: Some(FaucetError::InvalidRequest(format!("Unknown task: {task}"))),
+            }),
+        ),
+    }
 }
 
 pub fn create_wallet_context(
@@ -114,6 +180,8 @@ pub async fn start_faucet(app_state: Arc<AppState>) -> Result<(), anyhow::Error>
         .route("/v2/gas", post(request_local_gas))
         .route("/v1/gas", post(request_local_gas))
         .route("/gas", post(request_local_gas))
+        .route("/v2/batch/gas", post(batch_request_local_gas))
+        .route("/v2/status/{task}", get(request_status))
         .layer(
             ServiceBuilder::new()
                 .layer(HandleErrorLayer::new(handle_error))
@@ -157,10 +225,7 @@ mod tests {
             .await
             .unwrap();
 
-        let app_state = Arc::new(AppState {
-            faucet: local_faucet,
-            config,
-        });
+        let app_state = Arc::new(AppState::new(local_faucet, config));
 
         // Spawn the faucet in a background task
         let handle = tokio::spawn(async move {
@@ -203,4 +268,99 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_v2_batch_gas_and_status_endpoints() {
+        let cluster = TestClusterBuilder::new().build().await;
+        let port = 9091;
+        let config = FaucetConfig {
+            host_ip: "127.0.0.1".parse().unwrap(),
+            port,
+            max_requests_per_address: Some(1),
+            ..Default::default()
+        };
+        let local_faucet = LocalFaucet::new(cluster.wallet, config.clone())
+            .await
+            .unwrap();
+        let app_state = Arc::new(AppState::new(local_faucet, config));
+        let handle = tokio::spawn(async move {
+            start_faucet(app_state)
+                .await
+                .expect("Failed to start faucet");
+        });
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        let client = reqwest::Client::new();
+        let recipient = SuiAddress::random_for_testing_only();
+
+        // Rejected requests do not use up the quota of the recipient.
+        let response = client
+            .post(format!("http://127.0.0.1:{port}/v2/batch/gas"))
+            .json(&FaucetRequest::FixedAmountRequest(FixedAmountRequest {
+                recipient,
+                coin_types: vec!["0x2::unknown::UNKNOWN".to_string()],
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let req = FaucetRequest::new_fixed_amount_request(recipient);
+        let response = client
+            .post(format!("http://127.0.0.1:{port}/v2/batch/gas"))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let task = response
+            .json::<BatchFaucetResponse>()
+            .await
+            .unwrap()
+            .task
+            .unwrap();
+
+        let coins_sent = loop {
+            let response = client
+                .get(format!("http://127.0.0.1:{port}/v2/status/{task}"))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            match response
+                .json::<BatchStatusFaucetResponse>()
+                .await
+                .unwrap()
+                .status
+                .unwrap()
+            {
+                BatchSendStatus::Queued => {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
+                }
+                BatchSendStatus::Success { coins_sent } => break coins_sent,
+                BatchSendStatus::Failure(e) => panic!("request failed: {e}"),
+            }
+        };
+        assert!(!coins_sent.is_empty());
+
+        // The recipient exhausted its quota.
+        let response = client
+            .post(format!("http://127.0.0.1:{port}/v2/gas"))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = client
+            .get(format!(
+                "http://127.0.0.1:{port}/v2/status/{}",
+                Uuid::new_v4()
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        handle.abort();
+    }
 }
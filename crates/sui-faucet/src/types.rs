@@ -16,12 +16,24 @@ pub enum FaucetRequest {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FixedAmountRequest {
     pub recipient: SuiAddress,
+    /// Coin types to dispense in addition to SUI, which must be configured in the faucet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coin_types: Vec<String>,
 }
 
 impl FaucetRequest {
     pub fn new_fixed_amount_request(recipient: impl Into<SuiAddress>) -> Self {
         Self::FixedAmountRequest(FixedAmountRequest {
             recipient: recipient.into(),
+            coin_types: vec![],
+        })
+    }
+
+    pub fn with_coin_types(self, coin_types: Vec<String>) -> Self {
+        let Self::FixedAmountRequest(request) = self;
+        Self::FixedAmountRequest(FixedAmountRequest {
+            coin_types,
+            ..request
         })
     }
 }
@@ -44,6 +56,30 @@ pub struct CoinInfo {
     pub amount: u64,
     pub id: ObjectID,
     pub transfer_tx_digest: TransactionDigest,
+    /// The coin type, if the coin is not a SUI coin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<String>,
+}
+
+/// Response to a request queued by the faucet, identifying the task to query the status of.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchFaucetResponse {
+    pub task: Option<String>,
+    pub error: Option<FaucetError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BatchStatusFaucetResponse {
+    pub status: Option<BatchSendStatus>,
+    pub error: Option<FaucetError>,
+}
+
+/// Status of a request queued by the faucet.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum BatchSendStatus {
+    Queued,
+    Success { coins_sent: Vec<CoinInfo> },
+    Failure(FaucetError),
 }
 
 impl From<FaucetError> for FaucetResponse {
@@ -37,6 +37,7 @@ use crate::api::types::epoch::Epoch;
 use crate::api::types::event::CEvent;
 use crate::api::types::event::Event;
 use crate::api::types::event::filter::EventFilter;
+use crate::api::types::gas_price_estimate::GasPriceEstimate;
 use crate::api::types::move_object::MoveObject;
 use crate::api::types::move_package;
 use crate::api::types::move_package::MovePackage;
@@ -796,6 +797,52 @@ impl Query {
         }
     }
 
+    /// Suggest gas prices at several confidence levels for a transaction that writes to congested consensus objects, based on the congestion recently observed on those objects.
+    ///
+    /// Accepts a JSON transaction matching the [Sui gRPC API schema](https://docs.sui.io/references/fullnode-protocol#sui-rpc-v2-Transaction), which must be fully resolved, or BCS-encoded data: `{"bcs": {"value": "<base64>"}}`. Only the consensus objects that the transaction accesses mutably are considered.
+    ///
+    /// `objectIds` are additional consensus objects to consider. At least one of `transaction` and `objectIds` should be provided, otherwise the suggested gas prices are all the reference gas price.
+    async fn estimate_gas_price(
+        &self,
+        ctx: &Context<'_>,
+        transaction: Option<Json>,
+        object_ids: Option<Vec<SuiAddress>>,
+    ) -> Result<GasPriceEstimate, RpcError<TransactionInputError>> {
+        let fullnode_client: &FullnodeClient = ctx.data()?;
+
+        let proto_tx = transaction
+            .map(|transaction| {
+                let json_value: serde_json::Value = transaction.try_into().map_err(|err| {
+                    bad_user_input(TransactionInputError::InvalidTransactionJson(err))
+                })?;
+                serde_json::from_value::<proto::Transaction>(json_value).map_err(|err| {
+                    bad_user_input(TransactionInputError::InvalidTransactionJson(err))
+                })
+            })
+            .transpose()?;
+
+        let object_ids = object_ids
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        match fullnode_client
+            .estimate_gas_price(proto_tx, object_ids)
+            .await
+        {
+            Ok(response) => GasPriceEstimate::from_proto(response).map_err(upcast),
+            Err(GrpcExecutionError(status)) if status.code() == Code::InvalidArgument => {
+                Err(bad_user_input(TransactionInputError::InvalidArgument(
+                    status.message().to_string(),
+                )))
+            }
+            Err(other_error) => Err(anyhow!(other_error)
+                .context("Failed to estimate gas price")
+                .into()),
+        }
+    }
+
     /// Verify a zkLogin signature is from the given `author`.
     ///
     /// Returns successfully if the signature is valid. If the signature is invalid, returns an error with the reason for the failure.
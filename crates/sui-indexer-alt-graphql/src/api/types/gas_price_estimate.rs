@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::SimpleObject;
+use sui_rpc_api::grpc::alpha::gas_price_service_proto as proto;
+use sui_types::base_types::ObjectID;
+
+use crate::api::scalars::big_int::BigInt;
+use crate::api::scalars::date_time::DateTime;
+use crate::api::scalars::sui_address::SuiAddress;
+use crate::error::RpcError;
+
+/// Gas prices suggested for a transaction, based on the congestion recently observed on the consensus objects that it writes to.
+#[derive(Clone, SimpleObject)]
+pub(crate) struct GasPriceEstimate {
+    /// The current epoch's reference gas price. Suggested gas prices are never lower than this.
+    pub reference_gas_price: Option<BigInt>,
+
+    /// Suggested gas prices, from lowest to highest confidence.
+    pub suggestions: Vec<GasPriceSuggestion>,
+
+    /// Recent congestion on each of the consensus objects that were considered.
+    pub objects: Vec<ObjectCongestion>,
+}
+
+/// A gas price suggestion at a given confidence level.
+#[derive(Clone, SimpleObject)]
+pub(crate) struct GasPriceSuggestion {
+    /// Percentage (1-100) of the recently congested checkpoints for every object considered, in which a transaction with this gas price would have cleared congestion.
+    pub confidence: Option<u32>,
+
+    /// The suggested gas price.
+    pub gas_price: Option<BigInt>,
+}
+
+/// Congestion recently observed on a consensus object.
+#[derive(Clone, SimpleObject)]
+pub(crate) struct ObjectCongestion {
+    /// The object's address.
+    pub address: Option<SuiAddress>,
+
+    /// Checkpoints in which the object was recently congested, or cleared after being congested, oldest first. Empty if the object has not been congested recently.
+    pub history: Vec<CongestionSample>,
+}
+
+/// Congestion observed on a consensus object in a single checkpoint.
+#[derive(Clone, SimpleObject)]
+pub(crate) struct CongestionSample {
+    /// The timestamp of the checkpoint.
+    pub timestamp: Option<DateTime>,
+
+    /// The highest gas price of a transaction that was cancelled due to congestion on the object, if any were.
+    pub highest_cancelled_gas_price: Option<BigInt>,
+
+    /// The lowest gas price of a transaction that wrote to the object, if any did.
+    pub lowest_executed_gas_price: Option<BigInt>,
+}
+
+impl GasPriceEstimate {
+    /// Create a GasPriceEstimate from a gRPC EstimateGasPriceResponse.
+    pub(crate) fn from_proto(response: proto::EstimateGasPriceResponse) -> Result<Self, RpcError> {
+        let suggestions = response
+            .estimates
+            .into_iter()
+            .map(|estimate| GasPriceSuggestion {
+                confidence: estimate.confidence,
+                gas_price: estimate.gas_price.map(BigInt::from),
+            })
+            .collect();
+
+        let objects = response
+            .objects
+            .into_iter()
+            .map(ObjectCongestion::from_proto)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            reference_gas_price: response.reference_gas_price.map(BigInt::from),
+            suggestions,
+            objects,
+        })
+    }
+}
+
+impl ObjectCongestion {
+    fn from_proto(object: proto::ObjectCongestion) -> Result<Self, RpcError> {
+        let address = object
+            .object_id
+            .map(|id| id.parse::<ObjectID>())
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid object ID in gas price estimate: {e}"))?
+            .map(SuiAddress::from);
+
+        let history = object
+            .history
+            .into_iter()
+            .map(|sample| {
+                Ok(CongestionSample {
+                    timestamp: sample
+                        .checkpoint_timestamp_ms
+                        .map(|ms| DateTime::from_ms(ms as i64))
+                        .transpose()?,
+                    highest_cancelled_gas_price: sample
+                        .highest_cancelled_gas_price
+                        .map(BigInt::from),
+                    lowest_executed_gas_price: sample.lowest_executed_gas_price.map(BigInt::from),
+                })
+            })
+            .collect::<Result<_, RpcError>>()?;
+
+        Ok(Self { address, history })
+    }
+}
@@ -26,6 +26,7 @@ use crate::api::types::move_datatype::MoveStruct;
 use crate::api::types::move_function::MoveFunction;
 use crate::api::types::move_package::MovePackage;
 use crate::config::Limits;
+use crate::decompiler::Decompiler;
 use crate::error::RpcError;
 use crate::error::resource_exhausted;
 use crate::pagination::Page;
@@ -175,6 +176,28 @@ impl MoveModule {
         .transpose()
     }
 
+    /// Move source code for this module, reconstructed from its bytecode by a decompiler.
+    ///
+    /// This is available for any package, including those whose source has not been verified. The output is not guaranteed to compile, and it does not include comments or the original names of local variables.
+    async fn decompiled_source(&self, ctx: &Context<'_>) -> Option<Result<String, RpcError>> {
+        async {
+            let decompiler: &Decompiler = ctx.data()?;
+
+            let Some(native) = self.package.native(ctx).await?.as_ref() else {
+                return Ok(None);
+            };
+
+            let sources = decompiler
+                .decompile(native)
+                .await
+                .context("Failed to decompile package")?;
+
+            Ok(sources.get(&self.name).cloned())
+        }
+        .await
+        .transpose()
+    }
+
     /// Textual representation of the module's bytecode.
     async fn disassembly(&self, ctx: &Context<'_>) -> Option<Result<String, RpcError>> {
         async {
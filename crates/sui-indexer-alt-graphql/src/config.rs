@@ -281,6 +281,7 @@ impl Limits {
             tx_payload_args: BTreeSet::from([
                 ("Mutation", "executeTransaction", "transactionDataBcs"),
                 ("Mutation", "executeTransaction", "signatures"),
+                ("Query", "estimateGasPrice", "transaction"),
                 ("Query", "simulateTransaction", "transaction"),
                 ("Query", "verifyZkLoginSignature", "bytes"),
                 ("Query", "verifyZkLoginSignature", "signature"),
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::anyhow;
+use moka::sync::Cache;
+use move_binary_format::CompiledModule;
+use sui_types::base_types::ObjectID;
+use sui_types::move_package::MovePackage;
+
+/// Number of packages whose decompiled sources are kept in memory.
+const CACHE_CAPACITY: u64 = 1024;
+
+/// Decompiled sources for a package, keyed by module name.
+pub(crate) type DecompiledSources = Arc<BTreeMap<String, String>>;
+
+/// Decompiles packages on demand, caching the output by storage ID. The contents of a package at
+/// a given storage ID never change, so cache entries never need to be invalidated.
+#[derive(Clone)]
+pub(crate) struct Decompiler {
+    cache: Cache<ObjectID, DecompiledSources>,
+}
+
+impl Decompiler {
+    pub(crate) fn new() -> Self {
+        Self {
+            cache: Cache::new(CACHE_CAPACITY),
+        }
+    }
+
+    /// Decompile all the modules in `package` together, or return the cached result of a previous
+    /// decompilation. Decompilation runs on a blocking thread, so that large packages don't stall
+    /// other requests, and concurrent requests for the same package share a single decompilation.
+    pub(crate) async fn decompile(
+        &self,
+        package: &MovePackage,
+    ) -> anyhow::Result<DecompiledSources> {
+        let storage_id = package.id();
+        if let Some(sources) = self.cache.get(&storage_id) {
+            return Ok(sources);
+        }
+
+        let cache = self.cache.clone();
+        let package = package.clone();
+        tokio::task::spawn_blocking(move || {
+            cache.try_get_with(storage_id, || decompile_package(&package))
+        })
+        .await
+        .context("Decompilation task failed")?
+        .map_err(|e| anyhow!("{e:#}"))
+    }
+}
+
+fn decompile_package(package: &MovePackage) -> anyhow::Result<DecompiledSources> {
+    let modules = package
+        .serialized_module_map()
+        .iter()
+        .map(|(name, bytes)| {
+            CompiledModule::deserialize_with_defaults(bytes)
+                .with_context(|| format!("Failed to deserialize module {name}"))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let sources = move_decompiler::decompile_modules(modules)?;
+    Ok(Arc::new(
+        sources
+            .into_iter()
+            .map(|(name, source)| (name.to_string(), source))
+            .collect(),
+    ))
+}
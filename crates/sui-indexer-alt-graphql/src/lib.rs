@@ -64,6 +64,7 @@ use url::Url;
 
 use crate::api::mutation::Mutation;
 use crate::api::query::Query;
+use crate::decompiler::Decompiler;
 use crate::error::PanicHandler;
 use crate::extensions::logging::Logging;
 use crate::extensions::logging::Session;
@@ -73,6 +74,7 @@ use crate::middleware::version::Version;
 mod api;
 pub mod args;
 pub mod config;
+mod decompiler;
 mod error;
 pub mod extensions;
 mod health;
@@ -388,6 +390,7 @@ pub async fn start_rpc(
         .data(pg_loader)
         .data(kv_loader)
         .data(package_store)
+        .data(Decompiler::new())
         .data(fullnode_client);
 
     let s_rpc = rpc.run().await?;
@@ -9,6 +9,9 @@ use prost_types::FieldMask;
 use sui_rpc::field::FieldMaskUtil;
 use sui_rpc::proto::sui::rpc::v2 as proto;
 use sui_rpc::proto::sui::rpc::v2::transaction_execution_service_client::TransactionExecutionServiceClient;
+use sui_rpc_api::grpc::alpha::gas_price_service_proto as gas_price_proto;
+use sui_rpc_api::grpc::alpha::gas_price_service_proto::gas_price_service_client::GasPriceServiceClient;
+use sui_types::base_types::ObjectID;
 use sui_types::signature::GenericSignature;
 use sui_types::transaction::Transaction;
 use sui_types::transaction::TransactionData;
@@ -21,15 +24,18 @@ use crate::metrics::FullnodeClientMetrics;
 
 #[derive(clap::Args, Debug, Clone, Default)]
 pub struct FullnodeArgs {
-    /// gRPC URL for full node operations such as executeTransaction and simulateTransaction.
+    /// gRPC URL for full node operations such as executeTransaction, simulateTransaction, and
+    /// gas price estimation.
     #[clap(long)]
     pub fullnode_rpc_url: Option<Url>,
 }
 
-/// A client for executing and simulating transactions via the full node gRPC service.
+/// A client for executing and simulating transactions, and estimating their gas prices, via the
+/// full node gRPC service.
 #[derive(Clone)]
 pub struct FullnodeClient {
     execution_client: Option<TransactionExecutionServiceClient<Channel>>,
+    gas_price_client: Option<GasPriceServiceClient<Channel>>,
     metrics: Arc<FullnodeClientMetrics>,
 }
 
@@ -51,7 +57,7 @@ impl FullnodeClient {
         args: FullnodeArgs,
         registry: &Registry,
     ) -> Result<Self, Error> {
-        let execution_client = if let Some(url) = &args.fullnode_rpc_url {
+        let channel = if let Some(url) = &args.fullnode_rpc_url {
             let mut endpoint = Channel::from_shared(url.to_string())
                 .context("Failed to create channel for gRPC endpoint")?;
 
@@ -61,8 +67,7 @@ impl FullnodeClient {
                     .context("Failed to configure TLS for gRPC endpoint")?;
             }
 
-            let channel = endpoint.connect_lazy();
-            Some(TransactionExecutionServiceClient::new(channel))
+            Some(endpoint.connect_lazy())
         } else {
             None
         };
@@ -70,7 +75,8 @@ impl FullnodeClient {
         let metrics = FullnodeClientMetrics::new(prefix, registry);
 
         Ok(Self {
-            execution_client,
+            execution_client: channel.clone().map(TransactionExecutionServiceClient::new),
+            gas_price_client: channel.map(GasPriceServiceClient::new),
             metrics,
         })
     }
@@ -161,6 +167,26 @@ impl FullnodeClient {
         .await
     }
 
+    /// Suggest gas prices for a transaction and/or a set of consensus objects, based on the
+    /// congestion recently observed on them, via gRPC.
+    #[instrument(skip(self, transaction), level = "debug")]
+    pub async fn estimate_gas_price(
+        &self,
+        transaction: Option<proto::Transaction>,
+        object_ids: Vec<ObjectID>,
+    ) -> Result<gas_price_proto::EstimateGasPriceResponse, Error> {
+        let mut request = gas_price_proto::EstimateGasPriceRequest::default();
+        request.transaction = transaction;
+        request.object_ids = object_ids.iter().map(|id| id.to_string()).collect();
+
+        self.request(
+            "estimate_gas_price",
+            self.gas_price_client.clone(),
+            |mut client| async move { client.estimate_gas_price(request).await },
+        )
+        .await
+    }
+
     async fn request<C, F, Fut, R>(
         &self,
         method: &str,
@@ -223,6 +249,7 @@ mod tests {
     async fn no_url_means_not_configured() {
         let client = fn_client(None).await.unwrap();
         assert!(client.execution_client.is_none());
+        assert!(client.gas_price_client.is_none());
     }
 
     #[tokio::test]
@@ -17,7 +17,7 @@ use move_binary_format::{
 use move_bytecode_utils::{Modules, layout::SerdeLayoutBuilder, module_cache::GetModule};
 use move_compiler::{
     compiled_unit::AnnotatedCompiledModule,
-    diagnostics::{Diagnostics, report_diagnostics_to_buffer, report_warnings},
+    diagnostics::{Diagnostics, DiagnosticsFormat, report_diagnostics_to_buffer, report_warnings},
     linters::LINT_WARNING_PREFIX,
     shared::files::MappedFiles,
 };
@@ -142,10 +142,14 @@ impl BuildConfig {
         fn_info_map
     }
 
+    /// Compile `root_pkg`, writing build progress to `writer`. When SARIF output is requested, the
+    /// diagnostics are written as a SARIF log to `sarif_writer` instead of being reported with the
+    /// rest of the build output.
     fn compile_package<W: Write + Send, F: MoveFlavor>(
         &self,
         root_pkg: &RootPackage<F>,
         writer: &mut W,
+        sarif_writer: &mut impl Write,
     ) -> anyhow::Result<(MoveCompiledPackage, FnInfoMap)> {
         let mut config = self.config.clone();
         // set the default flavor to Sui if not already set by the user
@@ -158,7 +162,11 @@ impl BuildConfig {
             let (files, units_res) = compiler.build()?;
             match units_res {
                 Ok((units, warning_diags)) => {
-                    decorate_warnings(warning_diags, Some(&files));
+                    if config.sarif_errors {
+                        write_sarif(sarif_writer, &files, warning_diags)?;
+                    } else {
+                        decorate_warnings(warning_diags, Some(&files));
+                    }
                     fn_info = Some(Self::fn_info(&units));
                     Ok((files, units))
                 }
@@ -166,10 +174,17 @@ impl BuildConfig {
                     // with errors present don't even try decorating warnings output to avoid
                     // clutter
                     assert!(!error_diags.is_empty());
-                    let diags_buf =
-                        report_diagnostics_to_buffer(&files, error_diags, /* color */ true);
-                    if let Err(err) = std::io::stderr().write_all(&diags_buf) {
-                        anyhow::bail!("Cannot output compiler diagnostics: {}", err);
+                    if config.sarif_errors {
+                        write_sarif(sarif_writer, &files, error_diags)?;
+                    } else {
+                        let diags_buf = report_diagnostics_to_buffer(
+                            &files,
+                            error_diags,
+                            /* color */ true,
+                        );
+                        if let Err(err) = std::io::stderr().write_all(&diags_buf) {
+                            anyhow::bail!("Cannot output compiler diagnostics: {}", err);
+                        }
                     }
                     anyhow::bail!("Compilation error");
                 }
@@ -211,10 +226,12 @@ impl BuildConfig {
         self,
         root_pkg: &mut RootPackage<SuiFlavor>,
     ) -> anyhow::Result<CompiledPackage> {
+        // SARIF logs go to stdout so they can be redirected to a file separately from the
+        // human-readable build output
         let result = if self.print_diags_to_stderr {
-            self.compile_package(root_pkg, &mut std::io::stderr())
+            self.compile_package(root_pkg, &mut std::io::stderr(), &mut std::io::stdout())
         } else {
-            self.compile_package(root_pkg, &mut std::io::sink())
+            self.compile_package(root_pkg, &mut std::io::sink(), &mut std::io::sink())
         };
 
         let (package, fn_info) = result.map_err(|error| {
@@ -262,6 +279,20 @@ pub fn decorate_warnings(warning_diags: Diagnostics, files: Option<&MappedFiles>
     }
 }
 
+/// Write `diags` to `writer` as a single SARIF log
+fn write_sarif(
+    writer: &mut impl Write,
+    files: &MappedFiles,
+    mut diags: Diagnostics,
+) -> anyhow::Result<()> {
+    diags.set_format(DiagnosticsFormat::SARIF);
+    let diags_buf = report_diagnostics_to_buffer(files, diags, /* color */ false);
+    if let Err(err) = writer.write_all(&diags_buf) {
+        anyhow::bail!("Cannot output compiler diagnostics: {}", err);
+    }
+    Ok(())
+}
+
 /// Check that the compiled modules in `package` are valid
 fn verify_bytecode(package: &MoveCompiledPackage, fn_info: &FnInfoMap) -> SuiResult<()> {
     let compiled_modules = package.root_modules_map();
@@ -1,10 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use fs_extra::dir::CopyOptions;
 use move_compiler::editions::Edition;
+use move_package_alt_compilation::lint_flag::LintFlag;
 
 use crate::BuildConfig;
 
@@ -62,3 +63,51 @@ async fn development_mode_not_allowed() {
             .contains(&Edition::DEVELOPMENT.unknown_edition_error().to_string())
     );
 }
+
+fn lints_deny_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("unit_tests")
+        .join("data")
+        .join("lints_deny")
+}
+
+/// A build config that runs the linters and reports their warnings
+fn lints_build_config() -> BuildConfig {
+    let mut config = BuildConfig::new_for_testing();
+    config.config.silence_warnings = false;
+    config.config.lint_flag = LintFlag::LEVEL_DEFAULT;
+    config
+}
+
+#[tokio::test]
+async fn deny_lint_fails_compilation() {
+    let err = lints_build_config()
+        .build_async(&lints_deny_path())
+        .await
+        .expect_err("Should have failed due to a denied lint");
+    assert!(err.to_string().contains("Compilation error"));
+}
+
+#[tokio::test]
+async fn sarif_output() {
+    let mut config = lints_build_config();
+    config.config.sarif_errors = true;
+    let root_pkg = config
+        .config
+        .package_loader(&lints_deny_path(), &config.environment)
+        .load()
+        .await
+        .unwrap();
+
+    let mut sarif = vec![];
+    config
+        .compile_package(&root_pkg, &mut std::io::sink(), &mut sarif)
+        .expect_err("Should have failed due to a denied lint");
+
+    let log: serde_json::Value = serde_json::from_slice(&sarif).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["level"], "error");
+}
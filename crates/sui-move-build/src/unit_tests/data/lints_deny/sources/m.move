@@ -0,0 +1,12 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module lints_deny::m;
+
+public struct Obj has key, store {
+    id: UID,
+}
+
+public fun make(ctx: &mut TxContext) {
+    transfer::public_transfer(Obj { id: object::new(ctx) }, ctx.sender())
+}
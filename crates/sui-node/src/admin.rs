@@ -19,12 +19,14 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
     str::FromStr,
 };
+use sui_config::validator_client_monitor_config::ValidatorSelectionStrategyConfig;
 use sui_network::endpoint_manager::{AddressSource, EndpointId};
 use sui_types::{
     base_types::AuthorityName,
     crypto::{NetworkPublicKey, RandomnessPartialSignature, RandomnessRound, RandomnessSignature},
     digests::TransactionDigest,
     error::SuiErrorKind,
+    messages_checkpoint::CheckpointSequenceNumber,
     traffic_control::TrafficControlReconfigParams,
 };
 use telemetry_subscribers::TracingHandle;
@@ -86,6 +88,48 @@ use tracing::info;
 //
 //  $ curl -X POST 'http://127.0.0.1:1337/update-endpoint?endpoint_type=p2p&id=<hex_encoded_peer_id>&addresses=<multiaddr1>,<multiaddr2>'
 //  $ curl -X POST 'http://127.0.0.1:1337/update-endpoint?endpoint_type=consensus&id=<hex_encoded_network_pubkey>&addresses=<multiaddr1>,<multiaddr2>'
+//
+// Dump the per-command execution time estimates and per-object utilization used by congestion
+// control, as JSON
+//
+//  $ curl 'http://127.0.0.1:1337/execution-time-estimates'
+//
+// Take a db checkpoint at the highest executed checkpoint. If a db checkpoint object store is
+// configured, it is uploaded incrementally and can be restored with `sui-tool restore-db-incremental`.
+// Otherwise only the two most recent ones are kept on local disk. Taking a db checkpoint fails if
+// one already exists at the same checkpoint.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/db-checkpoint'
+//
+// View the client-observed scores of validators, by which a fullnode selects validators for
+// requests, as JSON
+//
+//  $ curl 'http://127.0.0.1:1337/validator-scores'
+//
+// Change the strategy used by a fullnode to select validators for requests. Either parameter may
+// be omitted to keep its current value.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/validator-selection-strategy?strategy=shuffled-preferred&delta=0.05'
+//
+// Pause checkpoint execution at checkpoint 1000 (or at the next checkpoint if no checkpoint is
+// given). State sync keeps syncing checkpoints while execution is paused.
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/checkpoint-execution/pause?checkpoint=1000'
+//
+// Execute the next checkpoint, or the next transaction of the next checkpoint, and pause again
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/checkpoint-execution/step-checkpoint'
+//  $ curl -X POST 'http://127.0.0.1:1337/checkpoint-execution/step-transaction'
+//
+// View the checkpoint execution controls, and dump the input objects and expected effects of the
+// next transaction while stepping through transactions, as JSON
+//
+//  $ curl 'http://127.0.0.1:1337/checkpoint-execution'
+//  $ curl 'http://127.0.0.1:1337/checkpoint-execution/next-transaction'
+//
+// Resume checkpoint execution
+//
+//  $ curl -X POST 'http://127.0.0.1:1337/checkpoint-execution/resume'
 
 const NO_TRACING_HANDLE: &str = "tracing handle not available";
 const LOGGING_ROUTE: &str = "/logging";
@@ -101,8 +145,18 @@ const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-s
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
 const GET_TX_COST_ROUTE: &str = "/get-tx-cost";
 const DUMP_CONSENSUS_TX_COST_ESTIMATES_ROUTE: &str = "/dump-consensus-tx-cost-estimates";
+const EXECUTION_TIME_ESTIMATES_ROUTE: &str = "/execution-time-estimates";
 const TRAFFIC_CONTROL: &str = "/traffic-control";
 const UPDATE_ENDPOINT: &str = "/update-endpoint";
+const DB_CHECKPOINT: &str = "/db-checkpoint";
+const VALIDATOR_SCORES_ROUTE: &str = "/validator-scores";
+const VALIDATOR_SELECTION_STRATEGY_ROUTE: &str = "/validator-selection-strategy";
+const CHECKPOINT_EXECUTION_ROUTE: &str = "/checkpoint-execution";
+const CHECKPOINT_EXECUTION_PAUSE_ROUTE: &str = "/checkpoint-execution/pause";
+const CHECKPOINT_EXECUTION_STEP_CHECKPOINT_ROUTE: &str = "/checkpoint-execution/step-checkpoint";
+const CHECKPOINT_EXECUTION_STEP_TRANSACTION_ROUTE: &str = "/checkpoint-execution/step-transaction";
+const CHECKPOINT_EXECUTION_NEXT_TRANSACTION_ROUTE: &str = "/checkpoint-execution/next-transaction";
+const CHECKPOINT_EXECUTION_RESUME_ROUTE: &str = "/checkpoint-execution/resume";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -154,8 +208,39 @@ pub async fn run_admin_server(
             DUMP_CONSENSUS_TX_COST_ESTIMATES_ROUTE,
             get(dump_consensus_tx_cost_estimates),
         )
+        .route(
+            EXECUTION_TIME_ESTIMATES_ROUTE,
+            get(execution_time_estimates),
+        )
         .route(TRAFFIC_CONTROL, post(traffic_control))
         .route(UPDATE_ENDPOINT, post(update_endpoint))
+        .route(DB_CHECKPOINT, post(db_checkpoint))
+        .route(VALIDATOR_SCORES_ROUTE, get(validator_scores))
+        .route(
+            VALIDATOR_SELECTION_STRATEGY_ROUTE,
+            post(set_validator_selection_strategy),
+        )
+        .route(CHECKPOINT_EXECUTION_ROUTE, get(checkpoint_execution))
+        .route(
+            CHECKPOINT_EXECUTION_PAUSE_ROUTE,
+            post(pause_checkpoint_execution),
+        )
+        .route(
+            CHECKPOINT_EXECUTION_STEP_CHECKPOINT_ROUTE,
+            post(step_checkpoint_execution),
+        )
+        .route(
+            CHECKPOINT_EXECUTION_STEP_TRANSACTION_ROUTE,
+            post(step_transaction_execution),
+        )
+        .route(
+            CHECKPOINT_EXECUTION_NEXT_TRANSACTION_ROUTE,
+            get(next_transaction_execution),
+        )
+        .route(
+            CHECKPOINT_EXECUTION_RESUME_ROUTE,
+            post(resume_checkpoint_execution),
+        )
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -530,6 +615,76 @@ async fn dump_consensus_tx_cost_estimates(
     (StatusCode::OK, format!("{:#?}", estimates))
 }
 
+async fn db_checkpoint(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let epoch_store = state.node.state().load_epoch_store_one_call_per_task();
+    match state.node.create_db_checkpoint(&epoch_store).await {
+        Ok((checkpoint_seq, path)) => (
+            StatusCode::OK,
+            format!(
+                "created db checkpoint at checkpoint {checkpoint_seq} in {}\n",
+                path.display()
+            ),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn execution_time_estimates(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let epoch_store = state.node.state().load_epoch_store_one_call_per_task();
+    let dump = epoch_store.get_execution_time_estimates_dump().await;
+    match serde_json::to_string_pretty(&dump) {
+        Ok(json) => (StatusCode::OK, json),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn validator_scores(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let Some(orchestrator) = state.node.transaction_orchestrator() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "validator scores are only tracked by fullnodes\n".to_string(),
+        );
+    };
+    let scores = orchestrator
+        .transaction_driver()
+        .client_monitor()
+        .validator_scores();
+    match serde_json::to_string_pretty(&scores) {
+        Ok(json) => (StatusCode::OK, json),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct SetValidatorSelectionStrategy {
+    strategy: Option<ValidatorSelectionStrategyConfig>,
+    delta: Option<f64>,
+}
+
+async fn set_validator_selection_strategy(
+    State(state): State<Arc<AppState>>,
+    args: Query<SetValidatorSelectionStrategy>,
+) -> (StatusCode, String) {
+    let Query(SetValidatorSelectionStrategy { strategy, delta }) = args;
+    let Some(orchestrator) = state.node.transaction_orchestrator() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "validator selection is only done by fullnodes\n".to_string(),
+        );
+    };
+    match orchestrator
+        .transaction_driver()
+        .client_monitor()
+        .update_selection_strategy(strategy, delta)
+    {
+        Ok((strategy, delta)) => (
+            StatusCode::OK,
+            format!("validator selection strategy set to {strategy:?} with delta {delta}\n"),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, format!("{err}\n")),
+    }
+}
+
 async fn traffic_control(
     State(state): State<Arc<AppState>>,
     args: Query<TrafficControlReconfigParams>,
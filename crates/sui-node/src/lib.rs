@@ -19,6 +19,7 @@ use prometheus::Registry;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::future::Future;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::str::FromStr;
 #[cfg(msim)]
@@ -52,6 +53,7 @@ use sui_types::crypto::RandomnessRound;
 use sui_types::digests::{
     ChainIdentifier, CheckpointDigest, TransactionDigest, TransactionEffectsDigest,
 };
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::messages_consensus::AuthorityCapabilitiesV2;
 use sui_types::sui_system_state::SuiSystemState;
 use tap::tap::TapFallible;
@@ -104,7 +106,10 @@ use sui_core::consensus_throughput_calculator::{
     ConsensusThroughputCalculator, ConsensusThroughputProfiler, ThroughputProfileRanges,
 };
 use sui_core::consensus_validator::{SuiTxValidator, SuiTxValidatorMetrics};
-use sui_core::db_checkpoint_handler::DBCheckpointHandler;
+use sui_core::db_checkpoint_handler::{
+    DBCheckpointHandler, NUM_LOCAL_ON_DEMAND_DB_CHECKPOINTS_TO_RETAIN,
+    garbage_collect_on_demand_db_checkpoints, upload_incremental_db_checkpoint,
+};
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::epoch::consensus_store_pruner::ConsensusStorePruner;
 use sui_core::epoch::epoch_metrics::EpochMetrics;
@@ -160,6 +165,8 @@ use crate::metrics::{GrpcMetrics, SuiNodeMetrics};
 pub mod admin;
 mod handle;
 pub mod metrics;
+#[cfg(not(tidehunter))]
+pub mod read_replica;
 
 pub struct ValidatorComponents {
     validator_server_handle: SpawnOnce,
@@ -770,7 +777,7 @@ impl SuiNode {
             config.firewall_config.clone(),
             pruner_watermarks,
         )
-        .await;
+        .await?;
         // ensure genesis txn was executed
         if epoch_store.epoch() == 0 {
             let txn = &genesis.transaction();
@@ -991,6 +998,61 @@ impl SuiNode {
             .set_override_protocol_upgrade_buffer_stake(epoch, buffer_stake_bps)
     }
 
+    /// Takes a db checkpoint at the highest executed checkpoint. If a db checkpoint object store
+    /// is configured, the db checkpoint is uploaded incrementally in the background and deleted
+    /// from local disk once uploaded. Otherwise only the most recent ones are kept on local disk.
+    pub async fn create_db_checkpoint(
+        &self,
+        epoch_store: &Arc<AuthorityPerEpochStore>,
+    ) -> Result<(CheckpointSequenceNumber, PathBuf)> {
+        let state = self.state.clone();
+        let cur_epoch_store = epoch_store.clone();
+        let (checkpoint_seq, checkpoint_path) = tokio::task::spawn_blocking(move || {
+            state.checkpoint_all_dbs_on_demand(&cur_epoch_store)
+        })
+        .await??;
+        info!(
+            "Created db checkpoint at checkpoint {checkpoint_seq} in {}",
+            checkpoint_path.display()
+        );
+
+        if let Some(object_store_config) = &self.config.db_checkpoint_config.object_store_config {
+            let object_store = object_store_config.make()?;
+            let epoch = epoch_store.epoch();
+            let local_path = checkpoint_path.clone();
+            spawn_monitored_task!(async move {
+                match upload_incremental_db_checkpoint(
+                    &local_path,
+                    epoch,
+                    checkpoint_seq,
+                    &object_store,
+                    NonZeroUsize::new(20).unwrap(),
+                )
+                .await
+                {
+                    Ok(_) => {
+                        if let Err(err) = std::fs::remove_dir_all(&local_path) {
+                            warn!("Failed to delete uploaded db checkpoint: {err:?}");
+                        }
+                    }
+                    Err(err) => {
+                        error!("Failed to upload db checkpoint {checkpoint_seq}: {err:?}");
+                    }
+                }
+            });
+        } else if let Some(checkpoint_root) = checkpoint_path.parent() {
+            let checkpoint_root = checkpoint_root.to_path_buf();
+            tokio::task::spawn_blocking(move || {
+                garbage_collect_on_demand_db_checkpoints(
+                    &checkpoint_root,
+                    NUM_LOCAL_ON_DEMAND_DB_CHECKPOINTS_TO_RETAIN,
+                )
+            })
+            .await??;
+        }
+        Ok((checkpoint_seq, checkpoint_path))
+    }
+
     // Testing-only API to start epoch close process.
     // For production code, please use the non-testing version.
     pub async fn close_epoch_for_testing(&self) -> SuiResult {
@@ -2517,6 +2579,17 @@ async fn build_http_servers(
         rpc_service.into_router().await
     };
 
+    router = router.merge(rpc_router);
+
+    Ok((
+        serve_rpc_router(config, router)?,
+        Some(subscription_service_checkpoint_sender),
+    ))
+}
+
+/// Serves the rpc `router` on the http address of the node, and on its https address if tls is
+/// configured.
+fn serve_rpc_router(config: &NodeConfig, router: axum::Router) -> Result<HttpServers> {
     let layers = ServiceBuilder::new()
         .map_request(|mut request: axum::http::Request<_>| {
             if let Some(connect_info) = request.extensions().get::<sui_http::ConnectInfo>() {
@@ -2535,7 +2608,7 @@ async fn build_http_servers(
                 .expose_headers(tower_http::cors::Any),
         );
 
-    router = router.merge(rpc_router).layer(layers);
+    let router = router.layer(layers);
 
     let https = if let Some((tls_config, https_address)) = config
         .rpc()
@@ -2567,13 +2640,10 @@ async fn build_http_servers(
         http.local_addr()
     );
 
-    Ok((
-        HttpServers {
-            http: Some(http),
-            https,
-        },
-        Some(subscription_service_checkpoint_sender),
-    ))
+    Ok(HttpServers {
+        http: Some(http),
+        https,
+    })
 }
 
 #[cfg(not(test))]
@@ -130,6 +130,37 @@ fn main() {
     let (runtime_shutdown_tx, runtime_shutdown_rx) = broadcast::channel::<()>(1);
 
     let server_version = ServerVersion::new(env!("CARGO_BIN_NAME"), VERSION);
+
+    #[cfg(not(tidehunter))]
+    if config.read_replica.is_some() {
+        runtimes.sui_node.spawn(async move {
+            let _replica = match sui_node::read_replica::ReadReplicaNode::start(
+                config,
+                registry_service,
+                server_version,
+            )
+            .await
+            {
+                Ok(replica) => replica,
+                Err(e) => {
+                    error!("Failed to start read replica: {e:?}");
+                    std::process::exit(1);
+                }
+            };
+            // A read replica is only stopped by terminating the process.
+            let _runtime_shutdown_tx = runtime_shutdown_tx;
+            futures::future::pending::<()>().await;
+        });
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(wait_termination(runtime_shutdown_rx));
+        drop(runtimes);
+        return;
+    }
+
     runtimes.sui_node.spawn(async move {
         match sui_node::SuiNode::start_async(config, registry_service, server_version).await {
             Ok(sui_node) => node_once_cell_clone
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read replica mode of `sui-node`, enabled by the `read-replica` section of the node config.
+//!
+//! A read replica serves `sui-rpc-api` reads from the database of a fullnode running on the same
+//! machine, opened as a RocksDB secondary, and periodically catches up with the writes of the
+//! fullnode. It runs no state sync, consensus, execution or indexing of its own, so it can be
+//! started next to a fullnode to add read capacity without another copy of the chain state.
+//!
+//! JSON-RPC, including its reads, and subscriptions are not served by a read replica. The JSON-RPC
+//! apis are built on the `AuthorityState` of a node executing checkpoints, with its epoch store
+//! and JSON-RPC indexes, none of which a replica opens; JSON-RPC clients keep using the fullnode.
+
+use crate::{HttpServers, serve_rpc_router};
+use anyhow::{Result, anyhow};
+use mysten_metrics::{RegistryService, spawn_monitored_task};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::NodeConfig;
+use sui_core::read_replica::ReadReplicaStore;
+use sui_rpc_api::{RpcMetrics, ServerVersion};
+use sui_types::digests::ChainIdentifier;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use typed_store::DBMetrics;
+
+pub struct ReadReplicaNode {
+    store: Arc<ReadReplicaStore>,
+    _http_servers: HttpServers,
+    _catch_up_task: JoinHandle<()>,
+}
+
+impl ReadReplicaNode {
+    pub async fn start(
+        config: NodeConfig,
+        registry_service: RegistryService,
+        server_version: ServerVersion,
+    ) -> Result<Arc<Self>> {
+        let replica_config = config
+            .read_replica
+            .clone()
+            .ok_or_else(|| anyhow!("read-replica is not set in the node config"))?;
+        if config.consensus_config().is_some() {
+            return Err(anyhow!("a validator cannot run as a read replica"));
+        }
+
+        let prometheus_registry = registry_service.default_registry();
+        DBMetrics::init(registry_service.clone());
+        mysten_metrics::init_metrics(&prometheus_registry);
+
+        let genesis = config.genesis()?;
+        let chain_identifier = ChainIdentifier::from(*genesis.checkpoint().digest());
+        info!(
+            "starting read replica of {} with chain identifier {chain_identifier}",
+            replica_config.primary_db_path.display()
+        );
+
+        let store = Arc::new(ReadReplicaStore::open(
+            &replica_config.primary_db_path,
+            &config.db_path(),
+            chain_identifier,
+            config.rpc().is_some_and(|rpc| rpc.enable_indexing()),
+            &config.execution_cache,
+            &prometheus_registry,
+        )?);
+
+        let rpc_router = {
+            let mut rpc_service = sui_rpc_api::RpcService::new(store.clone());
+            rpc_service.with_server_version(server_version);
+            if let Some(config) = config.rpc.clone() {
+                rpc_service.with_config(config);
+            }
+            rpc_service.with_metrics(RpcMetrics::new(&prometheus_registry));
+            rpc_service.into_router().await
+        };
+        let http_servers = serve_rpc_router(&config, rpc_router)?;
+
+        let catch_up_task = spawn_monitored_task!(Self::catch_up_loop(
+            store.clone(),
+            replica_config.catch_up_interval()
+        ));
+
+        Ok(Arc::new(Self {
+            store,
+            _http_servers: http_servers,
+            _catch_up_task: catch_up_task,
+        }))
+    }
+
+    pub fn store(&self) -> &Arc<ReadReplicaStore> {
+        &self.store
+    }
+
+    async fn catch_up_loop(store: Arc<ReadReplicaStore>, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut highest_executed_checkpoint = None;
+        loop {
+            interval.tick().await;
+            if let Err(e) = store.catch_up() {
+                warn!("read replica failed to catch up with the fullnode: {e:?}");
+                continue;
+            }
+            match store.get_highest_executed_checkpoint_seq_number() {
+                Ok(checkpoint) if checkpoint != highest_executed_checkpoint => {
+                    highest_executed_checkpoint = checkpoint;
+                    debug!(
+                        ?highest_executed_checkpoint,
+                        "read replica caught up with the fullnode"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => warn!("failed to read the highest executed checkpoint: {e:?}"),
+            }
+        }
+    }
+}
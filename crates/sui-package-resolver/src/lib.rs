@@ -756,6 +756,23 @@ impl Package {
         &self.modules
     }
 
+    pub fn storage_id(&self) -> AccountAddress {
+        self.storage_id
+    }
+
+    pub fn runtime_id(&self) -> AccountAddress {
+        self.runtime_id
+    }
+
+    pub fn version(&self) -> SequenceNumber {
+        self.version
+    }
+
+    /// The package's transitive dependencies, as a mapping from runtime ID to storage ID.
+    pub fn linkage(&self) -> &BTreeMap<AccountAddress, AccountAddress> {
+        &self.linkage
+    }
+
     fn data_def(&self, module_name: &str, datatype_name: &str) -> Result<DataDef> {
         let module = self.module(module_name)?;
         let Some(data_def) = module.data_def(datatype_name)? else {
@@ -0,0 +1,237 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::field_reassign_with_default)]
+
+use crate::ErrorReason;
+use crate::RpcError;
+use crate::RpcService;
+use crate::grpc::alpha::gas_price_service_proto::{
+    CongestionSample, EstimateGasPriceRequest, EstimateGasPriceResponse, GasPriceEstimate,
+    ObjectCongestion,
+};
+use std::collections::BTreeSet;
+use sui_rpc::proto::google::rpc::bad_request::FieldViolation;
+use sui_types::base_types::ObjectID;
+use sui_types::storage::CongestionSample as ObservedCongestion;
+use sui_types::sui_system_state::SuiSystemStateTrait;
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
+
+/// Confidence levels (as percentages) that gas prices are suggested at.
+const CONFIDENCE_LEVELS: [u32; 3] = [50, 90, 100];
+
+const MAX_OBJECTS: usize = 1000;
+
+#[tracing::instrument(skip(service))]
+pub fn estimate_gas_price(
+    service: &RpcService,
+    request: EstimateGasPriceRequest,
+) -> Result<EstimateGasPriceResponse, RpcError> {
+    let mut object_ids = BTreeSet::new();
+
+    if let Some(transaction) = &request.transaction {
+        let transaction = sui_sdk_types::Transaction::try_from(transaction)
+            .map_err(|e| {
+                FieldViolation::new("transaction")
+                    .with_description(format!("invalid transaction: {e}"))
+                    .with_reason(ErrorReason::FieldInvalid)
+            })
+            .and_then(|transaction| {
+                TransactionData::try_from(transaction).map_err(|e| {
+                    FieldViolation::new("transaction")
+                        .with_description(format!("invalid transaction: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })?;
+
+        object_ids.extend(
+            transaction
+                .shared_input_objects()
+                .into_iter()
+                .filter(|object| object.is_accessed_exclusively())
+                .map(|object| object.id),
+        );
+    }
+
+    for (i, object_id) in request.object_ids.iter().enumerate() {
+        let object_id = object_id.parse::<ObjectID>().map_err(|e| {
+            FieldViolation::new_at("object_ids", i)
+                .with_description(format!("invalid object_id: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })?;
+        object_ids.insert(object_id);
+    }
+
+    if object_ids.len() > MAX_OBJECTS {
+        return Err(RpcError::new(
+            tonic::Code::InvalidArgument,
+            format!("number of objects exceeds limit of {MAX_OBJECTS}"),
+        ));
+    }
+
+    let reference_gas_price = service.reader.get_system_state()?.reference_gas_price();
+
+    let histories: Vec<(ObjectID, Vec<ObservedCongestion>)> = object_ids
+        .into_iter()
+        .map(|object_id| {
+            let history = service
+                .reader
+                .inner()
+                .get_congestion_history(&object_id)
+                .unwrap_or_default();
+            (object_id, history)
+        })
+        .collect();
+
+    let mut response = EstimateGasPriceResponse::default();
+    response.reference_gas_price = Some(reference_gas_price);
+    response.estimates = CONFIDENCE_LEVELS
+        .into_iter()
+        .map(|confidence| {
+            let mut estimate = GasPriceEstimate::default();
+            estimate.confidence = Some(confidence);
+            estimate.gas_price = Some(suggested_gas_price(
+                reference_gas_price,
+                histories.iter().map(|(_, history)| history.as_slice()),
+                confidence,
+            ));
+            estimate
+        })
+        .collect();
+    response.objects = histories
+        .into_iter()
+        .map(|(object_id, history)| {
+            let mut object = ObjectCongestion::default();
+            object.object_id = Some(object_id.to_canonical_string(true));
+            object.history = history.into_iter().map(to_congestion_sample).collect();
+            object
+        })
+        .collect();
+
+    Ok(response)
+}
+
+/// The lowest gas price that would have cleared congestion on each object in at least
+/// `confidence` percent of the checkpoints in its `histories`, and is no lower than the reference
+/// gas price.
+fn suggested_gas_price<'a>(
+    reference_gas_price: u64,
+    histories: impl Iterator<Item = &'a [ObservedCongestion]>,
+    confidence: u32,
+) -> u64 {
+    histories
+        .map(|history| {
+            let mut prices: Vec<u64> = history
+                .iter()
+                .map(|sample| clearing_price(sample).max(reference_gas_price))
+                .collect();
+
+            if prices.is_empty() {
+                return reference_gas_price;
+            }
+
+            // Nearest-rank percentile of the per-checkpoint clearing prices.
+            prices.sort_unstable();
+            let rank = (prices.len() * confidence as usize).div_ceil(100);
+            prices[rank.saturating_sub(1)]
+        })
+        .fold(reference_gas_price, u64::max)
+}
+
+/// The gas price needed to clear congestion on an object in a single checkpoint, following the
+/// same rules as the suggested gas price reported when a transaction is cancelled for congestion.
+fn clearing_price(sample: &ObservedCongestion) -> u64 {
+    match (
+        sample.highest_cancelled_gas_price,
+        sample.lowest_executed_gas_price,
+    ) {
+        // There were both cancellations and successes, so the lowest success cleared congestion.
+        (Some(_), Some(executed)) => executed,
+        // Nothing succeeded, so a price above the highest cancelled one would have been needed.
+        (Some(cancelled), None) => cancelled.saturating_add(1),
+        // Nothing was cancelled, so the object was not congested.
+        (None, _) => 0,
+    }
+}
+
+fn to_congestion_sample(sample: ObservedCongestion) -> CongestionSample {
+    let mut message = CongestionSample::default();
+    message.checkpoint_timestamp_ms = Some(sample.checkpoint_timestamp_ms);
+    message.highest_cancelled_gas_price = sample.highest_cancelled_gas_price;
+    message.lowest_executed_gas_price = sample.lowest_executed_gas_price;
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cancelled: Option<u64>, executed: Option<u64>) -> ObservedCongestion {
+        ObservedCongestion {
+            checkpoint_timestamp_ms: 0,
+            highest_cancelled_gas_price: cancelled,
+            lowest_executed_gas_price: executed,
+        }
+    }
+
+    #[test]
+    fn uncongested_objects_use_reference_gas_price() {
+        let history = [sample(None, Some(2000))];
+        for confidence in CONFIDENCE_LEVELS {
+            assert_eq!(suggested_gas_price(1000, [].into_iter(), confidence), 1000);
+            assert_eq!(
+                suggested_gas_price(1000, [history.as_slice()].into_iter(), confidence),
+                1000
+            );
+        }
+    }
+
+    #[test]
+    fn confidence_levels() {
+        let history: Vec<_> = (1..=10)
+            .map(|i| sample(Some(i * 1000), Some(i * 1000 + 500)))
+            .collect();
+
+        assert_eq!(
+            suggested_gas_price(1000, [history.as_slice()].into_iter(), 50),
+            5500
+        );
+        assert_eq!(
+            suggested_gas_price(1000, [history.as_slice()].into_iter(), 90),
+            9500
+        );
+        assert_eq!(
+            suggested_gas_price(1000, [history.as_slice()].into_iter(), 100),
+            10500
+        );
+    }
+
+    #[test]
+    fn nothing_executed_needs_more_than_highest_cancelled() {
+        assert_eq!(clearing_price(&sample(Some(2000), None)), 2001);
+        assert_eq!(clearing_price(&sample(Some(u64::MAX), None)), u64::MAX);
+
+        let history = [sample(Some(2000), None)];
+        for confidence in CONFIDENCE_LEVELS {
+            assert_eq!(
+                suggested_gas_price(1000, [history.as_slice()].into_iter(), confidence),
+                2001
+            );
+        }
+    }
+
+    #[test]
+    fn most_congested_object_wins() {
+        let a = [sample(Some(1500), None), sample(Some(3000), Some(4000))];
+        let b = [sample(Some(2500), None)];
+
+        assert_eq!(
+            suggested_gas_price(1000, [a.as_slice(), b.as_slice()].into_iter(), 50),
+            2501
+        );
+        assert_eq!(
+            suggested_gas_price(1000, [a.as_slice(), b.as_slice()].into_iter(), 100),
+            4000
+        );
+    }
+}
@@ -0,0 +1,644 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::field_reassign_with_default)]
+
+use crate::ErrorReason;
+use crate::RpcError;
+use crate::RpcService;
+use crate::grpc::alpha::filtered_subscription_service_proto::{
+    EventFilter as EventFilterProto, ObjectChangeFilter as ObjectChangeFilterProto,
+    SubscribeEventsRequest, SubscribeEventsResponse, SubscribeObjectChangesRequest,
+    SubscribeObjectChangesResponse, SubscribeTransactionsRequest, SubscribeTransactionsResponse,
+    SubscriptionCursor, TransactionFilter as TransactionFilterProto,
+};
+use crate::reader::StateReader;
+use move_core_types::language_storage::StructTag;
+use prost_types::FieldMask;
+use sui_rpc::field::FieldMaskTree;
+use sui_rpc::field::FieldMaskUtil;
+use sui_rpc::merge::Merge;
+use sui_rpc::proto::google::rpc::bad_request::FieldViolation;
+use sui_rpc::proto::sui::rpc::v2::Event;
+use sui_rpc::proto::sui::rpc::v2::ExecutedTransaction;
+use sui_rpc::proto::sui::rpc::v2::Object;
+use sui_types::Identifier;
+use sui_types::balance_change::derive_balance_changes_2;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::full_checkpoint_content::Checkpoint;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Owner;
+use sui_types::parse_sui_struct_tag;
+use sui_types::storage::ObjectKey;
+use sui_types::transaction::TransactionDataAPI;
+use tonic::codegen::BoxStream;
+
+pub const EVENTS_READ_MASK_DEFAULT: &str = "package_id,module,sender,event_type";
+pub const TRANSACTIONS_READ_MASK_DEFAULT: &str = "digest";
+pub const OBJECT_CHANGES_READ_MASK_DEFAULT: &str = "object_id,version,digest,object_type";
+
+#[tracing::instrument(skip(service))]
+pub async fn subscribe_events(
+    service: &RpcService,
+    request: SubscribeEventsRequest,
+) -> Result<BoxStream<SubscribeEventsResponse>, RpcError> {
+    let read_mask = read_mask::<Event>(request.read_mask, EVENTS_READ_MASK_DEFAULT)?;
+    let filter = request
+        .filter
+        .as_ref()
+        .map(EventFilter::try_from)
+        .transpose()?
+        .unwrap_or_default();
+
+    subscribe(service, request.after, move |checkpoint| {
+        let sequence_number = checkpoint.summary.sequence_number;
+        checkpoint
+            .transactions
+            .iter()
+            .flat_map(|transaction| {
+                let digest = transaction.transaction.digest();
+                transaction
+                    .events
+                    .iter()
+                    .flat_map(|events| events.data.iter())
+                    .map(move |event| (digest, event))
+            })
+            .enumerate()
+            .filter(|(_, (_, event))| filter.matches(event))
+            .map(|(index, (digest, event))| {
+                let mut response = SubscribeEventsResponse::default();
+                response.cursor = Some(cursor(sequence_number, index));
+                response.transaction_digest = Some(digest.to_string());
+                response.event = Some(Event::merge_from(event, &read_mask));
+                (index as u64, response)
+            })
+            .collect()
+    })
+    .await
+}
+
+#[tracing::instrument(skip(service))]
+pub async fn subscribe_transactions(
+    service: &RpcService,
+    request: SubscribeTransactionsRequest,
+) -> Result<BoxStream<SubscribeTransactionsResponse>, RpcError> {
+    let read_mask =
+        read_mask::<ExecutedTransaction>(request.read_mask, TRANSACTIONS_READ_MASK_DEFAULT)?;
+    let filter = request
+        .filter
+        .as_ref()
+        .map(TransactionFilter::try_from)
+        .transpose()?
+        .unwrap_or_default();
+
+    subscribe(service, request.after, move |checkpoint| {
+        let sequence_number = checkpoint.summary.sequence_number;
+        let timestamp_ms = checkpoint.summary.timestamp_ms;
+        checkpoint
+            .transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, transaction)| filter.matches(transaction))
+            .map(|(index, transaction)| {
+                let mut message = ExecutedTransaction::merge_from(transaction, &read_mask);
+                message.checkpoint = read_mask
+                    .contains(ExecutedTransaction::CHECKPOINT_FIELD)
+                    .then_some(sequence_number);
+                message.timestamp = read_mask
+                    .contains(ExecutedTransaction::TIMESTAMP_FIELD)
+                    .then(|| sui_rpc::proto::timestamp_ms_to_proto(timestamp_ms));
+                if read_mask.contains(ExecutedTransaction::BALANCE_CHANGES_FIELD) {
+                    message.balance_changes =
+                        derive_balance_changes_2(&transaction.effects, &checkpoint.object_set)
+                            .into_iter()
+                            .map(Into::into)
+                            .collect();
+                }
+
+                let mut response = SubscribeTransactionsResponse::default();
+                response.cursor = Some(cursor(sequence_number, index));
+                response.transaction = Some(message);
+                (index as u64, response)
+            })
+            .collect()
+    })
+    .await
+}
+
+#[tracing::instrument(skip(service))]
+pub async fn subscribe_object_changes(
+    service: &RpcService,
+    request: SubscribeObjectChangesRequest,
+) -> Result<BoxStream<SubscribeObjectChangesResponse>, RpcError> {
+    let read_mask = read_mask::<Object>(request.read_mask, OBJECT_CHANGES_READ_MASK_DEFAULT)?;
+    let filter = request
+        .filter
+        .as_ref()
+        .map(ObjectChangeFilter::try_from)
+        .transpose()?
+        .unwrap_or_default();
+
+    subscribe(service, request.after, move |checkpoint| {
+        let sequence_number = checkpoint.summary.sequence_number;
+        checkpoint
+            .transactions
+            .iter()
+            .flat_map(|transaction| {
+                let digest = transaction.transaction.digest();
+                transaction
+                    .effects
+                    .object_changes()
+                    .into_iter()
+                    .map(move |change| (digest, change))
+            })
+            .enumerate()
+            .filter_map(|(index, (digest, change))| {
+                let load = |version: Option<_>| {
+                    version.and_then(|version| {
+                        checkpoint.object_set.get(&ObjectKey(change.id, version))
+                    })
+                };
+                let input = load(change.input_version);
+                let output = load(change.output_version);
+                if !filter.matches(input) && !filter.matches(output) {
+                    return None;
+                }
+
+                let mut response = SubscribeObjectChangesResponse::default();
+                response.cursor = Some(cursor(sequence_number, index));
+                response.transaction_digest = Some(digest.to_string());
+                response.object_id = Some(change.id.to_canonical_string(true));
+                response.input_object = input.map(|object| Object::merge_from(object, &read_mask));
+                response.output_object =
+                    output.map(|object| Object::merge_from(object, &read_mask));
+                Some((index as u64, response))
+            })
+            .collect()
+    })
+    .await
+}
+
+/// Streams the items that `select` picks out of each executed checkpoint, starting after the
+/// cursor `after` if one is given, or with the next checkpoint to be executed otherwise.
+///
+/// `select` returns the matching items of a checkpoint, in order, along with their index among all
+/// items of the same kind in that checkpoint.
+///
+/// Checkpoints that were executed before the subscription was made, or while it was catching up,
+/// are read back from the store. Newly executed checkpoints are received through a live
+/// subscription whose buffer is bounded, so a subscriber that stops consuming the stream is
+/// eventually dropped by the subscription service, ending the stream with `UNAVAILABLE`.
+async fn subscribe<T, F>(
+    service: &RpcService,
+    after: Option<SubscriptionCursor>,
+    select: F,
+) -> Result<BoxStream<T>, RpcError>
+where
+    T: Send + 'static,
+    F: Fn(&Checkpoint) -> Vec<(u64, T)> + Send + 'static,
+{
+    let subscription_service_handle =
+        service
+            .subscription_service_handle
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::new(
+                    tonic::Code::Unimplemented,
+                    "subscription service not enabled",
+                )
+            })?;
+
+    // The next checkpoint to stream, if resuming from a cursor, and the index of the last item
+    // already streamed from it.
+    let (mut next_checkpoint, resume_after_index) = match after {
+        None => (None, None),
+        Some(cursor) => {
+            let checkpoint = cursor.checkpoint.ok_or_else(|| {
+                FieldViolation::new("after.checkpoint")
+                    .with_description("missing checkpoint")
+                    .with_reason(ErrorReason::FieldMissing)
+            })?;
+
+            match cursor.index {
+                Some(index) => (Some(checkpoint), Some((checkpoint, index))),
+                None => (Some(checkpoint + 1), None),
+            }
+        }
+    };
+
+    if let Some(next_checkpoint) = next_checkpoint {
+        let lowest_available_checkpoint = service.reader.get_lowest_available_checkpoint()?;
+        if next_checkpoint < lowest_available_checkpoint {
+            return Err(RpcError::new(
+                tonic::Code::OutOfRange,
+                format!(
+                    "checkpoint {next_checkpoint} has been pruned; \
+                     the lowest available checkpoint is {lowest_available_checkpoint}"
+                ),
+            ));
+        }
+    }
+
+    let Some(mut receiver) = subscription_service_handle.register_subscription().await else {
+        return Err(RpcError::new(
+            tonic::Code::Unavailable,
+            "too many existing subscriptions",
+        ));
+    };
+
+    let reader = service.reader.clone();
+    let select_after = move |checkpoint: &Checkpoint| {
+        let mut items = select(checkpoint);
+        if let Some((resume_checkpoint, resume_index)) = resume_after_index
+            && checkpoint.summary.sequence_number == resume_checkpoint
+        {
+            items.retain(|(index, _)| *index > resume_index);
+        }
+        items
+    };
+
+    Ok(Box::pin(async_stream::stream! {
+        loop {
+            // Catch up on checkpoints that were executed before the live subscription started
+            // delivering them.
+            if let Some(next) = next_checkpoint.as_mut() {
+                let latest = match reader.inner().get_latest_checkpoint_sequence_number() {
+                    Ok(latest) => latest,
+                    Err(e) => {
+                        yield Err(tonic::Status::from(RpcError::from(e)));
+                        return;
+                    }
+                };
+
+                while *next <= latest {
+                    let checkpoint = match load_checkpoint(&reader, *next) {
+                        Ok(checkpoint) => checkpoint,
+                        Err(e) => {
+                            yield Err(tonic::Status::from(e));
+                            return;
+                        }
+                    };
+
+                    for (_, item) in select_after(&checkpoint) {
+                        yield Ok(item);
+                    }
+                    *next += 1;
+
+                    // Checkpoints that arrive on the live subscription while catching up will be
+                    // read from the store instead, so discard them rather than letting them fill up
+                    // the subscription's buffer.
+                    while receiver.try_recv().is_ok() {}
+                }
+            }
+
+            let Some(checkpoint) = receiver.recv().await else {
+                yield Err(tonic::Status::unavailable(
+                    "subscription fell too far behind; resume from the last cursor received",
+                ));
+                return;
+            };
+
+            let sequence_number = checkpoint.summary.sequence_number;
+            match next_checkpoint {
+                // Already streamed while catching up.
+                Some(next) if sequence_number < next => continue,
+                // Checkpoints were missed since the last one streamed, so catch up on them, and this
+                // one, from the store.
+                Some(next) if sequence_number > next => continue,
+                _ => {}
+            }
+
+            for (_, item) in select_after(checkpoint.as_ref()) {
+                yield Ok(item);
+            }
+            next_checkpoint = Some(sequence_number + 1);
+        }
+    }))
+}
+
+fn load_checkpoint(
+    reader: &StateReader,
+    sequence_number: CheckpointSequenceNumber,
+) -> Result<Checkpoint, RpcError> {
+    let not_found = || {
+        RpcError::new(
+            tonic::Code::NotFound,
+            format!("checkpoint {sequence_number} not found"),
+        )
+    };
+
+    let summary = reader
+        .inner()
+        .get_checkpoint_by_sequence_number(sequence_number)
+        .ok_or_else(not_found)?;
+    let contents = reader
+        .inner()
+        .get_checkpoint_contents_by_sequence_number(sequence_number)
+        .ok_or_else(not_found)?;
+
+    Ok(reader.inner().get_checkpoint_data(summary, contents)?)
+}
+
+fn cursor(checkpoint: CheckpointSequenceNumber, index: usize) -> SubscriptionCursor {
+    let mut cursor = SubscriptionCursor::default();
+    cursor.checkpoint = Some(checkpoint);
+    cursor.index = Some(index as u64);
+    cursor
+}
+
+fn read_mask<M: sui_rpc::field::MessageFields>(
+    read_mask: Option<FieldMask>,
+    default: &str,
+) -> Result<FieldMaskTree, RpcError> {
+    let read_mask = read_mask.unwrap_or_else(|| FieldMask::from_str(default));
+    read_mask.validate::<M>().map_err(|path| {
+        FieldViolation::new("read_mask")
+            .with_description(format!("invalid read_mask path: {path}"))
+            .with_reason(ErrorReason::FieldInvalid)
+    })?;
+    Ok(FieldMaskTree::from(read_mask))
+}
+
+/// Matches a struct type, either exactly, or any instantiation of it if no type parameters are
+/// given.
+#[derive(Debug)]
+struct TypeFilter(StructTag);
+
+impl TypeFilter {
+    fn parse(field: &str, type_: &str) -> Result<Self, RpcError> {
+        let tag = parse_sui_struct_tag(type_).map_err(|e| {
+            FieldViolation::new(field)
+                .with_description(format!("invalid type: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })?;
+        Ok(Self(tag))
+    }
+
+    fn matches(&self, tag: &StructTag) -> bool {
+        let filter = &self.0;
+        filter.address == tag.address
+            && filter.module == tag.module
+            && filter.name == tag.name
+            && (filter.type_params.is_empty() || filter.type_params == tag.type_params)
+    }
+}
+
+#[derive(Debug, Default)]
+struct EventFilter {
+    sender: Option<SuiAddress>,
+    package: Option<ObjectID>,
+    module: Option<Identifier>,
+    event_type: Option<TypeFilter>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &sui_types::event::Event) -> bool {
+        self.sender.is_none_or(|sender| sender == event.sender)
+            && self
+                .package
+                .is_none_or(|package| package == event.package_id)
+            && self
+                .module
+                .as_ref()
+                .is_none_or(|module| *module == event.transaction_module)
+            && self
+                .event_type
+                .as_ref()
+                .is_none_or(|event_type| event_type.matches(&event.type_))
+    }
+}
+
+impl TryFrom<&EventFilterProto> for EventFilter {
+    type Error = RpcError;
+
+    fn try_from(filter: &EventFilterProto) -> Result<Self, Self::Error> {
+        let sender = filter
+            .sender
+            .as_ref()
+            .map(|sender| {
+                sender.parse::<SuiAddress>().map_err(|e| {
+                    FieldViolation::new("filter.sender")
+                        .with_description(format!("invalid sender: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })
+            .transpose()?;
+
+        let package = filter
+            .package
+            .as_ref()
+            .map(|package| {
+                package.parse::<ObjectID>().map_err(|e| {
+                    FieldViolation::new("filter.package")
+                        .with_description(format!("invalid package: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })
+            .transpose()?;
+
+        let module = filter
+            .module
+            .as_ref()
+            .map(|module| {
+                if package.is_none() {
+                    return Err(FieldViolation::new("filter.module")
+                        .with_description("module filter requires a package")
+                        .with_reason(ErrorReason::FieldInvalid));
+                }
+
+                Identifier::new(module.as_str()).map_err(|e| {
+                    FieldViolation::new("filter.module")
+                        .with_description(format!("invalid module: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })
+            .transpose()?;
+
+        let event_type = filter
+            .event_type
+            .as_deref()
+            .map(|event_type| TypeFilter::parse("filter.event_type", event_type))
+            .transpose()?;
+
+        Ok(Self {
+            sender,
+            package,
+            module,
+            event_type,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct TransactionFilter {
+    affected_address: Option<SuiAddress>,
+    affected_object: Option<ObjectID>,
+}
+
+impl TransactionFilter {
+    fn matches(
+        &self,
+        transaction: &sui_types::full_checkpoint_content::ExecutedTransaction,
+    ) -> bool {
+        self.affected_address
+            .is_none_or(|address| affects_address(transaction, address))
+            && self.affected_object.is_none_or(|object_id| {
+                transaction
+                    .effects
+                    .object_changes()
+                    .iter()
+                    .any(|change| change.id == object_id)
+            })
+    }
+}
+
+/// Whether `address` sent or paid for the transaction, or owns an object it created or modified.
+fn affects_address(
+    transaction: &sui_types::full_checkpoint_content::ExecutedTransaction,
+    address: SuiAddress,
+) -> bool {
+    transaction.transaction.sender() == address
+        || transaction.transaction.gas_owner() == address
+        || transaction
+            .effects
+            .all_changed_objects()
+            .into_iter()
+            .any(|(_, owner, _)| match owner {
+                Owner::AddressOwner(owner) => owner == address,
+                Owner::ConsensusAddressOwner { owner, .. } => owner == address,
+                _ => false,
+            })
+}
+
+impl TryFrom<&TransactionFilterProto> for TransactionFilter {
+    type Error = RpcError;
+
+    fn try_from(filter: &TransactionFilterProto) -> Result<Self, Self::Error> {
+        let affected_address = filter
+            .affected_address
+            .as_ref()
+            .map(|address| {
+                address.parse::<SuiAddress>().map_err(|e| {
+                    FieldViolation::new("filter.affected_address")
+                        .with_description(format!("invalid affected_address: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })
+            .transpose()?;
+
+        let affected_object = filter
+            .affected_object
+            .as_ref()
+            .map(|object_id| {
+                object_id.parse::<ObjectID>().map_err(|e| {
+                    FieldViolation::new("filter.affected_object")
+                        .with_description(format!("invalid affected_object: {e}"))
+                        .with_reason(ErrorReason::FieldInvalid)
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            affected_address,
+            affected_object,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct ObjectChangeFilter {
+    object_type: Option<TypeFilter>,
+}
+
+impl ObjectChangeFilter {
+    /// Whether `object` (the state of a changed object either before or after the transaction)
+    /// matches the filter. A missing object never matches a filter with a type.
+    fn matches(&self, object: Option<&sui_types::object::Object>) -> bool {
+        let Some(object_type) = &self.object_type else {
+            return true;
+        };
+
+        object
+            .and_then(|object| object.struct_tag())
+            .is_some_and(|tag| object_type.matches(&tag))
+    }
+}
+
+impl TryFrom<&ObjectChangeFilterProto> for ObjectChangeFilter {
+    type Error = RpcError;
+
+    fn try_from(filter: &ObjectChangeFilterProto) -> Result<Self, Self::Error> {
+        let object_type = filter
+            .object_type
+            .as_deref()
+            .map(|object_type| TypeFilter::parse("filter.object_type", object_type))
+            .transpose()?;
+
+        Ok(Self { object_type })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(type_: &str) -> sui_types::event::Event {
+        let type_ = parse_sui_struct_tag(type_).unwrap();
+        sui_types::event::Event {
+            package_id: ObjectID::from(type_.address),
+            transaction_module: type_.module.clone(),
+            sender: SuiAddress::ZERO,
+            type_,
+            contents: vec![],
+        }
+    }
+
+    fn event_filter(
+        package: Option<&str>,
+        module: Option<&str>,
+        event_type: Option<&str>,
+    ) -> EventFilter {
+        let mut filter = EventFilterProto::default();
+        filter.package = package.map(String::from);
+        filter.module = module.map(String::from);
+        filter.event_type = event_type.map(String::from);
+        EventFilter::try_from(&filter).unwrap()
+    }
+
+    #[test]
+    fn type_filter_instantiations() {
+        let any_coin = TypeFilter::parse("type", "0x2::coin::Coin").unwrap();
+        let sui_coin = TypeFilter::parse("type", "0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+
+        let sui = parse_sui_struct_tag("0x2::coin::Coin<0x2::sui::SUI>").unwrap();
+        let other = parse_sui_struct_tag("0x2::coin::Coin<0x3::foo::FOO>").unwrap();
+        let treasury = parse_sui_struct_tag("0x2::coin::TreasuryCap<0x2::sui::SUI>").unwrap();
+
+        assert!(any_coin.matches(&sui));
+        assert!(any_coin.matches(&other));
+        assert!(!any_coin.matches(&treasury));
+        assert!(sui_coin.matches(&sui));
+        assert!(!sui_coin.matches(&other));
+    }
+
+    #[test]
+    fn event_filter_fields() {
+        let e = event("0x2::display::DisplayCreated<0x2::sui::SUI>");
+
+        assert!(EventFilter::default().matches(&e));
+        assert!(event_filter(Some("0x2"), None, None).matches(&e));
+        assert!(event_filter(Some("0x2"), Some("display"), None).matches(&e));
+        assert!(!event_filter(Some("0x2"), Some("coin"), None).matches(&e));
+        assert!(!event_filter(Some("0x3"), None, None).matches(&e));
+        assert!(event_filter(None, None, Some("0x2::display::DisplayCreated")).matches(&e));
+        assert!(!event_filter(Some("0x2"), None, Some("0x2::display::VersionUpdated")).matches(&e));
+    }
+
+    #[test]
+    fn event_filter_module_requires_package() {
+        let mut filter = EventFilterProto::default();
+        filter.module = Some("coin".to_owned());
+        assert!(EventFilter::try_from(&filter).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::RpcService;
+use crate::grpc::alpha::filtered_subscription;
+use crate::grpc::alpha::filtered_subscription_service_proto::filtered_subscription_service_server::FilteredSubscriptionService;
+use crate::grpc::alpha::filtered_subscription_service_proto::{
+    SubscribeEventsRequest, SubscribeEventsResponse, SubscribeObjectChangesRequest,
+    SubscribeObjectChangesResponse, SubscribeTransactionsRequest, SubscribeTransactionsResponse,
+};
+use tonic::codegen::BoxStream;
+
+#[tonic::async_trait]
+impl FilteredSubscriptionService for RpcService {
+    type SubscribeEventsStream = BoxStream<SubscribeEventsResponse>;
+    type SubscribeTransactionsStream = BoxStream<SubscribeTransactionsResponse>;
+    type SubscribeObjectChangesStream = BoxStream<SubscribeObjectChangesResponse>;
+
+    async fn subscribe_events(
+        &self,
+        request: tonic::Request<SubscribeEventsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeEventsStream>, tonic::Status> {
+        let stream = filtered_subscription::subscribe_events(self, request.into_inner())
+            .await
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(stream))
+    }
+
+    async fn subscribe_transactions(
+        &self,
+        request: tonic::Request<SubscribeTransactionsRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeTransactionsStream>, tonic::Status> {
+        let stream = filtered_subscription::subscribe_transactions(self, request.into_inner())
+            .await
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(stream))
+    }
+
+    async fn subscribe_object_changes(
+        &self,
+        request: tonic::Request<SubscribeObjectChangesRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeObjectChangesStream>, tonic::Status> {
+        let stream = filtered_subscription::subscribe_object_changes(self, request.into_inner())
+            .await
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(stream))
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::RpcService;
+use crate::grpc::alpha::estimate_gas_price;
+use crate::grpc::alpha::gas_price_service_proto::gas_price_service_server::GasPriceService;
+use crate::grpc::alpha::gas_price_service_proto::{
+    EstimateGasPriceRequest, EstimateGasPriceResponse,
+};
+
+#[tonic::async_trait]
+impl GasPriceService for RpcService {
+    async fn estimate_gas_price(
+        &self,
+        request: tonic::Request<EstimateGasPriceRequest>,
+    ) -> Result<tonic::Response<EstimateGasPriceResponse>, tonic::Status> {
+        let response = estimate_gas_price::estimate_gas_price(self, request.into_inner())
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(response))
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::field_reassign_with_default)]
+
+use crate::ErrorReason;
+use crate::RpcError;
+use crate::RpcService;
+use crate::grpc::alpha::transaction_status_service_proto::{
+    ExecuteTransactionResponse, GetTransactionStatusRequest, GetTransactionStatusResponse,
+    TransactionLifecycle, TransactionLifecycleEvent, TransactionLifecycleEventKind,
+    TransactionStatus,
+};
+use crate::grpc::v2::transaction_execution_service;
+use sui_rpc::proto::google::rpc::bad_request::FieldViolation;
+use sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest;
+use sui_types::base_types::TransactionDigest;
+use sui_types::transaction_driver_types;
+
+#[tracing::instrument(skip(service))]
+pub fn get_transaction_status(
+    service: &RpcService,
+    request: GetTransactionStatusRequest,
+) -> Result<GetTransactionStatusResponse, RpcError> {
+    let digest = request
+        .digest
+        .ok_or_else(|| {
+            FieldViolation::new("digest")
+                .with_description("missing digest")
+                .with_reason(ErrorReason::FieldMissing)
+        })?
+        .parse::<TransactionDigest>()
+        .map_err(|e| {
+            FieldViolation::new("digest")
+                .with_description(format!("invalid digest: {e}"))
+                .with_reason(ErrorReason::FieldInvalid)
+        })?;
+
+    let lifecycle = service
+        .executor
+        .as_ref()
+        .and_then(|executor| executor.transaction_lifecycle(&digest));
+
+    let mut message = GetTransactionStatusResponse::default();
+    message.digest = Some(digest.to_string());
+
+    let store = service.reader.inner();
+    if store.get_transaction_effects(&digest).is_some() {
+        // Effects in the local store are authoritative, even if the lifecycle is out of date or
+        // the transaction was submitted through a different node.
+        message.set_status(TransactionStatus::Executed);
+        message.checkpoint = store.get_transaction_checkpoint(&digest);
+    } else if let Some(lifecycle) = &lifecycle {
+        let (status, reason) = match &lifecycle.status {
+            transaction_driver_types::TransactionStatus::Pending => {
+                (TransactionStatus::Pending, None)
+            }
+            transaction_driver_types::TransactionStatus::Deferred { reason } => {
+                (TransactionStatus::Deferred, Some(reason.clone()))
+            }
+            transaction_driver_types::TransactionStatus::Rejected { reason } => {
+                (TransactionStatus::Rejected, Some(reason.clone()))
+            }
+            // Effects were certified but have not been executed locally yet.
+            transaction_driver_types::TransactionStatus::Executed => {
+                (TransactionStatus::Executed, None)
+            }
+        };
+        message.set_status(status);
+        message.reason = reason;
+    } else {
+        message.set_status(TransactionStatus::Unknown);
+    }
+
+    message.lifecycle = lifecycle.map(lifecycle_to_proto);
+    Ok(message)
+}
+
+/// Execute a transaction like `TransactionExecutionService/ExecuteTransaction`, returning its
+/// lifecycle in the response.
+#[tracing::instrument(skip(service, request))]
+pub async fn execute_transaction(
+    service: &RpcService,
+    request: ExecuteTransactionRequest,
+) -> Result<ExecuteTransactionResponse, RpcError> {
+    let executor = service
+        .executor
+        .as_ref()
+        .ok_or_else(|| RpcError::new(tonic::Code::Unimplemented, "no transaction executor"))?;
+
+    let digest = request
+        .transaction
+        .as_ref()
+        .and_then(|transaction| sui_sdk_types::Transaction::try_from(transaction).ok())
+        .map(|transaction| TransactionDigest::from(transaction.digest()));
+
+    let response =
+        transaction_execution_service::execute_transaction(service, executor, request).await?;
+
+    let mut message = ExecuteTransactionResponse::default();
+    message.response = Some(response);
+    message.lifecycle = digest
+        .and_then(|digest| executor.transaction_lifecycle(&digest))
+        .map(lifecycle_to_proto);
+    Ok(message)
+}
+
+fn lifecycle_to_proto(
+    lifecycle: transaction_driver_types::TransactionLifecycle,
+) -> TransactionLifecycle {
+    let mut message = TransactionLifecycle::default();
+    message.attempts = Some(lifecycle.attempts);
+    message.events = lifecycle
+        .events
+        .into_iter()
+        .map(lifecycle_event_to_proto)
+        .collect();
+    message
+}
+
+fn lifecycle_event_to_proto(
+    event: transaction_driver_types::TransactionLifecycleEvent,
+) -> TransactionLifecycleEvent {
+    use transaction_driver_types::TransactionLifecycleEventKind as Kind;
+
+    let mut message = TransactionLifecycleEvent::default();
+    message.timestamp_ms = Some(event.timestamp_ms);
+    let kind = match event.kind {
+        Kind::AttemptStarted { attempt } => {
+            message.attempt = Some(attempt);
+            TransactionLifecycleEventKind::AttemptStarted
+        }
+        Kind::Submitted { validator } => {
+            message.validator = Some(validator);
+            TransactionLifecycleEventKind::Submitted
+        }
+        Kind::RejectedByValidator {
+            validator,
+            reason,
+            retriable,
+        } => {
+            message.validator = Some(validator);
+            message.reason = Some(reason);
+            message.retriable = Some(retriable);
+            TransactionLifecycleEventKind::RejectedByValidator
+        }
+        Kind::EffectsAcknowledged { validator } => {
+            message.validator = Some(validator);
+            TransactionLifecycleEventKind::EffectsAcknowledged
+        }
+        Kind::AttemptFailed { reason, retriable } => {
+            message.reason = Some(reason);
+            message.retriable = Some(retriable);
+            TransactionLifecycleEventKind::AttemptFailed
+        }
+        Kind::EffectsCertified => TransactionLifecycleEventKind::EffectsCertified,
+    };
+    message.set_kind(kind);
+    message
+}
@@ -1,14 +1,32 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod estimate_gas_price;
 pub mod event_service;
+pub mod filtered_subscription;
+pub mod filtered_subscription_service;
+pub mod gas_price_service;
+pub mod get_transaction_status;
 pub mod list_authenticated_events;
 pub mod proof_service;
+pub mod transaction_status_service;
 
 pub mod event_service_proto {
     include!("../../proto/generated/sui.rpc.alpha.rs");
 }
 
+pub mod filtered_subscription_service_proto {
+    include!("../../proto/generated/sui.rpc.alpha.rs");
+}
+
+pub mod gas_price_service_proto {
+    include!("../../proto/generated/sui.rpc.alpha.rs");
+}
+
 pub mod proof_service_proto {
     include!("../../proto/generated/sui.rpc.alpha.rs");
 }
+
+pub mod transaction_status_service_proto {
+    include!("../../proto/generated/sui.rpc.alpha.rs");
+}
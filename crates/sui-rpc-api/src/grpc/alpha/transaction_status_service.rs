@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::RpcService;
+use crate::grpc::alpha::get_transaction_status;
+use crate::grpc::alpha::transaction_status_service_proto::transaction_status_service_server::TransactionStatusService;
+use crate::grpc::alpha::transaction_status_service_proto::{
+    ExecuteTransactionResponse, GetTransactionStatusRequest, GetTransactionStatusResponse,
+};
+use sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest;
+
+#[tonic::async_trait]
+impl TransactionStatusService for RpcService {
+    async fn get_transaction_status(
+        &self,
+        request: tonic::Request<GetTransactionStatusRequest>,
+    ) -> Result<tonic::Response<GetTransactionStatusResponse>, tonic::Status> {
+        let response = get_transaction_status::get_transaction_status(self, request.into_inner())
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn execute_transaction(
+        &self,
+        request: tonic::Request<ExecuteTransactionRequest>,
+    ) -> Result<tonic::Response<ExecuteTransactionResponse>, tonic::Status> {
+        let response = get_transaction_status::execute_transaction(self, request.into_inner())
+            .await
+            .map_err(tonic::Status::from)?;
+        Ok(tonic::Response::new(response))
+    }
+}
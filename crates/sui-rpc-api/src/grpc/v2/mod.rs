@@ -8,6 +8,6 @@ mod name_service;
 mod signature_verification_service;
 mod state_service;
 mod subscription_service;
-mod transaction_execution_service;
+pub(crate) mod transaction_execution_service;
 
 mod render;
@@ -135,10 +135,18 @@ impl RpcService {
                 crate::grpc::alpha::event_service_proto::event_service_server::EventServiceServer::new(
                     self.clone(),
                 );
+            let gas_price_service_alpha =
+                crate::grpc::alpha::gas_price_service_proto::gas_price_service_server::GasPriceServiceServer::new(
+                    self.clone(),
+                );
             let proof_service_alpha =
                 crate::grpc::alpha::proof_service_proto::proof_service_server::ProofServiceServer::new(
                     crate::grpc::alpha::proof_service::ProofServiceImpl::new(self.clone()),
                 );
+            let transaction_status_service_alpha =
+                crate::grpc::alpha::transaction_status_service_proto::transaction_status_service_server::TransactionStatusServiceServer::new(
+                    self.clone(),
+                );
 
             let (health_reporter, health_service) = tonic_health::server::health_reporter();
 
@@ -182,7 +190,9 @@ impl RpcService {
                 service_name(&move_package_service),
                 service_name(&name_service),
                 service_name(&event_service_alpha),
+                service_name(&gas_price_service_alpha),
                 service_name(&proof_service_alpha),
+                service_name(&transaction_status_service_alpha),
                 service_name(&reflection_v1),
                 service_name(&reflection_v1alpha),
             ] {
@@ -201,7 +211,9 @@ impl RpcService {
                 .add_service(name_service)
                 // alpha
                 .add_service(event_service_alpha)
+                .add_service(gas_price_service_alpha)
                 .add_service(proof_service_alpha)
+                .add_service(transaction_status_service_alpha)
                 // Reflection
                 .add_service(reflection_v1)
                 .add_service(reflection_v1alpha);
@@ -217,6 +229,19 @@ sui_rpc::proto::sui::rpc::v2::subscription_service_server::SubscriptionServiceSe
                     .await;
 
                 services = services.add_service(subscription_service);
+
+                let filtered_subscription_service_alpha =
+                    crate::grpc::alpha::filtered_subscription_service_proto::filtered_subscription_service_server::FilteredSubscriptionServiceServer::new(
+                        self.clone(),
+                    );
+                health_reporter
+                    .set_service_status(
+                        service_name(&filtered_subscription_service_alpha),
+                        tonic_health::ServingStatus::Serving,
+                    )
+                    .await;
+
+                services = services.add_service(filtered_subscription_service_alpha);
             }
 
             services.add_service(health_service).into_router()
@@ -366,52 +366,1575 @@ pub mod event_service_server {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
+/// The position of an item in a stream.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscriptionCursor {
+    /// The checkpoint that the item was included in.
+    #[prost(uint64, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<u64>,
+    /// Index of the item among all items of the same kind in the checkpoint, whether or not they
+    /// match the subscription's filter.
+    #[prost(uint64, optional, tag = "2")]
+    pub index: ::core::option::Option<u64>,
+}
+/// Selects events. An event must match every field that is set.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct EventFilter {
+    /// Address of the sender of the transaction that emitted the event.
+    #[prost(string, optional, tag = "1")]
+    pub sender: ::core::option::Option<::prost::alloc::string::String>,
+    /// ID of the package containing the module that emitted the event.
+    #[prost(string, optional, tag = "2")]
+    pub package: ::core::option::Option<::prost::alloc::string::String>,
+    /// Name of the module that emitted the event. Requires `package` to be set.
+    #[prost(string, optional, tag = "3")]
+    pub module: ::core::option::Option<::prost::alloc::string::String>,
+    /// The event's type, e.g. `0x2::coin::CoinMetadata`. If no type parameters are given, events of
+    /// every instantiation of the type match.
+    #[prost(string, optional, tag = "4")]
+    pub event_type: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscribeEventsRequest {
+    /// Events to stream. All events are streamed if no filter is set.
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<EventFilter>,
+    /// Resume the stream after this cursor. If the cursor has no `index`, the stream resumes after
+    /// the whole checkpoint. If unset, only events from checkpoints executed after the subscription
+    /// was made are streamed.
+    #[prost(message, optional, tag = "2")]
+    pub after: ::core::option::Option<SubscriptionCursor>,
+    /// Mask specifying which fields of each event to return.
+    ///
+    /// If no mask is specified, defaults to `package_id,module,sender,event_type`.
+    #[prost(message, optional, tag = "3")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeEventsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub cursor: ::core::option::Option<SubscriptionCursor>,
+    /// Digest of the transaction that emitted the event.
+    #[prost(string, optional, tag = "2")]
+    pub transaction_digest: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "3")]
+    pub event: ::core::option::Option<::sui_rpc::proto::sui::rpc::v2::Event>,
+}
+/// Selects transactions. A transaction must match every field that is set.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TransactionFilter {
+    /// An address that is the sender or gas owner of the transaction, or the owner of an object it
+    /// created or modified.
+    #[prost(string, optional, tag = "1")]
+    pub affected_address: ::core::option::Option<::prost::alloc::string::String>,
+    /// ID of an object that was created, modified, deleted or wrapped by the transaction.
+    #[prost(string, optional, tag = "2")]
+    pub affected_object: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscribeTransactionsRequest {
+    /// Transactions to stream. All transactions are streamed if no filter is set.
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<TransactionFilter>,
+    /// Resume the stream after this cursor. If the cursor has no `index`, the stream resumes after
+    /// the whole checkpoint. If unset, only transactions from checkpoints executed after the
+    /// subscription was made are streamed.
+    #[prost(message, optional, tag = "2")]
+    pub after: ::core::option::Option<SubscriptionCursor>,
+    /// Mask specifying which fields of each transaction to return.
+    ///
+    /// If no mask is specified, defaults to `digest`.
+    #[prost(message, optional, tag = "3")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeTransactionsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub cursor: ::core::option::Option<SubscriptionCursor>,
+    #[prost(message, optional, tag = "2")]
+    pub transaction: ::core::option::Option<
+        ::sui_rpc::proto::sui::rpc::v2::ExecutedTransaction,
+    >,
+}
+/// Selects object changes.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ObjectChangeFilter {
+    /// The type of the changed object, e.g. `0x2::coin::Coin`. If no type parameters are given,
+    /// objects of every instantiation of the type match. Changes to objects whose type matches
+    /// before or after the transaction are included.
+    #[prost(string, optional, tag = "1")]
+    pub object_type: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscribeObjectChangesRequest {
+    /// Object changes to stream. All object changes are streamed if no filter is set.
+    #[prost(message, optional, tag = "1")]
+    pub filter: ::core::option::Option<ObjectChangeFilter>,
+    /// Resume the stream after this cursor. If the cursor has no `index`, the stream resumes after
+    /// the whole checkpoint. If unset, only object changes from checkpoints executed after the
+    /// subscription was made are streamed.
+    #[prost(message, optional, tag = "2")]
+    pub after: ::core::option::Option<SubscriptionCursor>,
+    /// Mask specifying which fields of `input_object` and `output_object` to return.
+    ///
+    /// If no mask is specified, defaults to `object_id,version,digest,object_type`.
+    #[prost(message, optional, tag = "3")]
+    pub read_mask: ::core::option::Option<::prost_types::FieldMask>,
+}
+#[non_exhaustive]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeObjectChangesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub cursor: ::core::option::Option<SubscriptionCursor>,
+    /// Digest of the transaction that changed the object.
+    #[prost(string, optional, tag = "2")]
+    pub transaction_digest: ::core::option::Option<::prost::alloc::string::String>,
+    /// ID of the changed object.
+    #[prost(string, optional, tag = "3")]
+    pub object_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// The object before the transaction. Unset if the transaction created or unwrapped it.
+    #[prost(message, optional, tag = "4")]
+    pub input_object: ::core::option::Option<::sui_rpc::proto::sui::rpc::v2::Object>,
+    /// The object after the transaction. Unset if the transaction deleted or wrapped it.
+    #[prost(message, optional, tag = "5")]
+    pub output_object: ::core::option::Option<::sui_rpc::proto::sui::rpc::v2::Object>,
+}
+/// Generated client implementations.
+pub mod filtered_subscription_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// FilteredSubscriptionService streams the events, transactions and object changes from executed
+    /// checkpoints that match a filter, so that subscribers only receive the data they are interested
+    /// in.
+    ///
+    /// Every item in a stream carries a cursor. A subscriber that disconnects can resume by passing the
+    /// cursor of the last item it received as `after`: items from checkpoints executed in the meantime
+    /// are replayed from the node's store before the stream continues with newly executed checkpoints.
+    ///
+    /// Each subscription has a bounded buffer of checkpoints. A subscriber that falls too far behind is
+    /// disconnected with `UNAVAILABLE`, and should resume from the last cursor it received.
+    #[derive(Debug, Clone)]
+    pub struct FilteredSubscriptionServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl FilteredSubscriptionServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> FilteredSubscriptionServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> FilteredSubscriptionServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            FilteredSubscriptionServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Streams events matching a filter.
+        pub async fn subscribe_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SubscribeEventsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.FilteredSubscriptionService",
+                        "SubscribeEvents",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Streams executed transactions matching a filter.
+        pub async fn subscribe_transactions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SubscribeTransactionsResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeTransactions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.FilteredSubscriptionService",
+                        "SubscribeTransactions",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Streams changes to objects matching a filter.
+        pub async fn subscribe_object_changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeObjectChangesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SubscribeObjectChangesResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeObjectChanges",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.FilteredSubscriptionService",
+                        "SubscribeObjectChanges",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod filtered_subscription_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with FilteredSubscriptionServiceServer.
+    #[async_trait]
+    pub trait FilteredSubscriptionService: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the SubscribeEvents method.
+        type SubscribeEventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SubscribeEventsResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Streams events matching a filter.
+        async fn subscribe_events(
+            &self,
+            request: tonic::Request<super::SubscribeEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeEventsStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the SubscribeTransactions method.
+        type SubscribeTransactionsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SubscribeTransactionsResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Streams executed transactions matching a filter.
+        async fn subscribe_transactions(
+            &self,
+            request: tonic::Request<super::SubscribeTransactionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeTransactionsStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the SubscribeObjectChanges method.
+        type SubscribeObjectChangesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SubscribeObjectChangesResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        /// Streams changes to objects matching a filter.
+        async fn subscribe_object_changes(
+            &self,
+            request: tonic::Request<super::SubscribeObjectChangesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeObjectChangesStream>,
+            tonic::Status,
+        >;
+    }
+    /// FilteredSubscriptionService streams the events, transactions and object changes from executed
+    /// checkpoints that match a filter, so that subscribers only receive the data they are interested
+    /// in.
+    ///
+    /// Every item in a stream carries a cursor. A subscriber that disconnects can resume by passing the
+    /// cursor of the last item it received as `after`: items from checkpoints executed in the meantime
+    /// are replayed from the node's store before the stream continues with newly executed checkpoints.
+    ///
+    /// Each subscription has a bounded buffer of checkpoints. A subscriber that falls too far behind is
+    /// disconnected with `UNAVAILABLE`, and should resume from the last cursor it received.
+    #[derive(Debug)]
+    pub struct FilteredSubscriptionServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> FilteredSubscriptionServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for FilteredSubscriptionServiceServer<T>
+    where
+        T: FilteredSubscriptionService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeEventsSvc<T: FilteredSubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: FilteredSubscriptionService,
+                    > tonic::server::ServerStreamingService<super::SubscribeEventsRequest>
+                    for SubscribeEventsSvc<T> {
+                        type Response = super::SubscribeEventsResponse;
+                        type ResponseStream = T::SubscribeEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FilteredSubscriptionService>::subscribe_events(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeEventsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeTransactions" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeTransactionsSvc<T: FilteredSubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: FilteredSubscriptionService,
+                    > tonic::server::ServerStreamingService<super::SubscribeTransactionsRequest>
+                    for SubscribeTransactionsSvc<T> {
+                        type Response = super::SubscribeTransactionsResponse;
+                        type ResponseStream = T::SubscribeTransactionsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeTransactionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FilteredSubscriptionService>::subscribe_transactions(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeTransactionsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.rpc.alpha.FilteredSubscriptionService/SubscribeObjectChanges" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeObjectChangesSvc<T: FilteredSubscriptionService>(pub Arc<T>);
+                    impl<
+                        T: FilteredSubscriptionService,
+                    > tonic::server::ServerStreamingService<super::SubscribeObjectChangesRequest>
+                    for SubscribeObjectChangesSvc<T> {
+                        type Response = super::SubscribeObjectChangesResponse;
+                        type ResponseStream = T::SubscribeObjectChangesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeObjectChangesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FilteredSubscriptionService>::subscribe_object_changes(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeObjectChangesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for FilteredSubscriptionServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sui.rpc.alpha.FilteredSubscriptionService";
+    impl<T> tonic::server::NamedService for FilteredSubscriptionServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Request a gas price estimate for a transaction, or a set of consensus objects.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EstimateGasPriceRequest {
+    /// The transaction to estimate a gas price for. Only the consensus objects it accesses mutably
+    /// are considered. The transaction must be fully resolved.
+    #[prost(message, optional, tag = "1")]
+    pub transaction: ::core::option::Option<::sui_rpc::proto::sui::rpc::v2::Transaction>,
+    /// IDs of consensus objects to estimate a gas price for, in addition to those accessed by
+    /// `transaction`.
+    #[prost(string, repeated, tag = "2")]
+    pub object_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Response containing gas price estimates, and the congestion they are based on.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct EstimateGasPriceResponse {
+    /// The current epoch's reference gas price. Estimates are never lower than this.
+    #[prost(uint64, optional, tag = "1")]
+    pub reference_gas_price: ::core::option::Option<u64>,
+    /// Suggested gas prices, from lowest to highest confidence.
+    #[prost(message, repeated, tag = "2")]
+    pub estimates: ::prost::alloc::vec::Vec<GasPriceEstimate>,
+    /// Recent congestion on each of the objects considered.
+    #[prost(message, repeated, tag = "3")]
+    pub objects: ::prost::alloc::vec::Vec<ObjectCongestion>,
+}
+/// A gas price suggestion at a given confidence level.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GasPriceEstimate {
+    /// Percentage (1-100) of the recently congested checkpoints for every object considered, in
+    /// which a transaction with this gas price would have cleared congestion.
+    #[prost(uint32, optional, tag = "1")]
+    pub confidence: ::core::option::Option<u32>,
+    /// The suggested gas price.
+    #[prost(uint64, optional, tag = "2")]
+    pub gas_price: ::core::option::Option<u64>,
+}
+/// Congestion recently observed on a consensus object.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ObjectCongestion {
+    /// The object's ID.
+    #[prost(string, optional, tag = "1")]
+    pub object_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Checkpoints in which the object was recently congested, or cleared after being congested,
+    /// oldest first. Empty if the object has not been congested recently.
+    #[prost(message, repeated, tag = "2")]
+    pub history: ::prost::alloc::vec::Vec<CongestionSample>,
+}
+/// Congestion observed on a consensus object in a single checkpoint.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CongestionSample {
+    /// Timestamp of the checkpoint, in milliseconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "1")]
+    pub checkpoint_timestamp_ms: ::core::option::Option<u64>,
+    /// The highest gas price of a transaction that was cancelled due to congestion on the object, if
+    /// any were.
+    #[prost(uint64, optional, tag = "2")]
+    pub highest_cancelled_gas_price: ::core::option::Option<u64>,
+    /// The lowest gas price of a transaction that wrote to the object, if any did.
+    #[prost(uint64, optional, tag = "3")]
+    pub lowest_executed_gas_price: ::core::option::Option<u64>,
+}
+/// Generated client implementations.
+pub mod gas_price_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// GasPriceService suggests gas prices for transactions that write to congested consensus objects.
+    #[derive(Debug, Clone)]
+    pub struct GasPriceServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl GasPriceServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> GasPriceServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> GasPriceServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            GasPriceServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Returns suggested gas prices at several confidence levels, based on the congestion recently
+        /// observed on the consensus objects that a transaction writes to.
+        pub async fn estimate_gas_price(
+            &mut self,
+            request: impl tonic::IntoRequest<super::EstimateGasPriceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EstimateGasPriceResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.GasPriceService/EstimateGasPrice",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.GasPriceService",
+                        "EstimateGasPrice",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod gas_price_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with GasPriceServiceServer.
+    #[async_trait]
+    pub trait GasPriceService: std::marker::Send + std::marker::Sync + 'static {
+        /// Returns suggested gas prices at several confidence levels, based on the congestion recently
+        /// observed on the consensus objects that a transaction writes to.
+        async fn estimate_gas_price(
+            &self,
+            request: tonic::Request<super::EstimateGasPriceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::EstimateGasPriceResponse>,
+            tonic::Status,
+        >;
+    }
+    /// GasPriceService suggests gas prices for transactions that write to congested consensus objects.
+    #[derive(Debug)]
+    pub struct GasPriceServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> GasPriceServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for GasPriceServiceServer<T>
+    where
+        T: GasPriceService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sui.rpc.alpha.GasPriceService/EstimateGasPrice" => {
+                    #[allow(non_camel_case_types)]
+                    struct EstimateGasPriceSvc<T: GasPriceService>(pub Arc<T>);
+                    impl<
+                        T: GasPriceService,
+                    > tonic::server::UnaryService<super::EstimateGasPriceRequest>
+                    for EstimateGasPriceSvc<T> {
+                        type Response = super::EstimateGasPriceResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::EstimateGasPriceRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as GasPriceService>::estimate_gas_price(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = EstimateGasPriceSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for GasPriceServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sui.rpc.alpha.GasPriceService";
+    impl<T> tonic::server::NamedService for GasPriceServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
 /// Object Checkpoint State inclusion proof.
 #[non_exhaustive]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
-pub struct OcsInclusionProof {
-    /// BCS-encoded merkle proof nodes.
-    #[prost(bytes = "vec", optional, tag = "1")]
-    pub merkle_proof: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
-    /// Leaf index in the merkle tree.
-    #[prost(uint64, optional, tag = "2")]
-    pub leaf_index: ::core::option::Option<u64>,
-    /// Tree root digest (32 bytes).
-    #[prost(bytes = "vec", optional, tag = "3")]
-    pub tree_root: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+pub struct OcsInclusionProof {
+    /// BCS-encoded merkle proof nodes.
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub merkle_proof: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Leaf index in the merkle tree.
+    #[prost(uint64, optional, tag = "2")]
+    pub leaf_index: ::core::option::Option<u64>,
+    /// Tree root digest (32 bytes).
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub tree_root: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// Request for object inclusion proof at a checkpoint.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetObjectInclusionProofRequest {
+    /// Required. Object ID to get inclusion proof for.
+    #[prost(string, optional, tag = "1")]
+    pub object_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// Required. Checkpoint sequence number.
+    #[prost(uint64, optional, tag = "2")]
+    pub checkpoint: ::core::option::Option<u64>,
+}
+/// Response containing object inclusion proof and object data.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetObjectInclusionProofResponse {
+    /// Object reference being proven (object_id, version, digest).
+    #[prost(message, optional, tag = "1")]
+    pub object_ref: ::core::option::Option<
+        ::sui_rpc::proto::sui::rpc::v2::ObjectReference,
+    >,
+    /// Object inclusion proof.
+    #[prost(message, optional, tag = "2")]
+    pub inclusion_proof: ::core::option::Option<OcsInclusionProof>,
+    /// BCS-encoded object data.
+    #[prost(bytes = "vec", optional, tag = "3")]
+    pub object_data: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// BCS-encoded checkpoint summary needed to verify the proof.
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub checkpoint_summary: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+}
+/// Generated client implementations.
+pub mod proof_service_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// ProofService provides cryptographic proofs for blockchain objects.
+    #[derive(Debug, Clone)]
+    pub struct ProofServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ProofServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ProofServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ProofServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            ProofServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Returns an inclusion proof if a specified object id was written in a specified checkpoint.
+        pub async fn get_object_inclusion_proof(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetObjectInclusionProofRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetObjectInclusionProofResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.ProofService/GetObjectInclusionProof",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.ProofService",
+                        "GetObjectInclusionProof",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod proof_service_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ProofServiceServer.
+    #[async_trait]
+    pub trait ProofService: std::marker::Send + std::marker::Sync + 'static {
+        /// Returns an inclusion proof if a specified object id was written in a specified checkpoint.
+        async fn get_object_inclusion_proof(
+            &self,
+            request: tonic::Request<super::GetObjectInclusionProofRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetObjectInclusionProofResponse>,
+            tonic::Status,
+        >;
+    }
+    /// ProofService provides cryptographic proofs for blockchain objects.
+    #[derive(Debug)]
+    pub struct ProofServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> ProofServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ProofServiceServer<T>
+    where
+        T: ProofService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sui.rpc.alpha.ProofService/GetObjectInclusionProof" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetObjectInclusionProofSvc<T: ProofService>(pub Arc<T>);
+                    impl<
+                        T: ProofService,
+                    > tonic::server::UnaryService<super::GetObjectInclusionProofRequest>
+                    for GetObjectInclusionProofSvc<T> {
+                        type Response = super::GetObjectInclusionProofResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetObjectInclusionProofRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ProofService>::get_object_inclusion_proof(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetObjectInclusionProofSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for ProofServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sui.rpc.alpha.ProofService";
+    impl<T> tonic::server::NamedService for ProofServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Request the status of a transaction.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GetTransactionStatusRequest {
+    /// Required. The digest of the transaction.
+    #[prost(string, optional, tag = "1")]
+    pub digest: ::core::option::Option<::prost::alloc::string::String>,
 }
-/// Request for object inclusion proof at a checkpoint.
+/// The status of a transaction.
 #[non_exhaustive]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
-pub struct GetObjectInclusionProofRequest {
-    /// Required. Object ID to get inclusion proof for.
+pub struct GetTransactionStatusResponse {
+    /// The digest of the transaction.
     #[prost(string, optional, tag = "1")]
-    pub object_id: ::core::option::Option<::prost::alloc::string::String>,
-    /// Required. Checkpoint sequence number.
-    #[prost(uint64, optional, tag = "2")]
+    pub digest: ::core::option::Option<::prost::alloc::string::String>,
+    /// The transaction's status.
+    #[prost(enumeration = "TransactionStatus", optional, tag = "2")]
+    pub status: ::core::option::Option<i32>,
+    /// Why the transaction was deferred or rejected. Only set for `DEFERRED` and `REJECTED`.
+    #[prost(string, optional, tag = "3")]
+    pub reason: ::core::option::Option<::prost::alloc::string::String>,
+    /// The checkpoint that includes the transaction. Only set for `EXECUTED`, once the transaction has
+    /// been included in a checkpoint.
+    #[prost(uint64, optional, tag = "4")]
     pub checkpoint: ::core::option::Option<u64>,
+    /// The submission history of the transaction. Only available for transactions submitted through
+    /// this node, for a limited time after submission.
+    #[prost(message, optional, tag = "5")]
+    pub lifecycle: ::core::option::Option<TransactionLifecycle>,
 }
-/// Response containing object inclusion proof and object data.
+/// The result of executing a transaction, with its lifecycle.
 #[non_exhaustive]
-#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
-pub struct GetObjectInclusionProofResponse {
-    /// Object reference being proven (object_id, version, digest).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecuteTransactionResponse {
+    /// The response from `sui.rpc.v2.TransactionExecutionService/ExecuteTransaction`.
     #[prost(message, optional, tag = "1")]
-    pub object_ref: ::core::option::Option<
-        ::sui_rpc::proto::sui::rpc::v2::ObjectReference,
+    pub response: ::core::option::Option<
+        ::sui_rpc::proto::sui::rpc::v2::ExecuteTransactionResponse,
     >,
-    /// Object inclusion proof.
+    /// The submission history of the transaction.
     #[prost(message, optional, tag = "2")]
-    pub inclusion_proof: ::core::option::Option<OcsInclusionProof>,
-    /// BCS-encoded object data.
-    #[prost(bytes = "vec", optional, tag = "3")]
-    pub object_data: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
-    /// BCS-encoded checkpoint summary needed to verify the proof.
-    #[prost(bytes = "vec", optional, tag = "4")]
-    pub checkpoint_summary: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    pub lifecycle: ::core::option::Option<TransactionLifecycle>,
+}
+/// The history of a transaction's submission to validators.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TransactionLifecycle {
+    /// Number of attempts made to submit the transaction.
+    #[prost(uint32, optional, tag = "1")]
+    pub attempts: ::core::option::Option<u32>,
+    /// What happened to the transaction, oldest first. Only the most recent events are kept.
+    #[prost(message, repeated, tag = "2")]
+    pub events: ::prost::alloc::vec::Vec<TransactionLifecycleEvent>,
+}
+/// Something that happened while submitting a transaction.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TransactionLifecycleEvent {
+    /// When the event happened, in milliseconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "1")]
+    pub timestamp_ms: ::core::option::Option<u64>,
+    /// What happened.
+    #[prost(enumeration = "TransactionLifecycleEventKind", optional, tag = "2")]
+    pub kind: ::core::option::Option<i32>,
+    /// The submission attempt that started. Only set for `ATTEMPT_STARTED`.
+    #[prost(uint32, optional, tag = "3")]
+    pub attempt: ::core::option::Option<u32>,
+    /// The validator involved. Set for `SUBMITTED`, `REJECTED_BY_VALIDATOR` and
+    /// `EFFECTS_ACKNOWLEDGED`.
+    #[prost(string, optional, tag = "4")]
+    pub validator: ::core::option::Option<::prost::alloc::string::String>,
+    /// Why the transaction was rejected. Set for `REJECTED_BY_VALIDATOR` and `ATTEMPT_FAILED`.
+    #[prost(string, optional, tag = "5")]
+    pub reason: ::core::option::Option<::prost::alloc::string::String>,
+    /// Whether the rejection can be retried. Set for `REJECTED_BY_VALIDATOR` and `ATTEMPT_FAILED`.
+    #[prost(bool, optional, tag = "6")]
+    pub retriable: ::core::option::Option<bool>,
+}
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransactionStatus {
+    /// The transaction is not known to this node. It may never have been submitted through this
+    /// node, or its lifecycle may no longer be tracked.
+    Unknown = 0,
+    /// The transaction is being submitted to validators, or retried after a transient failure.
+    Pending = 1,
+    /// The latest submission attempt failed because validators were overloaded, for example by
+    /// congestion on the transaction's objects, and the transaction will be retried.
+    Deferred = 2,
+    /// The transaction was rejected and will not be retried.
+    Rejected = 3,
+    /// The transaction has been executed.
+    Executed = 4,
+}
+impl TransactionStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "TRANSACTION_STATUS_UNKNOWN",
+            Self::Pending => "PENDING",
+            Self::Deferred => "DEFERRED",
+            Self::Rejected => "REJECTED",
+            Self::Executed => "EXECUTED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRANSACTION_STATUS_UNKNOWN" => Some(Self::Unknown),
+            "PENDING" => Some(Self::Pending),
+            "DEFERRED" => Some(Self::Deferred),
+            "REJECTED" => Some(Self::Rejected),
+            "EXECUTED" => Some(Self::Executed),
+            _ => None,
+        }
+    }
+}
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransactionLifecycleEventKind {
+    Unknown = 0,
+    /// A submission attempt started.
+    AttemptStarted = 1,
+    /// A validator accepted the transaction for processing.
+    Submitted = 2,
+    /// A validator rejected the transaction.
+    RejectedByValidator = 3,
+    /// A validator acknowledged the transaction's effects.
+    EffectsAcknowledged = 4,
+    /// A submission attempt failed.
+    AttemptFailed = 5,
+    /// A quorum of validators certified the transaction's effects.
+    EffectsCertified = 6,
+}
+impl TransactionLifecycleEventKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "TRANSACTION_LIFECYCLE_EVENT_KIND_UNKNOWN",
+            Self::AttemptStarted => "ATTEMPT_STARTED",
+            Self::Submitted => "SUBMITTED",
+            Self::RejectedByValidator => "REJECTED_BY_VALIDATOR",
+            Self::EffectsAcknowledged => "EFFECTS_ACKNOWLEDGED",
+            Self::AttemptFailed => "ATTEMPT_FAILED",
+            Self::EffectsCertified => "EFFECTS_CERTIFIED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRANSACTION_LIFECYCLE_EVENT_KIND_UNKNOWN" => Some(Self::Unknown),
+            "ATTEMPT_STARTED" => Some(Self::AttemptStarted),
+            "SUBMITTED" => Some(Self::Submitted),
+            "REJECTED_BY_VALIDATOR" => Some(Self::RejectedByValidator),
+            "EFFECTS_ACKNOWLEDGED" => Some(Self::EffectsAcknowledged),
+            "ATTEMPT_FAILED" => Some(Self::AttemptFailed),
+            "EFFECTS_CERTIFIED" => Some(Self::EffectsCertified),
+            _ => None,
+        }
+    }
 }
 /// Generated client implementations.
-pub mod proof_service_client {
+pub mod transaction_status_service_client {
     #![allow(
         unused_variables,
         dead_code,
@@ -421,12 +1944,13 @@ pub mod proof_service_client {
     )]
     use tonic::codegen::*;
     use tonic::codegen::http::Uri;
-    /// ProofService provides cryptographic proofs for blockchain objects.
+    /// TransactionStatusService reports the progress of transactions submitted for execution through
+    /// this node, including why a transaction has not been executed yet.
     #[derive(Debug, Clone)]
-    pub struct ProofServiceClient<T> {
+    pub struct TransactionStatusServiceClient<T> {
         inner: tonic::client::Grpc<T>,
     }
-    impl ProofServiceClient<tonic::transport::Channel> {
+    impl TransactionStatusServiceClient<tonic::transport::Channel> {
         /// Attempt to create a new client by connecting to a given endpoint.
         pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
         where
@@ -437,7 +1961,7 @@ pub mod proof_service_client {
             Ok(Self::new(conn))
         }
     }
-    impl<T> ProofServiceClient<T>
+    impl<T> TransactionStatusServiceClient<T>
     where
         T: tonic::client::GrpcService<tonic::body::Body>,
         T::Error: Into<StdError>,
@@ -455,7 +1979,7 @@ pub mod proof_service_client {
         pub fn with_interceptor<F>(
             inner: T,
             interceptor: F,
-        ) -> ProofServiceClient<InterceptedService<T, F>>
+        ) -> TransactionStatusServiceClient<InterceptedService<T, F>>
         where
             F: tonic::service::Interceptor,
             T::ResponseBody: Default,
@@ -469,7 +1993,7 @@ pub mod proof_service_client {
                 http::Request<tonic::body::Body>,
             >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
         {
-            ProofServiceClient::new(InterceptedService::new(inner, interceptor))
+            TransactionStatusServiceClient::new(InterceptedService::new(inner, interceptor))
         }
         /// Compress requests with the given encoding.
         ///
@@ -502,12 +2026,13 @@ pub mod proof_service_client {
             self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
-        /// Returns an inclusion proof if a specified object id was written in a specified checkpoint.
-        pub async fn get_object_inclusion_proof(
+        /// Returns the status of a transaction: whether it is still pending, has been deferred or rejected
+        /// (and why), or has been executed.
+        pub async fn get_transaction_status(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetObjectInclusionProofRequest>,
+            request: impl tonic::IntoRequest<super::GetTransactionStatusRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetObjectInclusionProofResponse>,
+            tonic::Response<super::GetTransactionStatusResponse>,
             tonic::Status,
         > {
             self.inner
@@ -520,14 +2045,48 @@ pub mod proof_service_client {
                 })?;
             let codec = tonic_prost::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/sui.rpc.alpha.ProofService/GetObjectInclusionProof",
+                "/sui.rpc.alpha.TransactionStatusService/GetTransactionStatus",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
-                        "sui.rpc.alpha.ProofService",
-                        "GetObjectInclusionProof",
+                        "sui.rpc.alpha.TransactionStatusService",
+                        "GetTransactionStatus",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Executes a transaction like `sui.rpc.v2.TransactionExecutionService/ExecuteTransaction`, and
+        /// also returns how it made its way through the validators. If execution fails, only the error is
+        /// returned, and the lifecycle can be fetched with `GetTransactionStatus`.
+        pub async fn execute_transaction(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                ::sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::ExecuteTransactionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sui.rpc.alpha.TransactionStatusService/ExecuteTransaction",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "sui.rpc.alpha.TransactionStatusService",
+                        "ExecuteTransaction",
                     ),
                 );
             self.inner.unary(req, path, codec).await
@@ -535,7 +2094,7 @@ pub mod proof_service_client {
     }
 }
 /// Generated server implementations.
-pub mod proof_service_server {
+pub mod transaction_status_service_server {
     #![allow(
         unused_variables,
         dead_code,
@@ -544,28 +2103,42 @@ pub mod proof_service_server {
         clippy::let_unit_value,
     )]
     use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with ProofServiceServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with TransactionStatusServiceServer.
     #[async_trait]
-    pub trait ProofService: std::marker::Send + std::marker::Sync + 'static {
-        /// Returns an inclusion proof if a specified object id was written in a specified checkpoint.
-        async fn get_object_inclusion_proof(
+    pub trait TransactionStatusService: std::marker::Send + std::marker::Sync + 'static {
+        /// Returns the status of a transaction: whether it is still pending, has been deferred or rejected
+        /// (and why), or has been executed.
+        async fn get_transaction_status(
             &self,
-            request: tonic::Request<super::GetObjectInclusionProofRequest>,
+            request: tonic::Request<super::GetTransactionStatusRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetObjectInclusionProofResponse>,
+            tonic::Response<super::GetTransactionStatusResponse>,
+            tonic::Status,
+        >;
+        /// Executes a transaction like `sui.rpc.v2.TransactionExecutionService/ExecuteTransaction`, and
+        /// also returns how it made its way through the validators. If execution fails, only the error is
+        /// returned, and the lifecycle can be fetched with `GetTransactionStatus`.
+        async fn execute_transaction(
+            &self,
+            request: tonic::Request<
+                ::sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::ExecuteTransactionResponse>,
             tonic::Status,
         >;
     }
-    /// ProofService provides cryptographic proofs for blockchain objects.
+    /// TransactionStatusService reports the progress of transactions submitted for execution through
+    /// this node, including why a transaction has not been executed yet.
     #[derive(Debug)]
-    pub struct ProofServiceServer<T> {
+    pub struct TransactionStatusServiceServer<T> {
         inner: Arc<T>,
         accept_compression_encodings: EnabledCompressionEncodings,
         send_compression_encodings: EnabledCompressionEncodings,
         max_decoding_message_size: Option<usize>,
         max_encoding_message_size: Option<usize>,
     }
-    impl<T> ProofServiceServer<T> {
+    impl<T> TransactionStatusServiceServer<T> {
         pub fn new(inner: T) -> Self {
             Self::from_arc(Arc::new(inner))
         }
@@ -616,9 +2189,9 @@ pub mod proof_service_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for ProofServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for TransactionStatusServiceServer<T>
     where
-        T: ProofService,
+        T: TransactionStatusService,
         B: Body + std::marker::Send + 'static,
         B::Error: Into<StdError> + std::marker::Send + 'static,
     {
@@ -633,14 +2206,14 @@ pub mod proof_service_server {
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             match req.uri().path() {
-                "/sui.rpc.alpha.ProofService/GetObjectInclusionProof" => {
+                "/sui.rpc.alpha.TransactionStatusService/GetTransactionStatus" => {
                     #[allow(non_camel_case_types)]
-                    struct GetObjectInclusionProofSvc<T: ProofService>(pub Arc<T>);
+                    struct GetTransactionStatusSvc<T: TransactionStatusService>(pub Arc<T>);
                     impl<
-                        T: ProofService,
-                    > tonic::server::UnaryService<super::GetObjectInclusionProofRequest>
-                    for GetObjectInclusionProofSvc<T> {
-                        type Response = super::GetObjectInclusionProofResponse;
+                        T: TransactionStatusService,
+                    > tonic::server::UnaryService<super::GetTransactionStatusRequest>
+                    for GetTransactionStatusSvc<T> {
+                        type Response = super::GetTransactionStatusResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
@@ -648,12 +2221,12 @@ pub mod proof_service_server {
                         fn call(
                             &mut self,
                             request: tonic::Request<
-                                super::GetObjectInclusionProofRequest,
+                                super::GetTransactionStatusRequest,
                             >,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as ProofService>::get_object_inclusion_proof(
+                                <T as TransactionStatusService>::get_transaction_status(
                                         &inner,
                                         request,
                                     )
@@ -668,7 +2241,59 @@ pub mod proof_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetObjectInclusionProofSvc(inner);
+                        let method = GetTransactionStatusSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sui.rpc.alpha.TransactionStatusService/ExecuteTransaction" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExecuteTransactionSvc<T: TransactionStatusService>(pub Arc<T>);
+                    impl<
+                        T: TransactionStatusService,
+                    > tonic::server::UnaryService<
+                        ::sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest,
+                    > for ExecuteTransactionSvc<T> {
+                        type Response = super::ExecuteTransactionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                ::sui_rpc::proto::sui::rpc::v2::ExecuteTransactionRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransactionStatusService>::execute_transaction(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ExecuteTransactionSvc(inner);
                         let codec = tonic_prost::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -706,7 +2331,7 @@ pub mod proof_service_server {
             }
         }
     }
-    impl<T> Clone for ProofServiceServer<T> {
+    impl<T> Clone for TransactionStatusServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -719,8 +2344,8 @@ pub mod proof_service_server {
         }
     }
     /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "sui.rpc.alpha.ProofService";
-    impl<T> tonic::server::NamedService for ProofServiceServer<T> {
+    pub const SERVICE_NAME: &str = "sui.rpc.alpha.TransactionStatusService";
+    impl<T> tonic::server::NamedService for TransactionStatusServiceServer<T> {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
@@ -53,7 +53,7 @@ impl BenchmarkContext {
         let (_, admin_account) = user_accounts.pop_last().unwrap();
 
         info!("Initializing validator");
-        let validator = SingleValidator::new(&genesis_gas_objects, benchmark_component).await;
+        let validator = SingleValidator::new(&genesis_gas_objects, benchmark_component, None).await;
 
         Self {
             validator,
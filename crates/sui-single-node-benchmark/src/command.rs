@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 use strum_macros::EnumIter;
 
@@ -135,6 +135,45 @@ pub enum WorkloadKind {
         )]
         manifest_file: PathBuf,
     },
+    /// Re-execute the transactions of a range of recorded checkpoints, e.g. from mainnet.
+    /// Transactions are always executed in memory, as with the `execution-only` component.
+    Replay(ReplayOptions),
+}
+
+#[derive(Args, Clone)]
+pub struct ReplayOptions {
+    #[arg(
+        long,
+        required_unless_present = "remote_store_url",
+        help = "Local directory of checkpoint files, e.g. the data ingestion directory of a fullnode"
+    )]
+    pub checkpoint_dir: Option<PathBuf>,
+    #[arg(
+        long,
+        conflicts_with = "checkpoint_dir",
+        help = "URL of an object store of checkpoint files, e.g. https://checkpoints.mainnet.sui.io"
+    )]
+    pub remote_store_url: Option<String>,
+    #[arg(long, help = "First checkpoint to replay")]
+    pub start_checkpoint: u64,
+    #[arg(long, help = "Last checkpoint to replay, inclusive")]
+    pub end_checkpoint: u64,
+    #[arg(
+        long,
+        help = "gRPC URL of a fullnode, to fetch the packages which are not part of the checkpoints"
+    )]
+    pub rpc_url: Option<String>,
+    #[arg(
+        long,
+        help = "Protocol version to execute with. Defaults to the latest version. \
+            Effects only match the recorded ones at the protocol version of the checkpoints."
+    )]
+    pub protocol_version: Option<u64>,
+    #[arg(
+        long,
+        help = "Reference gas price to execute with. Defaults to the lowest gas price of the replayed transactions"
+    )]
+    pub reference_gas_price: Option<u64>,
 }
 
 impl WorkloadKind {
@@ -143,6 +182,8 @@ impl WorkloadKind {
             // Each transaction will always have 1 gas object, plus the number of owned objects that will be transferred.
             WorkloadKind::PTB { num_transfers, .. } => *num_transfers + 1,
             WorkloadKind::Publish { .. } => 1,
+            // Replayed transactions use the gas objects of the checkpoints.
+            WorkloadKind::Replay(_) => 0,
         }
     }
 }
@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::benchmark_context::BenchmarkContext;
-use crate::command::Component;
+use crate::command::{Component, WorkloadKind};
 use crate::workload::Workload;
+use tracing::warn;
 
 pub(crate) mod benchmark_context;
 pub mod command;
 pub(crate) mod mock_account;
 pub(crate) mod mock_storage;
+pub(crate) mod replay;
 pub(crate) mod single_node;
 pub(crate) mod tx_generator;
 pub mod workload;
@@ -23,6 +25,16 @@ pub async fn run_benchmark(
     checkpoint_size: usize,
     print_sample_tx: bool,
 ) {
+    if let WorkloadKind::Replay(options) = workload.workload_kind {
+        if !matches!(component, Component::ExecutionOnly) {
+            warn!("Replay always executes transactions in memory, ignoring the component");
+        }
+        replay::run_replay(options)
+            .await
+            .expect("Failed to replay checkpoints");
+        return;
+    }
+
     let mut ctx = BenchmarkContext::new(workload.clone(), component, print_sample_tx).await;
     let tx_generator = workload.create_tx_generator(&mut ctx).await;
     let transactions = ctx.generate_transactions(tx_generator).await;
@@ -35,6 +35,14 @@ impl InMemoryObjectStore {
         }
     }
 
+    /// Adds `objects` to the store, replacing any other version of the same objects.
+    pub(crate) fn insert_objects(&self, objects: impl IntoIterator<Item = Object>) {
+        let mut store = self.objects.write().unwrap();
+        for object in objects {
+            store.insert(object.id(), object);
+        }
+    }
+
     pub(crate) fn get_num_object_reads(&self) -> u64 {
         self.num_object_reads.get()
     }
@@ -0,0 +1,733 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay of a range of recorded checkpoints, e.g. from mainnet. The objects read by the
+//! transactions of the range are seeded into the in-memory object store at the versions they had
+//! before the range, and the transactions are then re-executed at max throughput: each transaction
+//! runs as soon as the earlier transactions touching the same objects have run.
+
+use crate::command::{Component, ReplayOptions};
+use crate::mock_storage::InMemoryObjectStore;
+use crate::single_node::SingleValidator;
+use anyhow::{Context, bail, ensure};
+use futures::future::{BoxFuture, FutureExt, Shared, join_all};
+use futures::{StreamExt, TryStreamExt};
+use mysten_common::ZipDebugEqIteratorExt;
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt};
+use prost::Message;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_core::authority::shared_object_version_manager::AssignedVersions;
+use sui_data_ingestion_core::create_remote_store_client;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_rpc::proto::sui::rpc::v2::Checkpoint as ProtoCheckpoint;
+use sui_types::base_types::{EpochId, ObjectID};
+use sui_types::effects::{InputConsensusObject, TransactionEffects, TransactionEffectsAPI};
+use sui_types::full_checkpoint_content::Checkpoint;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::storage::ObjectKey;
+use sui_types::transaction::{
+    DEFAULT_VALIDATOR_GAS_PRICE, InputObjectKind, Transaction, TransactionDataAPI,
+};
+use tracing::{info, warn};
+
+/// Number of checkpoint files fetched concurrently.
+const FETCH_CONCURRENCY: usize = 16;
+/// Number of rows of the per-package and per-object reports.
+const REPORT_ROWS: usize = 20;
+/// Number of failed transactions whose error is logged.
+const LOGGED_FAILURES: usize = 10;
+
+/// A recorded transaction, with what is needed to re-execute it.
+struct ReplayTransaction {
+    transaction: Transaction,
+    expected_effects: TransactionEffects,
+    /// Versions of the shared objects recorded in the effects, None if some are missing.
+    assigned_versions: Option<AssignedVersions>,
+    epoch: EpochId,
+    epoch_start_timestamp_ms: u64,
+    /// Packages called by the transaction.
+    packages: BTreeSet<ObjectID>,
+    /// Earlier transactions which must run before this one.
+    dependencies: Vec<usize>,
+}
+
+#[derive(Default)]
+struct ObjectAccesses {
+    last_write: Option<usize>,
+    reads_since_last_write: Vec<usize>,
+    num_reads: u64,
+    num_writes: u64,
+}
+
+struct ReplayPlan {
+    transactions: Vec<ReplayTransaction>,
+    /// Objects to seed the store with, at their version before the range.
+    seed_objects: HashMap<ObjectID, Object>,
+    /// Objects written by the transactions of the range.
+    written: HashSet<ObjectID>,
+    accesses: HashMap<ObjectID, ObjectAccesses>,
+}
+
+#[derive(Clone)]
+enum ReplayResult {
+    /// The effects match the recorded ones.
+    Matched,
+    Mismatched,
+    Failed(String),
+}
+
+#[derive(Clone)]
+struct Outcome {
+    execution_time: Duration,
+    computation_cost: u64,
+    result: ReplayResult,
+}
+
+#[derive(Default)]
+struct ResultCounts {
+    matched: usize,
+    mismatched: usize,
+    failed: usize,
+}
+
+#[derive(Default)]
+struct PackageCost {
+    num_transactions: u64,
+    execution_time: Duration,
+    computation_cost: u64,
+}
+
+pub(crate) struct ReplayReport {
+    num_transactions: usize,
+    results: ResultCounts,
+    /// Execution time and computation cost of the transactions calling each package, from the
+    /// most expensive package. Transactions calling several packages are counted for each of them.
+    package_costs: Vec<(ObjectID, PackageCost)>,
+}
+
+impl ReplayReport {
+    fn log(&self) {
+        info!(
+            "Replayed {} transactions. Effects matching the recorded ones: {}, mismatching: {}, failed to execute: {}",
+            self.num_transactions,
+            self.results.matched,
+            self.results.mismatched,
+            self.results.failed
+        );
+        info!(
+            "Execution cost per package (package, txs, total ms, avg us per tx, computation cost):"
+        );
+        for (package, cost) in self.package_costs.iter().take(REPORT_ROWS) {
+            info!(
+                "  {} {} {} {} {}",
+                package,
+                cost.num_transactions,
+                cost.execution_time.as_millis(),
+                cost.execution_time.as_micros() / cost.num_transactions as u128,
+                cost.computation_cost
+            );
+        }
+    }
+}
+
+pub(crate) async fn run_replay(options: ReplayOptions) -> anyhow::Result<ReplayReport> {
+    ensure!(
+        options.start_checkpoint <= options.end_checkpoint,
+        "Empty checkpoint range {}..={}",
+        options.start_checkpoint,
+        options.end_checkpoint
+    );
+    let checkpoints = fetch_checkpoints(&options).await?;
+    let mut plan = build_plan(&checkpoints);
+    drop(checkpoints);
+    if let Some(rpc_url) = &options.rpc_url {
+        fetch_missing_packages(rpc_url, &mut plan).await?;
+    }
+
+    // Recorded transactions pay at least the reference gas price of their epoch.
+    let reference_gas_price = options.reference_gas_price.unwrap_or_else(|| {
+        plan.transactions
+            .iter()
+            .map(|tx| tx.transaction.data().transaction_data())
+            .filter(|data| !data.is_system_tx())
+            .map(|data| data.gas_price())
+            .min()
+            .unwrap_or(DEFAULT_VALIDATOR_GAS_PRICE)
+    });
+    let protocol_config = options.protocol_version.map(|version| {
+        ProtocolConfig::get_for_version(ProtocolVersion::new(version), Chain::Mainnet)
+    });
+    info!("Initializing validator");
+    let validator = SingleValidator::new(&[], Component::ExecutionOnly, protocol_config).await;
+    let store = validator.create_in_memory_store();
+    info!("Seeding {} objects", plan.seed_objects.len());
+    store.insert_objects(std::mem::take(&mut plan.seed_objects).into_values());
+
+    let transactions = Arc::new(plan.transactions);
+    let tx_count = transactions.len();
+    let start_time = Instant::now();
+    info!(
+        "Started replaying {} transactions. You can now attach a profiler",
+        tx_count
+    );
+    let outcomes = execute(validator, store, transactions.clone(), reference_gas_price).await;
+    let elapsed = start_time.elapsed().as_millis() as f64 / 1000f64;
+    info!(
+        "Replay of checkpoints {}..={} finished in {}s, TPS={}",
+        options.start_checkpoint,
+        options.end_checkpoint,
+        elapsed,
+        tx_count as f64 / elapsed
+    );
+
+    let report = ReplayReport {
+        num_transactions: tx_count,
+        results: count_results(&transactions, &outcomes),
+        package_costs: package_costs(&transactions, &outcomes),
+    };
+    report.log();
+    report_contention(&transactions, &plan.accesses);
+    Ok(report)
+}
+
+async fn fetch_checkpoints(options: &ReplayOptions) -> anyhow::Result<Vec<Checkpoint>> {
+    let url = match (&options.checkpoint_dir, &options.remote_store_url) {
+        (Some(dir), _) => {
+            let dir = std::fs::canonicalize(dir)
+                .with_context(|| format!("Invalid checkpoint directory {}", dir.display()))?;
+            format!("file://{}", dir.display())
+        }
+        (None, Some(url)) => url.clone(),
+        (None, None) => bail!("Either a checkpoint directory or a remote store is required"),
+    };
+    let store = create_remote_store_client(url, vec![], 60)?;
+    info!(
+        "Fetching checkpoints {}..={}",
+        options.start_checkpoint, options.end_checkpoint
+    );
+    futures::stream::iter(options.start_checkpoint..=options.end_checkpoint)
+        .map(|sequence_number| fetch_checkpoint(store.as_ref(), sequence_number))
+        .buffered(FETCH_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+async fn fetch_checkpoint(
+    store: &dyn ObjectStore,
+    sequence_number: CheckpointSequenceNumber,
+) -> anyhow::Result<Checkpoint> {
+    let path = Path::from(format!("{}.binpb.zst", sequence_number));
+    let bytes = store
+        .get(&path)
+        .await
+        .with_context(|| format!("Failed to fetch checkpoint {sequence_number}"))?
+        .bytes()
+        .await?;
+    let decompressed = zstd::decode_all(&bytes[..])?;
+    let proto_checkpoint = ProtoCheckpoint::decode(&decompressed[..])?;
+    Ok((&proto_checkpoint).try_into()?)
+}
+
+/// Collects the transactions of the checkpoints in execution order, the objects they need, and
+/// the dependencies between them.
+fn build_plan(checkpoints: &[Checkpoint]) -> ReplayPlan {
+    let mut plan = ReplayPlan {
+        transactions: vec![],
+        seed_objects: HashMap::new(),
+        written: HashSet::new(),
+        accesses: HashMap::new(),
+    };
+    // The epoch start timestamp is only known for epochs starting in the range, other epochs use
+    // the timestamp of their first checkpoint in the range.
+    let mut epoch_start_timestamps = BTreeMap::new();
+    for checkpoint in checkpoints {
+        let summary = checkpoint.summary.data();
+        let epoch_start_timestamp_ms = *epoch_start_timestamps
+            .entry(summary.epoch)
+            .or_insert(summary.timestamp_ms);
+        if summary.end_of_epoch_data.is_some() {
+            epoch_start_timestamps.insert(summary.epoch + 1, summary.timestamp_ms);
+        }
+
+        for tx in &checkpoint.transactions {
+            let effects = &tx.effects;
+            let mut inputs: Vec<ObjectKey> = effects
+                .object_changes()
+                .into_iter()
+                .filter_map(|change| change.input_version.map(|v| ObjectKey(change.id, v)))
+                .collect();
+            let mut reads = BTreeSet::new();
+            let mut consensus_versions = HashMap::new();
+            for object in effects.input_consensus_objects() {
+                let (id, version) = object.id_and_version();
+                consensus_versions.insert(id, version);
+                match object {
+                    InputConsensusObject::Cancelled(..) => {
+                        reads.insert(id);
+                    }
+                    InputConsensusObject::ReadOnly(_)
+                    | InputConsensusObject::ReadConsensusStreamEnded(..) => {
+                        reads.insert(id);
+                        inputs.push(ObjectKey(id, version));
+                    }
+                    _ => inputs.push(ObjectKey(id, version)),
+                }
+            }
+            for key in &tx.unchanged_loaded_runtime_objects {
+                reads.insert(key.0);
+                inputs.push(*key);
+            }
+            let mut packages = BTreeSet::new();
+            for kind in tx.transaction.input_objects().unwrap_or_default() {
+                match kind {
+                    InputObjectKind::ImmOrOwnedMoveObject(object) => {
+                        inputs.push(ObjectKey(object.0, object.1))
+                    }
+                    InputObjectKind::MovePackage(id) => {
+                        packages.insert(id);
+                    }
+                    InputObjectKind::SharedMoveObject { .. } => {}
+                }
+            }
+            for key in inputs {
+                if !plan.written.contains(&key.0)
+                    && let Some(object) = checkpoint.object_set.get(&key)
+                {
+                    plan.seed_objects
+                        .entry(key.0)
+                        .or_insert_with(|| object.clone());
+                }
+            }
+
+            let shared_inputs = tx.transaction.shared_input_objects();
+            let shared_object_versions: Vec<_> = shared_inputs
+                .iter()
+                .filter_map(|object| {
+                    consensus_versions
+                        .get(&object.id)
+                        .map(|version| ((object.id, object.initial_shared_version), *version))
+                })
+                .collect();
+            let assigned_versions = (shared_object_versions.len() == shared_inputs.len())
+                .then(|| AssignedVersions::new(shared_object_versions, None));
+
+            let writes: BTreeSet<_> = effects.object_changes().iter().map(|c| c.id).collect();
+            let index = plan.transactions.len();
+            let mut dependencies = BTreeSet::new();
+            for id in reads.difference(&writes) {
+                let accesses = plan.accesses.entry(*id).or_default();
+                dependencies.extend(accesses.last_write);
+                accesses.reads_since_last_write.push(index);
+                accesses.num_reads += 1;
+            }
+            for id in &writes {
+                let accesses = plan.accesses.entry(*id).or_default();
+                dependencies.extend(accesses.last_write);
+                dependencies.extend(accesses.reads_since_last_write.drain(..));
+                accesses.last_write = Some(index);
+                accesses.num_writes += 1;
+            }
+            plan.written.extend(writes);
+
+            plan.transactions.push(ReplayTransaction {
+                transaction: Transaction::from_generic_sig_data(
+                    tx.transaction.clone(),
+                    tx.signatures.clone(),
+                ),
+                expected_effects: effects.clone(),
+                assigned_versions,
+                epoch: summary.epoch,
+                epoch_start_timestamp_ms,
+                packages: tx
+                    .transaction
+                    .move_calls()
+                    .into_iter()
+                    .map(|(_, package, _, _)| *package)
+                    .collect(),
+                dependencies: dependencies.into_iter().collect(),
+            });
+        }
+
+        // Packages are only loaded at runtime, so they are not part of the inputs of the
+        // transactions.
+        for object in checkpoint.object_set.iter() {
+            if object.is_package() && !plan.written.contains(&object.id()) {
+                plan.seed_objects
+                    .entry(object.id())
+                    .or_insert_with(|| object.clone());
+            }
+        }
+    }
+    plan
+}
+
+/// Fetches the packages used by the transactions which are not part of the checkpoints, along
+/// with their dependencies.
+async fn fetch_missing_packages(rpc_url: &str, plan: &mut ReplayPlan) -> anyhow::Result<()> {
+    let mut client = sui_rpc_api::Client::new(rpc_url.to_owned())?;
+    let mut pending: Vec<ObjectID> = plan
+        .transactions
+        .iter()
+        .flat_map(|tx| {
+            tx.transaction
+                .data()
+                .transaction_data()
+                .input_objects()
+                .unwrap_or_default()
+        })
+        .filter_map(|kind| match kind {
+            InputObjectKind::MovePackage(id) => Some(id),
+            _ => None,
+        })
+        .collect();
+    let mut num_fetched = 0;
+    while let Some(id) = pending.pop() {
+        if plan.seed_objects.contains_key(&id) || plan.written.contains(&id) {
+            continue;
+        }
+        let object = client
+            .get_object(id)
+            .await
+            .with_context(|| format!("Failed to fetch package {id}"))?;
+        if let Some(package) = object.data.try_as_package() {
+            pending.extend(
+                package
+                    .linkage_table()
+                    .values()
+                    .map(|info| info.upgraded_id),
+            );
+        }
+        plan.seed_objects.insert(id, object);
+        num_fetched += 1;
+    }
+    info!("Fetched {} packages from {}", num_fetched, rpc_url);
+    Ok(())
+}
+
+async fn execute(
+    validator: SingleValidator,
+    store: InMemoryObjectStore,
+    transactions: Arc<Vec<ReplayTransaction>>,
+    reference_gas_price: u64,
+) -> Vec<Outcome> {
+    let mut tasks: Vec<Shared<BoxFuture<'static, Outcome>>> =
+        Vec::with_capacity(transactions.len());
+    for index in 0..transactions.len() {
+        let dependencies: Vec<_> = transactions[index]
+            .dependencies
+            .iter()
+            .map(|dependency| tasks[*dependency].clone())
+            .collect();
+        let validator = validator.clone();
+        let store = store.clone();
+        let transactions = transactions.clone();
+        let task = async move {
+            join_all(dependencies).await;
+            let tx = &transactions[index];
+            let Some(assigned_versions) = &tx.assigned_versions else {
+                return Outcome {
+                    execution_time: Duration::ZERO,
+                    computation_cost: 0,
+                    result: ReplayResult::Failed(
+                        "Missing versions of shared objects in the recorded effects".to_string(),
+                    ),
+                };
+            };
+            let transaction = tx.transaction.clone();
+            let start_time = Instant::now();
+            let result = validator.replay_transaction_in_memory(
+                &store,
+                transaction,
+                assigned_versions,
+                tx.epoch,
+                tx.epoch_start_timestamp_ms,
+                reference_gas_price,
+            );
+            let execution_time = start_time.elapsed();
+            match result {
+                Ok(effects) => Outcome {
+                    execution_time,
+                    computation_cost: effects.gas_cost_summary().computation_cost,
+                    result: if effects == tx.expected_effects {
+                        ReplayResult::Matched
+                    } else {
+                        ReplayResult::Mismatched
+                    },
+                },
+                Err(e) => Outcome {
+                    execution_time,
+                    computation_cost: 0,
+                    result: ReplayResult::Failed(e.to_string()),
+                },
+            }
+        }
+        .boxed()
+        .shared();
+        tasks.push(task);
+    }
+    let handles: Vec<_> = tasks.into_iter().map(tokio::spawn).collect();
+    join_all(handles)
+        .await
+        .into_iter()
+        .map(|r| r.unwrap())
+        .collect()
+}
+
+/// Counts the transactions whose effects match the recorded ones, and logs the first failures.
+fn count_results(transactions: &[ReplayTransaction], outcomes: &[Outcome]) -> ResultCounts {
+    let mut counts = ResultCounts::default();
+    for (index, outcome) in outcomes.iter().enumerate() {
+        match &outcome.result {
+            ReplayResult::Matched => counts.matched += 1,
+            ReplayResult::Mismatched => counts.mismatched += 1,
+            ReplayResult::Failed(error) => {
+                if counts.failed < LOGGED_FAILURES {
+                    warn!(
+                        "Failed to execute transaction {}: {}",
+                        transactions[index].transaction.digest(),
+                        error
+                    );
+                }
+                counts.failed += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Reports how much the transactions of the range serialize on the objects they share.
+fn report_contention(
+    transactions: &[ReplayTransaction],
+    accesses: &HashMap<ObjectID, ObjectAccesses>,
+) {
+    let mut depths = Vec::with_capacity(transactions.len());
+    for tx in transactions {
+        let depth = tx
+            .dependencies
+            .iter()
+            .map(|d| depths[*d])
+            .max()
+            .unwrap_or(0)
+            + 1;
+        depths.push(depth);
+    }
+    let critical_path = depths.iter().copied().max().unwrap_or(0);
+    let num_dependent = transactions
+        .iter()
+        .filter(|tx| !tx.dependencies.is_empty())
+        .count();
+    info!(
+        "Scheduler contention: {} of {} transactions wait for earlier ones, critical path of {} transactions, available parallelism {:.1}",
+        num_dependent,
+        transactions.len(),
+        critical_path,
+        transactions.len() as f64 / critical_path.max(1) as f64
+    );
+
+    let mut contended: Vec<_> = accesses
+        .iter()
+        .filter(|(_, accesses)| accesses.num_writes > 1)
+        .collect();
+    contended.sort_by_key(|(_, accesses)| std::cmp::Reverse(accesses.num_writes));
+    info!("Most contended objects (object, writes, reads):");
+    for (id, accesses) in contended.into_iter().take(REPORT_ROWS) {
+        info!("  {} {} {}", id, accesses.num_writes, accesses.num_reads);
+    }
+}
+
+fn package_costs(
+    transactions: &[ReplayTransaction],
+    outcomes: &[Outcome],
+) -> Vec<(ObjectID, PackageCost)> {
+    let mut costs: HashMap<ObjectID, PackageCost> = HashMap::new();
+    for (tx, outcome) in transactions.iter().zip_debug_eq(outcomes) {
+        for package in &tx.packages {
+            let cost = costs.entry(*package).or_default();
+            cost.num_transactions += 1;
+            cost.execution_time += outcome.execution_time;
+            cost.computation_cost += outcome.computation_cost;
+        }
+    }
+    let mut costs: Vec<_> = costs.into_iter().collect();
+    costs.sort_by_key(|(_, cost)| std::cmp::Reverse(cost.execution_time));
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark_context::BenchmarkContext;
+    use crate::command::WorkloadKind;
+    use crate::workload::Workload;
+    use sui_rpc::field::{FieldMask, FieldMaskUtil};
+    use sui_rpc::merge::Merge;
+    use sui_types::full_checkpoint_content::{ExecutedTransaction, ObjectSet};
+    use sui_types::storage::ObjectStore as _;
+    use sui_types::{MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID};
+
+    const NUM_TRANSACTIONS: u64 = 4;
+    const CHECKPOINT_SIZE: usize = 2;
+
+    /// Writes `checkpoint` to `dir` the way the data ingestion of a fullnode does.
+    fn write_checkpoint(dir: &std::path::Path, checkpoint: &Checkpoint) {
+        let mask = FieldMask::from_paths([
+            ProtoCheckpoint::path_builder().sequence_number(),
+            ProtoCheckpoint::path_builder().summary().bcs().value(),
+            ProtoCheckpoint::path_builder().signature().finish(),
+            ProtoCheckpoint::path_builder().contents().bcs().value(),
+            ProtoCheckpoint::path_builder()
+                .transactions()
+                .transaction()
+                .bcs()
+                .value(),
+            ProtoCheckpoint::path_builder()
+                .transactions()
+                .effects()
+                .bcs()
+                .value(),
+            ProtoCheckpoint::path_builder()
+                .transactions()
+                .effects()
+                .unchanged_loaded_runtime_objects()
+                .finish(),
+            ProtoCheckpoint::path_builder()
+                .transactions()
+                .events()
+                .bcs()
+                .value(),
+            ProtoCheckpoint::path_builder()
+                .objects()
+                .objects()
+                .bcs()
+                .value(),
+        ]);
+        let proto_checkpoint = ProtoCheckpoint::merge_from(checkpoint, &mask.into());
+        let compressed = zstd::encode_all(&proto_checkpoint.encode_to_vec()[..], 3).unwrap();
+        let file_name = format!("{}.binpb.zst", checkpoint.summary.sequence_number);
+        std::fs::write(dir.join(file_name), compressed).unwrap();
+    }
+
+    /// Executes Move transactions calling the benchmark package and records them into checkpoints,
+    /// along with the objects they read.
+    async fn record_checkpoints(dir: &std::path::Path) -> (Vec<Checkpoint>, ObjectID) {
+        let workload = Workload::new(
+            NUM_TRANSACTIONS,
+            WorkloadKind::PTB {
+                num_transfers: 1,
+                use_native_transfer: false,
+                num_dynamic_fields: 0,
+                computation: 1,
+                num_shared_objects: 0,
+                num_mints: 1,
+                nft_size: 528,
+                use_batch_mint: false,
+            },
+        );
+        let mut ctx =
+            BenchmarkContext::new(workload.clone(), Component::ExecutionOnly, false).await;
+        let tx_generator = workload.create_tx_generator(&mut ctx).await;
+        let transactions = ctx.generate_transactions(tx_generator).await;
+        let package = *transactions[0].data().transaction_data().move_calls()[0].1;
+
+        let validator = ctx.validator();
+        let store = validator.create_in_memory_store();
+        let mut recorded = vec![];
+        let mut all_effects = BTreeMap::new();
+        for transaction in &transactions {
+            let mut objects: Vec<_> = [MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID]
+                .iter()
+                .filter_map(|id| store.get_object(id))
+                .collect();
+            for kind in transaction
+                .data()
+                .transaction_data()
+                .input_objects()
+                .unwrap()
+            {
+                objects.extend(store.get_object(&kind.object_id()));
+            }
+            let effects = validator
+                .execute_transaction_in_memory(
+                    store.clone(),
+                    transaction.clone(),
+                    &AssignedVersions::new(vec![], None),
+                )
+                .await;
+            all_effects.insert(*transaction.digest(), effects.clone());
+            let executed = ExecutedTransaction {
+                transaction: transaction.data().transaction_data().clone(),
+                signatures: transaction.data().tx_signatures().to_vec(),
+                effects,
+                events: None,
+                unchanged_loaded_runtime_objects: vec![],
+            };
+            recorded.push((executed, objects));
+        }
+
+        let mut checkpoints = vec![];
+        let built = validator
+            .build_checkpoints(transactions, all_effects, CHECKPOINT_SIZE)
+            .await;
+        for ((summary, contents), recorded) in built
+            .into_iter()
+            .zip_debug_eq(recorded.chunks(CHECKPOINT_SIZE))
+        {
+            let mut object_set = ObjectSet::default();
+            for object in recorded.iter().flat_map(|(_, objects)| objects) {
+                object_set.insert(object.clone());
+            }
+            let checkpoint = Checkpoint {
+                summary: summary.into_inner(),
+                contents: contents.into_checkpoint_contents(),
+                transactions: recorded.iter().map(|(tx, _)| tx.clone()).collect(),
+                object_set,
+            };
+            write_checkpoint(dir, &checkpoint);
+            checkpoints.push(checkpoint);
+        }
+        (checkpoints, package)
+    }
+
+    #[tokio::test]
+    async fn test_replay_recorded_checkpoints() {
+        let dir = mysten_common::tempdir().unwrap();
+        let (checkpoints, package) = record_checkpoints(dir.path()).await;
+        assert_eq!(
+            checkpoints.len(),
+            NUM_TRANSACTIONS as usize / CHECKPOINT_SIZE
+        );
+
+        let report = run_replay(ReplayOptions {
+            checkpoint_dir: Some(dir.path().to_path_buf()),
+            remote_store_url: None,
+            start_checkpoint: checkpoints[0].summary.sequence_number,
+            end_checkpoint: checkpoints.last().unwrap().summary.sequence_number,
+            rpc_url: None,
+            protocol_version: None,
+            reference_gas_price: None,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(report.num_transactions, NUM_TRANSACTIONS as usize);
+        assert_eq!(report.results.matched, NUM_TRANSACTIONS as usize);
+        assert_eq!(report.results.mismatched, 0);
+        assert_eq!(report.results.failed, 0);
+
+        // All transactions call the benchmark package, and only it.
+        assert_eq!(report.package_costs.len(), 1);
+        let (reported_package, cost) = &report.package_costs[0];
+        assert_eq!(*reported_package, package);
+        assert_eq!(cost.num_transactions, NUM_TRANSACTIONS);
+        let recorded_computation_cost: u64 = checkpoints
+            .iter()
+            .flat_map(|checkpoint| &checkpoint.transactions)
+            .map(|tx| tx.effects.gas_cost_summary().computation_cost)
+            .sum();
+        assert_eq!(cost.computation_cost, recorded_computation_cost);
+    }
+}
@@ -20,11 +20,13 @@ use sui_core::consensus_adapter::{
 use sui_core::global_state_hasher::GlobalStateHasher;
 use sui_core::mock_checkpoint_builder::{MockCheckpointBuilder, ValidatorKeypairProvider};
 use sui_core::mock_consensus::{ConsensusMode, MockConsensusClient};
+use sui_protocol_config::ProtocolConfig;
 use sui_test_transaction_builder::{PublishData, TestTransactionBuilder};
-use sui_types::base_types::{AuthorityName, ObjectRef, SuiAddress, TransactionDigest};
+use sui_types::base_types::{AuthorityName, EpochId, ObjectRef, SuiAddress, TransactionDigest};
 use sui_types::committee::Committee;
 use sui_types::crypto::{AccountKeyPair, AuthoritySignature, Signer};
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::SuiResult;
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
 use sui_types::execution_params::ExecutionOrEarlyError;
 use sui_types::messages_checkpoint::{VerifiedCheckpoint, VerifiedCheckpointContents};
@@ -40,14 +42,20 @@ pub struct SingleValidator {
 }
 
 impl SingleValidator {
-    pub(crate) async fn new(genesis_objects: &[Object], component: Component) -> Self {
-        let validator = TestAuthorityBuilder::new()
+    pub(crate) async fn new(
+        genesis_objects: &[Object],
+        component: Component,
+        protocol_config: Option<ProtocolConfig>,
+    ) -> Self {
+        let mut builder = TestAuthorityBuilder::new()
             .disable_indexer()
             .with_starting_objects(genesis_objects)
             // This is needed to properly run checkpoint executor.
-            .insert_genesis_checkpoint()
-            .build()
-            .await;
+            .insert_genesis_checkpoint();
+        if let Some(protocol_config) = protocol_config {
+            builder = builder.with_protocol_config(protocol_config);
+        }
+        let validator = builder.build().await;
         let epoch_store = validator.epoch_store_for_testing().clone();
         let consensus_mode = match component {
             Component::ValidatorWithFakeConsensus => ConsensusMode::DirectSequencing,
@@ -244,6 +252,55 @@ impl SingleValidator {
         effects
     }
 
+    /// Re-executes a recorded transaction in memory, at the epoch it was executed at. Unlike
+    /// `execute_transaction_in_memory`, input errors are returned rather than asserted on, and
+    /// failed executions are expected, as recorded transactions can fail.
+    pub(crate) fn replay_transaction_in_memory(
+        &self,
+        store: &InMemoryObjectStore,
+        transaction: Transaction,
+        assigned_versions: &AssignedVersions,
+        epoch: EpochId,
+        epoch_start_timestamp_ms: u64,
+        reference_gas_price: u64,
+    ) -> SuiResult<TransactionEffects> {
+        let input_objects = transaction.data().intent_message().value.input_objects()?;
+        let executable = self.create_executable(transaction);
+        let objects = store.read_objects_for_execution(
+            &executable.key(),
+            assigned_versions,
+            &input_objects,
+        )?;
+
+        let (gas_status, input_objects) = sui_transaction_checks::check_certificate_input(
+            &executable,
+            objects,
+            self.epoch_store.protocol_config(),
+            reference_gas_price,
+        )?;
+        let (kind, signer, gas_data) = executable.transaction_data().execution_parts();
+        let (inner_temp_store, _, effects, _timings, _) =
+            self.epoch_store.executor().execute_transaction_to_effects(
+                store,
+                self.epoch_store.protocol_config(),
+                self.get_validator().metrics.limits_metrics.clone(),
+                false,
+                ExecutionOrEarlyError::Ok(()),
+                &epoch,
+                epoch_start_timestamp_ms,
+                input_objects,
+                gas_data,
+                gas_status,
+                kind,
+                None, // compat_args
+                signer,
+                *executable.digest(),
+                &mut None,
+            );
+        store.commit_objects(inner_temp_store);
+        Ok(effects)
+    }
+
     pub(crate) async fn build_checkpoints(
         &self,
         transactions: Vec<Transaction>,
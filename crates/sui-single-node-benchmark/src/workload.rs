@@ -69,6 +69,7 @@ impl Workload {
             WorkloadKind::Publish {
                 manifest_file: manifest_path,
             } => Arc::new(PackagePublishTxGenerator::new(ctx, manifest_path.clone()).await),
+            WorkloadKind::Replay(_) => unreachable!("Replay does not generate transactions"),
         }
     }
 }
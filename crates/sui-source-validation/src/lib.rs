@@ -17,6 +17,7 @@ use sui_types::move_package::MovePackage;
 use toolchain::units_for_toolchain;
 
 pub mod error;
+pub mod service;
 mod toolchain;
 
 #[cfg(test)]
@@ -0,0 +1,12 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use sui_source_validation::service::{ServiceConfig, start_service};
+use telemetry_subscribers::TelemetryConfig;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _guard = TelemetryConfig::new().with_env().init();
+    start_service(ServiceConfig::parse()).await
+}
@@ -0,0 +1,281 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A service that verifies uploaded package sources against their on-chain bytecode, and serves
+//! the sources of verified packages, so that explorers can look up whether a package is verified
+//! and where to find its source.
+//!
+//! - `POST /api/v1/verify/{package_id}?toolchain=<version>` takes a gzipped tarball of the
+//!   package's source as its body, and stores it if it builds to the package's bytecode.
+//! - `GET /api/v1/packages/{package_id}` reports whether the package's source has been verified.
+//! - `GET /api/v1/packages/{package_id}/source` returns the verified source archive.
+
+use std::{
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use move_package_alt::schema::Environment;
+use serde::{Deserialize, Serialize};
+use sui_rpc_api::Client;
+use sui_types::base_types::ObjectID;
+use tokio::sync::Semaphore;
+use tower_http::cors::{Any, CorsLayer};
+use tracing::{info, warn};
+
+use crate::error::AggregateError;
+
+pub use storage::{SourceStore, VerifiedPackage};
+
+mod storage;
+mod verify;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(clap::Parser, Clone, Debug)]
+#[clap(
+    name = "sui-source-validation-service",
+    about = "Verify Move package sources against their on-chain bytecode, and serve verified sources",
+    rename_all = "kebab-case"
+)]
+pub struct ServiceConfig {
+    /// gRPC URL of the fullnode to fetch on-chain packages from.
+    #[clap(long, default_value = "https://fullnode.mainnet.sui.io:443")]
+    pub rpc_url: String,
+
+    /// Name of the environment in packages' `Move.toml` that corresponds to the network at
+    /// `rpc_url`, used to resolve dependencies.
+    #[clap(long, default_value = "mainnet")]
+    pub env: String,
+
+    /// Address to serve the HTTP API on.
+    #[clap(long, default_value = "0.0.0.0:8000")]
+    pub listen_address: SocketAddr,
+
+    /// Directory that verified sources are stored in.
+    #[clap(long)]
+    pub storage_dir: PathBuf,
+
+    /// Maximum number of verifications that can run at the same time.
+    #[clap(long, default_value_t = 4)]
+    pub max_concurrent_verifications: usize,
+
+    /// Maximum size of an uploaded source archive, in bytes.
+    #[clap(long, default_value_t = 16 * 1024 * 1024)]
+    pub max_archive_size: usize,
+}
+
+pub struct AppState {
+    client: Client,
+    env: Environment,
+    store: SourceStore,
+    verifications: Semaphore,
+}
+
+#[derive(Deserialize)]
+struct VerifyParams {
+    /// Compiler version to build the source with.
+    toolchain: Option<String>,
+}
+
+/// Response for a package lookup.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageStatus {
+    pub verified: bool,
+    #[serde(flatten)]
+    pub package: Option<VerifiedPackage>,
+    /// Path of the verified source archive, relative to the service's address.
+    pub source_url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Debug)]
+pub(crate) enum ServiceError {
+    BadRequest(String),
+    NotVerified(AggregateError),
+    Busy,
+    Internal(anyhow::Error),
+}
+
+impl AppState {
+    pub async fn new(config: &ServiceConfig) -> anyhow::Result<Self> {
+        let client = Client::new(config.rpc_url.as_str())?;
+        let chain_id = client.get_chain_identifier().await?;
+        Ok(Self {
+            client,
+            env: Environment::new(config.env.clone(), chain_id.to_string()),
+            store: SourceStore::new(&config.storage_dir)?,
+            verifications: Semaphore::new(config.max_concurrent_verifications),
+        })
+    }
+}
+
+/// Build the router for the service's HTTP API.
+pub fn router(state: Arc<AppState>, max_archive_size: usize) -> Router {
+    let cors = CorsLayer::new()
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .allow_origin(Any);
+
+    Router::new()
+        .route("/", get(health))
+        .route("/api/v1/verify/{package_id}", post(verify))
+        .route("/api/v1/packages/{package_id}", get(package_status))
+        .route("/api/v1/packages/{package_id}/source", get(package_source))
+        .layer(DefaultBodyLimit::max(max_archive_size))
+        .layer(cors)
+        .with_state(state)
+}
+
+/// Start the service, and serve its API until the process is stopped.
+pub async fn start_service(config: ServiceConfig) -> anyhow::Result<()> {
+    let state = Arc::new(AppState::new(&config).await?);
+    let app = router(state, config.max_archive_size);
+
+    info!("Listening on {}", config.listen_address);
+    let listener = tokio::net::TcpListener::bind(config.listen_address).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn health() -> &'static str {
+    "OK"
+}
+
+async fn verify(
+    State(state): State<Arc<AppState>>,
+    Path(package_id): Path<ObjectID>,
+    Query(VerifyParams { toolchain }): Query<VerifyParams>,
+    archive: Bytes,
+) -> Result<Json<PackageStatus>, ServiceError> {
+    let _permit = state
+        .verifications
+        .try_acquire()
+        .map_err(|_| ServiceError::Busy)?;
+
+    info!("Verifying source for {package_id}");
+    let verified = verify::verify_archive(
+        &state.client,
+        &state.env,
+        package_id,
+        toolchain,
+        archive.clone(),
+    )
+    .await?;
+
+    let package = VerifiedPackage {
+        package_id,
+        package_name: verified.package_name,
+        toolchain_version: verified.toolchain_version,
+        verified_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| ServiceError::Internal(e.into()))?
+            .as_millis() as u64,
+    };
+
+    let stored = package.clone();
+    with_store(&state, move |store| store.put(&stored, &archive)).await?;
+
+    info!("Verified source for {package_id}");
+    Ok(Json(PackageStatus::verified(package)))
+}
+
+async fn package_status(
+    State(state): State<Arc<AppState>>,
+    Path(package_id): Path<ObjectID>,
+) -> Result<Json<PackageStatus>, ServiceError> {
+    let status = match with_store(&state, move |store| store.get(&package_id)).await? {
+        Some(package) => PackageStatus::verified(package),
+        None => PackageStatus::unverified(),
+    };
+
+    Ok(Json(status))
+}
+
+async fn package_source(
+    State(state): State<Arc<AppState>>,
+    Path(package_id): Path<ObjectID>,
+) -> Result<Response, ServiceError> {
+    let Some(archive) = with_store(&state, move |store| store.source(&package_id)).await? else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{package_id}.tar.gz\""),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+/// Run `f` against the source store on a blocking thread, as the store uses synchronous
+/// filesystem operations.
+async fn with_store<T: Send + 'static>(
+    state: &Arc<AppState>,
+    f: impl FnOnce(&SourceStore) -> anyhow::Result<T> + Send + 'static,
+) -> Result<T, ServiceError> {
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || f(&state.store))
+        .await
+        .map_err(|e| ServiceError::Internal(e.into()))?
+        .map_err(ServiceError::Internal)
+}
+
+impl PackageStatus {
+    fn verified(package: VerifiedPackage) -> Self {
+        let source_url = format!("/api/v1/packages/{}/source", package.package_id);
+        Self {
+            verified: true,
+            package: Some(package),
+            source_url: Some(source_url),
+        }
+    }
+
+    fn unverified() -> Self {
+        Self {
+            verified: false,
+            package: None,
+            source_url: None,
+        }
+    }
+}
+
+impl IntoResponse for ServiceError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            ServiceError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ServiceError::NotVerified(e) => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            ServiceError::Busy => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Too many verifications in progress, please try again later".to_string(),
+            ),
+            ServiceError::Internal(e) => {
+                warn!("Internal error: {e:#}");
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}"))
+            }
+        };
+
+        (status, Json(ErrorResponse { error })).into_response()
+    }
+}
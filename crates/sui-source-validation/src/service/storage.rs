@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectID;
+
+const METADATA_FILE: &str = "metadata.json";
+const SOURCE_FILE: &str = "source.tar.gz";
+
+/// What is recorded about a package whose source has been verified.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedPackage {
+    /// The on-chain (storage) ID of the package that was verified.
+    pub package_id: ObjectID,
+    /// The package's name, from its manifest.
+    pub package_name: String,
+    /// The compiler version the source was built with to reproduce the on-chain bytecode.
+    pub toolchain_version: String,
+    /// When the package was verified, in milliseconds since the Unix epoch.
+    pub verified_at_ms: u64,
+}
+
+/// Stores the sources of verified packages on the local filesystem, as one directory per package
+/// ID containing the source archive that was uploaded, and the package's metadata.
+pub struct SourceStore {
+    root: PathBuf,
+    /// Held while a package is being written.
+    writes: Mutex<()>,
+}
+
+impl SourceStore {
+    pub fn new(root: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create storage directory {}", root.display()))?;
+        Ok(Self {
+            root,
+            writes: Mutex::new(()),
+        })
+    }
+
+    /// Metadata for the package at `package_id`, if its source has been verified.
+    pub fn get(&self, package_id: &ObjectID) -> anyhow::Result<Option<VerifiedPackage>> {
+        let Some(bytes) = read_if_exists(&self.package_dir(package_id).join(METADATA_FILE))? else {
+            return Ok(None);
+        };
+
+        let metadata = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to read metadata for package {package_id}"))?;
+        Ok(Some(metadata))
+    }
+
+    /// The verified source archive for the package at `package_id`, if there is one.
+    pub fn source(&self, package_id: &ObjectID) -> anyhow::Result<Option<Vec<u8>>> {
+        read_if_exists(&self.package_dir(package_id).join(SOURCE_FILE))
+    }
+
+    /// Record `archive` as the verified source for `package`, replacing any source previously
+    /// stored for the same package ID. Puts are serialized, and each file is written to a staging
+    /// directory first and then renamed into place atomically, so readers see either the previous
+    /// or the new version of a file, never a partially written one. The archive is moved into
+    /// place before the metadata, so a package is never reported as verified without its source.
+    pub fn put(&self, package: &VerifiedPackage, archive: &[u8]) -> anyhow::Result<()> {
+        let _guard = self
+            .writes
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Source store lock poisoned"))?;
+
+        let staging = tempfile::Builder::new()
+            .prefix(".staging-")
+            .tempdir_in(&self.root)
+            .context("Failed to create staging directory")?;
+
+        fs::write(staging.path().join(SOURCE_FILE), archive)?;
+        fs::write(
+            staging.path().join(METADATA_FILE),
+            serde_json::to_vec_pretty(package)?,
+        )?;
+
+        let dst = self.package_dir(&package.package_id);
+        fs::create_dir_all(&dst).with_context(|| format!("Failed to create {}", dst.display()))?;
+
+        for file in [SOURCE_FILE, METADATA_FILE] {
+            fs::rename(staging.path().join(file), dst.join(file))
+                .with_context(|| format!("Failed to store source for {}", package.package_id))?;
+        }
+
+        Ok(())
+    }
+
+    fn package_dir(&self, package_id: &ObjectID) -> PathBuf {
+        self.root.join(package_id.to_string())
+    }
+}
+
+fn read_if_exists(path: &Path) -> anyhow::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use flate2::{Compression, write::GzEncoder};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use test_cluster::TestClusterBuilder;
+use tower::ServiceExt;
+
+use super::{AppState, PackageStatus, ServiceConfig, SourceStore, VerifiedPackage, router};
+use crate::tests::{copy_published_package, publish_package};
+use crate::toolchain::CURRENT_COMPILER_VERSION;
+
+const MAX_ARCHIVE_SIZE: usize = 1024 * 1024;
+
+#[test]
+fn store_round_trip() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let store = SourceStore::new(dir.path())?;
+    let package_id = ObjectID::random();
+
+    assert_eq!(store.get(&package_id)?, None);
+    assert_eq!(store.source(&package_id)?, None);
+
+    let package = VerifiedPackage {
+        package_id,
+        package_name: "b".to_string(),
+        toolchain_version: CURRENT_COMPILER_VERSION.to_string(),
+        verified_at_ms: 42,
+    };
+
+    store.put(&package, b"v1")?;
+    assert_eq!(store.get(&package_id)?, Some(package.clone()));
+    assert_eq!(store.source(&package_id)?, Some(b"v1".to_vec()));
+
+    // Verifying again replaces the stored source
+    let package = VerifiedPackage {
+        verified_at_ms: 43,
+        ..package
+    };
+
+    store.put(&package, b"v2")?;
+    assert_eq!(store.get(&package_id)?, Some(package));
+    assert_eq!(store.source(&package_id)?, Some(b"v2".to_vec()));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn verify_and_serve_source() -> anyhow::Result<()> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let context = &cluster.wallet;
+
+    let fixtures = tempfile::tempdir()?;
+    let b_src = copy_published_package(&fixtures, "b", SuiAddress::ZERO).await?;
+    let b_ref = publish_package(context, b_src.clone()).await.0;
+    let b_id = b_ref.0;
+    let archive = archive(&b_src)?;
+
+    let storage = tempfile::tempdir()?;
+    let config = ServiceConfig {
+        rpc_url: cluster.rpc_url().to_string(),
+        env: "localnet".to_string(),
+        listen_address: "127.0.0.1:0".parse()?,
+        storage_dir: storage.path().to_path_buf(),
+        max_concurrent_verifications: 1,
+        max_archive_size: MAX_ARCHIVE_SIZE,
+    };
+    let app = router(Arc::new(AppState::new(&config).await?), MAX_ARCHIVE_SIZE);
+
+    // Not verified yet
+    let (status, body) = call(&app, get(&format!("/api/v1/packages/{b_id}"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let lookup: PackageStatus = serde_json::from_slice(&body)?;
+    assert!(!lookup.verified);
+
+    let (status, _) = call(&app, get(&format!("/api/v1/packages/{b_id}/source"))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Source that doesn't match the package at the given ID is rejected
+    let (status, _) = call(
+        &app,
+        post(&format!("/api/v1/verify/{}", ObjectID::ZERO), &archive),
+    )
+    .await;
+    assert_ne!(status, StatusCode::OK);
+
+    // Source that matches is stored
+    let (status, body) = call(&app, post(&format!("/api/v1/verify/{b_id}"), &archive)).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&body));
+
+    let (status, body) = call(&app, get(&format!("/api/v1/packages/{b_id}"))).await;
+    assert_eq!(status, StatusCode::OK);
+    let lookup: PackageStatus = serde_json::from_slice(&body)?;
+    assert!(lookup.verified);
+    assert_eq!(
+        lookup.source_url,
+        Some(format!("/api/v1/packages/{b_id}/source"))
+    );
+
+    let package = lookup.package.unwrap();
+    assert_eq!(package.package_id, b_id);
+    assert_eq!(package.package_name, "b");
+    assert_eq!(package.toolchain_version, CURRENT_COMPILER_VERSION);
+
+    let (status, body) = call(&app, get(&format!("/api/v1/packages/{b_id}/source"))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, archive);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_invalid_archive() -> anyhow::Result<()> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let storage = tempfile::tempdir()?;
+    let config = ServiceConfig {
+        rpc_url: cluster.rpc_url().to_string(),
+        env: "localnet".to_string(),
+        listen_address: "127.0.0.1:0".parse()?,
+        storage_dir: storage.path().to_path_buf(),
+        max_concurrent_verifications: 1,
+        max_archive_size: MAX_ARCHIVE_SIZE,
+    };
+    let app = router(Arc::new(AppState::new(&config).await?), MAX_ARCHIVE_SIZE);
+
+    let id = ObjectID::random();
+    let (status, _) = call(
+        &app,
+        post(&format!("/api/v1/verify/{id}"), b"not a tarball"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let oversized = vec![0u8; MAX_ARCHIVE_SIZE + 1];
+    let (status, _) = call(&app, post(&format!("/api/v1/verify/{id}"), &oversized)).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Dependencies that would be resolved on the service's machine are rejected
+    for dep in [
+        r#"{ local = "/" }"#,
+        r#"{ git = "https://github.com/MystenLabs/sui.git", rev = "main" }"#,
+    ] {
+        let package = tempfile::tempdir()?;
+        std::fs::write(
+            package.path().join("Move.toml"),
+            format!("[package]\nname = \"c\"\n\n[dependencies]\nd = {dep}\n"),
+        )?;
+
+        let (status, body) = call(
+            &app,
+            post(&format!("/api/v1/verify/{id}"), &archive(package.path())?),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(String::from_utf8_lossy(&body).contains("Dependency 'd'"));
+    }
+
+    Ok(())
+}
+
+/// Package the directory at `package` into a gzipped tarball, under a top-level directory.
+fn archive(package: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut tar = tar::Builder::new(GzEncoder::new(vec![], Compression::default()));
+    tar.append_dir_all("package", package)?;
+    Ok(tar.into_inner()?.finish()?)
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+fn post(uri: &str, body: &[u8]) -> Request<Body> {
+    Request::post(uri).body(Body::from(body.to_vec())).unwrap()
+}
+
+async fn call(app: &axum::Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, body.to_vec())
+}
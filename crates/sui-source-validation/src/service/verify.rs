@@ -0,0 +1,325 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use axum::body::Bytes;
+use flate2::read::GzDecoder;
+use move_binary_format::CompiledModule;
+use move_compiler::editions::{Edition, Flavor};
+use move_core_types::account_address::AccountAddress;
+use move_package_alt::schema::Environment;
+use move_package_alt_compilation::build_config::BuildConfig as MoveBuildConfig;
+use move_symbol_pool::Symbol;
+use sui_move_build::BuildConfig;
+use sui_rpc_api::Client;
+use sui_types::{base_types::ObjectID, move_package::MovePackage};
+use tar::Archive;
+use tempfile::TempDir;
+
+use crate::{
+    BytecodeSourceVerifier, ValidationMode,
+    error::{AggregateError, Error},
+    toolchain::{CURRENT_COMPILER_VERSION, ToolchainVersion, compile_with_toolchain},
+};
+
+use super::ServiceError;
+
+/// A package whose source was verified against its on-chain bytecode.
+pub(crate) struct Verified {
+    pub package_name: String,
+    pub toolchain_version: String,
+}
+
+/// Names of the Sui system packages as legacy manifests refer to them. Dependencies on these are
+/// replaced by the system packages bundled with the compiler, so they are never fetched.
+const SYSTEM_DEPENDENCY_NAMES: &[&str] = &["Sui", "MoveStdlib", "Bridge", "DeepBook", "SuiSystem"];
+
+/// Manifest sections that declare dependencies.
+const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "dev-dependencies"];
+
+/// Manifest section with per-environment dependency replacements.
+const DEP_REPLACEMENTS_SECTION: &str = "dep-replacements";
+
+/// The uploaded package source, unpacked into a temporary directory.
+struct Workspace {
+    /// Deleted when the workspace is dropped.
+    dir: TempDir,
+    /// The directory containing the package manifest.
+    root: PathBuf,
+    package_name: String,
+    /// The toolchain version recorded in the package's `Published.toml` or `Move.lock`.
+    recorded: Option<ToolchainVersion>,
+}
+
+/// Verify that the package source in `archive` (a gzipped tarball containing the package's
+/// `Move.toml`, either at its root or in a single top-level directory) builds to the bytecode of
+/// the package at `package_id`.
+///
+/// The package is built with the `toolchain` compiler version if one is given, otherwise with the
+/// version recorded in the package's `Published.toml` or `Move.lock`, falling back to the current
+/// compiler.
+pub(crate) async fn verify_archive(
+    client: &Client,
+    env: &Environment,
+    package_id: ObjectID,
+    toolchain: Option<String>,
+    archive: Bytes,
+) -> Result<Verified, ServiceError> {
+    let unpack_env = env.clone();
+    let Workspace {
+        dir,
+        root,
+        package_name,
+        recorded,
+    } = tokio::task::spawn_blocking(move || unpack(&archive, &unpack_env))
+        .await
+        .map_err(|e| ServiceError::Internal(e.into()))??;
+
+    let toolchain = match (toolchain, recorded) {
+        (Some(compiler_version), recorded) => ToolchainVersion {
+            compiler_version,
+            edition: recorded.map_or(Edition::LEGACY, |r| r.edition),
+            flavor: Flavor::Sui,
+        },
+        (None, Some(recorded)) => recorded,
+        (None, None) => ToolchainVersion {
+            compiler_version: CURRENT_COMPILER_VERSION.to_string(),
+            edition: Edition::LEGACY,
+            flavor: Flavor::Sui,
+        },
+    };
+
+    if toolchain.compiler_version == CURRENT_COMPILER_VERSION {
+        verify_with_current_compiler(client, env, package_id, &root, dir.path()).await?;
+    } else {
+        let on_chain = fetch_package(client, package_id).await?;
+        let name = Symbol::from(package_name.as_str());
+        let build_toolchain = toolchain.clone();
+        let modules = tokio::task::spawn_blocking(move || {
+            compile_with_toolchain(&root, &name, &build_toolchain)
+        })
+        .await
+        .map_err(|e| ServiceError::Internal(e.into()))?
+        .map_err(|e| ServiceError::BadRequest(format!("Failed to build package: {e:#}")))?;
+        compare_modules(name, &on_chain, modules).map_err(ServiceError::NotVerified)?;
+    }
+
+    Ok(Verified {
+        package_name,
+        toolchain_version: toolchain.compiler_version,
+    })
+}
+
+/// Unpack `archive` into a temporary directory, and read what is needed to build it. This only
+/// does blocking filesystem operations, so it should be run on a blocking thread.
+fn unpack(archive: &[u8], env: &Environment) -> Result<Workspace, ServiceError> {
+    let dir = tempfile::tempdir().map_err(|e| ServiceError::Internal(e.into()))?;
+    Archive::new(GzDecoder::new(archive))
+        .unpack(dir.path())
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid source archive: {e}")))?;
+
+    let root = package_root(dir.path())?;
+    let manifest = read_manifest(&root)?;
+    let package_name = package_name(&manifest)?;
+    check_dependencies(&manifest)?;
+
+    let recorded = ToolchainVersion::read(&root, env)
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid toolchain version: {e}")))?;
+
+    Ok(Workspace {
+        dir,
+        root,
+        package_name,
+        recorded,
+    })
+}
+
+/// Build the package at `root` in-process, and verify it with [`BytecodeSourceVerifier`].
+async fn verify_with_current_compiler(
+    client: &Client,
+    env: &Environment,
+    package_id: ObjectID,
+    root: &Path,
+    workspace: &Path,
+) -> Result<(), ServiceError> {
+    let config = BuildConfig {
+        config: MoveBuildConfig {
+            default_flavor: Some(Flavor::Sui),
+            install_dir: Some(workspace.join("build")),
+            silence_warnings: true,
+            ..MoveBuildConfig::default()
+        },
+        run_bytecode_verifier: true,
+        print_diags_to_stderr: false,
+        environment: env.clone(),
+    };
+
+    let package = config
+        .build_async(root)
+        .await
+        .map_err(|e| ServiceError::BadRequest(format!("Failed to build package: {e}")))?;
+
+    // A package that records where it was published is verified at that address, which must be
+    // the package being verified. Otherwise, its modules are expected at 0x0, and are substituted
+    // with the package's address.
+    let mode = match package.published_at {
+        Some(published_at) if published_at == package_id => ValidationMode::root(),
+        Some(published_at) => {
+            return Err(ServiceError::BadRequest(format!(
+                "Source is for the package published at {published_at}, not {package_id}"
+            )));
+        }
+        None => ValidationMode::root_at(package_id.into()),
+    };
+
+    BytecodeSourceVerifier::new(client)
+        .verify(&package, mode, env)
+        .await
+        .map_err(ServiceError::NotVerified)
+}
+
+/// Check that `modules`, compiled from source, match the modules of the `on_chain` package. The
+/// compiled modules may still have `0x0` as their self-address, in which case it is substituted
+/// with the on-chain package's original ID before comparing.
+fn compare_modules(
+    package: Symbol,
+    on_chain: &MovePackage,
+    modules: Vec<CompiledModule>,
+) -> Result<(), AggregateError> {
+    let address = AccountAddress::from(on_chain.original_package_id());
+
+    let mut on_chain_modules = BTreeMap::new();
+    for (name, bytes) in on_chain.serialized_module_map() {
+        let module = Symbol::from(name.as_str());
+        let Ok(compiled) = CompiledModule::deserialize_with_defaults(bytes) else {
+            return Err(Error::OnChainDependencyDeserializationError { address, module }.into());
+        };
+        on_chain_modules.insert(module, compiled);
+    }
+
+    let mut errs = vec![];
+    for mut local in modules {
+        let module = Symbol::from(local.self_id().name().as_str());
+        let self_address = local.self_handle().address;
+        if let Some(addr) = local.address_identifiers.get_mut(self_address.0 as usize)
+            && *addr == AccountAddress::ZERO
+        {
+            *addr = address;
+        }
+
+        match on_chain_modules.remove(&module) {
+            None => errs.push(Error::OnChainDependencyNotFound { package, module }),
+            Some(on_chain) if on_chain != local => errs.push(Error::ModuleBytecodeMismatch {
+                address,
+                package,
+                module,
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for module in on_chain_modules.into_keys() {
+        errs.push(Error::LocalDependencyNotFound { address, module });
+    }
+
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        Err(AggregateError(errs))
+    }
+}
+
+async fn fetch_package(client: &Client, package_id: ObjectID) -> Result<MovePackage, ServiceError> {
+    let object = client
+        .clone()
+        .get_object(package_id)
+        .await
+        .map_err(|e| ServiceError::BadRequest(format!("Failed to fetch {package_id}: {e}")))?;
+
+    match object.data.try_as_package() {
+        Some(package) => Ok(package.clone()),
+        None => Err(ServiceError::BadRequest(format!(
+            "{package_id} is not a package"
+        ))),
+    }
+}
+
+/// The directory containing the package manifest: either `dir` itself, or its only subdirectory.
+fn package_root(dir: &Path) -> Result<PathBuf, ServiceError> {
+    if dir.join("Move.toml").is_file() {
+        return Ok(dir.to_path_buf());
+    }
+
+    let mut subdirs = fs::read_dir(dir)
+        .map_err(|e| ServiceError::Internal(e.into()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir());
+
+    match (subdirs.next(), subdirs.next()) {
+        (Some(subdir), None) if subdir.join("Move.toml").is_file() => Ok(subdir),
+        _ => Err(ServiceError::BadRequest(
+            "Source archive does not contain a Move.toml".to_string(),
+        )),
+    }
+}
+
+fn read_manifest(root: &Path) -> Result<toml::Value, ServiceError> {
+    let manifest = fs::read_to_string(root.join("Move.toml"))
+        .map_err(|e| ServiceError::BadRequest(format!("Failed to read Move.toml: {e}")))?;
+
+    toml::from_str(&manifest)
+        .map_err(|e| ServiceError::BadRequest(format!("Invalid Move.toml: {e}")))
+}
+
+/// The package's name, from the `[package]` section of its manifest.
+fn package_name(manifest: &toml::Value) -> Result<String, ServiceError> {
+    manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(str::to_owned)
+        .ok_or_else(|| ServiceError::BadRequest("Move.toml has no package name".to_string()))
+}
+
+/// Dependencies are resolved on the service's machine, so a package may not depend on paths on
+/// that machine, or on git repositories that would be fetched by it. This leaves dependencies on
+/// the system packages, and dependencies resolved on-chain.
+fn check_dependencies(manifest: &toml::Value) -> Result<(), ServiceError> {
+    let sections = DEPENDENCY_SECTIONS
+        .iter()
+        .filter_map(|section| manifest.get(*section))
+        .chain(
+            manifest
+                .get(DEP_REPLACEMENTS_SECTION)
+                .and_then(|r| r.as_table())
+                .into_iter()
+                .flat_map(|envs| envs.values()),
+        );
+
+    for deps in sections {
+        let Some(deps) = deps.as_table() else {
+            continue;
+        };
+
+        for (name, dep) in deps {
+            if dep.get("local").is_some() {
+                return Err(ServiceError::BadRequest(format!(
+                    "Dependency '{name}' is a local dependency, which the service cannot resolve"
+                )));
+            }
+
+            if dep.get("git").is_some() && !SYSTEM_DEPENDENCY_NAMES.contains(&name.as_str()) {
+                return Err(ServiceError::BadRequest(format!(
+                    "Dependency '{name}' is a git dependency, which the service does not fetch"
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
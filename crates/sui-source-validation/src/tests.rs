@@ -845,7 +845,10 @@ fn sanitize_id(mut message: String, m: &HashMap<SuiAddress, &str>) -> String {
 }
 
 /// Compile and publish package at absolute path `package` to chain.
-async fn publish_package(context: &WalletContext, package: PathBuf) -> (ObjectRef, ObjectRef) {
+pub(crate) async fn publish_package(
+    context: &WalletContext,
+    package: PathBuf,
+) -> (ObjectRef, ObjectRef) {
     let txn = make_publish_transaction(context, package).await;
     let response = context.execute_transaction_must_succeed(txn).await;
     let package = response.get_new_package_obj().unwrap();
@@ -887,7 +890,7 @@ async fn publish_package_and_deps(context: &WalletContext, package: PathBuf) ->
 
 /// Copy `package` from fixtures into `directory`, setting its named address in the copied package's
 /// `Move.toml` to `address`. (A fixture's self-address is assumed to match its package name).
-async fn copy_published_package(
+pub(crate) async fn copy_published_package(
     directory: impl AsRef<Path>,
     package: &str,
     address: SuiAddress,
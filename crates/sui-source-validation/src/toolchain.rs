@@ -66,7 +66,7 @@ pub struct LockfileHeader {
 
 // TODO: pkg-alt this needs to work with both old style and new style formats. Particularly, for
 // the new pkg system, the toolchain version is in the Published.toml file, or Pub.env.toml file.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ToolchainVersion {
     /// The Move compiler version used to compile this package.
     #[serde(rename = "compiler-version")]
@@ -315,6 +315,43 @@ pub(crate) fn units_for_toolchain(
     Ok(units)
 }
 
+/// Compile the package at `root` with the compiler at `toolchain_version`, downloading it first if
+/// it is not installed yet, and return the bytecode of the package's own modules (excluding its
+/// dependencies).
+pub(crate) fn compile_with_toolchain(
+    root: &Path,
+    package: &Symbol,
+    toolchain_version: &ToolchainVersion,
+) -> anyhow::Result<Vec<CompiledModule>> {
+    let install_dir = tempfile::tempdir()?;
+    download_and_compile(root.to_path_buf(), &install_dir, toolchain_version, package)?;
+
+    let modules_dir = install_dir
+        .path()
+        .join(CompiledPackageLayout::Root.path())
+        .join(package.as_str())
+        .join(CompiledPackageLayout::CompiledModules.path());
+
+    let mut modules = vec![];
+    let entries = std::fs::read_dir(&modules_dir)
+        .with_context(|| format!("Reading compiled modules from {}", modules_dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && extension_equals(&path, MOVE_COMPILED_EXTENSION) {
+            let bytes = std::fs::read(&path)?;
+            modules.push(CompiledModule::deserialize_with_defaults(&bytes)?);
+        }
+    }
+
+    ensure!(
+        !modules.is_empty(),
+        "Building {package} with compiler @ {} produced no modules",
+        toolchain_version.compiler_version,
+    );
+
+    Ok(modules)
+}
+
 fn download_and_compile(
     root: PathBuf,
     install_dir: &TempDir,
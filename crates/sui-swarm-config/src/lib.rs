@@ -6,3 +6,4 @@ pub mod network_config;
 pub mod network_config_builder;
 pub mod node_config_builder;
 pub mod test_utils;
+pub mod topology;
@@ -284,6 +284,7 @@ impl ValidatorConfigBuilder {
             fork_recovery: None,
             transaction_driver_config: Some(TransactionDriverConfig::default()),
             congestion_log: None,
+            read_replica: None,
         }
     }
 
@@ -635,6 +636,7 @@ impl FullnodeConfigBuilder {
                 .transaction_driver_config
                 .or(Some(TransactionDriverConfig::default())),
             congestion_log: None,
+            read_replica: None,
         }
     }
 }
@@ -0,0 +1,329 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative description of a local network. A topology lists the validators and fullnodes of
+//! the network with their individual settings, and the delays of the links between them, which
+//! are only applied in the simulator. It is loaded from a YAML or TOML file, for example:
+//!
+//! ```yaml
+//! epoch_duration_ms: 60000
+//! validators:
+//!   - name: validator-0
+//!     stake: 30000000000000000
+//!     consensus_parameters:
+//!       max_blocks_per_fetch: 500
+//!   - name: validator-1
+//!     supported_protocol_versions: { min: 1, max: 90 }
+//!   - name: validator-2
+//!   - name: validator-3
+//! fullnodes:
+//!   - name: rpc
+//!     rpc_port: 9000
+//!     rpc:
+//!       enable-indexing: true
+//!     data_ingestion: true
+//! links:
+//!   - between: [validator-0, validator-3]
+//!     delay_ms: 150
+//!     jitter_ms: 20
+//! ```
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, bail, ensure};
+use consensus_config::Parameters as ConsensusParameters;
+use mysten_common::ZipDebugEqIteratorExt;
+use serde::{Deserialize, Serialize};
+use sui_config::RpcConfig;
+use sui_types::supported_protocol_versions::SupportedProtocolVersions;
+
+use crate::genesis_config::{ValidatorGenesisConfig, ValidatorGenesisConfigBuilder};
+use crate::network_config::NetworkConfig;
+use crate::network_config_builder::ProtocolVersionsConfig;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Topology {
+    /// Protocol version of the genesis. Defaults to the latest version.
+    #[serde(default)]
+    pub protocol_version: Option<u64>,
+    #[serde(default)]
+    pub epoch_duration_ms: Option<u64>,
+    pub validators: Vec<ValidatorTopology>,
+    #[serde(default)]
+    pub fullnodes: Vec<FullnodeTopology>,
+    #[serde(default)]
+    pub links: Vec<LinkDelay>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorTopology {
+    pub name: String,
+    /// Stake of the validator at genesis, in MIST.
+    #[serde(default)]
+    pub stake: Option<u64>,
+    #[serde(default)]
+    pub gas_price: Option<u64>,
+    /// Protocol versions the validator votes for, e.g. to run a validator on an older binary.
+    #[serde(default)]
+    pub supported_protocol_versions: Option<SupportedProtocolVersions>,
+    /// Overrides of the default consensus parameters.
+    #[serde(default)]
+    pub consensus_parameters: Option<ConsensusParameters>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FullnodeTopology {
+    pub name: String,
+    /// Port of the JSON-RPC and gRPC services. A random port is used when unset.
+    #[serde(default)]
+    pub rpc_port: Option<u16>,
+    #[serde(default)]
+    pub rpc: Option<RpcConfig>,
+    /// Write executed checkpoints to the data ingestion directory of the network, for indexers.
+    #[serde(default)]
+    pub data_ingestion: bool,
+    #[serde(default)]
+    pub disable_pruning: bool,
+    #[serde(default)]
+    pub supported_protocol_versions: Option<SupportedProtocolVersions>,
+}
+
+/// Delay of the messages between two nodes, in both directions. Link delays are only applied when
+/// running in the simulator.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LinkDelay {
+    pub between: [String; 2],
+    pub delay_ms: u64,
+    /// Each message is delayed by a uniformly random extra delay of up to `jitter_ms`.
+    #[serde(default)]
+    pub jitter_ms: u64,
+}
+
+impl Topology {
+    /// Loads a topology from a `.yaml`, `.yml` or `.toml` file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read topology file {}", path.display()))?;
+        let topology: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml" | "yml") => serde_yaml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Some("toml") => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            _ => bail!(
+                "Unknown topology file format {}, expected .yaml or .toml",
+                path.display()
+            ),
+        };
+        topology.validate()?;
+        Ok(topology)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            !self.validators.is_empty(),
+            "A topology needs at least one validator"
+        );
+        let mut names = BTreeSet::new();
+        for name in self.node_names() {
+            ensure!(names.insert(name), "Duplicate node name {name}");
+        }
+        let versions = self
+            .validators
+            .iter()
+            .map(|v| (&v.name, &v.supported_protocol_versions))
+            .chain(
+                self.fullnodes
+                    .iter()
+                    .map(|f| (&f.name, &f.supported_protocol_versions)),
+            );
+        for (name, versions) in versions {
+            if let Some(versions) = versions {
+                ensure!(
+                    versions.min <= versions.max,
+                    "Empty range of supported protocol versions for {name}"
+                );
+            }
+        }
+        for link in &self.links {
+            let [a, b] = &link.between;
+            ensure!(a != b, "Link of {a} to itself");
+            for name in [a, b] {
+                ensure!(names.contains(name.as_str()), "Link to unknown node {name}");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn node_names(&self) -> impl Iterator<Item = &str> {
+        self.validators
+            .iter()
+            .map(|v| v.name.as_str())
+            .chain(self.fullnodes.iter().map(|f| f.name.as_str()))
+    }
+
+    /// Genesis configs of the validators, in the order of the topology.
+    pub fn validator_genesis_configs<R: rand::RngCore + rand::CryptoRng>(
+        &self,
+        rng: &mut R,
+    ) -> Vec<ValidatorGenesisConfig> {
+        self.validators
+            .iter()
+            .map(|validator| {
+                let mut builder = ValidatorGenesisConfigBuilder::new();
+                if let Some(stake) = validator.stake {
+                    builder = builder.with_stake(stake);
+                }
+                if let Some(gas_price) = validator.gas_price {
+                    builder = builder.with_gas_price(gas_price);
+                }
+                let mut config = builder.build(rng);
+                config.name = Some(validator.name.clone());
+                config
+            })
+            .collect()
+    }
+
+    /// Applies the protocol version overrides of the validators on top of `config`.
+    pub fn protocol_versions_config(
+        &self,
+        config: ProtocolVersionsConfig,
+    ) -> ProtocolVersionsConfig {
+        let overrides = self
+            .validators
+            .iter()
+            .map(|v| v.supported_protocol_versions)
+            .collect::<Vec<_>>();
+        if overrides.iter().all(Option::is_none) {
+            return config;
+        }
+        ProtocolVersionsConfig::PerValidator(Arc::new(move |idx, name| {
+            overrides
+                .get(idx)
+                .copied()
+                .flatten()
+                .unwrap_or_else(|| match &config {
+                    ProtocolVersionsConfig::Default => SupportedProtocolVersions::SYSTEM_DEFAULT,
+                    ProtocolVersionsConfig::Global(versions) => *versions,
+                    ProtocolVersionsConfig::PerValidator(func) => func(idx, name),
+                })
+        }))
+    }
+
+    /// Sets the consensus parameters of the validators of `network_config`, which must have been
+    /// built from the validators of this topology.
+    pub fn apply_consensus_parameters(&self, network_config: &mut NetworkConfig) {
+        for (validator, config) in self
+            .validators
+            .iter()
+            .zip_debug_eq(network_config.validator_configs.iter_mut())
+        {
+            if let (Some(parameters), Some(consensus_config)) = (
+                &validator.consensus_parameters,
+                config.consensus_config.as_mut(),
+            ) {
+                consensus_config.parameters = Some(parameters.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConsensusParameters, Topology};
+
+    #[test]
+    fn parse_yaml_and_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = dir.path().join("topology.yaml");
+        std::fs::write(
+            &yaml,
+            r#"
+validators:
+  - name: a
+    stake: 100
+    consensus_parameters:
+      max_blocks_per_fetch: 7
+  - name: b
+    supported_protocol_versions: { min: 1, max: 2 }
+fullnodes:
+  - name: rpc
+    rpc:
+      enable-indexing: true
+links:
+  - between: [a, rpc]
+    delay_ms: 100
+"#,
+        )
+        .unwrap();
+        let topology = Topology::load(&yaml).unwrap();
+        assert_eq!(topology.validators[0].stake, Some(100));
+        let parameters = topology.validators[0]
+            .consensus_parameters
+            .as_ref()
+            .unwrap();
+        assert_eq!(parameters.max_blocks_per_fetch, 7);
+        // Parameters which are not overridden keep their defaults.
+        assert_eq!(
+            parameters.leader_timeout,
+            ConsensusParameters::default().leader_timeout
+        );
+        assert_eq!(
+            topology.fullnodes[0].rpc.as_ref().unwrap().enable_indexing,
+            Some(true)
+        );
+
+        let toml = dir.path().join("topology.toml");
+        std::fs::write(
+            &toml,
+            r#"
+[[validators]]
+name = "a"
+gas_price = 500
+
+[[validators]]
+name = "b"
+
+[[links]]
+between = ["a", "b"]
+delay_ms = 50
+jitter_ms = 10
+"#,
+        )
+        .unwrap();
+        let topology = Topology::load(&toml).unwrap();
+        assert_eq!(topology.validators[0].gas_price, Some(500));
+        assert_eq!(topology.links[0].jitter_ms, 10);
+    }
+
+    #[test]
+    fn validate() {
+        let parse = |yaml: &str| -> Topology { serde_yaml::from_str(yaml).unwrap() };
+        assert!(parse("validators: []").validate().is_err());
+        assert!(
+            parse("validators: [{name: a}, {name: a}]")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            parse("validators: [{name: a}]\nlinks: [{between: [a, b], delay_ms: 1}]")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            parse("validators: [{name: a, supported_protocol_versions: {min: 2, max: 1}}]")
+                .validate()
+                .is_err()
+        );
+        assert!(
+            parse("validators: [{name: a}, {name: b}]\nlinks: [{between: [a, b], delay_ms: 1}]")
+                .validate()
+                .is_ok()
+        );
+    }
+}
@@ -29,7 +29,8 @@ use sui_swarm_config::network_config_builder::{
     GlobalStateHashV2EnabledConfig, ProtocolVersionsConfig, SupportedProtocolVersionsCallback,
 };
 use sui_swarm_config::node_config_builder::FullnodeConfigBuilder;
-use sui_types::base_types::AuthorityName;
+use sui_swarm_config::topology::{LinkDelay, Topology};
+use sui_types::base_types::{AuthorityName, ConciseableName};
 use sui_types::object::Object;
 use sui_types::supported_protocol_versions::SupportedProtocolVersions;
 use tempfile::TempDir;
@@ -66,6 +67,7 @@ pub struct SwarmBuilder<R = OsRng> {
     funds_withdraw_scheduler_type_config: Option<FundsWithdrawSchedulerTypeConfig>,
     disable_fullnode_pruning: bool,
     state_sync_config: Option<sui_config::p2p::StateSyncConfig>,
+    topology: Option<Topology>,
     #[cfg(msim)]
     execution_time_observer_config: Option<ExecutionTimeObserverConfig>,
 }
@@ -102,6 +104,7 @@ impl SwarmBuilder {
             funds_withdraw_scheduler_type_config: None,
             disable_fullnode_pruning: false,
             state_sync_config: None,
+            topology: None,
             #[cfg(msim)]
             execution_time_observer_config: None,
         }
@@ -140,6 +143,7 @@ impl<R> SwarmBuilder<R> {
             funds_withdraw_scheduler_type_config: self.funds_withdraw_scheduler_type_config,
             disable_fullnode_pruning: self.disable_fullnode_pruning,
             state_sync_config: self.state_sync_config,
+            topology: self.topology,
             #[cfg(msim)]
             execution_time_observer_config: self.execution_time_observer_config,
         }
@@ -168,6 +172,20 @@ impl<R> SwarmBuilder<R> {
         self
     }
 
+    /// Builds the validators and fullnodes from `topology`, instead of the committee and fullnode
+    /// settings of this builder.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        assert!(self.network_config.is_none());
+        if let Some(protocol_version) = topology.protocol_version {
+            self = self.with_protocol_version(ProtocolVersion::new(protocol_version));
+        }
+        if let Some(epoch_duration_ms) = topology.epoch_duration_ms {
+            self = self.with_epoch_duration_ms(epoch_duration_ms);
+        }
+        self.topology = Some(topology);
+        self
+    }
+
     pub fn with_genesis_config(mut self, genesis_config: GenesisConfig) -> Self {
         assert!(self.network_config.is_none() && self.genesis_config.is_none());
         self.genesis_config = Some(genesis_config);
@@ -373,7 +391,15 @@ impl<R> SwarmBuilder<R> {
 
 impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
     /// Create the configured Swarm.
-    pub fn build(self) -> Swarm {
+    pub fn build(mut self) -> Swarm {
+        let topology = self.topology.take();
+        if let Some(topology) = &topology {
+            self.committee =
+                CommitteeConfig::Validators(topology.validator_genesis_configs(&mut self.rng));
+            self.supported_protocol_versions_config =
+                topology.protocol_versions_config(self.supported_protocol_versions_config.clone());
+        }
+
         let dir = if let Some(dir) = self.dir {
             SwarmDirectory::Persistent(dir)
         } else {
@@ -455,7 +481,11 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                     .with_execution_time_observer_config(execution_time_observer_config);
             }
 
-            final_builder.build()
+            let mut network_config = final_builder.build();
+            if let Some(topology) = &topology {
+                topology.apply_consensus_parameters(&mut network_config);
+            }
+            network_config
         });
 
         let mut nodes: HashMap<_, _> = network_config
@@ -498,7 +528,41 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 fullnode_config_builder.with_supported_protocol_versions(supported_versions);
         }
 
-        if self.fullnode_count > 0 {
+        let mut node_names = HashMap::new();
+        if let Some(topology) = &topology {
+            for (idx, config) in network_config.validator_configs().iter().enumerate() {
+                node_names.insert(
+                    topology.validators[idx].name.as_str(),
+                    config.protocol_public_key(),
+                );
+            }
+            for fullnode in &topology.fullnodes {
+                let mut builder = fullnode_config_builder.clone();
+                if let Some(rpc_port) = fullnode.rpc_port {
+                    builder = builder.with_rpc_port(rpc_port);
+                }
+                if let Some(rpc_config) = &fullnode.rpc {
+                    builder = builder.with_rpc_config(rpc_config.clone());
+                }
+                if !fullnode.data_ingestion {
+                    builder = builder.with_data_ingestion_dir(None);
+                }
+                if fullnode.disable_pruning {
+                    builder = builder.with_disable_pruning(true);
+                }
+                if let Some(versions) = fullnode.supported_protocol_versions {
+                    builder = builder.with_supported_protocol_versions(versions);
+                }
+                let config = builder.build(&mut OsRng, &network_config);
+                info!(
+                    "SwarmBuilder configuring full node {} with name {}",
+                    fullnode.name,
+                    config.protocol_public_key()
+                );
+                node_names.insert(fullnode.name.as_str(), config.protocol_public_key());
+                nodes.insert(config.protocol_public_key(), Node::new(config));
+            }
+        } else if self.fullnode_count > 0 {
             (0..self.fullnode_count).for_each(|idx| {
                 let mut builder = fullnode_config_builder.clone();
                 if idx == 0 {
@@ -522,11 +586,23 @@ impl<R: rand::RngCore + rand::CryptoRng> SwarmBuilder<R> {
                 nodes.insert(config.protocol_public_key(), Node::new(config));
             });
         }
+        let link_delays = topology
+            .iter()
+            .flat_map(|topology| &topology.links)
+            .map(|link| {
+                let [a, b] = &link.between;
+                (node_names[a.as_str()], node_names[b.as_str()], link.clone())
+            })
+            .collect();
+
         Swarm {
             dir,
             network_config,
             nodes,
             fullnode_config_builder,
+            link_delays,
+            #[cfg(msim)]
+            partitioned_links: Default::default(),
         }
     }
 }
@@ -539,6 +615,10 @@ pub struct Swarm {
     nodes: HashMap<AuthorityName, Node>,
     // Save a copy of the fullnode config builder to build future fullnodes.
     fullnode_config_builder: FullnodeConfigBuilder,
+    link_delays: Vec<(AuthorityName, AuthorityName, LinkDelay)>,
+    // Links cut by the last call to `set_partitioned_links`.
+    #[cfg(msim)]
+    partitioned_links: std::sync::Mutex<Vec<(AuthorityName, AuthorityName)>>,
 }
 
 impl Drop for Swarm {
@@ -559,11 +639,101 @@ impl Swarm {
 
     /// Start all nodes associated with this Swarm
     pub async fn launch(&mut self) -> Result<()> {
+        // Link delays can only be applied in the simulated network.
+        #[cfg(not(msim))]
+        anyhow::ensure!(
+            self.link_delays.is_empty(),
+            "The links of a topology are only supported when running in the simulator, remove \
+             them to start the network"
+        );
         try_join_all(self.nodes_iter_mut().map(|node| node.start())).await?;
+        #[cfg(msim)]
+        if self.has_links() {
+            self.apply_links();
+        }
         tracing::info!("Successfully launched Swarm");
         Ok(())
     }
 
+    /// Starts the node `name` if it is stopped, and applies the link delays and partitioned links
+    /// of the network to it.
+    pub async fn start_node(&self, name: &AuthorityName) -> Result<()> {
+        let node = self
+            .nodes
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Node {:?} is not part of the swarm", name.concise()))?;
+        if !node.is_running() {
+            node.start().await?;
+            #[cfg(msim)]
+            if self.has_links() {
+                self.apply_links();
+            }
+        }
+        Ok(())
+    }
+
+    /// Cuts the links between each pair of `partitioned` nodes in the simulated network, in
+    /// addition to the link delays of the topology. Replaces the links cut by a previous call, so
+    /// an empty slice heals the network. The links are applied again to nodes restarted with
+    /// `start_node`.
+    #[cfg(msim)]
+    pub fn set_partitioned_links(&self, partitioned: &[(AuthorityName, AuthorityName)]) {
+        *self.partitioned_links.lock().unwrap() = partitioned.to_vec();
+        self.apply_links();
+    }
+
+    #[cfg(msim)]
+    fn has_links(&self) -> bool {
+        !self.link_delays.is_empty() || !self.partitioned_links.lock().unwrap().is_empty()
+    }
+
+    /// Delays the messages between the nodes of each link of the topology, and cuts the
+    /// partitioned links, in the simulated network. Links of stopped nodes are left out.
+    #[cfg(msim)]
+    fn apply_links(&self) {
+        use std::sync::Arc;
+        use sui_simulator::net::NetSim;
+        use sui_simulator::net::config::{InterNodeLatencyMap, LatencyDistribution};
+        use sui_simulator::plugin::simulator;
+
+        // Messages sent over a cut link are delivered long after any test is over.
+        const PARTITION_LATENCY: Duration = Duration::from_secs(24 * 60 * 60);
+
+        let node_ids = |a: &AuthorityName, b: &AuthorityName| {
+            let node_id = |name: &AuthorityName| {
+                self.nodes[name]
+                    .get_node_handle()
+                    .map(|handle| handle.with(|node| node.get_sim_node_id()))
+            };
+            Some((node_id(a)?, node_id(b)?))
+        };
+        let mut latency_map = InterNodeLatencyMap::new();
+        for (a, b, link) in &self.link_delays {
+            let Some((a, b)) = node_ids(a, b) else {
+                continue;
+            };
+            let delay = Duration::from_millis(link.delay_ms);
+            // The range of the distribution is half open, so it can't be empty.
+            let latency = LatencyDistribution::uniform(
+                delay..delay + Duration::from_millis(link.jitter_ms + 1),
+            );
+            latency_map = latency_map.with_symmetric_link(a, b, latency);
+        }
+        for (a, b) in self.partitioned_links.lock().unwrap().iter() {
+            let Some((a, b)) = node_ids(a, b) else {
+                continue;
+            };
+            let latency = LatencyDistribution::uniform(
+                PARTITION_LATENCY..PARTITION_LATENCY + Duration::from_millis(1),
+            );
+            latency_map = latency_map.with_symmetric_link(a, b, latency);
+        }
+        let latency_map = Arc::new(latency_map);
+        simulator::<NetSim>().update_config(|config| {
+            config.latency.inter_node_latency = Some(latency_map.clone());
+        });
+    }
+
     /// Return the path to the directory where this Swarm's on-disk data is kept.
     pub fn dir(&self) -> &Path {
         self.dir.as_ref()
@@ -674,6 +844,8 @@ impl AsRef<Path> for SwarmDirectory {
 mod test {
     use super::Swarm;
     use std::num::NonZeroUsize;
+    use sui_config::RpcConfig;
+    use sui_swarm_config::topology::{FullnodeTopology, Topology, ValidatorTopology};
 
     #[tokio::test]
     async fn launch() {
@@ -695,4 +867,108 @@ mod test {
 
         println!("hello");
     }
+
+    #[tokio::test]
+    async fn launch_from_topology() {
+        telemetry_subscribers::init_for_testing();
+        let mut validators = (0..4)
+            .map(|i| ValidatorTopology {
+                name: format!("validator-{i}"),
+                stake: Some(if i == 0 { 60 } else { 20 } * 1_000_000_000_000_000),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let mut parameters = validators[0]
+            .consensus_parameters
+            .take()
+            .unwrap_or_default();
+        parameters.max_blocks_per_fetch = 7;
+        validators[0].consensus_parameters = Some(parameters);
+        let topology = Topology {
+            validators,
+            fullnodes: vec![FullnodeTopology {
+                name: "rpc".to_string(),
+                rpc: Some(RpcConfig {
+                    enable_indexing: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut swarm = Swarm::builder().with_topology(topology).build();
+        swarm.launch().await.unwrap();
+
+        let validator_configs = swarm.config().validator_configs();
+        assert_eq!(validator_configs.len(), 4);
+        let committee = swarm.config().genesis.committee();
+        let voting_power = |idx: usize| {
+            let name = validator_configs[idx].protocol_public_key();
+            committee
+                .members()
+                .find(|(member, _)| *member == name)
+                .unwrap()
+                .1
+        };
+        assert!(voting_power(0) > voting_power(1));
+        assert_eq!(
+            validator_configs[0]
+                .consensus_config
+                .as_ref()
+                .unwrap()
+                .parameters
+                .as_ref()
+                .unwrap()
+                .max_blocks_per_fetch,
+            7
+        );
+        assert!(
+            validator_configs[1]
+                .consensus_config
+                .as_ref()
+                .unwrap()
+                .parameters
+                .is_none()
+        );
+
+        let fullnodes = swarm.fullnodes().collect::<Vec<_>>();
+        assert_eq!(fullnodes.len(), 1);
+        assert_eq!(
+            fullnodes[0].config().rpc.as_ref().unwrap().enable_indexing,
+            Some(true)
+        );
+        for node in swarm.all_nodes() {
+            let is_validator = node.config().consensus_config.is_some();
+            node.health_check(is_validator).await.unwrap();
+        }
+    }
+
+    #[cfg(not(msim))]
+    #[tokio::test]
+    async fn launch_from_topology_rejects_links() {
+        telemetry_subscribers::init_for_testing();
+        let validators = (0..4)
+            .map(|i| ValidatorTopology {
+                name: format!("validator-{i}"),
+                ..Default::default()
+            })
+            .collect();
+        let topology = Topology {
+            validators,
+            links: vec![sui_swarm_config::topology::LinkDelay {
+                between: ["validator-0".to_string(), "validator-1".to_string()],
+                delay_ms: 100,
+                jitter_ms: 0,
+            }],
+            ..Default::default()
+        };
+        let mut swarm = Swarm::builder().with_topology(topology).build();
+        let error = swarm.launch().await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("only supported when running in the simulator")
+        );
+        assert!(swarm.all_nodes().all(|node| !node.is_running()));
+    }
 }
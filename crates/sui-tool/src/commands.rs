@@ -1,12 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::consensus_observer::observe_consensus;
+use crate::consensus_replay::replay_consensus;
+use crate::consensus_simulation::simulate_consensus;
 use crate::db_tool::{DbToolCommand, execute_db_tool_command, print_db_all_tables};
 use crate::{
     ConciseObjectOutput, GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
     check_completed_snapshot, download_db_snapshot, download_formal_snapshot,
     get_latest_available_epoch, get_object, get_transaction_block, make_clients,
-    restore_from_db_checkpoint,
+    print_execution_time_estimates, restore_from_db_checkpoint,
+    restore_from_incremental_db_checkpoint,
 };
 use anyhow::Result;
 use consensus_core::storage::{Store, rocksdb_store::RocksDBStore};
@@ -14,6 +18,7 @@ use consensus_core::{BlockAPI, CommitAPI, CommitRange};
 use futures::TryStreamExt;
 use futures::future::join_all;
 use std::path::PathBuf;
+use std::time::Duration;
 use std::{collections::BTreeMap, env, sync::Arc};
 use sui_config::genesis::Genesis;
 use sui_core::authority_client::AuthorityAPI;
@@ -22,6 +27,7 @@ use sui_replay::{ReplayToolCommand, execute_replay_command};
 use sui_rpc_api::Client;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages_consensus::ConsensusTransaction;
+use sui_types::multiaddr::Multiaddr;
 use sui_types::transaction::Transaction;
 use telemetry_subscribers::TracingHandle;
 
@@ -43,6 +49,16 @@ pub enum Verbosity {
     Verbose,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum DagFormat {
+    /// Graphviz DOT
+    Dot,
+    /// SVG, rendered with the `dot` command of Graphviz
+    Svg,
+    /// DAG format of consensus tests
+    Dsl,
+}
+
 #[derive(Parser)]
 pub enum ToolCommand {
     #[command(name = "scan-consensus-commits")]
@@ -55,6 +71,77 @@ pub enum ToolCommand {
         end_commit: Option<u32>,
     },
 
+    /// Follow consensus of the current epoch through the observer server of a validator. Accepted
+    /// blocks and certified commits are verified against the committee and written to a local
+    /// consensus store, which can be read with `scan-consensus-commits`.
+    #[command(name = "consensus-observe")]
+    ConsensusObserve {
+        /// RPC address to fetch the committee and protocol config of the current epoch
+        #[arg(long = "fullnode-rpc-url")]
+        fullnode_rpc_url: String,
+        /// Name of the validator to observe
+        #[arg(long)]
+        validator: String,
+        /// Address of the observer server of the validator, e.g. /dns/<host>/udp/<port>
+        #[arg(long = "observer-address")]
+        observer_address: Multiaddr,
+        /// Directory of the local consensus stores, one per epoch
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+        /// Network key of the observer. A new key is generated if not provided, which only
+        /// works with validators without an observer allowlist.
+        #[arg(long = "network-key-file")]
+        network_key_file: Option<PathBuf>,
+        /// Interval between progress reports, in seconds
+        #[arg(long = "stats-interval-secs", default_value_t = 10)]
+        stats_interval_secs: u64,
+    },
+
+    /// Replay the consensus commit rule over the blocks of a validator's consensus store, render
+    /// the DAG of a round range with its leaders and their decisions, and compare the replayed
+    /// commits with the stored commits. Exits with an error if they differ.
+    #[command(name = "replay-consensus")]
+    ReplayConsensus {
+        /// RPC address to fetch the committee and protocol config of the epoch
+        #[arg(long = "fullnode-rpc-url")]
+        fullnode_rpc_url: String,
+        /// Path of the consensus store of the epoch, e.g. <consensus db>/<epoch>
+        #[arg(long = "db-path")]
+        db_path: String,
+        /// Epoch of the consensus store
+        #[arg(long)]
+        epoch: u64,
+        /// First round of the rendered DAG. Blocks are always replayed from the start of the epoch
+        #[arg(long = "start-round", default_value_t = 1)]
+        start_round: u32,
+        /// Last replayed and rendered round. Defaults to the highest round in the store
+        #[arg(long = "end-round")]
+        end_round: Option<u32>,
+        /// Format of the rendered DAG
+        #[arg(long, value_enum, default_value_t = DagFormat::Dot)]
+        format: DagFormat,
+        /// File to write the rendered DAG to. Written to stdout if not set
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Simulate consensus of a committee over simulated time and network, once per parameter
+    /// set of a YAML file, and compare commit latency, throughput, leader skip rate and
+    /// reputation scores of the parameter sets.
+    #[command(name = "simulate-consensus")]
+    SimulateConsensus {
+        /// YAML file with a `simulation` config of the committee, network and authority
+        /// behaviors, and a list of `parameter_sets` with a `name` and consensus `parameters`
+        #[arg(long)]
+        config: PathBuf,
+        /// Protocol version of the consensus protocol config. Defaults to the latest version
+        #[arg(long = "protocol-version")]
+        protocol_version: Option<u64>,
+        /// File to write the reports to as JSON
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
     /// Inspect if a specific object is or all gas objects owned by an address are locked by validators
     #[command(name = "locked-object")]
     LockedObject {
@@ -210,6 +297,36 @@ pub enum ToolCommand {
         db_checkpoint_path: PathBuf,
     },
 
+    /// Restores the db of a node from a db checkpoint that was taken through the admin API and
+    /// uploaded incrementally to the db checkpoint object store of its config.
+    #[command(name = "restore-db-incremental")]
+    RestoreFromIncrementalDBCheckpoint {
+        #[arg(long = "config-path")]
+        config_path: PathBuf,
+        /// Sequence number of the checkpoint the db checkpoint was taken at. Defaults to the
+        /// latest db checkpoint available.
+        #[arg(long = "checkpoint")]
+        checkpoint_seq: Option<u64>,
+        /// Number of parallel downloads to perform.
+        #[arg(long = "num-parallel-downloads", default_value_t = 50)]
+        num_parallel_downloads: usize,
+    },
+
+    /// Print the execution time estimates and per-object utilization a node uses for
+    /// shared object congestion control, fetched from its admin API.
+    #[command(name = "execution-time-estimates")]
+    ExecutionTimeEstimates {
+        #[arg(long = "admin-url", default_value = "http://127.0.0.1:1337")]
+        admin_url: String,
+        /// Print the raw JSON dump, e.g. to use as `--estimates` of
+        /// `db-tool simulate-congestion`.
+        #[arg(long)]
+        json: bool,
+        /// Maximum number of commands and objects to print.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+
     #[clap(
         name = "download-db-snapshot",
         about = "Downloads the legacy database snapshot via cloud object store, outputs to local disk"
@@ -468,6 +585,51 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::ConsensusObserve {
+                fullnode_rpc_url,
+                validator,
+                observer_address,
+                db_path,
+                network_key_file,
+                stats_interval_secs,
+            } => {
+                observe_consensus(
+                    fullnode_rpc_url,
+                    validator,
+                    observer_address,
+                    db_path,
+                    network_key_file,
+                    Duration::from_secs(stats_interval_secs),
+                )
+                .await?;
+            }
+            ToolCommand::ReplayConsensus {
+                fullnode_rpc_url,
+                db_path,
+                epoch,
+                start_round,
+                end_round,
+                format,
+                output,
+            } => {
+                replay_consensus(
+                    fullnode_rpc_url,
+                    db_path,
+                    epoch,
+                    start_round,
+                    end_round,
+                    format,
+                    output,
+                )
+                .await?;
+            }
+            ToolCommand::SimulateConsensus {
+                config,
+                protocol_version,
+                output,
+            } => {
+                simulate_consensus(config, protocol_version, output).await?;
+            }
             ToolCommand::LockedObject {
                 id,
                 fullnode_rpc_url,
@@ -644,6 +806,26 @@ impl ToolCommand {
                 let config = sui_config::NodeConfig::load(config_path)?;
                 restore_from_db_checkpoint(&config, &db_checkpoint_path).await?;
             }
+            ToolCommand::RestoreFromIncrementalDBCheckpoint {
+                config_path,
+                checkpoint_seq,
+                num_parallel_downloads,
+            } => {
+                let config = sui_config::NodeConfig::load(config_path)?;
+                restore_from_incremental_db_checkpoint(
+                    &config,
+                    checkpoint_seq,
+                    num_parallel_downloads,
+                )
+                .await?;
+            }
+            ToolCommand::ExecutionTimeEstimates {
+                admin_url,
+                json,
+                limit,
+            } => {
+                print_execution_time_estimates(&admin_url, json, limit).await?;
+            }
             ToolCommand::DownloadFormalSnapshot {
                 epoch,
                 genesis,
@@ -0,0 +1,172 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, anyhow};
+use comfy_table::{ContentArrangement, Table};
+use consensus_config::{
+    Authority, AuthorityName, Committee, NetworkKeyPair, NetworkPublicKey, Parameters, PeerRecord,
+    ProtocolPublicKey, Stake,
+};
+use consensus_core::{ObserverClient, storage::rocksdb_store::RocksDBStore};
+use consensus_observer::{ConsensusObserver, ObserverStats};
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use itertools::Itertools;
+use prometheus::Registry;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_core::consensus_manager::to_consensus_protocol_config;
+use sui_keys::keypair_file::read_network_keypair_from_file;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_rpc_api::Client;
+use sui_types::crypto::get_key_pair;
+use sui_types::multiaddr::Multiaddr;
+use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use tokio::time::Instant;
+
+/// Observes consensus of the current epoch through the observer server of `validator`, writing
+/// verified blocks and commits to a consensus store under `db_path`, and printing progress every
+/// `stats_interval`.
+pub async fn observe_consensus(
+    fullnode_rpc_url: String,
+    validator: String,
+    observer_address: Multiaddr,
+    db_path: PathBuf,
+    network_key_file: Option<PathBuf>,
+    stats_interval: Duration,
+) -> Result<()> {
+    let sui_client = Client::new(fullnode_rpc_url)?;
+    let chain = sui_client.get_chain_identifier().await?.chain();
+    let system_state = sui_client.get_system_state_summary(None).await?;
+    let committee = consensus_committee(&system_state)?;
+    let peer = committee
+        .authorities()
+        .find(|(_, authority)| authority.hostname == validator)
+        .map(|(index, _)| index)
+        .ok_or_else(|| {
+            anyhow!(
+                "Validator {validator} is not in the committee of epoch {}",
+                system_state.epoch
+            )
+        })?;
+    let protocol_config =
+        ProtocolConfig::get_for_version(ProtocolVersion::new(system_state.protocol_version), chain);
+
+    let network_keypair = match network_key_file {
+        Some(path) => read_network_keypair_from_file(path)?,
+        None => get_key_pair().1,
+    };
+    let network_keypair = NetworkKeyPair::new(network_keypair);
+    // The key must be in the observer allowlist of the validator, if it has one.
+    println!(
+        "Observer network public key: {}",
+        Hex::encode(network_keypair.public().to_bytes())
+    );
+
+    let mut parameters = Parameters::default();
+    parameters.tonic.observer_peers = vec![PeerRecord {
+        public_key: committee.authority(peer).network_key.clone(),
+        address: observer_address,
+    }];
+    let client = ObserverClient::new(
+        system_state.epoch_start_timestamp_ms,
+        committee,
+        parameters,
+        to_consensus_protocol_config(&protocol_config, chain),
+        network_keypair,
+        Registry::new(),
+    );
+
+    // Rounds and commit indices restart every epoch, so each epoch has its own store, like the
+    // consensus store of validators.
+    let store_path = db_path.join(system_state.epoch.to_string());
+    let store_path = store_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid db path {}", store_path.display()))?;
+    let store = Arc::new(RocksDBStore::new(store_path, false));
+    let observer = Arc::new(ConsensusObserver::new(client, peer, store)?);
+
+    let printer = {
+        let observer = observer.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(stats_interval);
+            let mut previous = (Instant::now(), observer.stats());
+            loop {
+                interval.tick().await;
+                let stats = observer.stats();
+                print_stats(&stats, &previous.1, previous.0.elapsed());
+                previous = (Instant::now(), stats);
+            }
+        })
+    };
+    let result = observer.run().await;
+    printer.abort();
+    result.map_err(|e| anyhow!("Consensus observer failed: {e}"))
+}
+
+/// Builds the consensus committee from the system state, in the same way as validators do
+/// from the epoch start system state.
+pub(crate) fn consensus_committee(system_state: &SuiSystemStateSummary) -> Result<Committee> {
+    let mut authorities = vec![];
+    for validator in &system_state.active_validators {
+        authorities.push(Authority {
+            stake: validator.voting_power as Stake,
+            address: validator.primary_address.parse()?,
+            hostname: validator.name.clone(),
+            authority_name: AuthorityName::from_bytes(&validator.protocol_pubkey_bytes),
+            protocol_key: ProtocolPublicKey::new(Ed25519PublicKey::from_bytes(
+                &validator.worker_pubkey_bytes,
+            )?),
+            network_key: NetworkPublicKey::new(Ed25519PublicKey::from_bytes(
+                &validator.network_pubkey_bytes,
+            )?),
+        });
+    }
+    authorities.sort_by(|a1, a2| a1.authority_name.cmp(&a2.authority_name));
+    Ok(Committee::new(system_state.epoch, authorities))
+}
+
+fn print_stats(stats: &ObserverStats, previous: &ObserverStats, elapsed: Duration) {
+    let elapsed = elapsed.as_secs_f64().max(f64::EPSILON);
+    let rate = |current: u64, previous: u64| current.saturating_sub(previous) as f64 / elapsed;
+
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Authority",
+            "Highest round",
+            "Blocks",
+            "Blocks/s",
+            "Committed leaders",
+        ]);
+    for (authority, previous) in stats.authorities.iter().zip_eq(&previous.authorities) {
+        table.add_row(vec![
+            authority.hostname.clone(),
+            authority.highest_round.to_string(),
+            authority.blocks.to_string(),
+            format!("{:.1}", rate(authority.blocks, previous.blocks)),
+            authority.committed_leaders.to_string(),
+        ]);
+    }
+    println!("{table}");
+    println!(
+        "round {} ({:.1} rounds/s), commit {} of {} ({:.1} commits/s), \
+         {} rounds without committed leader, {} missing blocks fetched",
+        stats.highest_round(),
+        rate(
+            stats.highest_round() as u64,
+            previous.highest_round() as u64
+        ),
+        stats.last_commit_index,
+        stats.highest_commit_index,
+        rate(
+            stats.last_commit_index as u64,
+            previous.last_commit_index as u64
+        ),
+        stats.rounds_without_committed_leader,
+        stats.missing_blocks_fetched,
+    );
+}
@@ -0,0 +1,190 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commands::DagFormat;
+use crate::consensus_observer::consensus_committee;
+use anyhow::{Result, anyhow, bail};
+use comfy_table::{ContentArrangement, Table};
+use consensus_config::Parameters;
+use consensus_core::storage::{Store, rocksdb_store::RocksDBStore};
+use consensus_core::{BlockAPI, CommitAPI, DagReplay, LeaderDecision};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use sui_core::consensus_manager::to_consensus_protocol_config;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_rpc_api::Client;
+
+/// Replays the commit rule over the blocks of a consensus store of `epoch` up to `end_round`,
+/// renders the DAG between `start_round` and `end_round`, and compares the replayed commits with
+/// the commits in the store. Fails if no commit was replayed or if a replayed commit differs from
+/// the stored one, once the DAG is rendered so that it can be used to look into the divergence.
+pub async fn replay_consensus(
+    fullnode_rpc_url: String,
+    db_path: String,
+    epoch: u64,
+    start_round: u32,
+    end_round: Option<u32>,
+    format: DagFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let sui_client = Client::new(fullnode_rpc_url)?;
+    let chain = sui_client.get_chain_identifier().await?.chain();
+    let system_state = sui_client.get_system_state_summary(Some(epoch)).await?;
+    let committee = consensus_committee(&system_state)?;
+    if format == DagFormat::Dsl && committee.size() > 26 {
+        bail!(
+            "The DAG format of tests supports up to 26 authorities, the committee has {}",
+            committee.size()
+        );
+    }
+    let protocol_config =
+        ProtocolConfig::get_for_version(ProtocolVersion::new(system_state.protocol_version), chain);
+
+    let store = RocksDBStore::new(&db_path, true);
+    let end_round = match end_round {
+        Some(end_round) => end_round,
+        None => {
+            let mut highest_round = 0;
+            for (authority, _) in committee.authorities() {
+                if let Some(block) = store
+                    .scan_last_blocks_by_author(authority, 1, None)
+                    .map_err(|e| anyhow!("Failed to read blocks: {e}"))?
+                    .last()
+                {
+                    highest_round = highest_round.max(block.round());
+                }
+            }
+            highest_round
+        }
+    };
+    if start_round > end_round {
+        bail!("Start round {start_round} is above end round {end_round}");
+    }
+
+    let mut replay = DagReplay::new(
+        system_state.epoch_start_timestamp_ms,
+        committee,
+        Parameters::default(),
+        to_consensus_protocol_config(&protocol_config, chain),
+    );
+    replay
+        .load_from_store(&store, end_round)
+        .map_err(|e| anyhow!("Failed to replay blocks: {e}"))?;
+    print_leaders(&replay, start_round, end_round);
+
+    let rounds = start_round..=end_round;
+    let rendered = match format {
+        DagFormat::Dot | DagFormat::Svg => replay.render_dot(rounds),
+        DagFormat::Dsl => replay.render_dag_dsl(rounds),
+    }
+    .map_err(|e| anyhow!("Failed to render the DAG: {e}"))?
+    .into_bytes();
+    let rendered = if format == DagFormat::Svg {
+        dot_to_svg(&rendered)?
+    } else {
+        rendered
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            println!("DAG written to {}", path.display());
+        }
+        None => std::io::stdout().write_all(&rendered)?,
+    }
+    compare_commits(&replay, &store)
+}
+
+// Reports are printed to stderr, so the rendered DAG can be piped from stdout. The store can miss
+// the last replayed commits, as a node may have stored the blocks of a commit but not the commit.
+fn compare_commits(replay: &DagReplay, store: &RocksDBStore) -> Result<()> {
+    let replayed = replay
+        .commits((1..=u32::MAX).into())
+        .map_err(|e| anyhow!("Failed to read replayed commits: {e}"))?;
+    let Some(last_replayed) = replayed.last() else {
+        bail!("No commit was replayed");
+    };
+    let stored = store
+        .scan_commits((1..=last_replayed.index()).into())
+        .map_err(|e| anyhow!("Failed to read commits: {e}"))?;
+
+    for (i, replayed_commit) in replayed.iter().enumerate() {
+        let Some(stored_commit) = stored.get(i) else {
+            eprintln!(
+                "Replayed {} commits, only the first {} commits are in the store and they match",
+                replayed.len(),
+                stored.len()
+            );
+            return Ok(());
+        };
+        if replayed_commit.reference() != stored_commit.reference() {
+            bail!(
+                "Replayed commit {} with leader {} differs from stored commit {} with leader {}",
+                replayed_commit.reference(),
+                replayed_commit.leader(),
+                stored_commit.reference(),
+                stored_commit.leader(),
+            );
+        }
+    }
+    eprintln!(
+        "All {} replayed commits match the commits in the store",
+        replayed.len()
+    );
+    Ok(())
+}
+
+fn print_leaders(replay: &DagReplay, start_round: u32, end_round: u32) {
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Round", "Leader", "Decision", "Commit"]);
+    for leader in replay.leaders(start_round..=end_round) {
+        let (decision, commit) = match leader.decision {
+            LeaderDecision::Commit {
+                block,
+                direct,
+                commit_index,
+            } => (
+                format!(
+                    "{} commit of {block}",
+                    if direct { "Direct" } else { "Indirect" }
+                ),
+                commit_index.to_string(),
+            ),
+            LeaderDecision::Skip => ("Skip".to_string(), String::new()),
+            LeaderDecision::Undecided => ("Undecided".to_string(), String::new()),
+        };
+        table.add_row(vec![
+            leader.round.to_string(),
+            replay
+                .committee()
+                .authority(leader.authority)
+                .hostname
+                .clone(),
+            decision,
+            commit,
+        ]);
+    }
+    eprintln!("{table}");
+}
+
+/// Converts a DOT graph to SVG with the `dot` command of Graphviz.
+fn dot_to_svg(dot: &[u8]) -> Result<Vec<u8>> {
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run the dot command of Graphviz: {e}"))?;
+    child.stdin.take().expect("Stdin is piped").write_all(dot)?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "dot failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, anyhow, bail};
+use comfy_table::{ContentArrangement, Table};
+use consensus_config::Parameters;
+use consensus_core::{LatencyStats, SimulationConfig, SimulationReport, Simulator};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use sui_core::consensus_manager::to_consensus_protocol_config;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+
+/// Simulated committee and network, and the consensus parameters to evaluate on them.
+#[derive(Deserialize)]
+struct SimulationFile {
+    #[serde(default)]
+    simulation: SimulationConfig,
+    parameter_sets: Vec<ParameterSet>,
+}
+
+#[derive(Deserialize)]
+struct ParameterSet {
+    name: String,
+    /// Overrides of the default parameters.
+    #[serde(default)]
+    parameters: Parameters,
+}
+
+#[derive(Serialize)]
+struct NamedReport<'a> {
+    name: &'a str,
+    report: &'a SimulationReport,
+}
+
+/// Runs the consensus simulation of `config_path` once per parameter set, and prints the reports
+/// side by side.
+pub async fn simulate_consensus(
+    config_path: PathBuf,
+    protocol_version: Option<u64>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let file: SimulationFile = serde_yaml::from_reader(std::fs::File::open(&config_path)?)
+        .map_err(|e| anyhow!("Failed to parse {}: {e}", config_path.display()))?;
+    if file.parameter_sets.is_empty() {
+        bail!("No parameter set in {}", config_path.display());
+    }
+    let protocol_version = protocol_version
+        .map(ProtocolVersion::new)
+        .unwrap_or(ProtocolVersion::MAX);
+    let protocol_config = to_consensus_protocol_config(
+        &ProtocolConfig::get_for_version(protocol_version, Chain::Unknown),
+        Chain::Unknown,
+    );
+
+    let mut reports = vec![];
+    for parameter_set in &file.parameter_sets {
+        eprintln!("Simulating parameter set {}", parameter_set.name);
+        let report = Simulator::new(
+            file.simulation.clone(),
+            parameter_set.parameters.clone(),
+            protocol_config.clone(),
+        )
+        .map_err(|e| anyhow!("Invalid simulation: {e}"))?
+        .run()
+        .await
+        .map_err(|e| anyhow!("Simulation of {} failed: {e}", parameter_set.name))?;
+        reports.push((parameter_set.name.as_str(), report));
+    }
+
+    print_summary(&reports);
+    for (name, report) in &reports {
+        print_authorities(name, report);
+    }
+    if let Some(path) = output {
+        let reports = reports
+            .iter()
+            .map(|(name, report)| NamedReport { name, report })
+            .collect::<Vec<_>>();
+        std::fs::write(&path, serde_json::to_string_pretty(&reports)?)?;
+        println!("Reports written to {}", path.display());
+    }
+    Ok(())
+}
+
+fn print_summary(reports: &[(&str, SimulationReport)]) {
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Parameter set",
+            "Rounds/s",
+            "Commits/s",
+            "Committed blocks/s",
+            "Leader latency mean / p50 / p95 (ms)",
+            "Block latency mean / p50 / p95 (ms)",
+            "Leader skip rate",
+            "Schedule updates",
+        ]);
+    for (name, report) in reports {
+        let latency = |stats: &LatencyStats| {
+            format!("{:.0} / {} / {}", stats.mean_ms, stats.p50_ms, stats.p95_ms)
+        };
+        table.add_row(vec![
+            name.to_string(),
+            format!("{:.1}", report.rounds_per_second()),
+            format!("{:.1}", report.commits_per_second()),
+            format!("{:.1}", report.committed_blocks_per_second()),
+            latency(&report.leader_commit_latency),
+            latency(&report.block_commit_latency),
+            format!("{:.1}%", report.leader_skip_rate * 100.0),
+            report.reputation.len().to_string(),
+        ]);
+    }
+    println!("{table}");
+}
+
+fn print_authorities(name: &str, report: &SimulationReport) {
+    let mut header = vec![
+        "Authority".to_string(),
+        "Behavior".to_string(),
+        "Proposed blocks".to_string(),
+        "Committed leaders".to_string(),
+        "Skipped leaders".to_string(),
+    ];
+    // Reputation scores of each schedule update, and whether the authority was swapped out.
+    header.extend(
+        report
+            .reputation
+            .iter()
+            .map(|snapshot| format!("Score @{}", snapshot.commit_index)),
+    );
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(header);
+    for (i, authority) in report.authorities.iter().enumerate() {
+        let mut row = vec![
+            authority.hostname.clone(),
+            format!("{:?}", authority.behavior),
+            authority.proposed_blocks.to_string(),
+            authority.committed_leaders.to_string(),
+            authority.skipped_leaders.to_string(),
+        ];
+        row.extend(report.reputation.iter().map(|snapshot| {
+            let bad = snapshot.bad_nodes.iter().any(|index| index.value() == i);
+            format!("{}{}", snapshot.scores[i], if bad { " (bad)" } else { "" })
+        }));
+        table.add_row(row);
+    }
+    println!(
+        "Parameter set {name}, observed by authority {}:",
+        report.reference_authority
+    );
+    println!("{table}");
+}
@@ -0,0 +1,227 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, bail};
+use clap::{Parser, ValueEnum};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use sui_core::authority::authority_per_epoch_store::{AuthorityEpochTables, EPOCH_DB_PREFIX};
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_types::base_types::EpochId;
+use tracing::info;
+use typed_store::migration::{
+    DEFAULT_MIGRATION_BATCH_SIZE, DEFAULT_MIGRATION_CONCURRENCY, MigrationOptions,
+    MigrationProgress, TableMigrator, migrate_tables,
+};
+#[cfg(tidehunter)]
+use typed_store::rocks::MetricConf;
+
+/// Name of the file in the target directory that records the progress of a migration.
+const PROGRESS_FILE_NAME: &str = "migration-progress.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StorageBackend {
+    Rocksdb,
+    Tidehunter,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct MigrateOptions {
+    /// The storage backend of the database at `--db-path`
+    #[arg(long, value_enum)]
+    from: StorageBackend,
+    /// The storage backend to migrate the database to
+    #[arg(long, value_enum)]
+    to: StorageBackend,
+    /// The path of the migrated database. Use it as the `db-path` of the node once the migration
+    /// completes. An interrupted migration is resumed by running it again with the same path.
+    #[arg(long)]
+    target_path: PathBuf,
+    /// The number of rows written to the target in a single batch
+    #[arg(long, default_value_t = DEFAULT_MIGRATION_BATCH_SIZE)]
+    batch_size: usize,
+    /// The number of tables copied in parallel
+    #[arg(long, default_value_t = DEFAULT_MIGRATION_CONCURRENCY)]
+    concurrency: usize,
+    /// Do not compare the row count and checksum of every table after copying it
+    #[arg(long)]
+    skip_verification: bool,
+}
+
+/// Copies the perpetual and epoch tables of the node database at `db_path` to a database using
+/// a different storage backend. The node must be stopped while the migration is running.
+pub fn migrate(db_path: &Path, opts: MigrateOptions) -> anyhow::Result<()> {
+    if db_path == opts.target_path {
+        bail!("--target-path must be different from --db-path");
+    }
+
+    let source_store = db_path.join("store");
+    let target_store = opts.target_path.join("store");
+    std::fs::create_dir_all(&target_store)?;
+
+    let progress_path = opts.target_path.join(PROGRESS_FILE_NAME);
+    let progress: BTreeMap<String, MigrationProgress> = if progress_path.exists() {
+        let progress = std::fs::read(&progress_path)
+            .with_context(|| format!("failed to read {}", progress_path.display()))?;
+        info!("Resuming migration from {}", progress_path.display());
+        serde_json::from_slice(&progress)?
+    } else {
+        BTreeMap::new()
+    };
+    let progress = Mutex::new(progress);
+
+    let options = MigrationOptions {
+        batch_size: opts.batch_size,
+        concurrency: opts.concurrency,
+        verify: !opts.skip_verification,
+    };
+
+    // Stores are migrated in turn, with the tables of each store copied in parallel.
+    {
+        let source = open_perpetual_tables(&source_store, opts.from)?;
+        let target = open_perpetual_tables(&target_store, opts.to)?;
+        migrate_store(
+            "perpetual",
+            &AuthorityPerpetualTables::table_migrations(&source, &target),
+            &options,
+            &progress,
+            &progress_path,
+        )?;
+    }
+
+    for epoch in list_epochs(&source_store)? {
+        let source = open_epoch_tables(epoch, &source_store, opts.from)?;
+        let target = open_epoch_tables(epoch, &target_store, opts.to)?;
+        migrate_store(
+            &format!("{EPOCH_DB_PREFIX}{epoch}"),
+            &AuthorityEpochTables::table_migrations(&source, &target),
+            &options,
+            &progress,
+            &progress_path,
+        )?;
+    }
+
+    println!(
+        "Migrated {} to {}",
+        db_path.display(),
+        opts.target_path.display()
+    );
+    Ok(())
+}
+
+fn migrate_store(
+    store: &str,
+    migrations: &[Box<dyn TableMigrator + '_>],
+    options: &MigrationOptions,
+    progress: &Mutex<BTreeMap<String, MigrationProgress>>,
+    progress_path: &Path,
+) -> anyhow::Result<()> {
+    let store_progress = progress
+        .lock()
+        .unwrap()
+        .get(store)
+        .cloned()
+        .unwrap_or_default();
+
+    info!("Migrating {} tables of {store}", migrations.len());
+    let store_progress = migrate_tables(migrations, store_progress, options, &|store_progress| {
+        let mut progress = progress.lock().unwrap();
+        progress.insert(store.to_owned(), store_progress.clone());
+        write_progress(progress_path, &progress)
+    })?;
+
+    for (table, table_progress) in &store_progress.tables {
+        match table_progress.verified {
+            Some(checksum) => info!(
+                "{store}.{table}: copied {} rows, checksum {:#018x}",
+                checksum.num_rows, checksum.checksum
+            ),
+            None => info!(
+                "{store}.{table}: copied {} rows",
+                table_progress.num_rows_copied
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn write_progress(
+    path: &Path,
+    progress: &BTreeMap<String, MigrationProgress>,
+) -> anyhow::Result<()> {
+    // Write to a temporary file first so that the progress is never left half written.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec_pretty(progress)?)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn list_epochs(store_path: &Path) -> anyhow::Result<Vec<EpochId>> {
+    let mut epochs = vec![];
+    for entry in std::fs::read_dir(store_path)? {
+        let name = entry?.file_name();
+        if let Some(epoch) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(EPOCH_DB_PREFIX))
+            .and_then(|epoch| epoch.parse().ok())
+        {
+            epochs.push(epoch);
+        }
+    }
+    epochs.sort();
+    Ok(epochs)
+}
+
+#[cfg(not(tidehunter))]
+fn open_perpetual_tables(
+    parent_path: &Path,
+    backend: StorageBackend,
+) -> anyhow::Result<AuthorityPerpetualTables> {
+    match backend {
+        StorageBackend::Rocksdb => Ok(AuthorityPerpetualTables::open(parent_path, None, None)),
+        StorageBackend::Tidehunter => bail!("this build of sui-tool does not support tidehunter"),
+    }
+}
+
+#[cfg(tidehunter)]
+fn open_perpetual_tables(
+    parent_path: &Path,
+    backend: StorageBackend,
+) -> anyhow::Result<AuthorityPerpetualTables> {
+    match backend {
+        StorageBackend::Rocksdb => Ok(AuthorityPerpetualTables::open_tables_rocksdb(
+            AuthorityPerpetualTables::path(parent_path),
+            MetricConf::new("perpetual"),
+        )),
+        StorageBackend::Tidehunter => Ok(AuthorityPerpetualTables::open(parent_path, None, None)),
+    }
+}
+
+#[cfg(not(tidehunter))]
+fn open_epoch_tables(
+    epoch: EpochId,
+    parent_path: &Path,
+    backend: StorageBackend,
+) -> anyhow::Result<AuthorityEpochTables> {
+    match backend {
+        StorageBackend::Rocksdb => Ok(AuthorityEpochTables::open(epoch, parent_path, None)),
+        StorageBackend::Tidehunter => bail!("this build of sui-tool does not support tidehunter"),
+    }
+}
+
+#[cfg(tidehunter)]
+fn open_epoch_tables(
+    epoch: EpochId,
+    parent_path: &Path,
+    backend: StorageBackend,
+) -> anyhow::Result<AuthorityEpochTables> {
+    match backend {
+        StorageBackend::Rocksdb => Ok(AuthorityEpochTables::open_tables_rocksdb(
+            AuthorityEpochTables::path(epoch, parent_path),
+            MetricConf::new("epoch"),
+        )),
+        StorageBackend::Tidehunter => Ok(AuthorityEpochTables::open(epoch, parent_path, None)),
+    }
+}
@@ -3,6 +3,8 @@
 
 use self::db_dump::{StoreName, dump_table, list_tables, table_summary};
 use self::index_search::{SearchRange, search_index};
+use self::migrate::{MigrateOptions, migrate};
+use self::simulate_congestion::{SimulateCongestionOptions, simulate_congestion};
 use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
 use anyhow::{anyhow, bail};
 use clap::Parser;
@@ -19,6 +21,8 @@ use sui_types::messages_checkpoint::{CheckpointDigest, CheckpointSequenceNumber}
 use typed_store::rocks::{MetricConf, safe_drop_db};
 pub mod db_dump;
 mod index_search;
+mod migrate;
+mod simulate_congestion;
 
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
@@ -41,6 +45,8 @@ pub enum DbToolCommand {
     PruneObjects,
     PruneCheckpoints,
     SetCheckpointWatermark(SetCheckpointWatermarkOptions),
+    Migrate(MigrateOptions),
+    SimulateCongestion(SimulateCongestionOptions),
 }
 
 #[derive(Parser)]
@@ -232,6 +238,8 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
             Ok(())
         }
         DbToolCommand::SetCheckpointWatermark(d) => set_checkpoint_watermark(&db_path, d),
+        DbToolCommand::Migrate(m) => migrate(&db_path, m),
+        DbToolCommand::SimulateCongestion(s) => simulate_congestion(&db_path, s),
     }
 }
 
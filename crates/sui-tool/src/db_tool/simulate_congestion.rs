@@ -0,0 +1,375 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, anyhow, bail};
+use clap::Parser;
+use comfy_table::{ContentArrangement, Table};
+use itertools::Itertools;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_core::authority::authority_store_pruner::PrunerWatermarks;
+use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::congestion_simulator::{
+    CongestionSimulationParams, CongestionSimulationReport, CongestionSimulator,
+    SequencedObservations, SimulatedCommit, consensus_commit_prologue_round_and_timestamp,
+    load_sequenced_observations,
+};
+use sui_core::authority::execution_time_estimator::ExecutionTimeEstimatesDump;
+use sui_core::checkpoints::CheckpointStore;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::transaction::{TransactionDataAPI, VerifiedTransaction};
+use tracing::{info, warn};
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct SimulateCongestionOptions {
+    /// First checkpoint of the range to replay
+    #[arg(long)]
+    start_checkpoint: CheckpointSequenceNumber,
+    /// Last checkpoint of the range to replay, inclusive
+    #[arg(long)]
+    end_checkpoint: CheckpointSequenceNumber,
+    /// Execution time estimates to start from, as dumped by
+    /// `sui-tool execution-time-estimates --json`. They stand in for the observations stored at
+    /// the end of the epoch preceding the range, which are not kept in the db. If not set, the
+    /// estimator starts from the default duration of every command.
+    #[arg(long)]
+    estimates: Option<PathBuf>,
+    /// Protocol version whose congestion control parameters are simulated as the baseline.
+    /// Defaults to the protocol version of the epoch of each checkpoint.
+    #[arg(long)]
+    protocol_version: Option<u64>,
+    #[arg(long, value_enum, default_value = "mainnet")]
+    chain: Chain,
+    /// Additional parameters to simulate, as comma separated `name=value` overrides of the
+    /// baseline, e.g. `target_utilization=50,allowed_txn_cost_overage_burst_limit_us=10000`.
+    /// May be repeated.
+    #[arg(long = "scenario")]
+    scenarios: Vec<String>,
+}
+
+/// Committee, protocol version and execution time observations of an epoch being replayed.
+struct ReplayedEpoch {
+    committee: Arc<Committee>,
+    protocol_version: ProtocolVersion,
+    /// Observations sequenced in the epoch, along with the checkpoint they are replayed before.
+    observations: VecDeque<(CheckpointSequenceNumber, SequencedObservations)>,
+}
+
+/// Replays the consensus commits of a range of checkpoints through the execution time estimator
+/// and congestion control, reporting how many transactions would have been deferred under the
+/// congestion control parameters of each scenario.
+///
+/// Transactions are replayed in the commit they were executed in, so transactions which were
+/// actually deferred are only submitted to the simulation once they were scheduled. The
+/// execution time observations validators shared during each epoch are read from its epoch db
+/// and fed to the estimator as the replay progresses. Observations are not recorded along with
+/// the commit they were sequenced in, so they are spread evenly over the checkpoints of their
+/// epoch in the order of their generation.
+pub fn simulate_congestion(db_path: &Path, opts: SimulateCongestionOptions) -> anyhow::Result<()> {
+    if opts.start_checkpoint > opts.end_checkpoint {
+        bail!("--start-checkpoint must not be greater than --end-checkpoint");
+    }
+
+    let mut scenarios = vec![];
+    for scenario in &opts.scenarios {
+        let mut overrides = vec![];
+        for assignment in scenario.split(',') {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| anyhow!("invalid scenario parameter {assignment:?}"))?;
+            let value: u64 = value
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid value for {name} in scenario {scenario:?}"))?;
+            overrides.push((name.trim().to_string(), value));
+        }
+        scenarios.push((scenario.clone(), overrides));
+    }
+
+    let initial_estimates: Vec<_> = match &opts.estimates {
+        Some(path) => {
+            let dump: ExecutionTimeEstimatesDump = serde_json::from_slice(
+                &std::fs::read(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?,
+            )?;
+            info!(
+                "Starting from {} execution time estimates of epoch {}",
+                dump.commands.len(),
+                dump.epoch
+            );
+            dump.commands
+                .into_iter()
+                .filter(|command| !command.is_default)
+                .map(|command| (command.key, Duration::from_micros(command.estimate_us)))
+                .collect()
+        }
+        None => {
+            warn!("No --estimates given, starting from default execution time estimates");
+            vec![]
+        }
+    };
+
+    let checkpoint_store = CheckpointStore::new(
+        &db_path.join("checkpoints"),
+        Arc::new(PrunerWatermarks::default()),
+    );
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path.join("store"), None, None);
+
+    let mut simulators: Vec<CongestionSimulator> = vec![];
+    let mut scenario_names = vec![];
+    let mut current_epoch: Option<(EpochId, ReplayedEpoch)> = None;
+    let mut current_commit: Option<SimulatedCommit> = None;
+    let mut num_skipped = 0;
+    for seq in opts.start_checkpoint..=opts.end_checkpoint {
+        let checkpoint = checkpoint_store
+            .get_checkpoint_by_sequence_number(seq)?
+            .ok_or_else(|| anyhow!("checkpoint {seq} not found"))?;
+        let contents = checkpoint_store
+            .get_checkpoint_contents(&checkpoint.content_digest)?
+            .ok_or_else(|| anyhow!("contents of checkpoint {seq} not found"))?;
+
+        if current_epoch
+            .as_ref()
+            .is_none_or(|(epoch, _)| *epoch != checkpoint.epoch)
+        {
+            if let Some(commit) = current_commit.take() {
+                process_commit(&mut simulators, commit);
+            }
+            let replayed = load_epoch(db_path, &checkpoint_store, checkpoint.epoch, &opts)?;
+            let baseline = CongestionSimulationParams::from_protocol_config(
+                &ProtocolConfig::get_for_version(replayed.protocol_version, opts.chain),
+            )?;
+            let mut params = vec![baseline];
+            for (scenario, overrides) in &scenarios {
+                let mut scenario_params = baseline;
+                for (name, value) in overrides {
+                    override_param(&mut scenario_params, name, *value)
+                        .with_context(|| format!("invalid scenario {scenario:?}"))?;
+                }
+                params.push(scenario_params);
+            }
+            if simulators.is_empty() {
+                simulators = params
+                    .into_iter()
+                    .map(|params| {
+                        CongestionSimulator::new(
+                            replayed.committee.clone(),
+                            params,
+                            &initial_estimates,
+                        )
+                    })
+                    .collect();
+                scenario_names.push(if opts.protocol_version.is_some() {
+                    format!("protocol version {}", replayed.protocol_version.as_u64())
+                } else {
+                    "protocol version of each epoch".to_string()
+                });
+                scenario_names.extend(scenarios.iter().map(|(name, _)| name.clone()));
+            } else {
+                for (simulator, params) in simulators.iter_mut().zip_eq(params) {
+                    simulator.start_epoch(replayed.committee.clone(), params);
+                }
+            }
+            info!(
+                "Replaying epoch {} with protocol version {}",
+                checkpoint.epoch,
+                replayed.protocol_version.as_u64()
+            );
+            current_epoch = Some((checkpoint.epoch, replayed));
+        }
+
+        let (_, replayed) = current_epoch.as_mut().unwrap();
+        while replayed
+            .observations
+            .front()
+            .is_some_and(|(replay_before, _)| *replay_before <= seq)
+        {
+            let (_, observations) = replayed.observations.pop_front().unwrap();
+            for simulator in &mut simulators {
+                simulator.process_observations(&observations);
+            }
+        }
+
+        for digests in contents.iter() {
+            let transaction = perpetual_db
+                .get_transaction(&digests.transaction)?
+                .ok_or_else(|| anyhow!("transaction {} not found", digests.transaction))?;
+            let transaction = VerifiedExecutableTransaction::new_from_checkpoint(
+                VerifiedTransaction::from(transaction),
+                checkpoint.epoch,
+                seq,
+            );
+
+            if let Some((round, timestamp_ms)) =
+                consensus_commit_prologue_round_and_timestamp(transaction.transaction_data().kind())
+            {
+                if let Some(commit) = current_commit.take() {
+                    process_commit(&mut simulators, commit);
+                }
+                current_commit = Some(SimulatedCommit {
+                    round,
+                    timestamp_ms,
+                    transactions: vec![],
+                });
+            } else if let Some(commit) = &mut current_commit {
+                commit.transactions.push(transaction);
+            } else {
+                // The round of transactions preceding the first commit prologue is unknown.
+                num_skipped += 1;
+            }
+        }
+    }
+    if let Some(commit) = current_commit.take() {
+        process_commit(&mut simulators, commit);
+    }
+    if num_skipped > 0 {
+        info!("Skipped {num_skipped} transactions preceding the first consensus commit");
+    }
+
+    let reports: Vec<_> = simulators.into_iter().map(|s| s.finish()).collect();
+    print_reports(&scenario_names, &reports);
+    Ok(())
+}
+
+/// Reads the committee and protocol version of `epoch` from the end of epoch data of the
+/// previous epoch, and the execution time observations sequenced in it from its epoch db.
+fn load_epoch(
+    db_path: &Path,
+    checkpoint_store: &CheckpointStore,
+    epoch: EpochId,
+    opts: &SimulateCongestionOptions,
+) -> anyhow::Result<ReplayedEpoch> {
+    if epoch == 0 {
+        bail!("replaying checkpoints of epoch 0 is not supported");
+    }
+    let previous_last_checkpoint = checkpoint_store
+        .get_epoch_last_checkpoint(epoch - 1)?
+        .ok_or_else(|| anyhow!("last checkpoint of epoch {} not found", epoch - 1))?;
+    let end_of_epoch_data = previous_last_checkpoint
+        .end_of_epoch_data
+        .as_ref()
+        .ok_or_else(|| anyhow!("end of epoch data of epoch {} not found", epoch - 1))?;
+    let committee = Arc::new(Committee::new(
+        epoch,
+        end_of_epoch_data
+            .next_epoch_committee
+            .iter()
+            .cloned()
+            .collect(),
+    ));
+    let protocol_version = opts
+        .protocol_version
+        .map(ProtocolVersion::new)
+        .unwrap_or(end_of_epoch_data.next_epoch_protocol_version);
+
+    let first_checkpoint = previous_last_checkpoint.sequence_number + 1;
+    let last_checkpoint = match checkpoint_store.get_epoch_last_checkpoint_seq_number(epoch)? {
+        Some(last_checkpoint) => last_checkpoint,
+        None => checkpoint_store
+            .get_highest_executed_checkpoint_seq_number()?
+            .unwrap_or(opts.end_checkpoint)
+            .max(opts.end_checkpoint),
+    };
+    let num_checkpoints = u128::from(last_checkpoint.saturating_sub(first_checkpoint) + 1);
+
+    let Some(sequenced) = load_sequenced_observations(&db_path.join("store"), epoch)? else {
+        warn!(
+            "Epoch db of epoch {epoch} not found, replaying it without execution time observations"
+        );
+        return Ok(ReplayedEpoch {
+            committee,
+            protocol_version,
+            observations: VecDeque::new(),
+        });
+    };
+    let num_observations = sequenced.len() as u128;
+    let observations = sequenced
+        .into_iter()
+        .enumerate()
+        .map(|(i, observations)| {
+            let offset = i as u128 * num_checkpoints / num_observations;
+            (first_checkpoint + offset as u64, observations)
+        })
+        .collect();
+    Ok(ReplayedEpoch {
+        committee,
+        protocol_version,
+        observations,
+    })
+}
+
+fn process_commit(simulators: &mut [CongestionSimulator], commit: SimulatedCommit) {
+    let (last, rest) = simulators.split_last_mut().unwrap();
+    for simulator in rest {
+        simulator.process_commit(SimulatedCommit {
+            round: commit.round,
+            timestamp_ms: commit.timestamp_ms,
+            transactions: commit.transactions.clone(),
+        });
+    }
+    last.process_commit(commit);
+}
+
+fn override_param(
+    params: &mut CongestionSimulationParams,
+    name: &str,
+    value: u64,
+) -> anyhow::Result<()> {
+    let estimate_params = &mut params.execution_time_estimate;
+    match name {
+        "target_utilization" => estimate_params.target_utilization = value,
+        "allowed_txn_cost_overage_burst_limit_us" => {
+            estimate_params.allowed_txn_cost_overage_burst_limit_us = value
+        }
+        "randomness_scalar" => estimate_params.randomness_scalar = value,
+        "max_estimate_us" => estimate_params.max_estimate_us = value,
+        "max_deferral_rounds_for_congestion_control" => {
+            params.max_deferral_rounds_for_congestion_control = value
+        }
+        _ => bail!("unknown congestion control parameter {name}"),
+    }
+    Ok(())
+}
+
+fn print_reports(scenarios: &[String], reports: &[CongestionSimulationReport]) {
+    let mut table = Table::new();
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "scenario",
+            "commits",
+            "transactions",
+            "deferred",
+            "deferrals",
+            "cancelled",
+            "still deferred",
+        ]);
+    for (name, report) in scenarios.iter().zip_eq(reports) {
+        table.add_row(vec![
+            name.clone(),
+            report.num_commits.to_string(),
+            report.num_transactions.to_string(),
+            report.num_deferred_transactions.to_string(),
+            report.num_deferrals.to_string(),
+            report.num_cancelled_transactions.to_string(),
+            report.num_pending_transactions.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    for (name, report) in scenarios.iter().zip_eq(reports) {
+        if report.most_congested_objects.is_empty() {
+            continue;
+        }
+        println!("\nMost congested objects under {name}:");
+        for (object_id, count) in &report.most_congested_objects {
+            println!("  {object_id}: {count}");
+        }
+    }
+}
@@ -38,6 +38,7 @@ use tokio::time::Instant;
 
 use anyhow::anyhow;
 use clap::ValueEnum;
+use comfy_table::{ContentArrangement, Table};
 use eyre::ContextCompat;
 use fastcrypto::hash::MultisetHash;
 use futures::{StreamExt, TryStreamExt};
@@ -47,7 +48,9 @@ use serde::{Deserialize, Serialize};
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::AuthorityStore;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::execution_time_estimator::ExecutionTimeEstimatesDump;
 use sui_core::checkpoints::CheckpointStore;
+use sui_core::db_checkpoint_handler::restore_incremental_db_checkpoint;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
 use sui_snapshot::reader::StateSnapshotReaderV1;
@@ -55,7 +58,9 @@ use sui_snapshot::setup_db_state;
 use sui_storage::object_store::ObjectStoreGetExt;
 use sui_storage::object_store::util::{copy_file, exists, get_path};
 use sui_types::full_checkpoint_content::CheckpointData;
-use sui_types::messages_checkpoint::{CheckpointCommitment, ECMHLiveObjectSetDigest};
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, CheckpointSequenceNumber, ECMHLiveObjectSetDigest,
+};
 use sui_types::messages_grpc::{
     ObjectInfoRequest, ObjectInfoRequestKind, ObjectInfoResponse, TransactionInfoRequest,
     TransactionStatus,
@@ -68,6 +73,9 @@ use tracing::info;
 use typed_store::DBMetrics;
 
 pub mod commands;
+mod consensus_observer;
+mod consensus_replay;
+mod consensus_simulation;
 pub mod db_tool;
 mod formal_snapshot_util;
 
@@ -573,6 +581,107 @@ pub async fn restore_from_db_checkpoint(
     Ok(())
 }
 
+/// Restores the db of the node from an incremental db checkpoint uploaded to the db checkpoint
+/// object store of its config, at `checkpoint_seq` or the latest one available.
+pub async fn restore_from_incremental_db_checkpoint(
+    config: &NodeConfig,
+    checkpoint_seq: Option<CheckpointSequenceNumber>,
+    num_parallel_downloads: usize,
+) -> Result<(), anyhow::Error> {
+    let object_store = config
+        .db_checkpoint_config
+        .object_store_config
+        .as_ref()
+        .ok_or_else(|| anyhow!("db-checkpoint-config.object-store-config is not set"))?
+        .make()?;
+    let manifest = restore_incremental_db_checkpoint(
+        &object_store,
+        checkpoint_seq,
+        &config.db_path(),
+        NonZeroUsize::new(num_parallel_downloads)
+            .ok_or_else(|| anyhow!("--num-parallel-downloads must be positive"))?,
+    )
+    .await?;
+    println!(
+        "Restored db checkpoint at checkpoint {} of epoch {} ({} files) to {}",
+        manifest.checkpoint_seq,
+        manifest.epoch,
+        manifest.files.len(),
+        config.db_path().display()
+    );
+    Ok(())
+}
+
+/// Fetches the execution time estimates and object utilization used for congestion control from
+/// the admin API of a node, and prints them as tables or as raw JSON.
+pub async fn print_execution_time_estimates(
+    admin_url: &str,
+    json: bool,
+    limit: usize,
+) -> Result<(), anyhow::Error> {
+    let response = reqwest::get(format!(
+        "{}/execution-time-estimates",
+        admin_url.trim_end_matches('/')
+    ))
+    .await?
+    .error_for_status()?
+    .text()
+    .await?;
+    if json {
+        println!("{response}");
+        return Ok(());
+    }
+
+    let mut dump: ExecutionTimeEstimatesDump = serde_json::from_str(&response)?;
+    println!(
+        "Epoch {}: {} command estimates, {} objects with utilization",
+        dump.epoch,
+        dump.commands.len(),
+        dump.object_utilization.len()
+    );
+
+    dump.commands
+        .sort_by(|a, b| b.estimate_us.cmp(&a.estimate_us).then(a.key.cmp(&b.key)));
+    let mut commands = Table::new();
+    commands
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "command",
+            "estimate (us)",
+            "default",
+            "observations",
+            "local average (us)",
+        ]);
+    for command in dump.commands.iter().take(limit) {
+        commands.add_row(vec![
+            command.key.to_string(),
+            command.estimate_us.to_string(),
+            command.is_default.to_string(),
+            command.num_observations.to_string(),
+            command
+                .local_average_us
+                .map(|us| us.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+    println!("{commands}");
+
+    let mut objects = Table::new();
+    objects
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["object", "excess (us)", "overutilized", "indebted"]);
+    for object in dump.object_utilization.iter().take(limit) {
+        objects.add_row(vec![
+            object.object_id.to_string(),
+            object.excess_execution_time_us.to_string(),
+            object.overutilized.to_string(),
+            object.indebted.to_string(),
+        ]);
+    }
+    println!("{objects}");
+    Ok(())
+}
+
 fn start_summary_sync(
     perpetual_db: Arc<AuthorityPerpetualTables>,
     committee_store: Arc<CommitteeStore>,
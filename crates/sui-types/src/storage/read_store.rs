@@ -665,6 +665,13 @@ pub trait RpcStateReader:
         struct_tag: &StructTag,
         overlay: &ObjectSet,
     ) -> Result<Option<MoveTypeLayout>>;
+
+    /// Congestion recently observed on the consensus object `object_id`, oldest first. Returns
+    /// `None` if the object has not been congested recently, or if this store does not track
+    /// congestion.
+    fn get_congestion_history(&self, _object_id: &ObjectID) -> Option<Vec<CongestionSample>> {
+        None
+    }
 }
 
 pub type DynamicFieldIteratorItem = Result<DynamicFieldKey, TypedStoreError>;
@@ -796,6 +803,18 @@ impl TransactionInfo {
     }
 }
 
+/// Congestion observed on a consensus object in a single checkpoint.
+#[derive(Copy, Clone, Serialize, Deserialize, Eq, PartialEq, Debug)]
+pub struct CongestionSample {
+    pub checkpoint_timestamp_ms: u64,
+    /// The highest gas price of a transaction that was cancelled due to congestion on the object
+    /// in this checkpoint, if any were.
+    pub highest_cancelled_gas_price: Option<u64>,
+    /// The lowest gas price of a transaction that mutated the object in this checkpoint, if any
+    /// did.
+    pub lowest_executed_gas_price: Option<u64>,
+}
+
 #[derive(Clone, Default, Serialize, Deserialize, Eq, PartialEq, Debug)]
 pub struct EpochInfo {
     pub epoch: u64,
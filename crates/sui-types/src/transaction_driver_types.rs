@@ -224,3 +224,56 @@ impl FinalizedEffects {
         &self.effects
     }
 }
+
+/// The lifecycle of a transaction submitted through the Transaction Driver, as observed by the
+/// node that submitted it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionLifecycle {
+    pub status: TransactionStatus,
+    /// Number of attempts made to drive the transaction to finality.
+    pub attempts: u32,
+    /// Events in the order they were observed. Only the most recent events are kept for
+    /// transactions with long lifecycles.
+    pub events: Vec<TransactionLifecycleEvent>,
+}
+
+/// Where a submitted transaction currently is in its lifecycle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction is being submitted or retried, or is waiting for its effects to be certified.
+    Pending,
+    /// The transaction's last attempt failed because validators were overloaded, for example by
+    /// congestion on its objects, and it will be retried.
+    Deferred { reason: String },
+    /// The transaction failed with a non-retriable error, or timed out.
+    Rejected { reason: String },
+    /// The transaction's effects were certified.
+    Executed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TransactionLifecycleEvent {
+    /// Milliseconds since the Unix epoch when the event was observed.
+    pub timestamp_ms: u64,
+    pub kind: TransactionLifecycleEventKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TransactionLifecycleEventKind {
+    /// An attempt to drive the transaction to finality started.
+    AttemptStarted { attempt: u32 },
+    /// A validator accepted the transaction for processing.
+    Submitted { validator: String },
+    /// A validator rejected the transaction, during submission or while waiting for effects.
+    RejectedByValidator {
+        validator: String,
+        reason: String,
+        retriable: bool,
+    },
+    /// A validator returned the transaction's effects.
+    EffectsAcknowledged { validator: String },
+    /// An attempt failed, and the transaction will be retried if the failure is retriable.
+    AttemptFailed { reason: String, retriable: bool },
+    /// The transaction's effects were certified by a quorum of validators.
+    EffectsCertified,
+}
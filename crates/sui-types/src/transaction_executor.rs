@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::base_types::ObjectID;
+use crate::digests::TransactionDigest;
 use crate::effects::TransactionEffects;
 use crate::effects::TransactionEvents;
 use crate::error::ExecutionError;
@@ -12,6 +13,7 @@ use crate::storage::ObjectKey;
 use crate::transaction::TransactionData;
 use crate::transaction_driver_types::ExecuteTransactionRequestV3;
 use crate::transaction_driver_types::ExecuteTransactionResponseV3;
+use crate::transaction_driver_types::TransactionLifecycle;
 use crate::transaction_driver_types::TransactionSubmissionError;
 
 /// Trait to define the interface for how the gRPC service interacts with a  QuorumDriver or a
@@ -30,6 +32,12 @@ pub trait TransactionExecutor: Send + Sync {
         checks: TransactionChecks,
         allow_mock_gas_coin: bool,
     ) -> Result<SimulateTransactionResult, SuiError>;
+
+    /// Returns what is known about a transaction submitted through this executor that has not
+    /// necessarily reached finality yet.
+    fn transaction_lifecycle(&self, _digest: &TransactionDigest) -> Option<TransactionLifecycle> {
+        None
+    }
 }
 
 pub struct SimulateTransactionResult {
@@ -5,7 +5,10 @@ use crate::{
     clever_error_rendering::render_clever_error_opt,
     client_ptb::ptb::PTB,
     displays::Pretty,
-    upgrade_compatibility::check_compatibility,
+    upgrade_compatibility::{
+        check_compatibility,
+        impact::{ImpactAction, UpgradeImpact, upgrade_impact},
+    },
     verifier_meter::{AccumulatingMeter, Accumulator},
 };
 use futures::TryStreamExt;
@@ -13,6 +16,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, btree_map::Entry},
     fmt::{Debug, Display, Formatter, Write},
     fs,
+    panic::AssertUnwindSafe,
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
@@ -191,6 +195,21 @@ pub enum SuiClientCommands {
     #[clap(name = "chain-identifier")]
     ChainIdentifier,
 
+    /// Decompile an on-chain package into Move source, writing one file per module.
+    ///
+    /// The output is reconstructed from bytecode, so it may not compile as-is, and it lacks
+    /// comments and the original names of local variables.
+    #[clap(name = "decompile")]
+    Decompile {
+        /// Storage ID of the package to decompile
+        #[clap(name = "package_id")]
+        package_id: ObjectID,
+        /// Directory to write the decompiled sources to. Defaults to a directory named after the
+        /// package ID in the current directory.
+        #[clap(long, short)]
+        output_dir: Option<PathBuf>,
+    },
+
     /// Query a dynamic field by its address.
     #[clap(name = "dynamic-field")]
     DynamicFieldQuery {
@@ -469,6 +488,15 @@ pub enum SuiClientCommands {
     )]
     TestUpgrade(TestUpgradeArgs),
 
+    /// Check how upgrading a package affects the published packages that depend on it.
+    ///
+    /// Checks the upgrade against the package's upgrade policy, and reports which of the given
+    /// dependent packages need to be re-linked (if the upgrade is compatible) or re-published (if
+    /// it is not), in the order they need to be updated in. Dependents are not discovered, they
+    /// have to be listed with `--dependents`.
+    #[clap(name = "upgrade-impact")]
+    UpgradeImpact(UpgradeImpactArgs),
+
     /// Execute, dry-run, dev-inspect or otherwise inspect an already serialized transaction.
     SerializedTx {
         /// Base64-encoded BCS-serialized TransactionData.
@@ -812,6 +840,28 @@ pub struct TestUpgradeArgs {
     #[clap(flatten)]
     pub upgrade_args: UpgradeArgs,
 }
+
+#[derive(Args, Debug, Default)]
+pub struct UpgradeImpactArgs {
+    /// Path to directory containing the new version of the Move package
+    #[clap(name = "package_path", default_value = ".")]
+    pub package_path: PathBuf,
+
+    /// ID of the upgrade capability for the package being upgraded.
+    #[clap(long, short = 'c')]
+    pub upgrade_capability: Option<ObjectID>,
+
+    /// IDs of published packages to check for dependencies on the upgraded package. Packages that
+    /// have been upgraded must be listed by the ID of their latest version, as the package at each
+    /// given ID is the one analyzed.
+    #[clap(long, num_args(1..))]
+    pub dependents: Vec<ObjectID>,
+
+    /// Package build options
+    #[clap(flatten)]
+    pub build_config: MoveBuildConfig,
+}
+
 #[derive(serde::Deserialize, Debug)]
 struct FaucetResponse {
     error: Option<String>,
@@ -926,6 +976,18 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Balance(ordered_coins_sui_first, with_coins)
             }
 
+            SuiClientCommands::Decompile {
+                package_id,
+                output_dir,
+            } => {
+                let mut client = context.grpc_client()?;
+                let _ = context.cache_chain_id().await?;
+                let output_dir =
+                    output_dir.unwrap_or_else(|| PathBuf::from(package_id.to_string()));
+                let output = decompile_package(&mut client, package_id, &output_dir).await?;
+                SuiClientCommandResult::Decompile(output)
+            }
+
             SuiClientCommands::DynamicFieldQuery { id, cursor, limit } => {
                 let client = context.grpc_client()?;
                 let _ = context.cache_chain_id().await?;
@@ -952,6 +1014,14 @@ impl SuiClientCommands {
                 upgrade_command(args.upgrade_args, context, true).await?
             }
 
+            SuiClientCommands::UpgradeImpact(args) => {
+                verify_no_test_mode(&args.build_config)?;
+                verify_no_pubfile_path(&args.build_config, "upgrade-impact")?;
+                verify_no_build_env(&args.build_config, "upgrade-impact")?;
+                let _ = context.cache_chain_id().await?;
+                SuiClientCommandResult::UpgradeImpact(upgrade_impact_command(args, context).await?)
+            }
+
             SuiClientCommands::Publish(args) => {
                 verify_no_test_mode(&args.build_config)?;
                 verify_no_pubfile_path(&args.build_config, "publish")?;
@@ -2373,6 +2443,17 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::ChainIdentifier(ci) => {
                 writeln!(writer, "{}", ci)?;
             }
+            SuiClientCommandResult::Decompile(output) => {
+                writeln!(
+                    writer,
+                    "Decompiled {} module(s) of package {}:",
+                    output.files.len(),
+                    output.package_id
+                )?;
+                for file in &output.files {
+                    writeln!(writer, "  {}", file.display())?;
+                }
+            }
             SuiClientCommandResult::Switch(response) => {
                 write!(writer, "{}", response)?;
             }
@@ -2404,6 +2485,62 @@ impl Display for SuiClientCommandResult {
                 table.with(TableStyle::rounded());
                 write!(f, "{}", table)?
             }
+            SuiClientCommandResult::UpgradeImpact(impact) => {
+                if let Some(incompatibilities) = &impact.incompatibilities {
+                    writeln!(writer, "{incompatibilities}")?;
+                }
+
+                let compatible = if impact.incompatibilities.is_some() {
+                    "is not compatible"
+                } else {
+                    "is compatible"
+                };
+                writeln!(
+                    writer,
+                    "Upgrading package {} (original ID {}) {compatible} with its '{}' upgrade policy.",
+                    impact.package_id, impact.original_id, impact.policy,
+                )?;
+
+                if impact.dependents.is_empty() {
+                    writeln!(writer, "None of the given packages depend on it.")?;
+                } else {
+                    let action = match impact.dependents[0].action {
+                        ImpactAction::Relink => "re-linked to use the new version",
+                        ImpactAction::Republish => "re-published against a new package",
+                    };
+                    writeln!(
+                        writer,
+                        "{} dependent package(s) need to be {action}, in this order:",
+                        impact.dependents.len(),
+                    )?;
+
+                    let mut builder = TableBuilder::default();
+                    builder.set_header(["package", "linked against", "via", "uses modules"]);
+                    for dependent in &impact.dependents {
+                        builder.push_record([
+                            dependent.package_id.to_string(),
+                            dependent.linked_id.to_string(),
+                            dependent
+                                .via
+                                .iter()
+                                .map(|id| id.to_string())
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            dependent.uses_modules.join(", "),
+                        ]);
+                    }
+                    let mut table = builder.build();
+                    table.with(TableStyle::rounded());
+                    writeln!(writer, "{table}")?;
+                }
+
+                if !impact.unaffected.is_empty() {
+                    writeln!(writer, "Packages that do not depend on it:")?;
+                    for id in &impact.unaffected {
+                        writeln!(writer, "  {id}")?;
+                    }
+                }
+            }
             SuiClientCommandResult::VerifySource => {
                 writeln!(writer, "Source verification succeeded!")?;
             }
@@ -2511,6 +2648,57 @@ impl Display for SuiClientCommandResult {
     }
 }
 
+/// Fetch the package at `package_id` and write the decompiled source of each of its modules to
+/// `output_dir`, as `<module>.move`.
+async fn decompile_package(
+    client: &mut Client,
+    package_id: ObjectID,
+    output_dir: &Path,
+) -> Result<DecompileOutput, anyhow::Error> {
+    let object = client.get_object(package_id).await?;
+    let Some(package) = object.data.try_as_package() else {
+        bail!("Object {package_id} is not a package");
+    };
+    let modules = package
+        .serialized_module_map()
+        .iter()
+        .map(|(name, bytes)| {
+            CompiledModule::deserialize_with_defaults(bytes)
+                .map_err(|e| anyhow!("Failed to deserialize module '{name}': {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The decompiler is not hardened against every bytecode pattern found on chain, so a panic
+    // while decompiling is reported as an error for this package rather than crashing the CLI.
+    let sources = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        move_decompiler::decompile_modules(modules)
+    }))
+    .map_err(|panic| {
+        let reason = panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic.downcast_ref::<&str>().copied())
+            .unwrap_or("unknown reason");
+        anyhow!("Decompiler failed on package {package_id}: {reason}")
+    })??;
+
+    std::fs::create_dir_all(output_dir).map_err(|e| {
+        anyhow!(
+            "Failed to create output directory {}: {e}",
+            output_dir.display()
+        )
+    })?;
+    let mut files = vec![];
+    for (module_name, source) in sources {
+        let path = output_dir.join(format!("{module_name}.move"));
+        std::fs::write(&path, source)
+            .map_err(|e| anyhow!("Failed to write {}: {e}", path.display()))?;
+        files.push(path);
+    }
+
+    Ok(DecompileOutput { package_id, files })
+}
+
 struct NoopModuleCache;
 
 impl GetModule for NoopModuleCache {
@@ -2813,6 +3001,13 @@ impl SuiClientCommandResult {
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecompileOutput {
+    pub package_id: ObjectID,
+    pub files: Vec<PathBuf>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressesOutput {
@@ -2926,6 +3121,7 @@ pub enum SuiClientCommandResult {
     Balance(Vec<(Option<proto::GetCoinInfoResponse>, Vec<Coin>)>, bool),
     ChainIdentifier(String),
     ComputeTransactionDigest(TransactionData),
+    Decompile(DecompileOutput),
     DynamicFieldQuery(proto::ListDynamicFieldsResponse),
     DryRun(SimulateTransactionResponse),
     DevInspect(SimulateTransactionResponse),
@@ -2943,6 +3139,7 @@ pub enum SuiClientCommandResult {
     Switch(SwitchResponse),
     SyncClientState,
     TransactionBlock(ExecutedTransaction),
+    UpgradeImpact(UpgradeImpact),
     VerifyBytecodeMeter {
         success: bool,
         max_package_ticks: Option<u128>,
@@ -3936,16 +4133,7 @@ async fn upgrade_command(
 
     let verify = check_dep_verification_flags(skip_dependency_verification, verify_deps)?;
 
-    let upgrade_cap = if let Some(ref upgrade_cap) = upgrade_capability {
-        upgrade_cap
-    } else {
-        &root_pkg.publication().as_ref().ok_or_else(|| {
-                        anyhow!("Cannot determine the publication information. Please pass the upgrade cap with `-c <UPGRADE_CAP>`.")
-                    })?
-                    .metadata.upgrade_capability.ok_or_else(|| {
-                        anyhow!("No upgrade capability found in the published data. Please pass the upgrade cap with `-c <UPGRADE_CAP>`.")
-                    })?
-    };
+    let upgrade_cap = upgrade_capability_or_published(&root_pkg, upgrade_capability)?;
 
     // TODO: pkg-alt we should read upgrade cap from published file, but the question
     // is how do we migrate? During migration we might want to try to find the upgrade
@@ -3955,7 +4143,7 @@ async fn upgrade_command(
         &root_pkg,
         build_config.clone(),
         &package_path,
-        *upgrade_cap,
+        upgrade_cap,
         with_unpublished_dependencies,
         !verify,
     )
@@ -3997,7 +4185,7 @@ async fn upgrade_command(
             package_id,
             compiled_modules,
             dep_ids,
-            *upgrade_cap,
+            upgrade_cap,
             upgrade_policy,
             package_digest.to_vec(),
         )
@@ -4036,6 +4224,93 @@ async fn upgrade_command(
     Ok(result)
 }
 
+/// The upgrade capability to use for upgrading `root_pkg`: `upgrade_capability` if it was given,
+/// otherwise the one recorded when the package was published.
+fn upgrade_capability_or_published(
+    root_pkg: &RootPackage<SuiFlavor>,
+    upgrade_capability: Option<ObjectID>,
+) -> Result<ObjectID, anyhow::Error> {
+    if let Some(upgrade_cap) = upgrade_capability {
+        return Ok(upgrade_cap);
+    }
+
+    root_pkg
+        .publication()
+        .as_ref()
+        .ok_or_else(|| {
+            anyhow!("Cannot determine the publication information. Please pass the upgrade cap with `-c <UPGRADE_CAP>`.")
+        })?
+        .metadata
+        .upgrade_capability
+        .ok_or_else(|| {
+            anyhow!("No upgrade capability found in the published data. Please pass the upgrade cap with `-c <UPGRADE_CAP>`.")
+        })
+}
+
+async fn upgrade_impact_command(
+    args: UpgradeImpactArgs,
+    context: &mut WalletContext,
+) -> Result<UpgradeImpact, anyhow::Error> {
+    let UpgradeImpactArgs {
+        package_path,
+        upgrade_capability,
+        dependents,
+        mut build_config,
+    } = args;
+
+    let client = context.grpc_client()?;
+    let chain_id = client.get_chain_identifier().await?.to_string();
+
+    // Build the package exactly as `upgrade` would, with `0x0` as the root package's address
+    build_config.root_as_zero = true;
+
+    let package_path =
+        package_path
+            .canonicalize()
+            .map_err(|e| SuiErrorKind::ModulePublishFailure {
+                error: format!("Failed to canonicalize package path: {}", e),
+            })?;
+
+    let root_pkg = load_root_pkg_for_publish_upgrade(context, &build_config, &package_path).await?;
+    let upgrade_cap = upgrade_capability_or_published(&root_pkg, upgrade_capability)?;
+
+    let (upgrade_policy, compiled_package) = upgrade_package(
+        client.clone(),
+        &root_pkg,
+        build_config,
+        &package_path,
+        upgrade_cap,
+        false,
+        true,
+    )
+    .await
+    .map_err(|e| anyhow!("{e}"))?;
+
+    let package_id = compiled_package
+        .published_at
+        .ok_or_else(|| anyhow!("Cannot check the impact of upgrading an unpublished package"))?;
+
+    let protocol_version = client.get_protocol_config(None).await?.protocol_version();
+    let protocol_config = ProtocolConfig::get_for_version(
+        protocol_version.into(),
+        match ChainIdentifier::from_chain_short_id(&chain_id) {
+            Some(chain_id) => chain_id.chain(),
+            None => Chain::Unknown,
+        },
+    );
+
+    upgrade_impact(
+        client,
+        package_id,
+        compiled_package,
+        package_path,
+        upgrade_policy,
+        protocol_config,
+        dependents,
+    )
+    .await
+}
+
 async fn publish_ephemeral_unpublished_dependencies(
     args: &TestPublishArgs,
     chain_id: &str,
@@ -17,5 +17,6 @@ pub mod sui_commands;
 pub mod trace_analysis_commands;
 pub mod upgrade_compatibility;
 pub mod validator_commands;
+pub mod validator_status;
 mod verifier_meter;
 pub mod zklogin_commands_util;
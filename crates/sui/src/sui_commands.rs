@@ -76,6 +76,7 @@ use sui_swarm_config::genesis_config::GenesisConfig;
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_swarm_config::node_config_builder::FullnodeConfigBuilder;
+use sui_swarm_config::topology::Topology;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::crypto::{SignatureScheme, SuiKeyPair, ToFromBytes};
 use sui_types::move_package::MovePackage;
@@ -270,6 +271,14 @@ pub enum SuiCommand {
         /// genesis with the desired number of validators.
         #[clap(long)]
         committee_size: Option<usize>,
+
+        /// Start the network described by a topology file (YAML or TOML): its validators with
+        /// their stake, supported protocol versions and consensus parameters, and its fullnodes.
+        /// Topologies with link delays are rejected, as those are only supported in the simulator.
+        /// A new genesis is created each run, as with `--force-regenesis`. The first fullnode of
+        /// the topology serves the faucet and indexer.
+        #[clap(long, value_name = "TOPOLOGY_FILE", conflicts_with_all = ["config_dir", "committee_size", "no_full_node"])]
+        topology: Option<PathBuf>,
     },
     #[clap(name = "network")]
     Network {
@@ -467,6 +476,7 @@ impl SuiCommand {
                 no_full_node,
                 epoch_duration_ms,
                 committee_size,
+                topology,
             } => {
                 start(
                     config_dir.clone(),
@@ -478,6 +488,7 @@ impl SuiCommand {
                     data_ingestion_dir,
                     no_full_node,
                     committee_size,
+                    topology,
                 )
                 .await?;
 
@@ -847,7 +858,20 @@ async fn start(
     mut data_ingestion_dir: Option<PathBuf>,
     no_full_node: bool,
     committee_size: Option<usize>,
+    topology: Option<PathBuf>,
 ) -> Result<(), anyhow::Error> {
+    let topology = topology.map(|path| Topology::load(&path)).transpose()?;
+    if let Some(topology) = &topology {
+        ensure!(
+            config.is_none(),
+            "Cannot pass `--topology` and `--network.config` at the same time."
+        );
+        ensure!(
+            topology.links.is_empty(),
+            "The `links` of a topology are only supported when running in the simulator, remove \
+             them to start the network with `sui start`."
+        );
+    }
     if force_regenesis {
         ensure!(
             config.is_none(),
@@ -879,9 +903,19 @@ async fn start(
             !no_full_node,
             "Cannot start the indexer without a fullnode."
         );
+        if let Some(topology) = &topology {
+            ensure!(
+                !topology.fullnodes.is_empty(),
+                "Cannot start the indexer without a fullnode in the topology."
+            );
+        }
     }
 
-    if epoch_duration_ms.is_some() && genesis_blob_exists(config.clone()) && !force_regenesis {
+    if epoch_duration_ms.is_some()
+        && genesis_blob_exists(config.clone())
+        && !force_regenesis
+        && topology.is_none()
+    {
         bail!(
             "Epoch duration can only be set when passing the `--force-regenesis` flag, or when \
             there is no genesis configuration in the default Sui configuration folder or the given \
@@ -893,7 +927,7 @@ async fn start(
 
     // If this is set, then no data will be persisted between runs, and a new genesis will be
     // generated each run.
-    let config_dir = if force_regenesis {
+    let config_dir = if force_regenesis || topology.is_some() {
         let committee_size = match committee_size {
             Some(x) => NonZeroUsize::new(x),
             None => NonZeroUsize::new(1),
@@ -902,7 +936,12 @@ async fn start(
         swarm_builder = swarm_builder.committee_size(committee_size);
         let genesis_config = GenesisConfig::custom_genesis(1, 100);
         swarm_builder = swarm_builder.with_genesis_config(genesis_config);
-        let epoch_duration_ms = epoch_duration_ms.unwrap_or(DEFAULT_EPOCH_DURATION_MS);
+        let epoch_duration_ms = epoch_duration_ms
+            .or_else(|| topology.as_ref()?.epoch_duration_ms)
+            .unwrap_or(DEFAULT_EPOCH_DURATION_MS);
+        if let Some(topology) = topology.clone() {
+            swarm_builder = swarm_builder.with_topology(topology);
+        }
         swarm_builder = swarm_builder.with_epoch_duration_ms(epoch_duration_ms);
         mysten_common::tempdir()?.keep()
     } else {
@@ -1024,7 +1063,7 @@ async fn start(
 
     if no_full_node {
         swarm_builder = swarm_builder.with_fullnode_count(0);
-    } else {
+    } else if topology.is_none() {
         let rpc_config = sui_config::RpcConfig {
             enable_indexing: Some(true),
             ..Default::default()
@@ -1037,6 +1076,11 @@ async fn start(
     }
 
     let mut swarm = swarm_builder.build();
+    if topology.is_some()
+        && let Some(fullnode) = swarm.fullnodes().next()
+    {
+        fullnode_rpc_address = fullnode.json_rpc_address();
+    }
     swarm.launch().await?;
     // Let nodes connect to one another
     tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
@@ -1258,10 +1302,7 @@ async fn start(
         )
         .await?;
 
-        let app_state = Arc::new(AppState {
-            faucet: local_faucet,
-            config,
-        });
+        let app_state = Arc::new(AppState::new(local_faucet, config));
 
         start_faucet(app_state).await?;
     }
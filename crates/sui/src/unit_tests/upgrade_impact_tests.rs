@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_core_types::account_address::AccountAddress;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+
+use crate::upgrade_compatibility::impact::{ImpactAction, PackageGraph, PackageNode};
+
+fn addr(n: u8) -> AccountAddress {
+    let mut bytes = [0u8; AccountAddress::LENGTH];
+    bytes[AccountAddress::LENGTH - 1] = n;
+    AccountAddress::new(bytes)
+}
+
+/// A package at `storage`, first published at `runtime`, linked against `linkage` (pairs of
+/// runtime and storage IDs) and referring directly to modules in `direct` (pairs of runtime ID and
+/// module name).
+fn node(
+    storage: u8,
+    runtime: u8,
+    version: u64,
+    linkage: &[(u8, u8)],
+    direct: &[(u8, &str)],
+) -> PackageNode {
+    let mut refs: BTreeMap<AccountAddress, BTreeSet<String>> = BTreeMap::new();
+    for (id, module) in direct {
+        refs.entry(addr(*id))
+            .or_default()
+            .insert(module.to_string());
+    }
+
+    PackageNode::new(
+        addr(storage),
+        addr(runtime),
+        SequenceNumber::from_u64(version),
+        linkage.iter().map(|(r, s)| (addr(*r), addr(*s))).collect(),
+        refs,
+    )
+}
+
+fn ids(ns: &[u8]) -> Vec<ObjectID> {
+    ns.iter().map(|n| addr(*n).into()).collect()
+}
+
+#[test]
+fn test_no_dependents() {
+    let graph = PackageGraph::new([node(0xa, 0xa, 1, &[], &[]), node(0xb, 0xb, 1, &[], &[])]);
+
+    assert!(graph.dependents(addr(0xa), ImpactAction::Relink).is_empty());
+    assert_eq!(graph.unaffected(addr(0xa)), ids(&[0xb]));
+}
+
+#[test]
+fn test_transitive_dependents_in_order() {
+    // c -> b -> a, and d -> a, c
+    let graph = PackageGraph::new([
+        node(0xa, 0xa, 1, &[], &[]),
+        node(0xb, 0xb, 1, &[(0xa, 0xa)], &[(0xa, "coin")]),
+        node(0xc, 0xc, 1, &[(0xa, 0xa), (0xb, 0xb)], &[(0xb, "pool")]),
+        node(
+            0xd,
+            0xd,
+            1,
+            &[(0xa, 0xa), (0xb, 0xb), (0xc, 0xc)],
+            &[(0xa, "coin"), (0xa, "math"), (0xc, "router")],
+        ),
+        node(0xe, 0xe, 1, &[], &[]),
+    ]);
+
+    let dependents = graph.dependents(addr(0xa), ImpactAction::Relink);
+    let order: Vec<_> = dependents.iter().map(|d| d.package_id).collect();
+    assert_eq!(order, ids(&[0xb, 0xc, 0xd]));
+
+    let b = &dependents[0];
+    assert_eq!(b.via, ids(&[0xa]));
+    assert_eq!(b.uses_modules, vec!["coin".to_string()]);
+
+    // c only reaches a through b, so it doesn't use any of a's modules itself
+    let c = &dependents[1];
+    assert_eq!(c.via, ids(&[0xb]));
+    assert!(c.uses_modules.is_empty());
+
+    let d = &dependents[2];
+    assert_eq!(d.via, ids(&[0xa, 0xc]));
+    assert_eq!(d.uses_modules, vec!["coin".to_string(), "math".to_string()]);
+
+    assert!(dependents.iter().all(|d| d.action == ImpactAction::Relink));
+    assert_eq!(graph.unaffected(addr(0xa)), ids(&[0xe]));
+}
+
+#[test]
+fn test_latest_version_wins() {
+    // a has been upgraded from 0xa to 0xa2, and b has been upgraded from 0xb (linked against 0xa)
+    // to 0xb2 (linked against 0xa2). Only the latest version of b is reported.
+    let graph = PackageGraph::new([
+        node(0xa2, 0xa, 2, &[], &[]),
+        node(0xb, 0xb, 1, &[(0xa, 0xa)], &[(0xa, "coin")]),
+        node(0xb2, 0xb, 2, &[(0xa, 0xa2)], &[(0xa, "coin")]),
+        node(0xa, 0xa, 1, &[], &[]),
+    ]);
+
+    let dependents = graph.dependents(addr(0xa), ImpactAction::Republish);
+    assert_eq!(dependents.len(), 1);
+
+    let b = &dependents[0];
+    assert_eq!(b.package_id, addr(0xb2).into());
+    assert_eq!(b.original_id, addr(0xb).into());
+    assert_eq!(b.linked_id, addr(0xa2).into());
+    assert_eq!(b.via, ids(&[0xa2]));
+    assert_eq!(b.action, ImpactAction::Republish);
+}
+
+#[test]
+fn test_dependency_outside_graph() {
+    // c depends on a through b, but b was not part of the analysis, so c is still affected (through
+    // its linkage table), as if it depended on a directly.
+    let graph = PackageGraph::new([
+        node(0xa, 0xa, 1, &[], &[]),
+        node(0xc, 0xc, 1, &[(0xa, 0xa), (0xb, 0xb)], &[(0xb, "pool")]),
+        node(0xd, 0xd, 1, &[(0xa, 0xa), (0xc, 0xc)], &[(0xc, "router")]),
+    ]);
+
+    let dependents = graph.dependents(addr(0xa), ImpactAction::Relink);
+    let order: Vec<_> = dependents.iter().map(|d| d.package_id).collect();
+    assert_eq!(order, ids(&[0xc, 0xd]));
+    assert!(dependents[0].via.is_empty());
+    assert_eq!(dependents[1].via, ids(&[0xc]));
+}
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::validator_commands::{
-    SuiValidatorCommand, SuiValidatorCommandResponse, get_validator_summary,
+    SuiValidatorCommand, SuiValidatorCommandResponse, ValidatorStatus, get_validator_summary,
 };
+use crate::validator_status::ProbeResult;
 use anyhow::Ok;
 use fastcrypto::encoding::{Base64, Encoding};
 use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::committee::TOTAL_VOTING_POWER;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::transaction::TransactionData;
 use sui_types::{base_types::SuiAddress, crypto::Signature, transaction::Transaction};
@@ -65,3 +67,50 @@ async fn test_print_raw_rgp_txn() -> Result<(), anyhow::Error> {
     assert_eq!(summary.next_epoch_gas_price(), 42);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_validator_status() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let validator_address: SuiAddress = SuiAddress::from(
+        &test_cluster
+            .swarm
+            .config()
+            .validator_configs
+            .first()
+            .unwrap()
+            .account_key_pair
+            .keypair()
+            .public(),
+    );
+    let mut context = test_cluster.wallet;
+
+    let response = SuiValidatorCommand::Status {
+        validator_address: Some(validator_address),
+        probe_timeout_secs: 10,
+        skip_probes: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let SuiValidatorCommandResponse::Status(report) = response else {
+        panic!("Expected Status");
+    };
+
+    assert_eq!(report.status, ValidatorStatus::Active);
+    assert_eq!(report.validator.sui_address, validator_address);
+    assert!(report.pending_changes.is_empty());
+    assert!(report.reports.is_empty());
+    // All validators quote the same gas price at genesis.
+    let survey = report.gas_price_survey.unwrap();
+    assert_eq!(survey.projected_reference_gas_price, survey.quote);
+    assert_eq!(survey.voting_power_equal, TOTAL_VOTING_POWER);
+    // Every endpoint answers with the on-chain network key.
+    assert_eq!(report.endpoints.len(), 3);
+    for probe in &report.endpoints {
+        assert!(
+            matches!(probe.result, ProbeResult::Reachable { .. }),
+            "{} endpoint is not reachable",
+            probe.endpoint
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,307 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Upgrade impact analysis: builds the dependency graph between a set of published packages from
+//! their linkage tables, and works out which of them need to be re-linked or re-published after
+//! one package in the graph is upgraded.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::{Context, Error, anyhow};
+use move_core_types::account_address::AccountAddress;
+use serde::Serialize;
+use sui_move_build::CompiledPackage;
+use sui_package_resolver::Package;
+use sui_protocol_config::ProtocolConfig;
+use sui_rpc_api::Client;
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::move_package::UpgradePolicy;
+
+use super::{compatibility_report, deserialize_modules, policy_name};
+
+#[path = "../unit_tests/upgrade_impact_tests.rs"]
+#[cfg(test)]
+mod upgrade_impact_tests;
+
+/// What a dependent package has to do to pick up an upgrade of one of its dependencies.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImpactAction {
+    /// The upgrade is compatible, so the dependent keeps working against the version it is linked
+    /// against today, but needs to be upgraded with a new linkage to call into the new version.
+    Relink,
+    /// The upgrade is incompatible and can only be published as a new package, so the dependent
+    /// has to be published again against that new package.
+    Republish,
+}
+
+/// How a single published package is affected by the upgrade.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentImpact {
+    pub package_id: ObjectID,
+    pub original_id: ObjectID,
+    pub action: ImpactAction,
+    /// The version of the upgraded package this package is currently linked against.
+    pub linked_id: ObjectID,
+    /// Direct dependencies through which the upgrade reaches this package: the upgraded package
+    /// itself, or other affected packages.
+    pub via: Vec<ObjectID>,
+    /// Modules of the upgraded package that this package calls into or uses types from directly.
+    pub uses_modules: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeImpact {
+    pub package_id: ObjectID,
+    pub original_id: ObjectID,
+    pub policy: String,
+    /// Rendered diagnostics for every incompatible change, if the upgrade is not compatible with
+    /// the package's upgrade policy.
+    pub incompatibilities: Option<String>,
+    /// Affected packages, ordered so that each package comes after all the affected packages it
+    /// depends on, which is the order they need to be re-linked or re-published in.
+    pub dependents: Vec<DependentImpact>,
+    /// Packages that were analyzed but do not depend on the upgraded package.
+    pub unaffected: Vec<ObjectID>,
+}
+
+/// A published package, as a node in the dependency graph.
+#[derive(Debug, Clone)]
+pub(crate) struct PackageNode {
+    storage_id: AccountAddress,
+    runtime_id: AccountAddress,
+    version: SequenceNumber,
+    /// Transitive dependencies, from runtime ID to the storage ID this package is linked against.
+    linkage: BTreeMap<AccountAddress, AccountAddress>,
+    /// Runtime IDs of the packages this package refers to directly from its bytecode, and the
+    /// names of the modules it refers to in each of them.
+    direct: BTreeMap<AccountAddress, BTreeSet<String>>,
+}
+
+/// Dependency graph between published packages, holding the latest known version of each package.
+pub(crate) struct PackageGraph {
+    packages: BTreeMap<AccountAddress, PackageNode>,
+}
+
+/// Work out which of the `dependents` packages are affected by upgrading the package at
+/// `package_id` to `new_package`, and whether the upgrade itself is compatible under its policy.
+/// The `dependents` are analyzed at the versions they are given at, and are not resolved to their
+/// latest version.
+pub(crate) async fn upgrade_impact(
+    client: Client,
+    package_id: ObjectID,
+    new_package: CompiledPackage,
+    package_path: PathBuf,
+    upgrade_policy: u8,
+    protocol_config: ProtocolConfig,
+    dependents: Vec<ObjectID>,
+) -> Result<UpgradeImpact, Error> {
+    let policy =
+        UpgradePolicy::try_from(upgrade_policy).map_err(|_| anyhow!("Invalid upgrade policy"))?;
+
+    let mut ids = vec![package_id];
+    ids.extend(dependents.into_iter().filter(|id| *id != package_id));
+    let objects = client
+        .batch_get_objects(&ids)
+        .await
+        .context("Unable to fetch packages")?;
+
+    let mut nodes = vec![];
+    for object in &objects {
+        let package = Package::read_from_object(object)
+            .with_context(|| format!("Unable to read package {}", object.id()))?;
+        nodes.push(PackageNode::from_package(&package));
+    }
+
+    let existing_package = objects[0]
+        .data
+        .try_as_package()
+        .ok_or_else(|| anyhow!("Object found when package expected"))?;
+    let original_id = existing_package.original_package_id();
+    let existing_modules = deserialize_modules(existing_package, &protocol_config)?;
+
+    let incompatibilities = compatibility_report(
+        original_id.into(),
+        existing_modules,
+        new_package,
+        package_path,
+        policy,
+    )?;
+
+    let action = if incompatibilities.is_some() {
+        ImpactAction::Republish
+    } else {
+        ImpactAction::Relink
+    };
+
+    let graph = PackageGraph::new(nodes);
+    let dependents = graph.dependents(original_id.into(), action);
+    let unaffected = graph.unaffected(original_id.into());
+
+    Ok(UpgradeImpact {
+        package_id,
+        original_id,
+        policy: policy_name(policy).to_string(),
+        incompatibilities,
+        dependents,
+        unaffected,
+    })
+}
+
+impl PackageNode {
+    pub(crate) fn new(
+        storage_id: AccountAddress,
+        runtime_id: AccountAddress,
+        version: SequenceNumber,
+        linkage: BTreeMap<AccountAddress, AccountAddress>,
+        direct: BTreeMap<AccountAddress, BTreeSet<String>>,
+    ) -> Self {
+        Self {
+            storage_id,
+            runtime_id,
+            version,
+            linkage,
+            direct,
+        }
+    }
+
+    pub(crate) fn from_package(package: &Package) -> Self {
+        let runtime_id = package.runtime_id();
+        let mut direct: BTreeMap<AccountAddress, BTreeSet<String>> = BTreeMap::new();
+        for module in package.modules().values() {
+            let bytecode = module.bytecode();
+            for handle in bytecode.module_handles() {
+                let address = *bytecode.address_identifier_at(handle.address);
+                if address != runtime_id {
+                    direct
+                        .entry(address)
+                        .or_default()
+                        .insert(bytecode.identifier_at(handle.name).to_string());
+                }
+            }
+        }
+
+        Self::new(
+            package.storage_id(),
+            runtime_id,
+            package.version(),
+            package.linkage().clone(),
+            direct,
+        )
+    }
+}
+
+impl PackageGraph {
+    pub(crate) fn new(nodes: impl IntoIterator<Item = PackageNode>) -> Self {
+        let mut packages: BTreeMap<AccountAddress, PackageNode> = BTreeMap::new();
+        for node in nodes {
+            match packages.get(&node.runtime_id) {
+                Some(existing) if existing.version >= node.version => {}
+                _ => {
+                    packages.insert(node.runtime_id, node);
+                }
+            }
+        }
+        Self { packages }
+    }
+
+    /// All packages that depend on the package with runtime ID `upgraded`, directly or
+    /// transitively, in the order they need to be updated in.
+    pub(crate) fn dependents(
+        &self,
+        upgraded: AccountAddress,
+        action: ImpactAction,
+    ) -> Vec<DependentImpact> {
+        // Linkage tables include transitive dependencies, so they are enough to find every
+        // affected package without walking the graph.
+        let affected: BTreeMap<AccountAddress, &PackageNode> = self
+            .packages
+            .iter()
+            .filter(|(id, node)| **id != upgraded && node.linkage.contains_key(&upgraded))
+            .map(|(id, node)| (*id, node))
+            .collect();
+
+        let mut depths = BTreeMap::new();
+        let mut impacts: Vec<(usize, DependentImpact)> = affected
+            .values()
+            .map(|node| {
+                let depth = depth(node.runtime_id, upgraded, &affected, &mut depths);
+                let via = node
+                    .direct
+                    .keys()
+                    .filter(|id| **id == upgraded || affected.contains_key(*id))
+                    .map(|id| match affected.get(id) {
+                        Some(dep) => dep.storage_id.into(),
+                        None => self
+                            .packages
+                            .get(id)
+                            .map_or(*id, |pkg| pkg.storage_id)
+                            .into(),
+                    })
+                    .collect();
+
+                let impact = DependentImpact {
+                    package_id: node.storage_id.into(),
+                    original_id: node.runtime_id.into(),
+                    action,
+                    linked_id: node.linkage[&upgraded].into(),
+                    via,
+                    uses_modules: node
+                        .direct
+                        .get(&upgraded)
+                        .map(|modules| modules.iter().cloned().collect())
+                        .unwrap_or_default(),
+                };
+                (depth, impact)
+            })
+            .collect();
+
+        impacts.sort_by_key(|(depth, impact)| (*depth, impact.original_id));
+        impacts.into_iter().map(|(_, impact)| impact).collect()
+    }
+
+    /// Packages in the graph that do not depend on the package with runtime ID `upgraded`.
+    pub(crate) fn unaffected(&self, upgraded: AccountAddress) -> Vec<ObjectID> {
+        self.packages
+            .iter()
+            .filter(|(id, node)| **id != upgraded && !node.linkage.contains_key(&upgraded))
+            .map(|(_, node)| node.storage_id.into())
+            .collect()
+    }
+}
+
+/// Length of the longest chain of affected packages between `id` and the upgraded package. A
+/// package that reaches the upgraded package only through packages outside the graph is at depth
+/// 1, like a direct dependent.
+fn depth(
+    id: AccountAddress,
+    upgraded: AccountAddress,
+    affected: &BTreeMap<AccountAddress, &PackageNode>,
+    depths: &mut BTreeMap<AccountAddress, usize>,
+) -> usize {
+    if id == upgraded {
+        return 0;
+    }
+
+    if let Some(depth) = depths.get(&id) {
+        return *depth;
+    }
+
+    let Some(node) = affected.get(&id) else {
+        return 0;
+    };
+
+    let depth = 1 + node
+        .direct
+        .keys()
+        .filter(|dep| **dep == upgraded || affected.contains_key(*dep))
+        .map(|dep| depth(*dep, upgraded, affected, depths))
+        .max()
+        .unwrap_or(0);
+
+    depths.insert(id, depth);
+    depth
+}
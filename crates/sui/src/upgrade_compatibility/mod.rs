@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod formatting;
+pub mod impact;
 #[path = "../unit_tests/upgrade_compatibility_tests.rs"]
 #[cfg(test)]
 mod upgrade_compatibility_tests;
@@ -49,7 +50,7 @@ use move_package_alt_compilation::compiled_package::CompiledUnitWithSource;
 use sui_move_build::CompiledPackage;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::ObjectID;
-use sui_types::move_package::UpgradePolicy;
+use sui_types::move_package::{MovePackage, UpgradePolicy};
 
 type Enum = normalized::Enum<normalized::RcIdentifier>;
 type Field = normalized::Field<normalized::RcIdentifier>;
@@ -697,12 +698,7 @@ pub(crate) async fn check_compatibility(
         .try_as_package()
         .ok_or_else(|| anyhow!("Object found when package expected"))?;
 
-    let existing_modules = existing_package
-        .serialized_module_map()
-        .iter()
-        .map(|m| CompiledModule::deserialize_with_config(m.1, &protocol_config.binary_config(None)))
-        .collect::<Result<Vec<_>, _>>()
-        .context("Unable to get existing package")?;
+    let existing_modules = deserialize_modules(existing_package, &protocol_config)?;
 
     let policy =
         UpgradePolicy::try_from(upgrade_policy).map_err(|_| anyhow!("Invalid upgrade policy"))?;
@@ -716,14 +712,53 @@ pub(crate) async fn check_compatibility(
     )
 }
 
+/// Deserialize the modules of an on-chain package, using the binary config of `protocol_config`.
+pub(crate) fn deserialize_modules(
+    package: &MovePackage,
+    protocol_config: &ProtocolConfig,
+) -> Result<Vec<CompiledModule>, Error> {
+    package
+        .serialized_module_map()
+        .iter()
+        .map(|m| CompiledModule::deserialize_with_config(m.1, &protocol_config.binary_config(None)))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Unable to get existing package")
+}
+
 /// Collect all the errors into a single error message.
 fn compare_packages(
     package_id: AccountAddress,
     existing_modules: Vec<CompiledModule>,
-    mut new_package: CompiledPackage,
+    new_package: CompiledPackage,
     package_path: PathBuf,
     policy: UpgradePolicy,
 ) -> Result<(), Error> {
+    match compatibility_report(
+        package_id,
+        existing_modules,
+        new_package,
+        package_path,
+        policy,
+    )? {
+        None => Ok(()),
+        Some(report) => Err(anyhow!(
+            "{report}\nUpgrade failed, this package requires changes to be compatible with the existing package. \
+            Its upgrade policy is set to '{}'. Use --skip-verify-compatibility to bypass this check locally.",
+            policy_name(policy),
+        )),
+    }
+}
+
+/// Compare the modules of an existing package against its new version under `policy`, returning
+/// the rendered diagnostics for every incompatibility found, or `None` if the upgrade is
+/// compatible.
+pub(crate) fn compatibility_report(
+    package_id: AccountAddress,
+    existing_modules: Vec<CompiledModule>,
+    mut new_package: CompiledPackage,
+    package_path: PathBuf,
+    policy: UpgradePolicy,
+) -> Result<Option<String>, Error> {
     // create a map from the new modules
     let mut new_modules_map: HashMap<Identifier, CompiledModule> = new_package
         .get_modules()
@@ -791,29 +826,31 @@ fn compare_packages(
     }
 
     if diags.is_empty() {
-        Ok(())
-    } else {
-        // Sort diagnostics to ensure consistent error ordering across platforms
-        // Diagnostic implements Ord, so sorting will be deterministic
-        let mut sorted_vec = diags.into_vec();
-        sorted_vec.sort();
-        let sorted_diags: Diagnostics = sorted_vec.into_iter().collect();
-
-        Err(anyhow!(
-            "{}\nUpgrade failed, this package requires changes to be compatible with the existing package. \
-            Its upgrade policy is set to '{}'. Use --skip-verify-compatibility to bypass this check locally.",
-            String::from_utf8(report_diagnostics_to_buffer(
-                &new_package.package.file_map,
-                sorted_diags,
-                use_colors()
-            ))
-            .context("Unable to convert buffer to string")?,
-            match policy {
-                UpgradePolicy::Compatible => "compatible",
-                UpgradePolicy::Additive => "additive",
-                UpgradePolicy::DepOnly => "dependency only",
-            }
-        ))
+        return Ok(None);
+    }
+
+    // Sort diagnostics to ensure consistent error ordering across platforms
+    // Diagnostic implements Ord, so sorting will be deterministic
+    let mut sorted_vec = diags.into_vec();
+    sorted_vec.sort();
+    let sorted_diags: Diagnostics = sorted_vec.into_iter().collect();
+
+    let report = String::from_utf8(report_diagnostics_to_buffer(
+        &new_package.package.file_map,
+        sorted_diags,
+        use_colors(),
+    ))
+    .context("Unable to convert buffer to string")?;
+
+    Ok(Some(report))
+}
+
+/// Human-readable name of an upgrade policy, as used in error messages.
+pub(crate) fn policy_name(policy: UpgradePolicy) -> &'static str {
+    match policy {
+        UpgradePolicy::Compatible => "compatible",
+        UpgradePolicy::Additive => "additive",
+        UpgradePolicy::DepOnly => "dependency only",
     }
 }
 
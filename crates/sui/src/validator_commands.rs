@@ -9,6 +9,7 @@ use std::{
     fs,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use sui_genesis_builder::validator_info::GenesisValidatorInfo;
 use url::{ParseError, Url};
@@ -28,6 +29,7 @@ use sui_types::{
 use tap::tap::TapOptional;
 
 use crate::fire_drill::get_gas_obj_ref;
+use crate::validator_status::{ValidatorStatusReport, validator_status};
 use clap::*;
 use colored::Colorize;
 use fastcrypto::traits::ToFromBytes;
@@ -112,6 +114,20 @@ pub enum SuiValidatorCommand {
         #[clap(name = "json", long)]
         json: Option<bool>,
     },
+    /// Report the health of a validator: its on-chain metadata and stake, pending changes for the
+    /// next epoch, position in the gas price survey and reports against it, along with the
+    /// reachability of its network endpoints.
+    #[clap(name = "status")]
+    Status {
+        #[clap(name = "validator-address")]
+        validator_address: Option<SuiAddress>,
+        /// Timeout of each endpoint probe, in seconds.
+        #[clap(name = "probe-timeout-secs", long, default_value = "5")]
+        probe_timeout_secs: u64,
+        /// Only report the on-chain state, without probing the network endpoints.
+        #[clap(name = "skip-probes", long)]
+        skip_probes: bool,
+    },
     #[clap(name = "update-metadata")]
     UpdateMetadata {
         #[clap(subcommand)]
@@ -244,6 +260,7 @@ pub enum SuiValidatorCommandResponse {
         serialized_unsigned_transaction: Option<String>,
     },
     SerializedPayload(String),
+    Status(ValidatorStatusReport),
     DisplayGasPriceUpdateRawTxn {
         data: TransactionData,
         serialized_data: String,
@@ -473,6 +490,19 @@ impl SuiValidatorCommand {
                 SuiValidatorCommandResponse::DisplayMetadata
             }
 
+            SuiValidatorCommand::Status {
+                validator_address,
+                probe_timeout_secs,
+                skip_probes,
+            } => {
+                let validator_address = validator_address.unwrap_or(sui_address);
+                let sui_client = context.grpc_client()?;
+                let probe_timeout = (!skip_probes).then(|| Duration::from_secs(probe_timeout_secs));
+                SuiValidatorCommandResponse::Status(
+                    validator_status(&sui_client, validator_address, probe_timeout).await?,
+                )
+            }
+
             SuiValidatorCommand::UpdateMetadata { metadata, tx_args } => {
                 let gas_budget = tx_args.gas_budget.unwrap_or(DEFAULT_GAS_BUDGET);
                 let (response, serialized_unsigned_transaction) = update_metadata(
@@ -997,6 +1027,9 @@ impl Display for SuiValidatorCommandResponse {
             SuiValidatorCommandResponse::SerializedPayload(response) => {
                 write!(writer, "Serialized payload: {}", response)?;
             }
+            SuiValidatorCommandResponse::Status(report) => {
+                write!(writer, "{}", report)?;
+            }
             SuiValidatorCommandResponse::DisplayGasPriceUpdateRawTxn {
                 data,
                 serialized_data,
@@ -1081,7 +1114,7 @@ impl SuiValidatorCommandResponse {
     }
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Hash, PartialEq, Eq, Serialize)]
 pub enum ValidatorStatus {
     Active,
     Pending,
@@ -1159,6 +1192,18 @@ async fn get_pending_candidate_summary(
     sui_client: &Client,
     pending_active_validators_id: ObjectID,
 ) -> anyhow::Result<Option<proto::Validator>> {
+    Ok(
+        get_pending_validator(validator_address, sui_client, pending_active_validators_id)
+            .await?
+            .map(Into::into),
+    )
+}
+
+pub(crate) async fn get_pending_validator(
+    validator_address: SuiAddress,
+    sui_client: &Client,
+    pending_active_validators_id: ObjectID,
+) -> anyhow::Result<Option<ValidatorV1>> {
     let pending_validators = sui_client
         .get_dynamic_fields(pending_active_validators_id, None, None)
         .await?;
@@ -1173,7 +1218,7 @@ async fn get_pending_candidate_summary(
             )
         })?;
         if field.verified_metadata().sui_address == validator_address {
-            return Ok(Some(field.into()));
+            return Ok(Some(field));
         }
     }
     Ok(None)
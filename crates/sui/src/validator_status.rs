@@ -0,0 +1,565 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::encoding::{Encoding, Hex};
+use fastcrypto::traits::{KeyPair, ToFromBytes};
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sui_rpc_api::Client;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::{QUORUM_THRESHOLD, TOTAL_VOTING_POWER};
+use sui_types::crypto::{NetworkKeyPair, get_key_pair};
+use sui_types::multiaddr::Multiaddr;
+use sui_types::sui_system_state::sui_system_state_summary::{
+    SuiSystemStateSummary, SuiValidatorSummary,
+};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::pki_types::ServerName;
+
+use crate::validator_commands::{ValidatorStatus, get_pending_validator};
+
+/// Health report of a validator, as printed by `sui validator status`.
+#[derive(Serialize)]
+pub struct ValidatorStatusReport {
+    pub status: ValidatorStatus,
+    pub epoch: u64,
+    pub validator: SuiValidatorSummary,
+    /// Changes taking effect at the next epoch.
+    pub pending_changes: Vec<String>,
+    /// Only known for active validators.
+    pub gas_price_survey: Option<GasPriceSurvey>,
+    pub reports: Vec<Reporter>,
+    /// Number of epochs the validator has been below the low stake threshold, if it is at risk.
+    pub at_risk_epochs: Option<u64>,
+    pub endpoints: Vec<EndpointProbe>,
+    pub warnings: Vec<String>,
+}
+
+/// Position of the validator's gas price quote among the quotes of the active validators.
+#[derive(Serialize)]
+pub struct GasPriceSurvey {
+    pub reference_gas_price: u64,
+    /// Reference gas price the current quotes would set for the next epoch.
+    pub projected_reference_gas_price: u64,
+    pub quote: u64,
+    /// Voting power of the validators quoting a lower, the same and a higher price.
+    pub voting_power_below: u64,
+    pub voting_power_equal: u64,
+    pub voting_power_above: u64,
+}
+
+#[derive(Serialize)]
+pub struct Reporter {
+    pub address: SuiAddress,
+    pub name: Option<String>,
+    pub voting_power: u64,
+}
+
+#[derive(Serialize)]
+pub struct EndpointProbe {
+    pub endpoint: Endpoint,
+    pub address: String,
+    pub result: ProbeResult,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    Grpc,
+    Consensus,
+    P2p,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum ProbeResult {
+    /// The endpoint accepted a connection authenticated with the on-chain network key.
+    Reachable {
+        latency_ms: u64,
+    },
+    /// The endpoint accepted a connection, but did not prove the on-chain identity.
+    IdentityMismatch {
+        error: String,
+    },
+    Unreachable {
+        error: String,
+    },
+    InvalidAddress {
+        error: String,
+    },
+}
+
+/// Builds the status report of `validator_address`. Network endpoints are probed with
+/// `probe_timeout`, unless it is `None`.
+pub async fn validator_status(
+    client: &Client,
+    validator_address: SuiAddress,
+    probe_timeout: Option<Duration>,
+) -> Result<ValidatorStatusReport> {
+    let system_state = client.get_system_state_summary(None).await?;
+    let (status, validator) = match system_state
+        .active_validators
+        .iter()
+        .find(|validator| validator.sui_address == validator_address)
+    {
+        Some(validator) => (ValidatorStatus::Active, validator.clone()),
+        None => {
+            let validator = get_pending_validator(
+                validator_address,
+                client,
+                system_state.pending_active_validators_id,
+            )
+            .await?
+            .ok_or_else(|| anyhow!("{validator_address} is not an active or pending validator"))?;
+            (
+                ValidatorStatus::Pending,
+                validator.into_sui_validator_summary(),
+            )
+        }
+    };
+
+    let endpoints = match probe_timeout {
+        Some(timeout) => {
+            let chain_identifier = client.get_chain_identifier().await?;
+            probe_endpoints(
+                &validator,
+                system_state.epoch,
+                &format!("sui-{chain_identifier}"),
+                timeout,
+            )
+            .await
+        }
+        None => vec![],
+    };
+    Ok(ValidatorStatusReport::new(
+        status,
+        validator,
+        &system_state,
+        endpoints,
+    ))
+}
+
+impl ValidatorStatusReport {
+    pub fn new(
+        status: ValidatorStatus,
+        validator: SuiValidatorSummary,
+        system_state: &SuiSystemStateSummary,
+        endpoints: Vec<EndpointProbe>,
+    ) -> Self {
+        let address = validator.sui_address;
+        let active_index = system_state
+            .active_validators
+            .iter()
+            .position(|v| v.sui_address == address);
+        let mut warnings = vec![];
+
+        let mut pending_changes = vec![];
+        match active_index {
+            None => pending_changes.push("Joins the committee".to_string()),
+            Some(index) if system_state.pending_removals.contains(&(index as u64)) => {
+                pending_changes.push("Leaves the committee".to_string())
+            }
+            Some(_) => (),
+        }
+        let key_changes = [
+            ("Protocol key", &validator.next_epoch_protocol_pubkey_bytes),
+            ("Network key", &validator.next_epoch_network_pubkey_bytes),
+            ("Worker key", &validator.next_epoch_worker_pubkey_bytes),
+        ];
+        for (name, key) in key_changes {
+            if let Some(key) = key {
+                pending_changes.push(format!("{name} changes to {}", Hex::encode(key)));
+            }
+        }
+        let address_changes = [
+            ("Network address", &validator.next_epoch_net_address),
+            ("P2P address", &validator.next_epoch_p2p_address),
+            ("Primary address", &validator.next_epoch_primary_address),
+            ("Worker address", &validator.next_epoch_worker_address),
+        ];
+        for (name, address) in address_changes {
+            if let Some(address) = address {
+                pending_changes.push(format!("{name} changes to {address}"));
+            }
+        }
+        if validator.next_epoch_gas_price != validator.gas_price {
+            pending_changes.push(format!(
+                "Gas price changes from {} to {}",
+                validator.gas_price, validator.next_epoch_gas_price
+            ));
+        }
+        if validator.next_epoch_commission_rate != validator.commission_rate {
+            pending_changes.push(format!(
+                "Commission rate changes from {} to {} basis points",
+                validator.commission_rate, validator.next_epoch_commission_rate
+            ));
+        }
+        if validator.next_epoch_stake != validator.staking_pool_sui_balance {
+            pending_changes.push(format!(
+                "Stake changes from {} to {} MIST",
+                validator.staking_pool_sui_balance, validator.next_epoch_stake
+            ));
+        }
+
+        let gas_price_survey = active_index.map(|_| {
+            let quote = validator.next_epoch_gas_price;
+            let mut survey = GasPriceSurvey {
+                reference_gas_price: system_state.reference_gas_price,
+                projected_reference_gas_price: projected_reference_gas_price(
+                    &system_state.active_validators,
+                ),
+                quote,
+                voting_power_below: 0,
+                voting_power_equal: 0,
+                voting_power_above: 0,
+            };
+            for v in &system_state.active_validators {
+                match v.next_epoch_gas_price.cmp(&quote) {
+                    std::cmp::Ordering::Less => survey.voting_power_below += v.voting_power,
+                    std::cmp::Ordering::Equal => survey.voting_power_equal += v.voting_power,
+                    std::cmp::Ordering::Greater => survey.voting_power_above += v.voting_power,
+                }
+            }
+            survey
+        });
+
+        let reports = system_state
+            .validator_report_records
+            .iter()
+            .find(|(reportee, _)| *reportee == address)
+            .map(|(_, reporters)| {
+                reporters
+                    .iter()
+                    .map(|reporter| {
+                        let summary = system_state
+                            .active_validators
+                            .iter()
+                            .find(|v| v.sui_address == *reporter);
+                        Reporter {
+                            address: *reporter,
+                            name: summary.map(|v| v.name.clone()),
+                            voting_power: summary.map_or(0, |v| v.voting_power),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if !reports.is_empty() {
+            let voting_power: u64 = reports.iter().map(|r| r.voting_power).sum();
+            if voting_power >= QUORUM_THRESHOLD {
+                warnings.push(format!(
+                    "Reported by validators with a quorum of voting power ({voting_power}/{TOTAL_VOTING_POWER}), stake rewards will be slashed at the end of the epoch"
+                ));
+            } else {
+                warnings.push(format!(
+                    "Reported by {} validator(s) with {voting_power}/{TOTAL_VOTING_POWER} voting power",
+                    reports.len()
+                ));
+            }
+        }
+
+        let at_risk_epochs = system_state
+            .at_risk_validators
+            .iter()
+            .find(|(at_risk, _)| *at_risk == address)
+            .map(|(_, epochs)| *epochs);
+        if validator.next_epoch_stake < system_state.validator_very_low_stake_threshold {
+            warnings.push(format!(
+                "Next epoch stake {} is below the very low stake threshold {}, the validator will be removed at the end of the epoch",
+                validator.next_epoch_stake, system_state.validator_very_low_stake_threshold
+            ));
+        } else if let Some(epochs) = at_risk_epochs {
+            warnings.push(format!(
+                "Stake has been below the low stake threshold {} for {epochs} epoch(s), the validator will be removed after {} epochs",
+                system_state.validator_low_stake_threshold,
+                system_state.validator_low_stake_grace_period
+            ));
+        } else if validator.next_epoch_stake < system_state.validator_low_stake_threshold {
+            warnings.push(format!(
+                "Next epoch stake {} is below the low stake threshold {}",
+                validator.next_epoch_stake, system_state.validator_low_stake_threshold
+            ));
+        }
+
+        for probe in &endpoints {
+            match &probe.result {
+                ProbeResult::Reachable { .. } => (),
+                ProbeResult::IdentityMismatch { error } => warnings.push(format!(
+                    "{} endpoint {} does not match the on-chain network key: {error}",
+                    probe.endpoint, probe.address
+                )),
+                ProbeResult::Unreachable { error } => warnings.push(format!(
+                    "{} endpoint {} is unreachable: {error}",
+                    probe.endpoint, probe.address
+                )),
+                ProbeResult::InvalidAddress { error } => warnings.push(format!(
+                    "{} endpoint {} is invalid: {error}",
+                    probe.endpoint, probe.address
+                )),
+            }
+        }
+
+        Self {
+            status,
+            epoch: system_state.epoch,
+            validator,
+            pending_changes,
+            gas_price_survey,
+            reports,
+            at_risk_epochs,
+            endpoints,
+            warnings,
+        }
+    }
+}
+
+/// Mirrors the reference gas price derivation of the system package: the highest quote such that
+/// validators with a quorum of voting power quote at most that price.
+pub fn projected_reference_gas_price(validators: &[SuiValidatorSummary]) -> u64 {
+    let mut quotes = validators
+        .iter()
+        .map(|v| (v.next_epoch_gas_price, v.voting_power))
+        .collect::<Vec<_>>();
+    quotes.sort_by(|a, b| b.0.cmp(&a.0));
+    let threshold = TOTAL_VOTING_POWER - QUORUM_THRESHOLD;
+    let mut sum = 0;
+    let mut result = 0;
+    for (gas_price, voting_power) in quotes {
+        if sum >= threshold {
+            break;
+        }
+        result = gas_price;
+        sum += voting_power;
+    }
+    result
+}
+
+async fn probe_endpoints(
+    validator: &SuiValidatorSummary,
+    epoch: u64,
+    p2p_server_name: &str,
+    timeout: Duration,
+) -> Vec<EndpointProbe> {
+    let network_key = Ed25519PublicKey::from_bytes(&validator.network_pubkey_bytes);
+    let probes = [
+        (
+            Endpoint::Grpc,
+            &validator.net_address,
+            sui_tls::SUI_VALIDATOR_SERVER_NAME.to_string(),
+        ),
+        (
+            Endpoint::Consensus,
+            &validator.primary_address,
+            format!("consensus_epoch_{epoch}"),
+        ),
+        (
+            Endpoint::P2p,
+            &validator.p2p_address,
+            p2p_server_name.to_string(),
+        ),
+    ];
+    let mut results = vec![];
+    for (endpoint, address, server_name) in probes {
+        let result = match (Multiaddr::try_from(address.clone()), &network_key) {
+            (Err(e), _) => ProbeResult::InvalidAddress {
+                error: e.to_string(),
+            },
+            (_, Err(e)) => ProbeResult::IdentityMismatch {
+                error: format!("invalid on-chain network key: {e}"),
+            },
+            (Ok(multiaddr), Ok(network_key)) => {
+                let start = Instant::now();
+                let probe = async {
+                    match endpoint {
+                        Endpoint::Grpc | Endpoint::Consensus => {
+                            probe_tls(&multiaddr, network_key.clone(), server_name).await
+                        }
+                        Endpoint::P2p => probe_anemo(&multiaddr, network_key, server_name).await,
+                    }
+                };
+                match tokio::time::timeout(timeout, probe).await {
+                    Ok(Ok(())) => ProbeResult::Reachable {
+                        latency_ms: start.elapsed().as_millis() as u64,
+                    },
+                    Ok(Err(result)) => result,
+                    Err(_) => ProbeResult::Unreachable {
+                        error: format!("timed out after {timeout:?}"),
+                    },
+                }
+            }
+        };
+        results.push(EndpointProbe {
+            endpoint,
+            address: address.clone(),
+            result,
+        });
+    }
+    results
+}
+
+/// Opens a TLS connection to `address`, and checks the server certificate against `network_key`.
+/// Consensus requires a committee key from clients, which the server checks after the handshake
+/// completes on the client side, so a random client key is enough to verify the server.
+async fn probe_tls(
+    address: &Multiaddr,
+    network_key: Ed25519PublicKey,
+    server_name: String,
+) -> Result<(), ProbeResult> {
+    let (Some(host), Some(port)) = (address.hostname(), address.port()) else {
+        return Err(ProbeResult::InvalidAddress {
+            error: "address has no host or port".to_string(),
+        });
+    };
+    let stream = TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(|e| ProbeResult::Unreachable {
+            error: e.to_string(),
+        })?;
+    let (_, client_key): (_, NetworkKeyPair) = get_key_pair();
+    let tls_config = sui_tls::create_rustls_client_config(
+        network_key,
+        server_name.clone(),
+        Some(client_key.private()),
+    );
+    let server_name =
+        ServerName::try_from(server_name).map_err(|e| ProbeResult::InvalidAddress {
+            error: e.to_string(),
+        })?;
+    TlsConnector::from(Arc::new(tls_config))
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| ProbeResult::IdentityMismatch {
+            error: e.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Connects to the anemo network at `address`, and checks the peer id it answers with.
+async fn probe_anemo(
+    address: &Multiaddr,
+    network_key: &Ed25519PublicKey,
+    server_name: String,
+) -> Result<(), ProbeResult> {
+    let anemo_address = address
+        .to_anemo_address()
+        .map_err(|e| ProbeResult::InvalidAddress {
+            error: e.to_string(),
+        })?;
+    let (_, client_key): (_, NetworkKeyPair) = get_key_pair();
+    let network = anemo::Network::bind("0.0.0.0:0")
+        .server_name(&server_name)
+        .private_key(client_key.private().0.to_bytes())
+        .start(anemo::Router::new())
+        .map_err(|e| ProbeResult::Unreachable {
+            error: format!("failed to start local network: {e}"),
+        })?;
+    let peer_id = network
+        .connect(anemo_address)
+        .await
+        .map_err(|e| ProbeResult::Unreachable {
+            error: e.to_string(),
+        })?;
+    let expected = anemo::PeerId(network_key.0.to_bytes());
+    let _ = network.disconnect(peer_id);
+    if peer_id != expected {
+        return Err(ProbeResult::IdentityMismatch {
+            error: format!("peer id is {peer_id}, expected {expected}"),
+        });
+    }
+    Ok(())
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Endpoint::Grpc => write!(f, "gRPC"),
+            Endpoint::Consensus => write!(f, "Consensus"),
+            Endpoint::P2p => write!(f, "P2P"),
+        }
+    }
+}
+
+impl Display for ValidatorStatusReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let v = &self.validator;
+        writeln!(f, "{}", "----- Validator ----".bold())?;
+        writeln!(f, "Name: {}", v.name)?;
+        writeln!(f, "Address: {}", v.sui_address)?;
+        writeln!(f, "Status: {:?} in epoch {}", self.status, self.epoch)?;
+        writeln!(f, "Network address: {}", v.net_address)?;
+        writeln!(f, "P2P address: {}", v.p2p_address)?;
+        writeln!(f, "Primary address: {}", v.primary_address)?;
+        writeln!(f, "Voting power: {}/{TOTAL_VOTING_POWER}", v.voting_power)?;
+        writeln!(f, "Stake: {} MIST", v.staking_pool_sui_balance)?;
+        writeln!(f, "Gas price: {}", v.gas_price)?;
+        writeln!(f, "Commission rate: {} basis points", v.commission_rate)?;
+
+        writeln!(f, "\n{}", "----- Pending changes ----".bold())?;
+        if self.pending_changes.is_empty() {
+            writeln!(f, "None")?;
+        }
+        for change in &self.pending_changes {
+            writeln!(f, "{change}")?;
+        }
+
+        if let Some(survey) = &self.gas_price_survey {
+            writeln!(f, "\n{}", "----- Gas price survey ----".bold())?;
+            writeln!(f, "Quote for next epoch: {}", survey.quote)?;
+            writeln!(f, "Reference gas price: {}", survey.reference_gas_price)?;
+            writeln!(
+                f,
+                "Projected reference gas price: {}",
+                survey.projected_reference_gas_price
+            )?;
+            writeln!(
+                f,
+                "Voting power quoting lower / the same / higher: {} / {} / {}",
+                survey.voting_power_below, survey.voting_power_equal, survey.voting_power_above
+            )?;
+        }
+
+        writeln!(f, "\n{}", "----- Reports ----".bold())?;
+        if self.reports.is_empty() {
+            writeln!(f, "None")?;
+        }
+        for reporter in &self.reports {
+            writeln!(
+                f,
+                "{} ({}), voting power {}",
+                reporter.address,
+                reporter.name.as_deref().unwrap_or("not active"),
+                reporter.voting_power
+            )?;
+        }
+
+        if !self.endpoints.is_empty() {
+            writeln!(f, "\n{}", "----- Endpoints ----".bold())?;
+        }
+        for probe in &self.endpoints {
+            let result = match &probe.result {
+                ProbeResult::Reachable { latency_ms } => {
+                    format!("reachable ({latency_ms}ms)").green()
+                }
+                ProbeResult::IdentityMismatch { .. } => "identity mismatch".red(),
+                ProbeResult::Unreachable { .. } => "unreachable".red(),
+                ProbeResult::InvalidAddress { .. } => "invalid address".red(),
+            };
+            writeln!(f, "{} {}: {result}", probe.endpoint, probe.address)?;
+        }
+
+        writeln!(f, "\n{}", "----- Warnings ----".bold())?;
+        if self.warnings.is_empty() {
+            writeln!(f, "{}", "None".green())?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "{}", warning.yellow())?;
+        }
+        Ok(())
+    }
+}
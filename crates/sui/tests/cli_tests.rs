@@ -439,6 +439,7 @@ async fn test_genesis() -> Result<(), anyhow::Error> {
         epoch_duration_ms: None,
         no_full_node: false,
         committee_size: None,
+        topology: None,
         rpc_args: RpcArgs::for_testing(),
     }
     .execute()
@@ -774,6 +775,31 @@ async fn test_object_info_get_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_decompile_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let context = &mut test_cluster.wallet;
+    let output_dir = tempfile::tempdir()?;
+
+    let resp = SuiClientCommands::Decompile {
+        package_id: ObjectID::from_hex_literal("0x1").unwrap(),
+        output_dir: Some(output_dir.path().to_path_buf()),
+    }
+    .execute(context)
+    .await?;
+    resp.print(true);
+
+    let SuiClientCommandResult::Decompile(output) = resp else {
+        panic!("Unexpected response: {resp:?}");
+    };
+    assert!(!output.files.is_empty());
+    let option_path = output_dir.path().join("option.move");
+    assert!(output.files.contains(&option_path));
+    assert!(std::fs::read_to_string(&option_path)?.contains("module"));
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_gas_command() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -0,0 +1,363 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scripted fault injection for simulation tests. A [`FaultScenario`] is a schedule of faults,
+//! each applied to the nodes of a [`TestCluster`] at a fixed time after the scenario starts:
+//!
+//! ```ignore
+//! let validators = test_cluster.get_validator_pubkeys();
+//! let timeline = FaultScenario::new()
+//!     .crash_for(Duration::from_secs(5), validators[0], Duration::from_secs(10))
+//!     .partition_for(
+//!         Duration::from_secs(20),
+//!         vec![validators[1]],
+//!         vec![validators[2], validators[3]],
+//!         Duration::from_secs(10),
+//!     )
+//!     .clock_skew_for(Duration::from_secs(40), validators[2], -2_000, Duration::from_secs(10))
+//!     .run(&test_cluster)
+//!     .await;
+//! ```
+//!
+//! The simulator runs a scenario deterministically for a given seed, and the [`FaultTimeline`] it
+//! returns records when each fault was applied.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::{Rng, seq::SliceRandom};
+use sui_macros::{
+    clear_fail_point, register_fail_point_arg, register_fail_point_async, register_fail_points,
+};
+use sui_simulator::task::NodeId;
+use sui_types::base_types::{AuthorityName, ConciseableName};
+use tokio::time::{Instant, sleep_until};
+use tracing::info;
+
+use crate::TestCluster;
+
+const CLOCK_SKEW_FAIL_POINT: &str = "consensus-clock-skew";
+const EXECUTION_DELAY_FAIL_POINT: &str = "transaction_execution_delay";
+const DB_WRITE_FAIL_POINTS: &[&str] = &["batch-write-before", "put-cf-before", "delete-cf-before"];
+
+#[derive(Clone, Debug)]
+pub enum Fault {
+    /// Stops the node.
+    Crash(AuthorityName),
+    /// Starts the node again if it is stopped.
+    Restart(AuthorityName),
+    /// Cuts the network between each node of the first set and each node of the second set,
+    /// replacing any previous partition.
+    Partition(Vec<AuthorityName>, Vec<AuthorityName>),
+    /// Restores the links cut by [`Fault::Partition`].
+    Heal,
+    /// Shifts the consensus clock of the validator by `skew_ms`. A skew of zero restores the clock.
+    ClockSkew { node: AuthorityName, skew_ms: i64 },
+    /// The node crashes on its next database write, as if its disk was full, and stays down.
+    DiskFull(AuthorityName),
+    /// Frees the disk of the node and starts it again.
+    DiskFreed(AuthorityName),
+    /// Delays the execution of each transaction on the node by `delay`. A zero delay restores
+    /// normal execution.
+    SlowExecution {
+        node: AuthorityName,
+        delay: Duration,
+    },
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = |names: &[AuthorityName]| {
+            names
+                .iter()
+                .map(|name| format!("{:?}", name.concise()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Fault::Crash(node) => write!(f, "crash {:?}", node.concise()),
+            Fault::Restart(node) => write!(f, "restart {:?}", node.concise()),
+            Fault::Partition(a, b) => write!(f, "partition [{}] from [{}]", nodes(a), nodes(b)),
+            Fault::Heal => write!(f, "heal partition"),
+            Fault::ClockSkew { node, skew_ms } => {
+                write!(f, "skew clock of {:?} by {skew_ms}ms", node.concise())
+            }
+            Fault::DiskFull(node) => write!(f, "fill disk of {:?}", node.concise()),
+            Fault::DiskFreed(node) => write!(f, "free disk of {:?}", node.concise()),
+            Fault::SlowExecution { node, delay } => {
+                write!(f, "delay execution on {:?} by {delay:?}", node.concise())
+            }
+        }
+    }
+}
+
+/// A schedule of faults. Steps scheduled at the same time are applied in the order they were
+/// added.
+#[derive(Clone, Debug, Default)]
+pub struct FaultScenario {
+    steps: Vec<(Duration, Fault)>,
+}
+
+impl FaultScenario {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `fault` at `at` after the start of the scenario.
+    pub fn at(mut self, at: Duration, fault: Fault) -> Self {
+        self.steps.push((at, fault));
+        self
+    }
+
+    pub fn crash_for(self, at: Duration, node: AuthorityName, downtime: Duration) -> Self {
+        self.at(at, Fault::Crash(node))
+            .at(at + downtime, Fault::Restart(node))
+    }
+
+    pub fn partition_for(
+        self,
+        at: Duration,
+        a: Vec<AuthorityName>,
+        b: Vec<AuthorityName>,
+        duration: Duration,
+    ) -> Self {
+        self.at(at, Fault::Partition(a, b))
+            .at(at + duration, Fault::Heal)
+    }
+
+    pub fn clock_skew_for(
+        self,
+        at: Duration,
+        node: AuthorityName,
+        skew_ms: i64,
+        duration: Duration,
+    ) -> Self {
+        self.at(at, Fault::ClockSkew { node, skew_ms })
+            .at(at + duration, Fault::ClockSkew { node, skew_ms: 0 })
+    }
+
+    pub fn disk_full_for(self, at: Duration, node: AuthorityName, duration: Duration) -> Self {
+        self.at(at, Fault::DiskFull(node))
+            .at(at + duration, Fault::DiskFreed(node))
+    }
+
+    pub fn slow_execution_for(
+        self,
+        at: Duration,
+        node: AuthorityName,
+        delay: Duration,
+        duration: Duration,
+    ) -> Self {
+        self.at(at, Fault::SlowExecution { node, delay }).at(
+            at + duration,
+            Fault::SlowExecution {
+                node,
+                delay: Duration::ZERO,
+            },
+        )
+    }
+
+    /// Adds `count` crashes of nodes picked from `nodes`, each starting at a random time within
+    /// `window` and lasting a random time within `downtime`. The random choices derive from the
+    /// seed of the simulator. Crashes may overlap, so more nodes than the committee tolerates can
+    /// be down at once.
+    pub fn random_crashes(
+        mut self,
+        nodes: &[AuthorityName],
+        count: usize,
+        window: Range<Duration>,
+        downtime: Range<Duration>,
+    ) -> Self {
+        let mut rng = rand::thread_rng();
+        for _ in 0..count {
+            let node = *nodes.choose(&mut rng).unwrap();
+            let at = rng.gen_range(window.clone());
+            let downtime = rng.gen_range(downtime.clone());
+            self = self.crash_for(at, node, downtime);
+        }
+        self
+    }
+
+    /// Applies the faults of the scenario to `cluster`, returning once the last one is applied.
+    /// When the scenario ends the network is healed and the clock skews, disk faults and
+    /// execution delays are lifted, but stopped nodes stay stopped.
+    pub async fn run(mut self, cluster: &TestCluster) -> FaultTimeline {
+        self.steps.sort_by_key(|(at, _)| *at);
+        let injected = Arc::new(Mutex::new(InjectedFaults::default()));
+        injected.lock().unwrap().update_sim_nodes(cluster);
+        let _fail_points = FailPoints::register(injected.clone());
+
+        let mut timeline = FaultTimeline {
+            seed: std::env::var("MSIM_TEST_SEED").ok(),
+            entries: vec![],
+        };
+        let mut partition = vec![];
+        let start = Instant::now();
+        for (at, fault) in self.steps {
+            sleep_until(start + at).await;
+            info!("Fault scenario at {at:?}: {fault}");
+            match &fault {
+                Fault::Crash(node) => cluster.stop_node(node),
+                Fault::Restart(node) => {
+                    cluster.start_node(node).await;
+                    injected.lock().unwrap().update_sim_nodes(cluster);
+                }
+                Fault::Partition(a, b) => {
+                    partition = a
+                        .iter()
+                        .flat_map(|a| b.iter().map(move |b| (*a, *b)))
+                        .collect();
+                    cluster.swarm.set_partitioned_links(&partition);
+                }
+                Fault::Heal => {
+                    partition.clear();
+                    cluster.swarm.set_partitioned_links(&partition);
+                }
+                Fault::ClockSkew { node, skew_ms } => {
+                    let mut injected = injected.lock().unwrap();
+                    if *skew_ms == 0 {
+                        injected.clock_skews.remove(node);
+                    } else {
+                        injected.clock_skews.insert(*node, *skew_ms);
+                    }
+                }
+                Fault::DiskFull(node) => {
+                    injected.lock().unwrap().full_disks.insert(*node);
+                }
+                Fault::DiskFreed(node) => {
+                    injected.lock().unwrap().full_disks.remove(node);
+                    // Drop the container of the killed node, so that it can be started again.
+                    cluster.stop_node(node);
+                    cluster.start_node(node).await;
+                    injected.lock().unwrap().update_sim_nodes(cluster);
+                }
+                Fault::SlowExecution { node, delay } => {
+                    let mut injected = injected.lock().unwrap();
+                    if delay.is_zero() {
+                        injected.execution_delays.remove(node);
+                    } else {
+                        injected.execution_delays.insert(*node, *delay);
+                    }
+                }
+            }
+            timeline.entries.push(TimelineEntry {
+                at: start.elapsed(),
+                fault,
+            });
+        }
+        if !partition.is_empty() {
+            cluster.swarm.set_partitioned_links(&[]);
+        }
+        timeline
+    }
+}
+
+/// Faults applied by the fail points, looked up by the simulator node hitting the fail point.
+#[derive(Default)]
+struct InjectedFaults {
+    sim_nodes: HashMap<NodeId, AuthorityName>,
+    clock_skews: HashMap<AuthorityName, i64>,
+    execution_delays: HashMap<AuthorityName, Duration>,
+    full_disks: HashSet<AuthorityName>,
+}
+
+impl InjectedFaults {
+    /// Restarted nodes run on new simulator nodes, so the mapping is refreshed after each restart.
+    fn update_sim_nodes(&mut self, cluster: &TestCluster) {
+        self.sim_nodes = cluster
+            .swarm
+            .all_nodes()
+            .filter_map(|node| {
+                let sim_node = node.get_node_handle()?.with(|n| n.get_sim_node_id());
+                Some((sim_node, node.name()))
+            })
+            .collect();
+    }
+
+    fn current_node(&self) -> Option<&AuthorityName> {
+        self.sim_nodes.get(&sui_simulator::current_simnode_id())
+    }
+}
+
+/// Registers the fail points injecting the faults, and clears them when dropped.
+struct FailPoints;
+
+impl FailPoints {
+    fn register(injected: Arc<Mutex<InjectedFaults>>) -> Self {
+        let faults = injected.clone();
+        register_fail_point_arg(CLOCK_SKEW_FAIL_POINT, move || {
+            let faults = faults.lock().unwrap();
+            faults
+                .current_node()
+                .and_then(|node| faults.clock_skews.get(node).copied())
+        });
+
+        let faults = injected.clone();
+        register_fail_point_async(EXECUTION_DELAY_FAIL_POINT, move || {
+            let delay = {
+                let faults = faults.lock().unwrap();
+                faults
+                    .current_node()
+                    .and_then(|node| faults.execution_delays.get(node).copied())
+            };
+            async move {
+                if let Some(delay) = delay {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        });
+
+        register_fail_points(DB_WRITE_FAIL_POINTS, move || {
+            let disk_full = {
+                let faults = injected.lock().unwrap();
+                faults
+                    .current_node()
+                    .is_some_and(|node| faults.full_disks.contains(node))
+            };
+            // The lock must be released before killing the node, which panics.
+            if disk_full {
+                sui_simulator::task::kill_current_node(None);
+            }
+        });
+        Self
+    }
+}
+
+impl Drop for FailPoints {
+    fn drop(&mut self) {
+        clear_fail_point(CLOCK_SKEW_FAIL_POINT);
+        clear_fail_point(EXECUTION_DELAY_FAIL_POINT);
+        for fail_point in DB_WRITE_FAIL_POINTS {
+            clear_fail_point(fail_point);
+        }
+    }
+}
+
+pub struct TimelineEntry {
+    /// Time since the start of the scenario.
+    pub at: Duration,
+    pub fault: Fault,
+}
+
+/// The faults applied by a scenario, in order.
+pub struct FaultTimeline {
+    pub seed: Option<String>,
+    pub entries: Vec<TimelineEntry>,
+}
+
+impl fmt::Display for FaultTimeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Fault timeline (seed {})",
+            self.seed.as_deref().unwrap_or("unknown")
+        )?;
+        for entry in &self.entries {
+            writeln!(f, "{:>10.3}s  {}", entry.at.as_secs_f64(), entry.fault)?;
+        }
+        Ok(())
+    }
+}
@@ -67,6 +67,8 @@ use tonic::IntoRequest;
 use tracing::{error, info};
 
 pub mod addr_balance_test_env;
+#[cfg(msim)]
+pub mod fault_scenario;
 
 const NUM_VALIDATOR: usize = 4;
 
@@ -222,20 +224,13 @@ impl TestCluster {
     pub async fn start_all_validators(&self) {
         info!("Starting all validators in the cluster");
         for v in self.swarm.validator_nodes() {
-            if v.is_running() {
-                continue;
-            }
-            v.start().await.unwrap();
+            self.swarm.start_node(&v.name()).await.unwrap();
         }
         tokio::time::sleep(Duration::from_secs(3)).await;
     }
 
     pub async fn start_node(&self, name: &AuthorityName) {
-        let node = self.swarm.node(name).unwrap();
-        if node.is_running() {
-            return;
-        }
-        node.start().await.unwrap();
+        self.swarm.start_node(name).await.unwrap();
     }
 
     pub async fn spawn_new_validator(
@@ -0,0 +1,1163 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coverage-guided fuzzing of programmable transactions against user supplied Move packages.
+//!
+//! Packages are published into an in-process authority, and every fuzzed program is executed
+//! against that same starting state with the Move VM tracer enabled. Traces are folded into a
+//! `move-coverage` `CoverageMap`: programs that reach new bytecode offsets are kept in the corpus
+//! and mutated further, and Move calls are biased towards the functions with the least coverage
+//! so far. Each execution is checked for SUI conservation, object version monotonicity and
+//! consistency between the effects and the objects actually read and written. Programs that
+//! break an invariant are minimized and can be saved as crash files and replayed later.
+//!
+//! Instruction level coverage is only recorded when built with the `tracing` feature. Without it
+//! the fuzzer still checks invariants, but it is not guided by coverage.
+
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use anyhow::ensure;
+use move_binary_format::file_format::Visibility;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use move_coverage::coverage_map::{CoverageMap, TraceConsumer};
+use move_trace_format::format::{MoveTraceBuilder, MoveTraceReader};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sui_core::authority::authority_test_utils::submit_and_execute;
+use sui_move_build::BuildConfig;
+use sui_types::TypeTag;
+use sui_types::base_types::{
+    ObjectID, ObjectRef, SequenceNumber, SuiAddress, TxContext, TxContextKind,
+};
+use sui_types::crypto::{AccountKeyPair, get_key_pair_from_rng};
+use sui_types::digests::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::execution_params::ExecutionOrEarlyError;
+use sui_types::execution_status::{ExecutionErrorKind, ExecutionFailure, ExecutionStatus};
+use sui_types::gas_coin::GAS;
+use sui_types::inner_temporary_store::InnerTemporaryStore;
+use sui_types::object::{Object, Owner};
+use sui_types::transaction::{
+    Argument, CallArg, Command, InputObjectKind, InputObjects, ObjectArg, ObjectReadResult,
+    ProgrammableMoveCall, ProgrammableTransaction, SharedObjectMutability, TransactionData,
+    TransactionDataAPI, VerifiedTransaction,
+};
+use sui_types::utils::to_sender_signed_transaction;
+use tracing::info;
+
+use crate::account_universe::PUBLISH_BUDGET;
+use crate::executor::Executor;
+
+/// Name under which executions are recorded in the coverage map.
+const FUZZ_EXEC_ID: &str = "coverage_fuzzer";
+/// Seed for the sender's key pair and the IDs of its coins, so that every build of a target
+/// publishes the same packages at the same addresses.
+const TARGET_SEED: u64 = 0;
+const GAS_BALANCE: u64 = 100_000_000_000;
+const GAS_BUDGET: u64 = 5_000_000_000;
+/// Number of SUI coins, besides the gas coin, owned by the sender and available as inputs.
+const POOL_COINS: usize = 4;
+const MAX_MUTATIONS: usize = 4;
+const INTERESTING_U64: [u64; 6] = [0, 1, 2, 1_000, u64::MAX - 1, u64::MAX];
+
+/// An input to a fuzzed program. Objects are referred to by their index in the target's object
+/// pool rather than by ID, so that programs can be replayed against a freshly built target.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FuzzInput {
+    Pure(Vec<u8>),
+    Object(usize),
+}
+
+/// A command in a fuzzed program. Move calls and type arguments refer to the target's function
+/// and type pools by index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FuzzCommand {
+    MoveCall {
+        function: usize,
+        type_arguments: Vec<usize>,
+        arguments: Vec<Argument>,
+    },
+    TransferObjects(Vec<Argument>, Argument),
+    SplitCoins(Argument, Vec<Argument>),
+    MergeCoins(Argument, Vec<Argument>),
+    MakeMoveVec(Option<usize>, Vec<Argument>),
+}
+
+impl FuzzCommand {
+    /// The variable length argument list of the command.
+    pub fn argument_list_mut(&mut self) -> &mut Vec<Argument> {
+        match self {
+            Self::MoveCall { arguments, .. }
+            | Self::TransferObjects(arguments, _)
+            | Self::SplitCoins(_, arguments)
+            | Self::MergeCoins(_, arguments)
+            | Self::MakeMoveVec(_, arguments) => arguments,
+        }
+    }
+
+    pub fn arguments_mut(&mut self) -> Vec<&mut Argument> {
+        match self {
+            Self::MoveCall { arguments, .. } | Self::MakeMoveVec(_, arguments) => {
+                arguments.iter_mut().collect()
+            }
+            Self::TransferObjects(arguments, argument)
+            | Self::SplitCoins(argument, arguments)
+            | Self::MergeCoins(argument, arguments) => {
+                let mut all = vec![argument];
+                all.extend(arguments.iter_mut());
+                all
+            }
+        }
+    }
+}
+
+/// A programmable transaction expressed against a `FuzzTarget`'s pools.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzProgram {
+    pub inputs: Vec<FuzzInput>,
+    pub commands: Vec<FuzzCommand>,
+}
+
+impl FuzzProgram {
+    /// Removes the command at `index`, renumbering results of later commands. Arguments that
+    /// referred to the removed command are left dangling.
+    pub fn remove_command(&mut self, index: usize) {
+        self.commands.remove(index);
+        for command in &mut self.commands[index..] {
+            for argument in command.arguments_mut() {
+                match argument {
+                    Argument::Result(i) | Argument::NestedResult(i, _) if *i as usize > index => {
+                        *i -= 1
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Removes the input at `index`, renumbering arguments that refer to later inputs. Arguments
+    /// that referred to the removed input are left dangling.
+    pub fn remove_input(&mut self, index: usize) {
+        self.inputs.remove(index);
+        for command in &mut self.commands {
+            for argument in command.arguments_mut() {
+                if let Argument::Input(i) = argument
+                    && *i as usize > index
+                {
+                    *i -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// An invariant broken by a fuzzed program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Finding {
+    /// Execution panicked.
+    Panic(String),
+    /// Execution reported an invariant violation.
+    InvariantViolation(String),
+    /// The transaction created or destroyed SUI.
+    SuiNotConserved { input: u64, output: u64 },
+    /// An object was written at a version that does not follow the versions of the inputs.
+    VersionNotMonotonic {
+        id: ObjectID,
+        input: SequenceNumber,
+        output: SequenceNumber,
+    },
+    /// The effects disagree with the objects the transaction read or wrote.
+    InconsistentEffects(String),
+}
+
+impl Finding {
+    fn same_kind(&self, other: &Finding) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+}
+
+/// A minimized program together with the invariant it breaks, as saved to a crash file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Crash {
+    pub finding: Finding,
+    pub program: FuzzProgram,
+}
+
+impl Crash {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Writes the crash to `dir`, named after a hash of its contents, and returns its path.
+    pub fn save(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        let bytes = serde_json::to_vec_pretty(self)?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("crash-{:016x}.json", hasher.finish()));
+        std::fs::write(&path, bytes)?;
+        Ok(path)
+    }
+}
+
+/// A public or entry function that fuzzed programs can call.
+#[derive(Clone, Debug)]
+pub struct FuzzFunction {
+    pub package: ObjectID,
+    pub module: Identifier,
+    pub name: Identifier,
+    pub type_parameters: usize,
+    /// Number of parameters, not counting the `TxContext`.
+    pub parameters: usize,
+}
+
+/// The result of executing a program that passed input checks.
+pub struct Execution {
+    pub digest: TransactionDigest,
+    pub effects: TransactionEffects,
+    pub inner_temporary_store: InnerTemporaryStore,
+    pub coverage: CoverageMap,
+}
+
+pub enum Outcome {
+    /// The program was rejected before execution, e.g. for referring to a missing result.
+    Rejected,
+    Executed(CoverageMap),
+    Failed(Finding),
+}
+
+/// User supplied packages published into an in-process authority, along with the pools of
+/// functions, types and objects that fuzzed programs draw from. Programs are never committed, so
+/// every program runs against the state left by publishing.
+pub struct FuzzTarget {
+    executor: Executor,
+    sender: SuiAddress,
+    key: AccountKeyPair,
+    gas: ObjectRef,
+    gas_price: u64,
+    pub functions: Vec<FuzzFunction>,
+    pub types: Vec<TypeTag>,
+    pub objects: Vec<(ObjectRef, Owner)>,
+}
+
+impl FuzzTarget {
+    /// Builds and publishes the packages at `package_paths`, in order. Packages can only depend
+    /// on the framework or on packages whose addresses are already published.
+    pub fn new(package_paths: &[PathBuf]) -> anyhow::Result<Self> {
+        let mut executor = Executor::new();
+        let mut rng = StdRng::seed_from_u64(TARGET_SEED);
+        let (sender, key): (_, AccountKeyPair) = get_key_pair_from_rng(&mut rng);
+        let gas_price = executor.get_reference_gas_price();
+
+        let mut new_coin = || {
+            Object::with_id_owner_gas_for_testing(
+                ObjectID::random_from_rng(&mut rng),
+                sender,
+                GAS_BALANCE,
+            )
+        };
+        let gas = new_coin();
+        let coins: Vec<_> = (0..POOL_COINS).map(|_| new_coin()).collect();
+        executor.add_object(gas.clone());
+        executor.add_objects(&coins);
+
+        let mut functions = vec![];
+        let mut types = vec![
+            TypeTag::Bool,
+            TypeTag::U8,
+            TypeTag::U64,
+            TypeTag::U128,
+            TypeTag::Address,
+            GAS::type_tag(),
+        ];
+        let mut created = vec![];
+        for path in package_paths {
+            let package = BuildConfig::new_for_testing().build(path)?;
+            let publish_gas = new_coin();
+            executor.add_object(publish_gas.clone());
+            let data = TransactionData::new_module(
+                sender,
+                publish_gas.compute_object_reference(),
+                package.get_package_bytes(false),
+                package.get_dependency_storage_package_ids(),
+                PUBLISH_BUDGET,
+                gas_price,
+            );
+            let txn = to_sender_signed_transaction(data, &key);
+            let effects = executor
+                .rt
+                .block_on(submit_and_execute(&executor.state, txn))?
+                .1
+                .into_data();
+            ensure!(
+                effects.status().is_ok(),
+                "publishing {} failed: {:?}",
+                path.display(),
+                effects.status()
+            );
+            let package_id = effects.published_packages()[0];
+
+            for module in package.get_modules() {
+                let module_name = module.self_id().name().to_owned();
+                for def in module.function_defs() {
+                    if def.visibility != Visibility::Public && !def.is_entry {
+                        continue;
+                    }
+                    let handle = module.function_handle_at(def.function);
+                    let parameters = module
+                        .signature_at(handle.parameters)
+                        .0
+                        .iter()
+                        .filter(|token| TxContext::kind(module, token) == TxContextKind::None)
+                        .count();
+                    functions.push(FuzzFunction {
+                        package: package_id,
+                        module: module_name.clone(),
+                        name: module.identifier_at(handle.name).to_owned(),
+                        type_parameters: handle.type_parameters.len(),
+                        parameters,
+                    });
+                }
+                for def in module.struct_defs() {
+                    let handle = module.datatype_handle_at(def.struct_handle);
+                    if handle.type_parameters.is_empty() {
+                        types.push(TypeTag::Struct(Box::new(StructTag {
+                            address: package_id.into(),
+                            module: module_name.clone(),
+                            name: module.identifier_at(handle.name).to_owned(),
+                            type_params: vec![],
+                        })));
+                    }
+                }
+            }
+
+            created.extend(effects.created().into_iter().filter(|(object_ref, owner)| {
+                object_ref.0 != package_id && !matches!(owner, Owner::ObjectOwner(_))
+            }));
+        }
+        ensure!(
+            !functions.is_empty(),
+            "no public or entry functions to fuzz in the given packages"
+        );
+
+        // Order created objects by type, then by ID, so that indices in saved programs refer to
+        // the same objects regardless of the order in which effects list them.
+        let object_store = executor.state.get_object_store();
+        created.sort_by_cached_key(|(object_ref, _)| {
+            let name = object_store
+                .get_object(&object_ref.0)
+                .and_then(|object| object.struct_tag())
+                .map(|tag| format!("{}::{}", tag.module, tag.name));
+            (name, object_ref.0)
+        });
+        let mut objects: Vec<_> = coins
+            .iter()
+            .map(|coin| (coin.compute_object_reference(), coin.owner.clone()))
+            .collect();
+        objects.extend(created);
+
+        Ok(Self {
+            executor,
+            sender,
+            key,
+            gas: gas.compute_object_reference(),
+            gas_price,
+            functions,
+            types,
+            objects,
+        })
+    }
+
+    pub fn sender(&self) -> SuiAddress {
+        self.sender
+    }
+
+    pub fn programmable_transaction(&self, program: &FuzzProgram) -> ProgrammableTransaction {
+        let inputs = program
+            .inputs
+            .iter()
+            .map(|input| match input {
+                FuzzInput::Pure(bytes) => CallArg::Pure(bytes.clone()),
+                FuzzInput::Object(index) => {
+                    let (object_ref, owner) = &self.objects[index % self.objects.len()];
+                    match owner.start_version() {
+                        Some(initial_shared_version) => CallArg::Object(ObjectArg::SharedObject {
+                            id: object_ref.0,
+                            initial_shared_version,
+                            mutability: SharedObjectMutability::Mutable,
+                        }),
+                        None => CallArg::Object(ObjectArg::ImmOrOwnedObject(*object_ref)),
+                    }
+                }
+            })
+            .collect();
+        let type_at = |index: usize| self.types[index % self.types.len()].clone();
+        let commands = program
+            .commands
+            .iter()
+            .map(|command| match command {
+                FuzzCommand::MoveCall {
+                    function,
+                    type_arguments,
+                    arguments,
+                } => {
+                    let function = &self.functions[function % self.functions.len()];
+                    Command::MoveCall(Box::new(ProgrammableMoveCall {
+                        package: function.package,
+                        module: function.module.to_string(),
+                        function: function.name.to_string(),
+                        type_arguments: type_arguments
+                            .iter()
+                            .map(|index| type_at(*index).into())
+                            .collect(),
+                        arguments: arguments.clone(),
+                    }))
+                }
+                FuzzCommand::TransferObjects(objects, address) => {
+                    Command::TransferObjects(objects.clone(), *address)
+                }
+                FuzzCommand::SplitCoins(coin, amounts) => {
+                    Command::SplitCoins(*coin, amounts.clone())
+                }
+                FuzzCommand::MergeCoins(coin, coins) => Command::MergeCoins(*coin, coins.clone()),
+                FuzzCommand::MakeMoveVec(type_argument, elements) => Command::MakeMoveVec(
+                    type_argument.map(|index| type_at(index).into()),
+                    elements.clone(),
+                ),
+            })
+            .collect();
+        ProgrammableTransaction { inputs, commands }
+    }
+
+    /// Executes `program` without committing its effects, tracing the Move VM along the way.
+    /// Errors are returned for programs that fail input checks.
+    pub fn execute(&self, program: &FuzzProgram) -> SuiResult<Execution> {
+        let data = TransactionData::new_programmable(
+            self.sender,
+            vec![self.gas],
+            self.programmable_transaction(program),
+            GAS_BUDGET,
+            self.gas_price,
+        );
+        let state = &self.executor.state;
+        let epoch_store = state.load_epoch_store_one_call_per_task();
+        let executable = VerifiedExecutableTransaction::new_from_consensus(
+            VerifiedTransaction::new_unchecked(to_sender_signed_transaction(data, &self.key)),
+            epoch_store.epoch(),
+        );
+        let input_objects = self.read_input_objects(executable.transaction_data())?;
+        let (gas_status, input_objects) = sui_transaction_checks::check_certificate_input(
+            &executable,
+            input_objects,
+            epoch_store.protocol_config(),
+            epoch_store.reference_gas_price(),
+        )?;
+        let (kind, signer, gas_data) = executable.transaction_data().execution_parts();
+        let digest = *executable.digest();
+        let mut trace_builder = Some(MoveTraceBuilder::new());
+        let (inner_temporary_store, _, effects, _timings, _) =
+            epoch_store.executor().execute_transaction_to_effects(
+                state.get_backing_store().as_ref(),
+                epoch_store.protocol_config(),
+                state.metrics.limits_metrics.clone(),
+                true, // enable_expensive_checks
+                ExecutionOrEarlyError::Ok(()),
+                &epoch_store.epoch(),
+                0,
+                input_objects,
+                gas_data,
+                gas_status,
+                kind,
+                None, // compat_args
+                signer,
+                digest,
+                &mut trace_builder,
+            );
+
+        let mut coverage = CoverageMap::default();
+        if let Some(trace_builder) = trace_builder {
+            let bytes = trace_builder.into_trace().into_compressed_json_bytes();
+            let reader = MoveTraceReader::new(bytes.as_slice()).expect("Move trace is readable");
+            coverage = coverage.ingest_trace(FUZZ_EXEC_ID, reader);
+        }
+        Ok(Execution {
+            digest,
+            effects,
+            inner_temporary_store,
+            coverage,
+        })
+    }
+
+    /// Executes `program` and checks its invariants, turning panics into findings.
+    pub fn run(&self, program: &FuzzProgram) -> Outcome {
+        let result = panic::catch_unwind(AssertUnwindSafe(
+            || -> SuiResult<Result<CoverageMap, Finding>> {
+                let execution = self.execute(program)?;
+                Ok(self
+                    .check_invariants(&execution)
+                    .map(|()| execution.coverage))
+            },
+        ));
+        match result {
+            Err(payload) => Outcome::Failed(Finding::Panic(panic_message(payload))),
+            Ok(Err(_)) => Outcome::Rejected,
+            Ok(Ok(Err(finding))) => Outcome::Failed(finding),
+            Ok(Ok(Ok(coverage))) => Outcome::Executed(coverage),
+        }
+    }
+
+    /// Re-executes a saved program, returning the invariant it breaks, if any.
+    pub fn replay(&self, program: &FuzzProgram) -> Option<Finding> {
+        match self.run(program) {
+            Outcome::Failed(finding) => Some(finding),
+            Outcome::Rejected | Outcome::Executed(_) => None,
+        }
+    }
+
+    /// Greedily removes commands, arguments and inputs from `program` while it still breaks an
+    /// invariant of the same kind as `finding`.
+    pub fn minimize(&self, mut program: FuzzProgram, finding: &Finding) -> FuzzProgram {
+        let reproduces = |candidate: &FuzzProgram| matches!(self.run(candidate), Outcome::Failed(f) if f.same_kind(finding));
+        loop {
+            let mut shrunk = false;
+            for index in (0..program.commands.len()).rev() {
+                let mut candidate = program.clone();
+                candidate.remove_command(index);
+                if reproduces(&candidate) {
+                    program = candidate;
+                    shrunk = true;
+                }
+            }
+            for command in (0..program.commands.len()).rev() {
+                let len = program.commands[command].argument_list_mut().len();
+                for index in (0..len).rev() {
+                    let mut candidate = program.clone();
+                    candidate.commands[command]
+                        .argument_list_mut()
+                        .remove(index);
+                    if reproduces(&candidate) {
+                        program = candidate;
+                        shrunk = true;
+                    }
+                }
+            }
+            for index in (0..program.inputs.len()).rev() {
+                let mut candidate = program.clone();
+                candidate.remove_input(index);
+                if reproduces(&candidate) {
+                    program = candidate;
+                    shrunk = true;
+                }
+            }
+            if !shrunk {
+                return program;
+            }
+        }
+    }
+
+    fn read_input_objects(&self, transaction: &TransactionData) -> SuiResult<InputObjects> {
+        let cache = self.executor.state.get_object_cache_reader();
+        let mut objects = vec![];
+        for kind in transaction.input_objects()? {
+            let object: Option<Object> = match kind {
+                InputObjectKind::MovePackage(id) => {
+                    cache.get_package_object(&id)?.map(|package| package.into())
+                }
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    cache.get_object_by_key(&id, version)
+                }
+                InputObjectKind::SharedMoveObject { id, .. } => cache.get_object(&id),
+            };
+            let Some(object) = object else {
+                return Err(SuiError::from(kind.object_not_found_error()));
+            };
+            objects.push(ObjectReadResult::new(kind, object.into()));
+        }
+        Ok(objects.into())
+    }
+
+    fn check_invariants(&self, execution: &Execution) -> Result<(), Finding> {
+        let Execution {
+            digest,
+            effects,
+            inner_temporary_store: inner,
+            ..
+        } = execution;
+
+        if let status @ ExecutionStatus::Failure(ExecutionFailure {
+            error: ExecutionErrorKind::InvariantViolation,
+            ..
+        }) = effects.status()
+        {
+            return Err(Finding::InvariantViolation(format!("{status:?}")));
+        }
+        if effects.transaction_digest() != digest {
+            return Err(Finding::InconsistentEffects(format!(
+                "effects are for transaction {}, expected {digest}",
+                effects.transaction_digest()
+            )));
+        }
+        if effects.gas_object().is_none() {
+            return Err(Finding::InconsistentEffects("no gas object".to_string()));
+        }
+
+        let store = self.executor.state.get_object_store();
+        let changes = effects.object_changes();
+        let changed: BTreeSet<_> = changes.iter().map(|change| change.id).collect();
+        for change in &changes {
+            if let (Some(version), Some(digest)) = (change.input_version, change.input_digest)
+                && store
+                    .get_object_by_key(&change.id, version)
+                    .is_none_or(|object| object.digest() != digest)
+            {
+                return Err(Finding::InconsistentEffects(format!(
+                    "input {} at version {version} does not match the store",
+                    change.id
+                )));
+            }
+            match (change.output_version, change.output_digest) {
+                (Some(version), Some(digest)) => {
+                    let Some(object) = inner.written.get(&change.id) else {
+                        return Err(Finding::InconsistentEffects(format!(
+                            "{} is written in effects but not by execution",
+                            change.id
+                        )));
+                    };
+                    if object.version() != version || object.digest() != digest {
+                        return Err(Finding::InconsistentEffects(format!(
+                            "{} is written at {:?} but effects report {:?}",
+                            change.id,
+                            (object.version(), object.digest()),
+                            (version, digest)
+                        )));
+                    }
+                }
+                _ if inner.written.contains_key(&change.id) => {
+                    return Err(Finding::InconsistentEffects(format!(
+                        "{} is written by execution but removed in effects",
+                        change.id
+                    )));
+                }
+                _ => (),
+            }
+            if let (Some(input), Some(output)) = (change.input_version, change.output_version)
+                && output <= input
+            {
+                return Err(Finding::VersionNotMonotonic {
+                    id: change.id,
+                    input,
+                    output,
+                });
+            }
+        }
+        if let Some(id) = inner.written.keys().find(|id| !changed.contains(id)) {
+            return Err(Finding::InconsistentEffects(format!(
+                "{id} is written by execution but missing from effects"
+            )));
+        }
+        let lamport_version = effects.lamport_version();
+        for (id, object) in &inner.input_objects {
+            if !object.is_package() && object.version() >= lamport_version {
+                return Err(Finding::VersionNotMonotonic {
+                    id: *id,
+                    input: object.version(),
+                    output: lamport_version,
+                });
+            }
+        }
+
+        self.check_sui_conservation(execution)
+    }
+
+    /// Checks that the SUI held by objects before the transaction equals the SUI held after it,
+    /// plus the computation fee and the non-refundable storage fee that it burns.
+    fn check_sui_conservation(&self, execution: &Execution) -> Result<(), Finding> {
+        let state = &self.executor.state;
+        let epoch_store = state.load_epoch_store_one_call_per_task();
+        let mut layout_resolver = epoch_store
+            .executor()
+            .type_layout_resolver(Box::new(state.get_backing_package_store().clone()));
+        let store = state.get_object_store();
+        let mut total_sui = |object: &Object| {
+            object.get_total_sui(layout_resolver.as_mut()).map_err(|e| {
+                Finding::InconsistentEffects(format!(
+                    "cannot resolve SUI held by {}: {e}",
+                    object.id()
+                ))
+            })
+        };
+
+        let (mut input, mut output) = (0, 0);
+        for change in execution.effects.object_changes() {
+            if let Some(object) = change
+                .input_version
+                .and_then(|version| store.get_object_by_key(&change.id, version))
+            {
+                input += total_sui(&object)?;
+            }
+            if let Some(object) = execution.inner_temporary_store.written.get(&change.id) {
+                output += total_sui(object)?;
+            }
+        }
+        for event in &execution.inner_temporary_store.accumulator_events {
+            let (event_input, event_output) = event.total_sui_in_event();
+            input += event_input;
+            output += event_output;
+        }
+        let gas_summary = execution.effects.gas_cost_summary();
+        output += gas_summary.computation_cost + gas_summary.non_refundable_storage_fee;
+
+        if input != output {
+            return Err(Finding::SuiNotConserved { input, output });
+        }
+        Ok(())
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic with a non-string payload".to_string()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FuzzConfig {
+    pub seed: u64,
+    pub iterations: usize,
+    pub max_commands: usize,
+    /// Directory minimized crashes are written to. Crashes are only kept in memory when unset.
+    pub crash_dir: Option<PathBuf>,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            iterations: 1_000,
+            max_commands: 8,
+            crash_dir: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FuzzStats {
+    pub executions: usize,
+    pub rejected: usize,
+    pub covered_offsets: usize,
+}
+
+struct CorpusEntry {
+    program: FuzzProgram,
+    /// Number of new bytecode offsets this program covered when it was added.
+    energy: usize,
+}
+
+/// Drives a `FuzzTarget`, keeping programs that reach new code in a corpus and mutating them.
+pub struct CoverageFuzzer {
+    target: FuzzTarget,
+    config: FuzzConfig,
+    rng: StdRng,
+    corpus: Vec<CorpusEntry>,
+    coverage: CoverageMap,
+    covered: BTreeSet<(AccountAddress, Identifier, Identifier, u64)>,
+    function_index: HashMap<(AccountAddress, Identifier, Identifier), usize>,
+    /// Number of covered offsets in each function of the target's function pool.
+    function_coverage: Vec<usize>,
+    crashes: Vec<Crash>,
+    stats: FuzzStats,
+}
+
+impl CoverageFuzzer {
+    pub fn new(target: FuzzTarget, config: FuzzConfig) -> Self {
+        let function_index = target
+            .functions
+            .iter()
+            .enumerate()
+            .map(|(index, function)| {
+                let key = (
+                    AccountAddress::from(function.package),
+                    function.module.clone(),
+                    function.name.clone(),
+                );
+                (key, index)
+            })
+            .collect();
+        Self {
+            function_coverage: vec![0; target.functions.len()],
+            rng: StdRng::seed_from_u64(config.seed),
+            target,
+            config,
+            corpus: vec![],
+            coverage: CoverageMap::default(),
+            covered: BTreeSet::new(),
+            function_index,
+            crashes: vec![],
+            stats: FuzzStats::default(),
+        }
+    }
+
+    pub fn run(&mut self) -> anyhow::Result<()> {
+        for _ in 0..self.config.iterations {
+            self.step()?;
+        }
+        info!(
+            executions = self.stats.executions,
+            rejected = self.stats.rejected,
+            corpus = self.corpus.len(),
+            covered_offsets = self.stats.covered_offsets,
+            crashes = self.crashes.len(),
+            "Coverage fuzzing finished"
+        );
+        Ok(())
+    }
+
+    pub fn step(&mut self) -> anyhow::Result<()> {
+        let program = self.next_program();
+        self.stats.executions += 1;
+        match self.target.run(&program) {
+            Outcome::Rejected => self.stats.rejected += 1,
+            Outcome::Executed(coverage) => {
+                let energy = self.record_coverage(coverage);
+                if energy > 0 {
+                    self.corpus.push(CorpusEntry { program, energy });
+                }
+            }
+            Outcome::Failed(finding) => self.record_crash(program, finding)?,
+        }
+        Ok(())
+    }
+
+    pub fn target(&self) -> &FuzzTarget {
+        &self.target
+    }
+
+    /// Coverage accumulated over all executions.
+    pub fn coverage(&self) -> &CoverageMap {
+        &self.coverage
+    }
+
+    pub fn corpus(&self) -> impl Iterator<Item = &FuzzProgram> {
+        self.corpus.iter().map(|entry| &entry.program)
+    }
+
+    pub fn crashes(&self) -> &[Crash] {
+        &self.crashes
+    }
+
+    pub fn stats(&self) -> &FuzzStats {
+        &self.stats
+    }
+
+    fn next_program(&mut self) -> FuzzProgram {
+        let mut mutator = Mutator {
+            rng: &mut self.rng,
+            target: &self.target,
+            function_coverage: &self.function_coverage,
+            max_commands: self.config.max_commands,
+        };
+        if self.corpus.is_empty() || mutator.rng.gen_ratio(1, 10) {
+            return mutator.generate();
+        }
+        let weights = WeightedIndex::new(self.corpus.iter().map(|entry| entry.energy))
+            .expect("corpus entries have positive energy");
+        let mut program = self.corpus[weights.sample(mutator.rng)].program.clone();
+        mutator.mutate(&mut program);
+        program
+    }
+
+    /// Folds `coverage` into the accumulated coverage, returning the number of newly covered
+    /// offsets.
+    fn record_coverage(&mut self, coverage: CoverageMap) -> usize {
+        let mut new_offsets = 0;
+        for exec_map in coverage.exec_maps.values() {
+            for ((address, module), module_map) in &exec_map.module_maps {
+                for (function, offsets) in &module_map.function_maps {
+                    let key = (*address, module.clone(), function.clone());
+                    for pc in offsets.keys() {
+                        if self
+                            .covered
+                            .insert((key.0, key.1.clone(), key.2.clone(), *pc))
+                        {
+                            new_offsets += 1;
+                            if let Some(index) = self.function_index.get(&key) {
+                                self.function_coverage[*index] += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.stats.covered_offsets += new_offsets;
+        self.coverage = mem::take(&mut self.coverage).merge(coverage);
+        new_offsets
+    }
+
+    fn record_crash(&mut self, program: FuzzProgram, finding: Finding) -> anyhow::Result<()> {
+        if self.crashes.iter().any(|crash| crash.finding == finding) {
+            return Ok(());
+        }
+        let crash = Crash {
+            program: self.target.minimize(program, &finding),
+            finding,
+        };
+        info!(finding = ?crash.finding, "Found a crashing program");
+        if let Some(dir) = &self.config.crash_dir {
+            let path = crash.save(dir)?;
+            info!("Saved crash to {}", path.display());
+        }
+        self.crashes.push(crash);
+        Ok(())
+    }
+}
+
+/// Generates and mutates programs against a target's pools.
+struct Mutator<'a> {
+    rng: &'a mut StdRng,
+    target: &'a FuzzTarget,
+    function_coverage: &'a [usize],
+    max_commands: usize,
+}
+
+impl Mutator<'_> {
+    fn generate(&mut self) -> FuzzProgram {
+        let mut program = FuzzProgram::default();
+        for _ in 0..self.rng.gen_range(1..=self.max_commands) {
+            self.push_command(&mut program);
+        }
+        program
+    }
+
+    fn mutate(&mut self, program: &mut FuzzProgram) {
+        for _ in 0..self.rng.gen_range(1..=MAX_MUTATIONS) {
+            let commands = program.commands.len();
+            match self.rng.gen_range(0..5) {
+                0 if commands > 1 => {
+                    let index = self.rng.gen_range(0..commands);
+                    program.remove_command(index);
+                }
+                1 if commands > 0 => {
+                    let index = self.rng.gen_range(0..commands);
+                    let argument = self.gen_argument(program, index);
+                    let mut slots = program.commands[index].arguments_mut();
+                    if !slots.is_empty() {
+                        let slot = self.rng.gen_range(0..slots.len());
+                        *slots[slot] = argument;
+                    }
+                }
+                2 if !program.inputs.is_empty() => {
+                    let index = self.rng.gen_range(0..program.inputs.len());
+                    self.mutate_input(&mut program.inputs[index]);
+                }
+                3 if commands > 0 => {
+                    let index = self.rng.gen_range(0..commands);
+                    self.retarget(program, index);
+                }
+                _ if commands < self.max_commands => self.push_command(program),
+                _ => (),
+            }
+        }
+    }
+
+    fn push_command(&mut self, program: &mut FuzzProgram) {
+        let index = program.commands.len();
+        let command = match self.rng.gen_range(0..10) {
+            0..=5 => {
+                let function = self.pick_function();
+                let (type_arguments, arguments) = self.gen_call_arguments(program, index, function);
+                FuzzCommand::MoveCall {
+                    function,
+                    type_arguments,
+                    arguments,
+                }
+            }
+            6 => {
+                let coin = self.gen_argument(program, index);
+                let amounts = (0..self.rng.gen_range(1..=3))
+                    .map(|_| {
+                        let amount = *INTERESTING_U64.choose(self.rng).unwrap();
+                        push_input(program, FuzzInput::Pure(bcs::to_bytes(&amount).unwrap()))
+                    })
+                    .collect();
+                FuzzCommand::SplitCoins(coin, amounts)
+            }
+            7 => {
+                let coin = self.gen_argument(program, index);
+                let coins = self.gen_arguments(program, index, 1..=2);
+                FuzzCommand::MergeCoins(coin, coins)
+            }
+            8 => {
+                let objects = self.gen_arguments(program, index, 1..=2);
+                let sender = bcs::to_bytes(&self.target.sender).unwrap();
+                FuzzCommand::TransferObjects(objects, push_input(program, FuzzInput::Pure(sender)))
+            }
+            _ => {
+                let type_argument = self.rng.gen_bool(0.5).then(|| self.pick_type());
+                let elements = self.gen_arguments(program, index, 0..=3);
+                FuzzCommand::MakeMoveVec(type_argument, elements)
+            }
+        };
+        program.commands.push(command);
+    }
+
+    /// Changes the function or a type argument of the command at `index`. Commands without
+    /// either get an extra argument instead.
+    fn retarget(&mut self, program: &mut FuzzProgram, index: usize) {
+        let type_argument = self.pick_type();
+        let retype = self.rng.gen_bool(0.5);
+        match &mut program.commands[index] {
+            FuzzCommand::MoveCall { type_arguments, .. }
+                if retype && !type_arguments.is_empty() =>
+            {
+                let position = self.rng.gen_range(0..type_arguments.len());
+                type_arguments[position] = type_argument;
+            }
+            FuzzCommand::MakeMoveVec(ty, _) => *ty = Some(type_argument),
+            FuzzCommand::MoveCall { .. } => {
+                let function = self.pick_function();
+                let (type_arguments, arguments) = self.gen_call_arguments(program, index, function);
+                program.commands[index] = FuzzCommand::MoveCall {
+                    function,
+                    type_arguments,
+                    arguments,
+                };
+            }
+            FuzzCommand::TransferObjects(..)
+            | FuzzCommand::SplitCoins(..)
+            | FuzzCommand::MergeCoins(..) => {
+                let argument = self.gen_argument(program, index);
+                program.commands[index].argument_list_mut().push(argument);
+            }
+        }
+    }
+
+    fn mutate_input(&mut self, input: &mut FuzzInput) {
+        match input {
+            FuzzInput::Object(index) => *index = self.rng.gen_range(0..self.target.objects.len()),
+            FuzzInput::Pure(bytes) => match self.rng.gen_range(0..4) {
+                0 if !bytes.is_empty() => {
+                    let bit = self.rng.gen_range(0..bytes.len() * 8);
+                    bytes[bit / 8] ^= 1 << (bit % 8);
+                }
+                1 => {
+                    let value = *INTERESTING_U64.choose(self.rng).unwrap();
+                    *bytes = bcs::to_bytes(&value).unwrap();
+                }
+                2 if !bytes.is_empty() => {
+                    bytes.pop();
+                }
+                _ => *bytes = self.gen_pure(),
+            },
+        }
+    }
+
+    /// Picks a function, favouring those with the least coverage so far.
+    fn pick_function(&mut self) -> usize {
+        let weights = self
+            .function_coverage
+            .iter()
+            .map(|covered| 1.0 / (1 + covered) as f64);
+        WeightedIndex::new(weights)
+            .expect("function pool is not empty")
+            .sample(self.rng)
+    }
+
+    fn pick_type(&mut self) -> usize {
+        self.rng.gen_range(0..self.target.types.len())
+    }
+
+    fn gen_call_arguments(
+        &mut self,
+        program: &mut FuzzProgram,
+        index: usize,
+        function: usize,
+    ) -> (Vec<usize>, Vec<Argument>) {
+        let target = self.target;
+        let function = &target.functions[function];
+        let type_arguments = (0..function.type_parameters)
+            .map(|_| self.pick_type())
+            .collect();
+        let arguments = (0..function.parameters)
+            .map(|_| self.gen_argument(program, index))
+            .collect();
+        (type_arguments, arguments)
+    }
+
+    fn gen_arguments(
+        &mut self,
+        program: &mut FuzzProgram,
+        index: usize,
+        len: std::ops::RangeInclusive<usize>,
+    ) -> Vec<Argument> {
+        (0..self.rng.gen_range(len))
+            .map(|_| self.gen_argument(program, index))
+            .collect()
+    }
+
+    /// Generates an argument for the command at `index`, adding inputs to `program` as needed.
+    fn gen_argument(&mut self, program: &mut FuzzProgram, index: usize) -> Argument {
+        match self.rng.gen_range(0..10) {
+            0..=3 => {
+                let bytes = self.gen_pure();
+                push_input(program, FuzzInput::Pure(bytes))
+            }
+            4 | 5 => {
+                let object = self.rng.gen_range(0..self.target.objects.len());
+                push_input(program, FuzzInput::Object(object))
+            }
+            6 | 7 if index > 0 => Argument::Result(self.rng.gen_range(0..index) as u16),
+            8 if index > 0 => Argument::NestedResult(
+                self.rng.gen_range(0..index) as u16,
+                self.rng.gen_range(0..2),
+            ),
+            _ => Argument::GasCoin,
+        }
+    }
+
+    fn gen_pure(&mut self) -> Vec<u8> {
+        let bytes = match self.rng.gen_range(0..7) {
+            0 => bcs::to_bytes(&self.rng.r#gen::<bool>()),
+            1 => bcs::to_bytes(&self.rng.r#gen::<u8>()),
+            2 => bcs::to_bytes(INTERESTING_U64.choose(self.rng).unwrap()),
+            3 => bcs::to_bytes(&self.rng.r#gen::<u64>()),
+            4 => bcs::to_bytes(&self.rng.r#gen::<u128>()),
+            5 => bcs::to_bytes(&self.target.sender),
+            _ => {
+                let len = self.rng.gen_range(0..16);
+                bcs::to_bytes(&(0..len).map(|_| self.rng.r#gen()).collect::<Vec<u8>>())
+            }
+        };
+        bytes.unwrap()
+    }
+}
+
+/// Adds `input` to `program`, reusing an existing object input so that no object is passed twice.
+fn push_input(program: &mut FuzzProgram, input: FuzzInput) -> Argument {
+    if matches!(input, FuzzInput::Object(_))
+        && let Some(position) = program
+            .inputs
+            .iter()
+            .position(|existing| *existing == input)
+    {
+        return Argument::Input(position as u16);
+    }
+    program.inputs.push(input);
+    Argument::Input((program.inputs.len() - 1) as u16)
+}
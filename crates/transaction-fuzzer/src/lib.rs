@@ -3,6 +3,7 @@
 
 pub mod account_universe;
 pub mod config_fuzzer;
+pub mod coverage_fuzzer;
 pub mod executor;
 pub mod programmable_transaction_gen;
 pub mod transaction_data_gen;
@@ -0,0 +1,125 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use sui_types::transaction::Argument;
+use transaction_fuzzer::coverage_fuzzer::{
+    CoverageFuzzer, Crash, Finding, FuzzCommand, FuzzConfig, FuzzInput, FuzzProgram, FuzzTarget,
+};
+
+fn coin_factory_target() -> FuzzTarget {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data/coin_factory");
+    FuzzTarget::new(&[path]).unwrap()
+}
+
+fn split_gas_program(amount: u64) -> FuzzProgram {
+    FuzzProgram {
+        inputs: vec![FuzzInput::Pure(bcs::to_bytes(&amount).unwrap())],
+        commands: vec![FuzzCommand::SplitCoins(
+            Argument::GasCoin,
+            vec![Argument::Input(0)],
+        )],
+    }
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn coverage_fuzz_coin_factory() {
+    let config = FuzzConfig {
+        iterations: 300,
+        ..Default::default()
+    };
+    let mut fuzzer = CoverageFuzzer::new(coin_factory_target(), config);
+    fuzzer.run().unwrap();
+
+    assert!(fuzzer.crashes().is_empty(), "{:#?}", fuzzer.crashes());
+    assert_eq!(fuzzer.stats().executions, 300);
+    if cfg!(feature = "tracing") {
+        assert!(fuzzer.stats().covered_offsets > 0);
+        assert!(fuzzer.corpus().next().is_some());
+    }
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn crash_file_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let crash = Crash {
+        finding: Finding::SuiNotConserved {
+            input: 10,
+            output: 11,
+        },
+        program: split_gas_program(1_000),
+    };
+    let path = crash.save(dir.path()).unwrap();
+    assert_eq!(Crash::load(&path).unwrap(), crash);
+
+    // The saved program is well-behaved, so replaying it reports nothing.
+    let target = coin_factory_target();
+    assert_eq!(target.replay(&crash.program), None);
+}
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn crash_replays_against_fresh_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = coin_factory_target();
+    let crash = Crash {
+        finding: Finding::InconsistentEffects("split coin was not written".to_string()),
+        program: FuzzProgram {
+            inputs: vec![
+                FuzzInput::Object(0),
+                FuzzInput::Pure(bcs::to_bytes(&1_000u64).unwrap()),
+                FuzzInput::Pure(bcs::to_bytes(&target.sender()).unwrap()),
+            ],
+            commands: vec![
+                FuzzCommand::SplitCoins(Argument::Input(0), vec![Argument::Input(1)]),
+                FuzzCommand::TransferObjects(vec![Argument::Result(0)], Argument::Input(2)),
+            ],
+        },
+    };
+    let path = crash.save(dir.path()).unwrap();
+    let expected = target.execute(&crash.program).unwrap().effects;
+
+    // A freshly built target resolves the saved program to the same sender and objects, so the
+    // program executes exactly as it did when it was saved.
+    let fresh = coin_factory_target();
+    let saved = Crash::load(&path).unwrap();
+    assert_eq!(fresh.sender(), target.sender());
+    assert_eq!(fresh.objects, target.objects);
+    assert_eq!(fresh.execute(&saved.program).unwrap().effects, expected);
+    assert_eq!(fresh.replay(&saved.program), None);
+}
+
+#[test]
+fn remove_command_renumbers_results() {
+    let mut program = split_gas_program(1);
+    program
+        .commands
+        .push(FuzzCommand::MakeMoveVec(None, vec![]));
+    program.commands.push(FuzzCommand::MergeCoins(
+        Argument::Result(0),
+        vec![Argument::NestedResult(2, 0), Argument::Input(0)],
+    ));
+    program.commands.push(FuzzCommand::TransferObjects(
+        vec![Argument::Result(2)],
+        Argument::Input(0),
+    ));
+
+    program.remove_command(1);
+    assert_eq!(
+        program.commands[1],
+        FuzzCommand::MergeCoins(
+            Argument::Result(0),
+            vec![Argument::NestedResult(1, 0), Argument::Input(0)],
+        )
+    );
+    assert_eq!(
+        program.commands[2],
+        FuzzCommand::TransferObjects(vec![Argument::Result(1)], Argument::Input(0))
+    );
+
+    program.remove_input(0);
+    assert!(program.inputs.is_empty());
+}
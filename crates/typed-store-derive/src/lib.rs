@@ -203,6 +203,9 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
         deprecated_cfs,
     } = extract_struct_info(input.clone());
 
+    // Safe to index because we will have at least one field_name entry in the struct
+    let first_field_name = &field_names[0];
+
     let (key_names, value_names): (Vec<_>, Vec<_>) = inner_types
         .iter()
         .map(|q| (q.args.first().unwrap(), q.args.last().unwrap()))
@@ -248,6 +251,14 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     (stringify!(#cf_names).to_owned(), (stringify!(#key_names).to_owned(), stringify!(#value_names).to_owned())),
                 )*].into_iter().collect()
             }
+
+            /// Pairs every table of `source` with the same table of `target`, to copy all tables
+            /// between databases which may use different storage backends.
+            pub fn table_migrations<'a>(source: &'a Self, target: &'a Self) -> Vec<Box<dyn typed_store::migration::TableMigrator + 'a>> {
+                vec![#(
+                    Box::new(typed_store::migration::TableMigration::new(stringify!(#cf_names), &source.#field_names, &target.#field_names)),
+                )*]
+            }
         }
     };
 
@@ -431,6 +442,26 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     Self::open_tables_read_write(path, metric_conf, cf_configs)
                 }
 
+                /// Opens the tables in a RocksDB database with default options, e.g. to migrate them
+                /// to or from TideHunter.
+                pub fn open_tables_rocksdb(
+                    path: std::path::PathBuf,
+                    metric_conf: typed_store::rocks::MetricConf,
+                ) -> Self {
+                    let opt_cfs = [
+                        #(
+                            (stringify!(#cf_names), typed_store::rocks::default_db_options().options),
+                        )*
+                    ];
+                    let db = typed_store::rocks::open_cf_opts(&path, None, metric_conf, &opt_cfs)
+                        .expect(&format!("Cannot open DB at {:?}", path));
+                    Self {
+                        #(
+                            #field_names: DBMap::reopen(&db, Some(stringify!(#cf_names)), &typed_store::rocks::ReadWriteOptions::default(), false).expect(&format!("Cannot open {} CF.", stringify!(#cf_names))[..]),
+                        )*
+                    }
+                }
+
                 pub fn table_summary(&self, table_name: &str) -> eyre::Result<typed_store::traits::TableSummary> {
                     match table_name {
                         #(
@@ -583,6 +614,31 @@ pub fn derive_dbmap_utils_general(input: TokenStream) -> TokenStream {
                     }
                 }
 
+                /// Opens the tables of the DB at `primary_path` as a RocksDB secondary instance, keeping
+                /// the secondary's own files at `secondary_path`. Writes to the returned tables fail.
+                /// The tables only observe the writes of the primary once `try_catch_up_with_primary_all` is called
+                #[allow(unused_parens)]
+                pub fn open_tables_as_secondary(
+                    primary_path: std::path::PathBuf,
+                    secondary_path: std::path::PathBuf,
+                    metric_conf: typed_store::rocks::MetricConf,
+                    global_db_options_override: Option<typed_store::rocksdb::Options>,
+                ) -> Self {
+                    let inner = #intermediate_db_map_struct_name::open_tables_impl(primary_path, Some(secondary_path), metric_conf, global_db_options_override, None, false);
+                    Self {
+                        #(
+                            #field_names: inner.#field_names,
+                        )*
+                    }
+                }
+
+                /// Try catch up with primary for all tables.
+                /// Tables must be opened as a secondary using `open_tables_as_secondary`.
+                /// All tables share one DB, so catching up through any of them catches up all of them
+                pub fn try_catch_up_with_primary_all(&self) -> Result<(), typed_store::TypedStoreError> {
+                    typed_store::traits::Map::try_catch_up_with_primary(&self.#first_field_name)
+                }
+
                 /// This opens the DB in read only mode and returns a struct which exposes debug features
                 pub fn get_read_only_handle (
                     primary_path: std::path::PathBuf,
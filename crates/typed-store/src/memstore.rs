@@ -9,7 +9,7 @@ use typed_store_error::TypedStoreError;
 
 type InMemoryStoreInternal = Arc<RwLock<HashMap<String, BTreeMap<Vec<u8>, Vec<u8>>>>>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct InMemoryDB {
     data: InMemoryStoreInternal,
 }
@@ -47,6 +47,10 @@ impl InMemoryBatch {
 }
 
 impl InMemoryDB {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     pub fn get<K: AsRef<[u8]>>(&self, cf_name: &str, key: K) -> Option<Vec<u8>> {
         let data = self.data.read().expect("can't read data");
         match data.get(cf_name) {
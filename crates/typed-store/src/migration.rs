@@ -0,0 +1,444 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Copies tables between databases, possibly backed by different storage backends.
+//!
+//! Tables are copied row by row through the `Map` interface, so the source and target can be
+//! any combination of RocksDB, TideHunter and in-memory databases. Progress is reported after
+//! every batch so that an interrupted migration can be resumed, and every table is verified by
+//! comparing the row count and an order-independent checksum of the source and the target.
+
+use crate::TypedStoreError;
+use crate::rocks::DBMap;
+use crate::rocks::errors::typed_store_err_from_bincode_err;
+use crate::traits::Map;
+use crate::util::be_fix_int_ser;
+use anyhow::{anyhow, bail};
+use bincode::Options;
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::info;
+
+/// Number of rows of a table and a checksum of its contents that does not depend on the order
+/// in which rows are visited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableChecksum {
+    pub num_rows: u64,
+    pub checksum: u64,
+}
+
+impl TableChecksum {
+    fn add_row(&mut self, key: &[u8], value: &[u8]) {
+        let mut hasher = Blake2b256::default();
+        hasher.update((key.len() as u64).to_be_bytes());
+        hasher.update(key);
+        hasher.update(value);
+        let digest = hasher.finalize().digest;
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+
+        self.num_rows += 1;
+        self.checksum = self.checksum.wrapping_add(u64::from_be_bytes(prefix));
+    }
+}
+
+/// Copies a single table from a source database to a target database.
+///
+/// Implemented by `TableMigration` for every `DBMap`, and returned for all tables of a
+/// `DBMapUtils` struct by its generated `table_migrations` method.
+pub trait TableMigrator: Send + Sync {
+    fn table_name(&self) -> &str;
+
+    /// Copies all rows of the source table with keys greater than `resume_after`, which is the
+    /// serialized key of the last row copied by a previous run. `on_batch` is called after
+    /// every batch is written to the target, with the serialized key of its last row and the
+    /// number of rows in it.
+    fn copy_rows(
+        &self,
+        resume_after: Option<&[u8]>,
+        batch_size: usize,
+        on_batch: &mut dyn FnMut(&[u8], u64) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()>;
+
+    /// Returns the checksums of the source and the target tables.
+    fn checksums(&self) -> Result<(TableChecksum, TableChecksum), TypedStoreError>;
+}
+
+pub struct TableMigration<'a, K, V> {
+    table_name: &'static str,
+    source: &'a DBMap<K, V>,
+    target: &'a DBMap<K, V>,
+}
+
+impl<'a, K, V> TableMigration<'a, K, V> {
+    pub fn new(table_name: &'static str, source: &'a DBMap<K, V>, target: &'a DBMap<K, V>) -> Self {
+        Self {
+            table_name,
+            source,
+            target,
+        }
+    }
+}
+
+impl<K, V> TableMigrator for TableMigration<'_, K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    fn table_name(&self) -> &str {
+        self.table_name
+    }
+
+    fn copy_rows(
+        &self,
+        resume_after: Option<&[u8]>,
+        batch_size: usize,
+        on_batch: &mut dyn FnMut(&[u8], u64) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let lower_bound = resume_after
+            .map(|key| {
+                bincode::DefaultOptions::new()
+                    .with_big_endian()
+                    .with_fixint_encoding()
+                    .deserialize::<K>(key)
+                    .map_err(typed_store_err_from_bincode_err)
+            })
+            .transpose()?;
+
+        let mut batch = self.target.batch();
+        let mut num_rows = 0;
+        let mut last_key = vec![];
+        for item in self.source.safe_iter_with_bounds(lower_bound, None) {
+            let (key, value) = item?;
+            let raw_key = be_fix_int_ser(&key);
+            // The lower bound is inclusive, and the row at `resume_after` was already copied.
+            if resume_after == Some(raw_key.as_slice()) {
+                continue;
+            }
+
+            batch.insert_batch(self.target, [(key, value)])?;
+            num_rows += 1;
+            last_key = raw_key;
+
+            if num_rows as usize >= batch_size {
+                std::mem::replace(&mut batch, self.target.batch()).write()?;
+                on_batch(&last_key, num_rows)?;
+                num_rows = 0;
+            }
+        }
+
+        if num_rows > 0 {
+            batch.write()?;
+            on_batch(&last_key, num_rows)?;
+        }
+        Ok(())
+    }
+
+    fn checksums(&self) -> Result<(TableChecksum, TableChecksum), TypedStoreError> {
+        Ok((checksum(self.source)?, checksum(self.target)?))
+    }
+}
+
+/// Computes the checksum of all rows of `table`.
+pub fn checksum<K, V>(table: &DBMap<K, V>) -> Result<TableChecksum, TypedStoreError>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    let mut checksum = TableChecksum::default();
+    for item in table.safe_iter() {
+        let (key, value) = item?;
+        let value = bcs::to_bytes(&value)
+            .map_err(|e| TypedStoreError::SerializationError(e.to_string()))?;
+        checksum.add_row(&be_fix_int_ser(&key), &value);
+    }
+    Ok(checksum)
+}
+
+/// Progress of the migration of a single table.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableProgress {
+    /// Serialized key of the last row written to the target.
+    pub last_key: Option<Vec<u8>>,
+    pub num_rows_copied: u64,
+    /// Set once all rows were written to the target.
+    pub copied: bool,
+    /// Set once the target was verified to match the source.
+    pub verified: Option<TableChecksum>,
+}
+
+/// Progress of a migration, keyed by table name. Persisted between runs to resume an
+/// interrupted migration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationProgress {
+    pub tables: BTreeMap<String, TableProgress>,
+}
+
+impl MigrationProgress {
+    /// Returns true if all of `tables` were copied.
+    pub fn is_complete<'a>(&self, tables: impl IntoIterator<Item = &'a str>) -> bool {
+        tables.into_iter().all(|table| {
+            self.tables
+                .get(table)
+                .is_some_and(|progress| progress.copied)
+        })
+    }
+}
+
+/// Default number of rows written to the target in a single batch.
+pub const DEFAULT_MIGRATION_BATCH_SIZE: usize = 10_000;
+/// Default number of tables copied in parallel.
+pub const DEFAULT_MIGRATION_CONCURRENCY: usize = 8;
+
+#[derive(Clone, Debug)]
+pub struct MigrationOptions {
+    /// Number of rows written to the target in a single batch.
+    pub batch_size: usize,
+    /// Number of tables copied in parallel.
+    pub concurrency: usize,
+    /// Compare the row count and checksum of every table once it is copied.
+    pub verify: bool,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_MIGRATION_BATCH_SIZE,
+            concurrency: DEFAULT_MIGRATION_CONCURRENCY,
+            verify: true,
+        }
+    }
+}
+
+/// Copies all `tables` in parallel, starting from `progress`. `persist` is called with the
+/// updated progress after every batch and whenever a table is copied or verified, and should
+/// store it for `migrate_tables` to be resumed from if it is interrupted.
+pub fn migrate_tables(
+    tables: &[Box<dyn TableMigrator + '_>],
+    progress: MigrationProgress,
+    options: &MigrationOptions,
+    persist: &(dyn Fn(&MigrationProgress) -> anyhow::Result<()> + Sync),
+) -> anyhow::Result<MigrationProgress> {
+    if options.batch_size == 0 || options.concurrency == 0 {
+        bail!("batch size and concurrency must be greater than zero");
+    }
+
+    let progress = Mutex::new(progress);
+    let next_table = AtomicUsize::new(0);
+    let update_progress = |table: &str, update: &mut dyn FnMut(&mut TableProgress)| {
+        let mut progress = progress.lock().unwrap();
+        update(progress.tables.entry(table.to_owned()).or_default());
+        persist(&progress)
+    };
+
+    let migrate_table = |table: &dyn TableMigrator| -> anyhow::Result<()> {
+        let name = table.table_name();
+        let TableProgress {
+            last_key,
+            num_rows_copied,
+            copied,
+            verified,
+        } = progress
+            .lock()
+            .unwrap()
+            .tables
+            .get(name)
+            .cloned()
+            .unwrap_or_default();
+
+        if !copied {
+            info!(table = name, num_rows_copied, "Copying table");
+            table.copy_rows(
+                last_key.as_deref(),
+                options.batch_size,
+                &mut |key, num_rows| {
+                    update_progress(name, &mut |progress| {
+                        progress.last_key = Some(key.to_vec());
+                        progress.num_rows_copied += num_rows;
+                    })
+                },
+            )?;
+            update_progress(name, &mut |progress| progress.copied = true)?;
+        }
+
+        if options.verify && verified.is_none() {
+            let (source, target) = table.checksums()?;
+            if source != target {
+                return Err(anyhow!(
+                    "table {name} does not match after migration: source has {} rows \
+                     (checksum {:#018x}), target has {} rows (checksum {:#018x})",
+                    source.num_rows,
+                    source.checksum,
+                    target.num_rows,
+                    target.checksum,
+                ));
+            }
+            info!(table = name, num_rows = source.num_rows, "Verified table");
+            update_progress(name, &mut |progress| progress.verified = Some(source))?;
+        }
+        Ok(())
+    };
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..options.concurrency.min(tables.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    loop {
+                        let Some(table) = tables.get(next_table.fetch_add(1, Ordering::Relaxed))
+                        else {
+                            return Ok(());
+                        };
+                        if let Err(e) = migrate_table(table.as_ref()) {
+                            // Stop the other workers from picking up more tables.
+                            next_table.store(tables.len(), Ordering::Relaxed);
+                            return Err(e);
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("migration worker panicked"))
+            .collect::<anyhow::Result<()>>()
+    })?;
+
+    Ok(progress.into_inner().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rocks::{DBMap, MetricConf, open_in_memory};
+
+    struct Tables {
+        numbers: DBMap<u64, String>,
+        names: DBMap<String, Vec<u8>>,
+    }
+
+    impl Tables {
+        fn open() -> Self {
+            let db = open_in_memory(MetricConf::default());
+            Self {
+                numbers: DBMap::reopen_in_memory(&db, "numbers"),
+                names: DBMap::reopen_in_memory(&db, "names"),
+            }
+        }
+
+        fn populate(&self, num_rows: u64) {
+            self.numbers
+                .multi_insert((0..num_rows).map(|i| (i, i.to_string())))
+                .unwrap();
+            self.names
+                .multi_insert((0..num_rows).map(|i| (format!("name-{i}"), vec![i as u8; 4])))
+                .unwrap();
+        }
+
+        fn migrations<'a>(&'a self, target: &'a Tables) -> Vec<Box<dyn TableMigrator + 'a>> {
+            vec![
+                Box::new(TableMigration::new(
+                    "numbers",
+                    &self.numbers,
+                    &target.numbers,
+                )),
+                Box::new(TableMigration::new("names", &self.names, &target.names)),
+            ]
+        }
+    }
+
+    fn options() -> MigrationOptions {
+        MigrationOptions {
+            batch_size: 7,
+            concurrency: 2,
+            verify: true,
+        }
+    }
+
+    #[test]
+    fn test_migrate_tables() {
+        let source = Tables::open();
+        let target = Tables::open();
+        source.populate(100);
+
+        let progress = migrate_tables(
+            &source.migrations(&target),
+            MigrationProgress::default(),
+            &options(),
+            &|_| Ok(()),
+        )
+        .unwrap();
+
+        assert!(progress.is_complete(["numbers", "names"]));
+        assert_eq!(progress.tables["numbers"].num_rows_copied, 100);
+        assert_eq!(progress.tables["numbers"].verified.unwrap().num_rows, 100);
+        assert_eq!(
+            checksum(&source.names).unwrap(),
+            checksum(&target.names).unwrap()
+        );
+        assert_eq!(target.numbers.get(&42).unwrap(), Some("42".to_string()));
+    }
+
+    #[test]
+    fn test_resume_migration() {
+        let source = Tables::open();
+        let target = Tables::open();
+        source.populate(100);
+
+        // Interrupt the migration after a few batches.
+        let persisted = Mutex::new(MigrationProgress::default());
+        let num_batches = AtomicUsize::new(0);
+        let options = MigrationOptions {
+            concurrency: 1,
+            ..options()
+        };
+        migrate_tables(
+            &source.migrations(&target),
+            MigrationProgress::default(),
+            &options,
+            &|progress| {
+                if num_batches.fetch_add(1, Ordering::Relaxed) == 5 {
+                    bail!("interrupted");
+                }
+                *persisted.lock().unwrap() = progress.clone();
+                Ok(())
+            },
+        )
+        .unwrap_err();
+
+        let persisted = persisted.into_inner().unwrap();
+        assert!(!persisted.is_complete(["numbers", "names"]));
+        assert_eq!(persisted.tables["numbers"].num_rows_copied, 35);
+
+        let progress = migrate_tables(
+            &source.migrations(&target),
+            persisted,
+            &options,
+            &|_| Ok(()),
+        )
+        .unwrap();
+        assert!(progress.is_complete(["numbers", "names"]));
+        assert_eq!(progress.tables["numbers"].num_rows_copied, 100);
+        assert_eq!(progress.tables["names"].num_rows_copied, 100);
+    }
+
+    #[test]
+    fn test_verification_failure() {
+        let source = Tables::open();
+        let target = Tables::open();
+        source.populate(10);
+        target.names.insert(&"extra".to_string(), &vec![]).unwrap();
+
+        let err = migrate_tables(
+            &source.migrations(&target),
+            MigrationProgress::default(),
+            &options(),
+            &|_| Ok(()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("table names does not match"));
+    }
+}
@@ -644,6 +644,17 @@ impl<K, V> DBMap<K, V> {
         ))
     }
 
+    /// Opens a typed map over a column family of a database created with `open_in_memory`.
+    pub fn reopen_in_memory(db: &Arc<Database>, cf_name: &str) -> Self {
+        DBMap::new(
+            db.clone(),
+            &ReadWriteOptions::default(),
+            cf_name,
+            ColumnFamily::InMemory(cf_name.to_string()),
+            false,
+        )
+    }
+
     #[cfg(tidehunter)]
     pub fn reopen_th(
         db: Arc<Database>,
@@ -2046,6 +2057,15 @@ pub fn open_cf_opts_secondary<P: AsRef<Path>>(
     })
 }
 
+/// Opens an empty database that is only kept in memory. Column families are created on first use.
+pub fn open_in_memory(metric_conf: MetricConf) -> Arc<Database> {
+    Arc::new(Database::new(
+        Storage::InMemory(InMemoryDB::new()),
+        metric_conf,
+        None,
+    ))
+}
+
 // Drops a database if there is no other handle to it, with retries and timeout.
 #[cfg(not(tidehunter))]
 pub async fn safe_drop_db(path: PathBuf, timeout: Duration) -> Result<(), rocksdb::Error> {
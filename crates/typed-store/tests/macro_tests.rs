@@ -11,6 +11,7 @@ use std::fmt::Debug;
 use std::sync::Mutex;
 use std::time::Duration;
 use typed_store::metrics::SamplingInterval;
+use typed_store::migration::{MigrationOptions, MigrationProgress, migrate_tables};
 use typed_store::rocks::DBMap;
 use typed_store::rocks::MetricConf;
 use typed_store::traits::Map;
@@ -280,6 +281,34 @@ async fn test_sampling_time() {
     assert!(sampling_interval.sample());
 }
 
+#[tokio::test]
+async fn table_migrations_test() {
+    let source = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    let target = Tables::open_tables_read_write(temp_dir(), MetricConf::default(), None, None);
+    source
+        .table1
+        .multi_insert((0..100).map(|i| (i.to_string(), i.to_string())))
+        .unwrap();
+    source
+        .table2
+        .multi_insert((0..50).map(|i| (i, i.to_string())))
+        .unwrap();
+
+    let migrations = Tables::table_migrations(&source, &target);
+    let progress = migrate_tables(
+        &migrations,
+        MigrationProgress::default(),
+        &MigrationOptions::default(),
+        &|_| Ok(()),
+    )
+    .unwrap();
+
+    assert!(progress.is_complete(["table1", "table2"]));
+    assert_eq!(progress.tables["table1"].verified.unwrap().num_rows, 100);
+    assert_eq!(progress.tables["table2"].verified.unwrap().num_rows, 50);
+    assert_eq!(target.table2.get(&7).unwrap(), Some("7".to_string()));
+}
+
 #[cfg(tidehunter)]
 mod tidehunter_tests {
     use super::*;
@@ -310,4 +339,33 @@ mod tidehunter_tests {
         let result = db.table1.get(&key).unwrap();
         assert_eq!(result, Some(value));
     }
+
+    #[tokio::test]
+    async fn test_migrate_rocksdb_to_tidehunter() {
+        let source = ThTable::open_tables_rocksdb(temp_dir(), MetricConf::default());
+        source
+            .table2
+            .multi_insert((0..100).map(|i| (i, i.to_string())))
+            .unwrap();
+
+        let configs = vec![
+            ("table1".to_string(), ThConfig::new(11, 1, 1)),
+            ("table2".to_string(), ThConfig::new(11, 1, 1)),
+        ];
+        let target = ThTable::open_tables_read_write(
+            temp_dir(),
+            MetricConf::new("test_th"),
+            BTreeMap::from_iter(configs),
+        );
+
+        let progress = migrate_tables(
+            &ThTable::table_migrations(&source, &target),
+            MigrationProgress::default(),
+            &MigrationOptions::default(),
+            &|_| Ok(()),
+        )
+        .unwrap();
+        assert_eq!(progress.tables["table2"].verified.unwrap().num_rows, 100);
+        assert_eq!(target.table2.get(&42).unwrap(), Some("42".to_string()));
+    }
 }
@@ -42,3 +42,5 @@ pub const MOVE_COMPILED_INTERFACES_DIR: &str = "mv_interfaces";
 pub const COMPILED_NAMED_ADDRESS_MAPPING: &str = "compiled-module-address-name";
 
 pub const JSON_ERRORS: &str = "json-errors";
+
+pub const SARIF_ERRORS: &str = "sarif-errors";
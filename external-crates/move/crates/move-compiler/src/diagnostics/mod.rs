@@ -80,6 +80,8 @@ pub enum DiagnosticsFormat {
     #[default]
     Text,
     JSON,
+    /// Static Analysis Results Interchange Format (SARIF) v2.1.0, for code scanning tools
+    SARIF,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -218,11 +220,12 @@ pub fn env_color() -> ColorChoice {
 }
 
 fn render_diagnostics(writer: &mut dyn WriteColor, mapping: &MappedFiles, diags: Diagnostics) {
-    let Diagnostics {
-        diags: Some(mut diags),
-        format,
-    } = diags
-    else {
+    let Diagnostics { diags, format } = diags;
+    let Some(mut diags) = diags else {
+        // a SARIF consumer expects a log even when there is nothing to report
+        if matches!(format, DiagnosticsFormat::SARIF) {
+            emit_diagnostics_sarif(writer, mapping, Diagnostics_::default());
+        }
         return;
     };
 
@@ -237,6 +240,7 @@ fn render_diagnostics(writer: &mut dyn WriteColor, mapping: &MappedFiles, diags:
     match format {
         DiagnosticsFormat::Text => emit_diagnostics_text(writer, mapping, diags),
         DiagnosticsFormat::JSON => emit_diagnostics_json(writer, mapping, diags),
+        DiagnosticsFormat::SARIF => emit_diagnostics_sarif(writer, mapping, diags),
     }
 }
 
@@ -327,6 +331,55 @@ fn emit_diagnostics_json(
     .expect("ICE reporting error");
 }
 
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_TOOL_NAME: &str = "move-compiler";
+
+fn emit_diagnostics_sarif(
+    writer: &mut dyn WriteColor,
+    mapped_files: &MappedFiles,
+    diags: Diagnostics_,
+) {
+    let mut seen: HashSet<Diagnostic> = HashSet::new();
+    let mut rules = BTreeMap::new();
+    let mut results = vec![];
+    for diag in diags.diagnostics {
+        if seen.contains(&diag) {
+            continue;
+        }
+        seen.insert(diag.clone());
+        let rule_id = diag.sarif_rule_id();
+        rules
+            .entry(rule_id.clone())
+            .or_insert_with(|| diag.info().message());
+        results.push(diag.to_sarif_result(mapped_files, rule_id));
+    }
+    let rules = rules
+        .into_iter()
+        .map(|(id, message)| {
+            serde_json::json!({
+                "id": id,
+                "shortDescription": { "text": message },
+            })
+        })
+        .collect::<Vec<_>>();
+    let log = serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": SARIF_TOOL_NAME,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    writeln!(writer, "{}", serde_json::to_string_pretty(&log).unwrap())
+        .expect("ICE reporting error");
+}
+
 //**************************************************************************************************
 // Migration Diff Reporting
 //**************************************************************************************************
@@ -342,7 +395,7 @@ pub fn generate_migration_diff(
         } => {
             assert!(
                 matches!(format, DiagnosticsFormat::Text),
-                "Cannot migrate with json mode set"
+                "Cannot migrate with json or sarif mode set"
             );
             let migration_diags = inner
                 .diagnostics
@@ -447,7 +500,7 @@ impl<'env> DiagnosticReporter<'env> {
                     );
                     diag.add_note(help)
                 }
-                if self.flags.warnings_are_errors() {
+                if self.flags.warnings_are_errors() || self.warning_filters_scope.is_denied(&diag) {
                     diag = diag.set_severity(Severity::NonblockingError)
                 }
             }
@@ -721,7 +774,7 @@ impl Diagnostics {
     fn env_color(&self) -> ColorChoice {
         match self.format {
             DiagnosticsFormat::Text => (),
-            DiagnosticsFormat::JSON => {
+            DiagnosticsFormat::JSON | DiagnosticsFormat::SARIF => {
                 return ColorChoice::Never;
             }
         };
@@ -832,6 +885,54 @@ impl Diagnostic {
             msg: info.message().to_string(),
         }
     }
+
+    /// The SARIF rule for this diagnostic. Unlike the rendered code, this does not include the
+    /// severity, so that a warning promoted to an error is still reported under the same rule.
+    fn sarif_rule_id(&self) -> String {
+        let info = self.info();
+        let prefix = info.external_prefix().map(str::trim).unwrap_or("");
+        format!("{prefix}{:02}{:03}", info.category(), info.code())
+    }
+
+    fn to_sarif_result(&self, mapped_files: &MappedFiles, rule_id: String) -> serde_json::Value {
+        let Diagnostic {
+            info,
+            primary_label: (ploc, pmsg),
+            secondary_labels: _,
+            notes,
+        } = self;
+        let level = match info.severity() {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::NonblockingError | Severity::BlockingError | Severity::Bug => "error",
+        };
+        let mut text = format!("{}: {pmsg}", info.message());
+        for note in notes {
+            text.push('\n');
+            text.push_str(note);
+        }
+        let mut result = serde_json::json!({
+            "ruleId": rule_id,
+            "level": level,
+            "message": { "text": text },
+        });
+        if let Some(bloc) = mapped_files.position_opt(ploc) {
+            result["locations"] = serde_json::json!([{
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "uri": mapped_files.file_path(&bloc.file_hash).to_string_lossy(),
+                    },
+                    "region": {
+                        "startLine": bloc.start.user_line(),
+                        "startColumn": bloc.start.column_offset() + 1,
+                        "endLine": bloc.end.user_line(),
+                        "endColumn": bloc.end.column_offset() + 1,
+                    }
+                }
+            }]);
+        }
+        result
+    }
 }
 
 #[macro_export]
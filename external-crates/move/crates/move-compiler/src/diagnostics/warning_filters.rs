@@ -103,6 +103,9 @@ unsafe impl Sync for WarningFilters {}
 /// Used to filter out diagnostics, specifically used for warning suppression
 pub struct WarningFiltersBuilder {
     filters: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+    /// Warnings that are not filtered but should instead be reported as errors, e.g. lints set to
+    /// `deny` in the package manifest
+    denied: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
     for_dependency: bool, // if false, the filters are used for source code
 }
 
@@ -195,6 +198,29 @@ impl WarningFiltersScope {
         }
     }
 
+    /// Returns true if the innermost scope with an opinion on `diag` denies it. A filter in an
+    /// inner scope (e.g. an `#[allow(...)]` attribute) takes precedence over an outer deny.
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        let mut scope = &self.0;
+        loop {
+            match scope {
+                WarningFiltersScope_::Empty => return false,
+                WarningFiltersScope_::Static(filters) => {
+                    return !filters.is_filtered(diag) && filters.is_denied(diag);
+                }
+                WarningFiltersScope_::Node(node) => {
+                    if node.filters.is_filtered(diag) {
+                        return false;
+                    }
+                    if node.filters.is_denied(diag) {
+                        return true;
+                    }
+                    scope = &node.prev;
+                }
+            }
+        }
+    }
+
     pub fn is_filtered_for_dependency(&self) -> bool {
         let mut scope = &self.0;
         loop {
@@ -247,6 +273,10 @@ impl WarningFilters {
         self.borrow().is_filtered(diag)
     }
 
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        self.borrow().is_denied(diag)
+    }
+
     pub fn for_dependency(&self) -> bool {
         self.borrow().for_dependency()
     }
@@ -260,6 +290,7 @@ impl WarningFiltersBuilder {
     pub const fn new_for_source() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -267,6 +298,7 @@ impl WarningFiltersBuilder {
     pub const fn new_for_dependency() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: true,
         }
     }
@@ -292,6 +324,14 @@ impl WarningFiltersBuilder {
             .is_some_and(|filters| filters.is_filtered_by_info(info))
     }
 
+    /// Returns true if the diagnostic is a warning that should be promoted to an error
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        let info = &diag.info;
+        self.denied
+            .get(&info.external_prefix())
+            .is_some_and(|denied| denied.is_filtered_by_info(info))
+    }
+
     pub fn union(&mut self, other: &Self) {
         for (prefix, filters) in &other.filters {
             self.filters
@@ -299,6 +339,12 @@ impl WarningFiltersBuilder {
                 .or_insert_with(UnprefixedWarningFilters::new)
                 .union(filters);
         }
+        for (prefix, denied) in &other.denied {
+            self.denied
+                .entry(*prefix)
+                .or_insert_with(UnprefixedWarningFilters::new)
+                .union(denied);
+        }
         // if there is a dependency code filter on the stack, it means we are filtering dependent
         // code and this information must be preserved when stacking up additional filters (which
         // involves union of the current filter with the new one)
@@ -312,9 +358,22 @@ impl WarningFiltersBuilder {
     }
 
     pub fn add(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.filters, filter)
+    }
+
+    /// Marks the warnings matched by `filter` as errors, unless they are filtered in this or an
+    /// inner scope
+    pub fn deny(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.denied, filter)
+    }
+
+    fn add_to(
+        filters: &mut BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+        filter: WarningFilter,
+    ) {
         let (prefix, category, code, name) = match filter {
             WarningFilter::All(prefix) => {
-                self.filters.insert(prefix, UnprefixedWarningFilters::All);
+                filters.insert(prefix, UnprefixedWarningFilters::All);
                 return;
             }
             WarningFilter::Category {
@@ -329,7 +388,7 @@ impl WarningFiltersBuilder {
                 name,
             } => (prefix, category, Some(code), name),
         };
-        self.filters
+        filters
             .entry(prefix)
             .or_insert(UnprefixedWarningFilters::Empty)
             .add(category, code, name)
@@ -341,6 +400,7 @@ impl WarningFiltersBuilder {
                 None,
                 UnprefixedWarningFilters::unused_warnings_filter_for_test(),
             )]),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -1254,7 +1254,7 @@ fn module_(
         )));
     }
     let attributes = expand_attributes(context, AttributePosition::Module, attributes);
-    let warning_filter = module_warning_filter(context, package_name, &attributes);
+    let warning_filter = module_warning_filter(context, package_name, &name, &attributes);
     context.push_warning_filter_scope(warning_filter);
     assert!(context.address.is_none());
     assert!(address.is_none());
@@ -1560,6 +1560,7 @@ fn stdlib_definitions(context: &mut Context, mloc: Loc) -> StdlibDefinitions {
 fn module_warning_filter(
     context: &mut Context,
     package: Option<Symbol>,
+    module: &ModuleName,
     attributes: &E::Attributes,
 ) -> WarningFilters {
     let mut filters = warning_filter_(context, attributes);
@@ -1576,7 +1577,11 @@ fn module_warning_filter(
         context.all_filter_alls
     } else {
         let config = context.env().package_config(package);
-        filters.union(&config.warning_filter);
+        let package_filter = config
+            .module_warning_filters
+            .get(&module.value())
+            .unwrap_or(&config.warning_filter);
+        filters.union(package_filter);
         context
             .warning_filters_table
             .get_mut()
@@ -316,6 +316,8 @@ impl CompilationEnv {
         let mut diags = Diagnostics::new();
         if flags.json_errors() {
             diags.set_format(DiagnosticsFormat::JSON);
+        } else if flags.sarif_errors() {
+            diags.set_format(DiagnosticsFormat::SARIF);
         }
 
         let modes = Self::compute_modes(&flags);
@@ -719,6 +721,13 @@ pub struct Flags {
     )]
     json_errors: bool,
 
+    /// If set, report errors and warnings as SARIF.
+    #[clap(
+        long = cli::SARIF_ERRORS,
+        conflicts_with = "json_errors",
+    )]
+    sarif_errors: bool,
+
     /// If set, all warnings are silenced
     #[clap(
         long = cli::SILENCE_WARNINGS,
@@ -774,6 +783,7 @@ impl Flags {
             warnings_are_errors: false,
             silence_warnings: false,
             json_errors: false,
+            sarif_errors: false,
             keep_testing_functions: false,
             ide_mode: false,
             ide_test_mode: false,
@@ -788,6 +798,7 @@ impl Flags {
             bytecode_version: None,
             warnings_are_errors: false,
             json_errors: false,
+            sarif_errors: false,
             silence_warnings: false,
             keep_testing_functions: false,
             ide_mode: false,
@@ -831,6 +842,13 @@ impl Flags {
         }
     }
 
+    pub fn set_sarif_errors(self, value: bool) -> Self {
+        Self {
+            sarif_errors: value,
+            ..self
+        }
+    }
+
     pub fn set_ide_test_mode(self, value: bool) -> Self {
         Self {
             ide_test_mode: value,
@@ -882,6 +900,10 @@ impl Flags {
         self.json_errors
     }
 
+    pub fn sarif_errors(&self) -> bool {
+        self.sarif_errors
+    }
+
     pub fn silence_warnings(&self) -> bool {
         self.silence_warnings
     }
@@ -916,6 +938,9 @@ fn parse_symbol(s: &str) -> Result<Symbol, String> {
 pub struct PackageConfig {
     pub is_dependency: bool,
     pub warning_filter: WarningFiltersBuilder,
+    /// Warning filters for specific modules of the package, keyed by module name. When present,
+    /// these are used in place of `warning_filter` for that module.
+    pub module_warning_filters: BTreeMap<Symbol, WarningFiltersBuilder>,
     pub flavor: Flavor,
     pub edition: Edition,
 }
@@ -925,6 +950,7 @@ impl Default for PackageConfig {
         Self {
             is_dependency: false,
             warning_filter: WarningFiltersBuilder::new_for_source(),
+            module_warning_filters: BTreeMap::new(),
             flavor: Flavor::default(),
             edition: Edition::default(),
         }
@@ -0,0 +1,172 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags public functions that take capability-like objects by value. Taking a
+//! capability by value lets the function (or anything it calls) transfer, wrap or destroy it,
+//! while most operations only need to check that the caller owns it, which a reference is enough
+//! for. Functions that hand the capability back in their return type are not flagged, and
+//! neither are functions that destroy, transfer, share or freeze it, as those need it by value.
+
+use move_core_types::account_address::AccountAddress;
+
+use crate::{
+    diag,
+    diagnostics::codes::{DiagnosticInfo, Severity, custom},
+    expansion::ast::{ModuleIdent, Visibility},
+    naming::ast::{self as N, TypeName_, Var},
+    parser::ast::FunctionName,
+    shared::Identifier,
+    sui_mode::SUI_ADDR_VALUE,
+    typing::{
+        ast as T, core,
+        visitor::{seq_satisfies, simple_visitor},
+    },
+};
+
+use super::{
+    FREEZE_FUN, LINT_WARNING_PREFIX, LinterDiagnosticCategory, LinterDiagnosticCode,
+    PUBLIC_FREEZE_FUN, PUBLIC_SHARE_FUN, PUBLIC_TRANSFER_FUN, SHARE_FUN, TRANSFER_FUN,
+    TRANSFER_MOD_NAME, freezing_capability::REGEX,
+};
+
+/// Functions that take ownership of an object by value
+const CONSUMING_FUNCTIONS: &[(AccountAddress, &str, &str)] = &[
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, TRANSFER_FUN),
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, PUBLIC_TRANSFER_FUN),
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, SHARE_FUN),
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, PUBLIC_SHARE_FUN),
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, FREEZE_FUN),
+    (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, PUBLIC_FREEZE_FUN),
+];
+
+const CAPABILITY_BY_VALUE_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::CapabilityByValue as u8,
+    "capability passed by value",
+);
+
+simple_visitor!(
+    CapabilityByValueVisitor,
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only() || ident.value.address.is(&SUI_ADDR_VALUE)
+    },
+    fn visit_function_custom(
+        &mut self,
+        _module: ModuleIdent,
+        fname: FunctionName,
+        fdef: &T::Function,
+    ) -> bool {
+        if fdef.attributes.is_test_or_test_only()
+            || !matches!(fdef.visibility, Visibility::Public(_))
+        {
+            return true;
+        }
+        let T::FunctionBody_::Defined(body) = &fdef.body.value else {
+            return true;
+        };
+        for (_, var, t) in &fdef.signature.parameters {
+            let Some(cap_name) = capability_type_name(t) else {
+                continue;
+            };
+            if mentions_type(&fdef.signature.return_type, cap_name) || consumes(body, var) {
+                continue;
+            }
+            let msg = format!(
+                "'public' function '{fname}' takes the capability '{}' by value",
+                var.value.name
+            );
+            let mut d = diag!(CAPABILITY_BY_VALUE_DIAG, (t.loc, msg));
+            d.add_note(format!(
+                "The type {} is potentially a capability based on its name",
+                core::error_format(t, &core::Subst::empty()),
+            ));
+            d.add_note(
+                "Callers lose the capability unless it is returned. Take it by reference if \
+                 the function only needs to check that the caller owns it",
+            );
+            self.add_diag(d);
+        }
+        true
+    }
+);
+
+/// Returns the name of `t` if it is a capability-like struct taken by value
+fn capability_type_name(sp!(_, t): &N::Type) -> Option<&N::TypeName> {
+    let N::TypeInner::Apply(_, tn @ sp!(_, TypeName_::ModuleType(_, struct_name)), _) = t.inner()
+    else {
+        return None;
+    };
+    REGEX.is_match(struct_name.value().as_str()).then_some(tn)
+}
+
+/// Returns true if `body` destroys `var` by unpacking it, or passes it to one of the
+/// `CONSUMING_FUNCTIONS`
+fn consumes(body: &T::Sequence, var: &Var) -> bool {
+    use T::UnannotatedExp_ as E;
+    unpacks(body, var)
+        || seq_satisfies(body, |e| match &e.exp.value {
+            E::ModuleCall(call) => {
+                CONSUMING_FUNCTIONS
+                    .iter()
+                    .any(|(addr, module, fun)| call.is(addr, module, fun))
+                    && passes_by_value(&call.arguments, var)
+            }
+            E::Block(seq) | E::NamedBlock(_, seq) => unpacks(seq, var),
+            E::Match(subject, _) | E::VariantMatch(subject, _, _) => moves(subject, var),
+            _ => false,
+        })
+}
+
+/// Returns true if `seq` binds `var` to an unpacking pattern, e.g. `let Cap { id } = cap;`
+fn unpacks(seq: &T::Sequence, var: &Var) -> bool {
+    seq.1.iter().any(|item| match &item.value {
+        T::SequenceItem_::Bind(sp!(_, lvalues), _, e) => {
+            let [sp!(_, lvalue)] = lvalues.as_slice() else {
+                return false;
+            };
+            moves(e, var)
+                && matches!(
+                    lvalue,
+                    T::LValue_::Unpack(..) | T::LValue_::UnpackVariant(..)
+                )
+        }
+        T::SequenceItem_::Declare(_) | T::SequenceItem_::Seq(_) => false,
+    })
+}
+
+/// Returns true if `var` is moved into one of the call arguments `args`
+fn passes_by_value(args: &T::Exp, var: &Var) -> bool {
+    match &args.exp.value {
+        T::UnannotatedExp_::ExpList(items) => items.iter().any(|item| match item {
+            T::ExpListItem::Single(e, _) => moves(e, var),
+            T::ExpListItem::Splat(..) => false,
+        }),
+        _ => moves(args, var),
+    }
+}
+
+fn moves(e: &T::Exp, var: &Var) -> bool {
+    matches!(&e.exp.value, T::UnannotatedExp_::Move { var: v, .. } if v == var)
+}
+
+/// Returns true if `name` appears anywhere in `t`, e.g. as a tuple element or a type argument
+fn mentions_type(sp!(_, t): &N::Type, name: &N::TypeName) -> bool {
+    match t.inner() {
+        N::TypeInner::Apply(_, sp!(_, tn), args) => {
+            tn == &name.value || args.iter().any(|arg| mentions_type(arg, name))
+        }
+        N::TypeInner::Ref(_, inner) => mentions_type(inner, name),
+        N::TypeInner::Fun(args, ret) => {
+            args.iter().any(|arg| mentions_type(arg, name)) || mentions_type(ret, name)
+        }
+        N::TypeInner::Unit
+        | N::TypeInner::Param(_)
+        | N::TypeInner::Var(_)
+        | N::TypeInner::Anything
+        | N::TypeInner::Void
+        | N::TypeInner::UnresolvedError => false,
+    }
+}
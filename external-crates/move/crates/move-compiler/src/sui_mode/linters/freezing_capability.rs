@@ -38,7 +38,7 @@ const FREEZE_FUNCTIONS: &[(AccountAddress, &str, &str)] = &[
     (SUI_ADDR_VALUE, TRANSFER_MOD_NAME, FREEZE_FUN),
 ];
 
-static REGEX: LazyLock<Regex> =
+pub(super) static REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r".*Cap(?:[A-Z0-9_]+|ability|$).*").unwrap());
 
 simple_visitor!(
@@ -13,6 +13,7 @@ use crate::{
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 
+pub mod capability_by_value;
 pub mod coin_field;
 pub mod collection_equality;
 pub mod custom_state_change;
@@ -24,7 +25,9 @@ pub mod public_random;
 pub mod self_transfer;
 pub mod share_owned;
 pub mod uncallable_function;
+pub mod unchecked_balance_arithmetic;
 pub mod unnecessary_public_entry;
+pub mod weak_randomness;
 
 pub const TRANSFER_MOD_NAME: &str = "transfer";
 pub const TRANSFER_FUN: &str = "transfer";
@@ -39,6 +42,14 @@ pub const PUBLIC_RECEIVE_FUN: &str = "public_receive";
 pub const COIN_MOD_NAME: &str = "coin";
 pub const COIN_STRUCT_NAME: &str = "Coin";
 
+pub const BALANCE_MOD_NAME: &str = "balance";
+pub const VALUE_FUN: &str = "value";
+
+pub const TX_CONTEXT_MOD_NAME: &str = "tx_context";
+
+pub const OBJECT_MOD_NAME: &str = "object";
+pub const OBJECT_NEW_FUN: &str = "new";
+
 pub const BAG_MOD_NAME: &str = "bag";
 pub const BAG_STRUCT_NAME: &str = "Bag";
 
@@ -75,6 +86,9 @@ pub const FREEZING_CAPABILITY_FILTER_NAME: &str = "freezing_capability";
 pub const PREFER_MUTABLE_TX_CONTEXT_FILTER_NAME: &str = "prefer_mut_tx_context";
 pub const UNNECESSARY_PUBLIC_ENTRY_FILTER_NAME: &str = "public_entry";
 pub const UNCALLABLE_FUNCTION_FILTER_NAME: &str = "uncallable_function";
+pub const WEAK_RANDOMNESS_FILTER_NAME: &str = "weak_randomness";
+pub const UNCHECKED_BALANCE_ARITHMETIC_FILTER_NAME: &str = "unchecked_balance_arithmetic";
+pub const CAPABILITY_BY_VALUE_FILTER_NAME: &str = "capability_by_value";
 
 pub const RANDOM_MOD_NAME: &str = "random";
 pub const RANDOM_STRUCT_NAME: &str = "Random";
@@ -96,6 +110,9 @@ pub enum LinterDiagnosticCode {
     PreferMutableTxContext,
     UnnecessaryPublicEntry,
     UncallableFunction,
+    WeakRandomness,
+    UncheckedBalanceArithmetic,
+    CapabilityByValue,
 }
 
 pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
@@ -173,6 +190,24 @@ pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
             LinterDiagnosticCode::UncallableFunction as u8,
             Some(UNCALLABLE_FUNCTION_FILTER_NAME),
         ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::WeakRandomness as u8,
+            Some(WEAK_RANDOMNESS_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::UncheckedBalanceArithmetic as u8,
+            Some(UNCHECKED_BALANCE_ARITHMETIC_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagnosticCategory::Sui as u8,
+            LinterDiagnosticCode::CapabilityByValue as u8,
+            Some(CAPABILITY_BY_VALUE_FILTER_NAME),
+        ),
     ];
 
     (Some(ALLOW_ATTR_CATEGORY.into()), filters)
@@ -198,6 +233,9 @@ pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
             visitors.extend([
                 freezing_capability::WarnFreezeCapability.visitor(),
                 public_mut_tx_context::PreferMutableTxContext.visitor(),
+                weak_randomness::WeakRandomnessVerifier.visitor(),
+                unchecked_balance_arithmetic::UncheckedBalanceArithmeticVerifier.visitor(),
+                capability_by_value::CapabilityByValueVisitor.visitor(),
             ]);
             visitors
         }
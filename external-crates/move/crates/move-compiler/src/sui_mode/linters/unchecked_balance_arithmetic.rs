@@ -0,0 +1,291 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags subtraction and multiplication on the value of a `Coin` or `Balance` in
+//! entry functions, when the value has not been compared against a bound first. An underflow or
+//! overflow aborts the transaction with an arithmetic error, instead of an error code that callers
+//! can act on.
+//!
+//! A subtraction between two locals only counts as checked if those two locals were compared with
+//! each other on every path leading to it. Otherwise (e.g. a multiplication, or a subtraction of a
+//! constant) any ordering comparison of the value counts as a bound.
+
+use move_core_types::account_address::AccountAddress;
+use move_ir_types::location::*;
+
+use crate::{
+    cfgir::{
+        CFGContext,
+        absint::JoinResult,
+        cfg::ImmForwardCFG,
+        visitor::{
+            LocalState, SimpleAbsInt, SimpleAbsIntConstructor, SimpleDomain,
+            SimpleExecutionContext, calls_special_function,
+        },
+    },
+    diag,
+    diagnostics::{
+        Diagnostic, Diagnostics,
+        codes::{DiagnosticInfo, Severity, custom},
+    },
+    hlir::ast::{Exp, Label, ModuleCall, Type, UnannotatedExp_, Var},
+    parser::ast::BinOp_,
+    sui_mode::SUI_ADDR_VALUE,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    BALANCE_MOD_NAME, COIN_MOD_NAME, INVALID_LOC, LINT_WARNING_PREFIX, LinterDiagnosticCategory,
+    LinterDiagnosticCode, VALUE_FUN,
+};
+
+const VALUE_FUNCTIONS: &[(AccountAddress, &str, &str)] = &[
+    (SUI_ADDR_VALUE, COIN_MOD_NAME, VALUE_FUN),
+    (SUI_ADDR_VALUE, BALANCE_MOD_NAME, VALUE_FUN),
+];
+
+const UNCHECKED_BALANCE_ARITHMETIC_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::UncheckedBalanceArithmetic as u8,
+    "unchecked arithmetic on a coin or balance value",
+);
+
+//**************************************************************************************************
+// types
+//**************************************************************************************************
+
+pub struct UncheckedBalanceArithmeticVerifier;
+
+pub struct UncheckedBalanceArithmeticVerifierAI;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Value {
+    /// the result of `coin::value` or `balance::value`
+    UncheckedAmount(Loc),
+    #[default]
+    Other,
+}
+
+pub struct ExecutionContext {
+    diags: Diagnostics,
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    locals: BTreeMap<Var, LocalState<Value>>,
+    /// locals that have been compared against some bound on every path
+    bounded: BTreeSet<Var>,
+    /// pairs of locals that have been compared with each other on every path, in both orders
+    compared: BTreeSet<(Var, Var)>,
+}
+
+//**************************************************************************************************
+// impls
+//**************************************************************************************************
+
+impl SimpleAbsIntConstructor for UncheckedBalanceArithmeticVerifier {
+    type AI<'a> = UncheckedBalanceArithmeticVerifierAI;
+
+    fn new<'a>(
+        context: &'a CFGContext<'a>,
+        cfg: &ImmForwardCFG,
+        _init_state: &mut <Self::AI<'a> as SimpleAbsInt>::State,
+    ) -> Option<Self::AI<'a>> {
+        if context.entry.is_none()
+            || context.attributes.is_test_or_test_only()
+            || context
+                .info
+                .module(&context.module)
+                .attributes
+                .is_test_or_test_only()
+        {
+            return None;
+        }
+        if !calls_special_function(VALUE_FUNCTIONS, cfg) {
+            return None;
+        }
+        Some(UncheckedBalanceArithmeticVerifierAI)
+    }
+}
+
+impl SimpleAbsInt for UncheckedBalanceArithmeticVerifierAI {
+    type State = State;
+    type ExecutionContext = ExecutionContext;
+
+    fn finish(&mut self, _final_states: BTreeMap<Label, State>, diags: Diagnostics) -> Diagnostics {
+        diags
+    }
+
+    fn start_command(&self, _: &mut State) -> ExecutionContext {
+        ExecutionContext {
+            diags: Diagnostics::new(),
+        }
+    }
+
+    fn finish_command(&self, context: ExecutionContext, _state: &mut State) -> Diagnostics {
+        let ExecutionContext { diags } = context;
+        diags
+    }
+
+    fn exp_custom(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut State,
+        e: &Exp,
+    ) -> Option<Vec<Value>> {
+        use BinOp_ as B;
+        use UnannotatedExp_ as E;
+
+        let E::BinopExp(lhs, sp!(_, op), rhs) = &e.exp.value else {
+            return None;
+        };
+        let lhs_value = self.exp(context, state, lhs);
+        let rhs_value = self.exp(context, state, rhs);
+        match op {
+            B::Lt | B::Le | B::Gt | B::Ge => state.record_comparison(lhs, rhs),
+            B::Sub | B::Mul => {
+                let unchecked_amount = |value: &[Value], operand: &Exp, other: &Exp| {
+                    value.iter().find_map(|v| match v {
+                        Value::UncheckedAmount(loc) if !state.is_checked(*op, operand, other) => {
+                            Some(*loc)
+                        }
+                        Value::UncheckedAmount(_) | Value::Other => None,
+                    })
+                };
+                let amount_loc = unchecked_amount(&lhs_value, lhs, rhs)
+                    .or_else(|| unchecked_amount(&rhs_value, rhs, lhs));
+                if let Some(amount_loc) = amount_loc {
+                    let op_msg = if *op == B::Sub {
+                        "Subtraction"
+                    } else {
+                        "Multiplication"
+                    };
+                    let msg = format!("{op_msg} on a coin or balance value without a bounds check");
+                    let mut d = diag!(UNCHECKED_BALANCE_ARITHMETIC_DIAG, (e.exp.loc, msg));
+                    if amount_loc != INVALID_LOC {
+                        d.add_secondary_label((amount_loc, "Value read from here"));
+                    }
+                    d.add_note(
+                        "Consider asserting on the value first, so that the transaction aborts with \
+                         a meaningful error code instead of an arithmetic error",
+                    );
+                    context.add_diag(d);
+                }
+            }
+            _ => (),
+        }
+        Some(vec![Value::Other])
+    }
+
+    fn call_custom(
+        &self,
+        _context: &mut ExecutionContext,
+        _state: &mut State,
+        loc: &Loc,
+        _return_ty: &Type,
+        f: &ModuleCall,
+        _args: Vec<Value>,
+    ) -> Option<Vec<Value>> {
+        if VALUE_FUNCTIONS
+            .iter()
+            .any(|(addr, module, fun)| f.is(addr, module, fun))
+        {
+            return Some(vec![Value::UncheckedAmount(*loc)]);
+        }
+        None
+    }
+}
+
+/// The local read by `e`, if it is a plain read of a local
+fn read_local(e: &Exp) -> Option<Var> {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Copy { var, .. } | E::Move { var, .. } => Some(*var),
+        _ => None,
+    }
+}
+
+impl State {
+    fn record_comparison(&mut self, lhs: &Exp, rhs: &Exp) {
+        let lhs = read_local(lhs);
+        let rhs = read_local(rhs);
+        self.bounded.extend(lhs.into_iter().chain(rhs));
+        if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+            self.compared.insert((lhs, rhs));
+            self.compared.insert((rhs, lhs));
+        }
+    }
+
+    /// Returns true if the amount read by `operand` was checked before being used in `op` with
+    /// `other`
+    fn is_checked(&self, op: BinOp_, operand: &Exp, other: &Exp) -> bool {
+        let Some(amount) = read_local(operand) else {
+            // the amount is used directly, without having been stored and compared first
+            return false;
+        };
+        match (op, read_local(other)) {
+            // a subtraction between two locals is only safe if they were compared with each other
+            (BinOp_::Sub, Some(other)) => self.compared.contains(&(amount, other)),
+            _ => self.bounded.contains(&amount),
+        }
+    }
+}
+
+impl SimpleDomain for State {
+    type Value = Value;
+
+    fn new(context: &CFGContext, mut locals: BTreeMap<Var, LocalState<Value>>) -> Self {
+        for (_mut, v, _) in &context.signature.parameters {
+            let local_state = locals.get_mut(v).unwrap();
+            if let LocalState::Available(loc, _) = local_state {
+                *local_state = LocalState::Available(*loc, Value::Other);
+            }
+        }
+        State {
+            locals,
+            bounded: BTreeSet::new(),
+            compared: BTreeSet::new(),
+        }
+    }
+
+    fn locals_mut(&mut self) -> &mut BTreeMap<Var, LocalState<Value>> {
+        &mut self.locals
+    }
+
+    fn locals(&self) -> &BTreeMap<Var, LocalState<Value>> {
+        &self.locals
+    }
+
+    fn join_value(v1: &Value, v2: &Value) -> Value {
+        match (v1, v2) {
+            (Value::UncheckedAmount(loc1), Value::UncheckedAmount(loc2)) => {
+                if loc1 == loc2 {
+                    *v1
+                } else {
+                    Value::UncheckedAmount(INVALID_LOC)
+                }
+            }
+            // only report amounts that are unchecked on every path
+            (Value::Other, _) | (_, Value::Other) => Value::Other,
+        }
+    }
+
+    fn join_impl(&mut self, other: &Self, result: &mut JoinResult) {
+        // a check only counts if it happened on every path
+        let bounded_len = self.bounded.len();
+        let compared_len = self.compared.len();
+        self.bounded.retain(|v| other.bounded.contains(v));
+        self.compared.retain(|pair| other.compared.contains(pair));
+        if self.bounded.len() != bounded_len || self.compared.len() != compared_len {
+            *result = JoinResult::Changed;
+        }
+    }
+}
+
+impl SimpleExecutionContext for ExecutionContext {
+    fn add_diag(&mut self, diag: Diagnostic) {
+        self.diags.add(diag)
+    }
+}
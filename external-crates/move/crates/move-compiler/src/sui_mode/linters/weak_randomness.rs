@@ -0,0 +1,258 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags public functions that use values derived from the transaction context
+//! (e.g. the transaction digest or fresh object IDs) as a source of randomness, by taking them
+//! modulo some bound or masking out some of their bits. These values are known or controllable by
+//! the sender, so `sui::random` should be used instead.
+
+use move_core_types::account_address::AccountAddress;
+use move_ir_types::location::*;
+
+use crate::{
+    cfgir::{
+        CFGContext,
+        absint::JoinResult,
+        cfg::ImmForwardCFG,
+        visitor::{
+            LocalState, SimpleAbsInt, SimpleAbsIntConstructor, SimpleDomain,
+            SimpleExecutionContext, calls_special_function,
+        },
+    },
+    diag,
+    diagnostics::{
+        Diagnostic, Diagnostics,
+        codes::{DiagnosticInfo, Severity, custom},
+    },
+    expansion::ast::Visibility,
+    hlir::ast::{Exp, Label, ModuleCall, Type, Type_, UnannotatedExp_, Var},
+    parser::ast::BinOp_,
+    sui_mode::SUI_ADDR_VALUE,
+};
+use std::collections::BTreeMap;
+
+use super::{
+    INVALID_LOC, LINT_WARNING_PREFIX, LinterDiagnosticCategory, LinterDiagnosticCode,
+    OBJECT_MOD_NAME, OBJECT_NEW_FUN, TX_CONTEXT_MOD_NAME,
+};
+
+/// Functions whose results are derived from the transaction context and are predictable by (or
+/// under the control of) the transaction sender
+const TX_CONTEXT_DERIVED_FUNCTIONS: &[(AccountAddress, &str, &str)] = &[
+    (SUI_ADDR_VALUE, TX_CONTEXT_MOD_NAME, "digest"),
+    (SUI_ADDR_VALUE, TX_CONTEXT_MOD_NAME, "fresh_object_address"),
+    (SUI_ADDR_VALUE, TX_CONTEXT_MOD_NAME, "epoch_timestamp_ms"),
+    (SUI_ADDR_VALUE, OBJECT_MOD_NAME, OBJECT_NEW_FUN),
+];
+
+const WEAK_RANDOMNESS_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagnosticCategory::Sui as u8,
+    LinterDiagnosticCode::WeakRandomness as u8,
+    "randomness derived from the transaction context",
+);
+
+//**************************************************************************************************
+// types
+//**************************************************************************************************
+
+pub struct WeakRandomnessVerifier;
+
+pub struct WeakRandomnessVerifierAI;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Value {
+    /// a value computed from the result of one of the `TX_CONTEXT_DERIVED_FUNCTIONS`
+    TxContextDerived(Loc),
+    #[default]
+    Other,
+}
+
+pub struct ExecutionContext {
+    diags: Diagnostics,
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    locals: BTreeMap<Var, LocalState<Value>>,
+}
+
+//**************************************************************************************************
+// impls
+//**************************************************************************************************
+
+impl SimpleAbsIntConstructor for WeakRandomnessVerifier {
+    type AI<'a> = WeakRandomnessVerifierAI;
+
+    fn new<'a>(
+        context: &'a CFGContext<'a>,
+        cfg: &ImmForwardCFG,
+        _init_state: &mut <Self::AI<'a> as SimpleAbsInt>::State,
+    ) -> Option<Self::AI<'a>> {
+        if !matches!(context.visibility, Visibility::Public(_))
+            || context.attributes.is_test_or_test_only()
+            || context
+                .info
+                .module(&context.module)
+                .attributes
+                .is_test_or_test_only()
+        {
+            return None;
+        }
+        if !calls_special_function(TX_CONTEXT_DERIVED_FUNCTIONS, cfg) {
+            return None;
+        }
+        Some(WeakRandomnessVerifierAI)
+    }
+}
+
+impl SimpleAbsInt for WeakRandomnessVerifierAI {
+    type State = State;
+    type ExecutionContext = ExecutionContext;
+
+    fn finish(&mut self, _final_states: BTreeMap<Label, State>, diags: Diagnostics) -> Diagnostics {
+        diags
+    }
+
+    fn start_command(&self, _: &mut State) -> ExecutionContext {
+        ExecutionContext {
+            diags: Diagnostics::new(),
+        }
+    }
+
+    fn finish_command(&self, context: ExecutionContext, _state: &mut State) -> Diagnostics {
+        let ExecutionContext { diags } = context;
+        diags
+    }
+
+    fn exp_custom(
+        &self,
+        context: &mut ExecutionContext,
+        state: &mut State,
+        e: &Exp,
+    ) -> Option<Vec<Value>> {
+        use UnannotatedExp_ as E;
+
+        match &e.exp.value {
+            E::BorrowLocal(_, var) => {
+                let value = match state.locals.get(var) {
+                    Some(LocalState::Available(_, value)) => *value,
+                    _ => Value::Other,
+                };
+                Some(vec![value])
+            }
+            E::Freeze(inner)
+            | E::Dereference(inner)
+            | E::Borrow(_, inner, _, _)
+            | E::Cast(inner, _)
+            | E::UnaryExp(_, inner) => {
+                let values = self.exp(context, state, inner);
+                Some(vec![join_all(&values)])
+            }
+            E::BinopExp(lhs, sp!(_, op), rhs) => {
+                let lhs_value = join_all(&self.exp(context, state, lhs));
+                let rhs_value = join_all(&self.exp(context, state, rhs));
+                let value = State::join_value(&lhs_value, &rhs_value);
+                let op_msg = match op {
+                    BinOp_::Mod => Some("Modulo"),
+                    BinOp_::BitAnd => Some("Bit mask"),
+                    _ => None,
+                };
+                if let Some(op_msg) = op_msg
+                    && let Value::TxContextDerived(source_loc) = value
+                {
+                    let msg = format!("{op_msg} of a value derived from the transaction context");
+                    let mut d = diag!(WEAK_RANDOMNESS_DIAG, (e.exp.loc, msg));
+                    if source_loc != INVALID_LOC {
+                        d.add_secondary_label((source_loc, "Value derived from here"));
+                    }
+                    d.add_note(
+                        "Values derived from the transaction context are predictable and can be \
+                         influenced by the sender. Use 'sui::random' for randomness instead",
+                    );
+                    context.add_diag(d);
+                }
+                Some(vec![value])
+            }
+            _ => None,
+        }
+    }
+
+    fn call_custom(
+        &self,
+        _context: &mut ExecutionContext,
+        _state: &mut State,
+        loc: &Loc,
+        return_ty: &Type,
+        f: &ModuleCall,
+        args: Vec<Value>,
+    ) -> Option<Vec<Value>> {
+        let value = if TX_CONTEXT_DERIVED_FUNCTIONS
+            .iter()
+            .any(|(addr, module, fun)| f.is(addr, module, fun))
+        {
+            Value::TxContextDerived(*loc)
+        } else {
+            // results of calls on derived values (e.g. hashing a digest) are still derived
+            join_all(&args)
+        };
+        Some(match &return_ty.value {
+            Type_::Unit => vec![],
+            Type_::Single(_) => vec![value],
+            Type_::Multiple(types) => vec![value; types.len()],
+        })
+    }
+}
+
+/// A value is derived from the transaction context if any of `values` is
+fn join_all(values: &[Value]) -> Value {
+    values
+        .iter()
+        .fold(Value::Other, |acc, v| State::join_value(&acc, v))
+}
+
+impl SimpleDomain for State {
+    type Value = Value;
+
+    fn new(context: &CFGContext, mut locals: BTreeMap<Var, LocalState<Value>>) -> Self {
+        for (_mut, v, _) in &context.signature.parameters {
+            let local_state = locals.get_mut(v).unwrap();
+            if let LocalState::Available(loc, _) = local_state {
+                *local_state = LocalState::Available(*loc, Value::Other);
+            }
+        }
+        State { locals }
+    }
+
+    fn locals_mut(&mut self) -> &mut BTreeMap<Var, LocalState<Value>> {
+        &mut self.locals
+    }
+
+    fn locals(&self) -> &BTreeMap<Var, LocalState<Value>> {
+        &self.locals
+    }
+
+    fn join_value(v1: &Value, v2: &Value) -> Value {
+        match (v1, v2) {
+            (Value::TxContextDerived(loc1), Value::TxContextDerived(loc2)) => {
+                if loc1 == loc2 {
+                    *v1
+                } else {
+                    Value::TxContextDerived(INVALID_LOC)
+                }
+            }
+            (derived @ Value::TxContextDerived(_), Value::Other)
+            | (Value::Other, derived @ Value::TxContextDerived(_)) => *derived,
+            (Value::Other, Value::Other) => Value::Other,
+        }
+    }
+
+    fn join_impl(&mut self, _: &Self, _: &mut JoinResult) {}
+}
+
+impl SimpleExecutionContext for ExecutionContext {
+    fn add_diag(&mut self, diag: Diagnostic) {
+        self.diags.add(diag)
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for capability-like objects passed by value into public functions
+// these cases correctly should not trigger the lint
+module a::capability_by_value_true_negatives {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+
+    struct AdminCap has key, store {
+        id: UID,
+    }
+
+    struct Recap has key {
+        id: UID,
+    }
+
+    // taken by reference
+    public fun check(_cap: &AdminCap) {}
+
+    public fun check_mut(_cap: &mut AdminCap) {}
+
+    // handed back to the caller
+    public fun pass_through(cap: AdminCap, amount: u64): (AdminCap, u64) {
+        (cap, amount)
+    }
+
+    // destroyed
+    public fun destroy(cap: AdminCap) {
+        let AdminCap { id } = cap;
+        object::delete(id);
+    }
+
+    // transferred
+    public fun send(cap: AdminCap, recipient: address) {
+        transfer::public_transfer(cap, recipient);
+    }
+
+    // frozen
+    #[allow(lint(freezing_capability))]
+    public fun freeze_cap(cap: AdminCap) {
+        transfer::public_freeze_object(cap);
+    }
+
+    // shared
+    #[allow(lint(share_owned))]
+    public fun share(cap: AdminCap) {
+        transfer::public_share_object(cap);
+    }
+
+    // not capability-like based on its name
+    public fun destroy_recap(recap: Recap) {
+        let Recap { id } = recap;
+        object::delete(id);
+    }
+
+    // not public
+    fun forward(cap: AdminCap) {
+        destroy(cap)
+    }
+
+    #[allow(lint(capability_by_value))]
+    public fun suppressed_forward(cap: AdminCap) {
+        destroy(cap)
+    }
+}
+
+module sui::object {
+    const ZERO: u64 = 0;
+
+    struct UID has store {
+        id: address,
+    }
+
+    public fun delete(_: UID) {
+        abort ZERO
+    }
+}
+
+module sui::transfer {
+    const ZERO: u64 = 0;
+
+    public fun public_transfer<T: key + store>(_: T, _: address) {
+        abort ZERO
+    }
+
+    public fun public_freeze_object<T: key + store>(_: T) {
+        abort ZERO
+    }
+
+    public fun public_share_object<T: key + store>(_: T) {
+        abort ZERO
+    }
+}
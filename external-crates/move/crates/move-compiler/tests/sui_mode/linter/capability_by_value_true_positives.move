@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for capability-like objects passed by value into public functions
+module a::capability_by_value_true_positives {
+    use sui::object::{Self, UID};
+
+    struct AdminCap has key, store {
+        id: UID,
+    }
+
+    struct MintCapability has key {
+        id: UID,
+    }
+
+    struct Holder has key {
+        id: UID,
+        cap: AdminCap,
+    }
+
+    // wrapped, so the caller can no longer use it
+    public fun lock(cap: AdminCap, id: UID): Holder {
+        Holder { id, cap }
+    }
+
+    // only used to show that the caller owns it, but not handed back
+    public fun mint(amount: u64, cap: MintCapability): u64 {
+        burn(cap);
+        amount
+    }
+
+    fun burn(cap: MintCapability) {
+        let MintCapability { id } = cap;
+        object::delete(id);
+    }
+}
+
+module sui::object {
+    const ZERO: u64 = 0;
+
+    struct UID has store {
+        id: address,
+    }
+
+    public fun delete(_: UID) {
+        abort ZERO
+    }
+}
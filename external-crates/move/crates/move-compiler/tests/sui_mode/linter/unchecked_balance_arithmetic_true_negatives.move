@@ -0,0 +1,92 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for unchecked arithmetic on coin and balance values
+// these cases correctly should not trigger the lint
+module a::unchecked_balance_arithmetic_true_negatives {
+    use sui::coin::{Self, Coin};
+    use sui::object::UID;
+
+    const ENotEnough: u64 = 0;
+
+    struct SUI has drop {}
+
+    struct Vault has key {
+        id: UID,
+        remaining: u64,
+    }
+
+    // checked with an assertion first
+    entry fun asserted(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        assert!(value >= amount, ENotEnough);
+        vault.remaining = value - amount;
+    }
+
+    // checked with an early return first
+    entry fun early_return(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        if (amount > value) return;
+        vault.remaining = value - amount;
+    }
+
+    // checked on every path
+    entry fun both_paths(vault: &mut Vault, c: &Coin<SUI>, amount: u64, strict: bool) {
+        let value = coin::value(c);
+        if (strict) assert!(value > amount, ENotEnough)
+        else assert!(value >= amount, ENotEnough);
+        vault.remaining = value - amount;
+    }
+
+    // subtracting a constant after bounding the value
+    entry fun decrement(vault: &mut Vault, c: &Coin<SUI>) {
+        let value = coin::value(c);
+        assert!(value >= 1, ENotEnough);
+        vault.remaining = value - 1;
+    }
+
+    // multiplication after bounding the value
+    entry fun scaled(vault: &mut Vault, c: &Coin<SUI>) {
+        let value = coin::value(c);
+        assert!(value <= 1000, ENotEnough);
+        vault.remaining = value * 1000;
+    }
+
+    // addition is not flagged
+    entry fun deposit(vault: &mut Vault, c: &Coin<SUI>) {
+        let value = coin::value(c);
+        vault.remaining = vault.remaining + value;
+    }
+
+    // not an entry function
+    public fun withdraw(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        vault.remaining = value - amount;
+    }
+
+    #[allow(lint(unchecked_balance_arithmetic))]
+    entry fun suppressed(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        vault.remaining = value - amount;
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+
+module sui::coin {
+    use sui::object::UID;
+
+    const ZERO: u64 = 0;
+
+    struct Coin<phantom T> has key, store {
+        id: UID,
+    }
+
+    public fun value<T>(_: &Coin<T>): u64 {
+        abort ZERO
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for unchecked arithmetic on coin and balance values in entry functions
+module a::unchecked_balance_arithmetic_true_positives {
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+    use sui::object::UID;
+
+    struct SUI has drop {}
+
+    struct Vault has key {
+        id: UID,
+        balance: Balance<SUI>,
+        remaining: u64,
+    }
+
+    entry fun withdraw(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        vault.remaining = value - amount;
+    }
+
+    entry fun fee(vault: &mut Vault, rate: u64) {
+        let value = balance::value(&vault.balance);
+        vault.remaining = value * rate;
+    }
+
+    // compared against a bound that does not cover the subtraction
+    entry fun wrong_bound(vault: &mut Vault, c: &Coin<SUI>, amount: u64) {
+        let value = coin::value(c);
+        assert!(value > 0, 0);
+        vault.remaining = value - amount;
+    }
+
+    // only checked on one path
+    entry fun one_path(vault: &mut Vault, c: &Coin<SUI>, amount: u64, check: bool) {
+        let value = coin::value(c);
+        if (check) assert!(value >= amount, 0);
+        vault.remaining = value - amount;
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+
+module sui::balance {
+    const ZERO: u64 = 0;
+
+    struct Balance<phantom T> has store {
+        value: u64,
+    }
+
+    public fun value<T>(_: &Balance<T>): u64 {
+        abort ZERO
+    }
+}
+
+module sui::coin {
+    use sui::balance::Balance;
+    use sui::object::UID;
+
+    const ZERO: u64 = 0;
+
+    struct Coin<phantom T> has key, store {
+        id: UID,
+        balance: Balance<T>,
+    }
+
+    public fun value<T>(_: &Coin<T>): u64 {
+        abort ZERO
+    }
+}
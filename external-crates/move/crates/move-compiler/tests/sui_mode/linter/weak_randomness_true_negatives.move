@@ -0,0 +1,54 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for randomness derived from the transaction context
+// these cases correctly should not trigger the lint
+module a::weak_randomness_true_negatives {
+    use sui::tx_context::{Self, TxContext};
+
+    // not a modulo
+    public fun to_seconds(ctx: &mut TxContext): u64 {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts / 1000
+    }
+
+    // modulo of a value that is not derived from the transaction context
+    public fun bucket(n: u64, buckets: u64, ctx: &mut TxContext): u64 {
+        let _ts = tx_context::epoch_timestamp_ms(ctx);
+        n % buckets
+    }
+
+    // comparing against a deadline is not a draw
+    public fun expired(deadline: u64, ctx: &mut TxContext): bool {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts > deadline
+    }
+
+    // shifting is not flagged
+    public fun coarse(ctx: &mut TxContext): u64 {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts >> 10
+    }
+
+    // not public
+    fun private_draw(n: u64, ctx: &mut TxContext): u64 {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts % n
+    }
+
+    #[allow(lint(weak_randomness))]
+    public fun suppressed_draw(n: u64, ctx: &mut TxContext): u64 {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts % n
+    }
+}
+
+module sui::tx_context {
+    const ZERO: u64 = 0;
+
+    struct TxContext has drop {}
+
+    public fun epoch_timestamp_ms(_: &TxContext): u64 {
+        abort ZERO
+    }
+}
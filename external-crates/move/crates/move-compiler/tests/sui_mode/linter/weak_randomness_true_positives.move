@@ -0,0 +1,45 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests the lint for randomness derived from the transaction context in public functions
+module a::weak_randomness_true_positives {
+    use sui::address;
+    use sui::tx_context::{Self, TxContext};
+
+    public fun from_timestamp(n: u64, ctx: &mut TxContext): u64 {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        ts % n
+    }
+
+    public fun from_fresh_address(ctx: &mut TxContext): u256 {
+        let addr = tx_context::fresh_object_address(ctx);
+        address::to_u256(addr) % 6
+    }
+
+    public fun coin_flip(ctx: &mut TxContext): bool {
+        let ts = tx_context::epoch_timestamp_ms(ctx);
+        (ts & 1) == 0
+    }
+}
+
+module sui::tx_context {
+    const ZERO: u64 = 0;
+
+    struct TxContext has drop {}
+
+    public fun epoch_timestamp_ms(_: &TxContext): u64 {
+        abort ZERO
+    }
+
+    public fun fresh_object_address(_: &mut TxContext): address {
+        abort ZERO
+    }
+}
+
+module sui::address {
+    const ZERO: u64 = 0;
+
+    public fun to_u256(_: address): u256 {
+        abort ZERO
+    }
+}
@@ -11,11 +11,13 @@ pub mod testing;
 pub mod translate;
 
 use anyhow::anyhow;
+use move_binary_format::file_format::CompiledModule;
 use move_model_2::{
     compiled_model as CM,
     model::{self as M, Model},
     source_kind::SourceKind,
 };
+use move_symbol_pool::Symbol;
 
 use std::{
     collections::BTreeMap,
@@ -53,16 +55,46 @@ pub fn generate_from_files(input_files: &[PathBuf], output: &Path) -> anyhow::Re
         .iter()
         .map(|(path, bytes)| {
             let path = path.display();
-            move_binary_format::file_format::CompiledModule::deserialize_with_defaults(bytes)
+            CompiledModule::deserialize_with_defaults(bytes)
                 .map_err(|e| anyhow!(format!("Failed to deserialize module at {path}: {e}")))
         })
         .collect::<Result<Vec<_>, _>>()?;
+    generate_from_model(model_from_compiled(modules), output)
+}
+
+/// Decompile a list of compiled modules in memory, without writing anything to disk.
+/// # Arguments
+/// * `modules` - The compiled modules to decompile, e.g. the modules of an on-chain package.
+///   Their dependencies do not need to be provided.
+/// # Returns
+/// * `anyhow::Result<BTreeMap<Symbol, String>>` - The decompiled source of each module, keyed by
+///   module name
+pub fn decompile_modules(modules: Vec<CompiledModule>) -> anyhow::Result<BTreeMap<Symbol, String>> {
+    let decompiled = crate::translate::model(model_from_compiled(modules))?;
+
+    let crate::ast::Decompiled { model, packages } = decompiled;
+
+    let mut sources = BTreeMap::new();
+    for pkg in packages {
+        let name = package_name(&pkg);
+        let Some(model_pkg) = model.maybe_package(&pkg.address) else {
+            anyhow::bail!("Package with address {} not found in model", pkg.address);
+        };
+        for (module_name, module) in &pkg.modules {
+            let source = render_module(&model, model_pkg, &name, module)?;
+            sources.insert(*module_name, source);
+        }
+    }
+
+    Ok(sources)
+}
+
+fn model_from_compiled(modules: Vec<CompiledModule>) -> CM::Model {
     let model_config = M::ModelConfig {
         // During decompilation, we do not need to resolve all dependencies.
         allow_missing_dependencies: true,
     };
-    let model = CM::Model::from_compiled_with_config(model_config, &BTreeMap::new(), modules);
-    generate_from_model(model, output)
+    CM::Model::from_compiled_with_config(model_config, &BTreeMap::new(), modules)
 }
 
 /// Generate Move source code from a model and write the output to the specified directory. The
@@ -94,10 +126,7 @@ pub fn generate_from_model<S: SourceKind>(
 
     println!("Modules\n----------------------------------");
     for pkg in packages {
-        let name = pkg
-            .name
-            .map(|name| name.as_str().to_owned())
-            .unwrap_or_else(|| format!("{}", pkg.address));
+        let name = package_name(&pkg);
 
         // Ensure the package directory exists and is empty: output/pkg_name
         let pkg_dir = output.join(&name);
@@ -125,15 +154,29 @@ fn generate_module<S: SourceKind>(
     pkg_name: &str,
     module: &crate::ast::Module,
 ) -> anyhow::Result<PathBuf> {
+    let output = render_module(model, pkg, pkg_name, module)?;
+    println!("- {}", path.display());
+    let _ = std::fs::remove_file(path); // ignore error if file does not exist
+    std::fs::write(path, output)?;
+    Ok(path.into())
+}
+
+fn render_module<S: SourceKind>(
+    model: &Model<S>,
+    pkg: M::Package<'_, S>,
+    pkg_name: &str,
+    module: &crate::ast::Module,
+) -> anyhow::Result<String> {
     let Some(model_mod) = pkg.maybe_module(module.name) else {
         anyhow::bail!("Module {} not found in package {}", module.name, pkg_name);
     };
 
     let doc = pretty_printer::module(model, pkg_name, model_mod, module)?;
+    Ok(doc.render(100))
+}
 
-    let output = doc.render(100);
-    println!("- {}", path.display());
-    let _ = std::fs::remove_file(path); // ignore error if file does not exist
-    std::fs::write(path, output)?;
-    Ok(path.into())
+fn package_name(pkg: &crate::ast::Package) -> String {
+    pkg.name
+        .map(|name| name.as_str().to_owned())
+        .unwrap_or_else(|| format!("{}", pkg.address))
 }
@@ -81,6 +81,14 @@ pub struct BuildConfig {
     #[clap(long = move_compiler::command_line::JSON_ERRORS, global = true)]
     pub json_errors: bool,
 
+    /// If set, reports errors and warnings as SARIF, e.g. for code scanning dashboards
+    #[clap(
+        long = move_compiler::command_line::SARIF_ERRORS,
+        global = true,
+        conflicts_with = "json_errors"
+    )]
+    pub sarif_errors: bool,
+
     /// Additional named address mapping. Useful for tools in rust
     #[clap(skip)]
     pub additional_named_addresses: BTreeMap<String, AccountAddress>,
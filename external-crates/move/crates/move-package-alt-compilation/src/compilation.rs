@@ -6,6 +6,7 @@ use crate::{
     build_plan::BuildPlan,
     compiled_package::{CompiledPackage, CompiledPackageInfo, CompiledUnitWithSource},
     documentation::build_docs,
+    lint_config::LintFilters,
     shared,
     source_discovery::get_sources,
 };
@@ -69,6 +70,7 @@ pub fn compiler_flags(build_config: &BuildConfig) -> Flags {
     flags
         .set_warnings_are_errors(build_config.warnings_are_errors)
         .set_json_errors(build_config.json_errors)
+        .set_sarif_errors(build_config.sarif_errors)
         .set_silence_warnings(build_config.silence_warnings)
         .set_modes(build_config.modes.clone())
 }
@@ -380,14 +382,24 @@ pub fn make_deps_for_compiler<W: Write + Send, F: MoveFlavor>(
         let addresses = build_config.addresses_for_config(pkg.named_addresses()?);
 
         // TODO: better default handling for edition and flavor
+        let flavor = Flavor::from_str(pkg.flavor().unwrap_or("sui"))?;
+        // warnings in dependencies are never reported, so only the root package's lint levels
+        // are relevant
+        let (warning_filter, module_warning_filters) = if pkg.is_root() {
+            let LintFilters { package, modules } = LintFilters::new(pkg.lints(), flavor)?;
+            (package, modules)
+        } else {
+            (WarningFiltersBuilder::new_for_source(), BTreeMap::new())
+        };
         let config = PackageConfig {
             is_dependency: !pkg.is_root(),
             edition: pkg
                 .edition()
                 .or(build_config.default_edition)
                 .unwrap_or(Edition::LEGACY), // TODO require edition
-            flavor: Flavor::from_str(pkg.flavor().unwrap_or("sui"))?,
-            warning_filter: WarningFiltersBuilder::new_for_source(),
+            flavor,
+            warning_filter,
+            module_warning_filters,
         };
 
         // Assign a unique name for the compiler for each package.
@@ -59,6 +59,12 @@ pub mod documentation;
 /// artifacts.
 pub mod layout;
 
+/// Package-level lint configuration.
+///
+/// This module translates the `[lints]` table of a package manifest into the warning filters
+/// used by the compiler, both package-wide and for individual modules.
+pub mod lint_config;
+
 /// Linting configuration and flag management.
 ///
 /// This module provides types and utilities for configuring the Move linter,
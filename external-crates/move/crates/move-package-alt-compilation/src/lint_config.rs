@@ -0,0 +1,136 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use anyhow::bail;
+use move_compiler::{
+    diagnostics::warning_filters::{FILTER_ALL, WarningFilter, WarningFiltersBuilder},
+    editions::Flavor,
+    linters::{self, LINT_WARNING_PREFIX},
+    sui_mode,
+};
+use move_package_alt::schema::{LintSeverity, LintsTable};
+use move_symbol_pool::Symbol;
+
+/// The warning filters for a package, derived from the `[lints]` table of its manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFilters {
+    /// Filters applied to every module of the package without a more specific entry
+    pub package: WarningFiltersBuilder,
+    /// Filters for modules with their own `[lints.modules.<name>]` table. These already include
+    /// the package-wide levels that the module does not override.
+    pub modules: BTreeMap<Symbol, WarningFiltersBuilder>,
+}
+
+impl LintFilters {
+    /// Translate `lints` into warning filters. Lints set to `allow` are filtered like an
+    /// `#[allow(lint(..))]` attribute on the module would; lints set to `deny` are reported as
+    /// errors unless filtered by such an attribute in source.
+    pub fn new(lints: &LintsTable, flavor: Flavor) -> anyhow::Result<Self> {
+        let known = known_lints(flavor);
+        let package = filters_for_levels(&known, &lints.package)?;
+        let modules = lints
+            .modules
+            .iter()
+            .map(|(module, levels)| {
+                let mut merged = lints.package.clone();
+                merged.extend(levels.iter().map(|(lint, level)| (lint.clone(), *level)));
+                let filters = filters_for_levels(&known, &merged)?;
+                Ok((Symbol::from(module.as_str()), filters))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { package, modules })
+    }
+}
+
+/// All lints that can be configured for the given flavor, keyed by their filter name
+fn known_lints(flavor: Flavor) -> BTreeMap<&'static str, WarningFilter> {
+    let mut filters = linters::known_filters().1;
+    if flavor == Flavor::Sui {
+        filters.extend(sui_mode::linters::known_filters().1);
+    }
+    filters.push(WarningFilter::All(Some(LINT_WARNING_PREFIX)));
+    filters
+        .into_iter()
+        .filter_map(|filter| Some((filter.to_str()?, filter)))
+        .collect()
+}
+
+fn filters_for_levels(
+    known: &BTreeMap<&'static str, WarningFilter>,
+    levels: &BTreeMap<String, LintSeverity>,
+) -> anyhow::Result<WarningFiltersBuilder> {
+    let mut builder = WarningFiltersBuilder::new_for_source();
+    for (lint, level) in levels {
+        let Some(filter) = known.get(lint.as_str()) else {
+            bail!(
+                "Unknown lint '{lint}' in the [lints] table. Known lints: {}",
+                known
+                    .keys()
+                    .filter(|name| **name != FILTER_ALL)
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        };
+        match level {
+            LintSeverity::Allow => builder.add(*filter),
+            LintSeverity::Warn => (),
+            LintSeverity::Deny => builder.deny(*filter),
+        }
+    }
+    Ok(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lints(
+        package: &[(&str, LintSeverity)],
+        modules: &[(&str, &str, LintSeverity)],
+    ) -> LintsTable {
+        let mut table = LintsTable::default();
+        for (lint, level) in package {
+            table.package.insert(lint.to_string(), *level);
+        }
+        for (module, lint, level) in modules {
+            table
+                .modules
+                .entry(module.to_string())
+                .or_default()
+                .insert(lint.to_string(), *level);
+        }
+        table
+    }
+
+    #[test]
+    fn empty_table_has_no_filters() {
+        let filters = LintFilters::new(&LintsTable::default(), Flavor::Sui).unwrap();
+        assert_eq!(filters.package, WarningFiltersBuilder::new_for_source());
+        assert!(filters.modules.is_empty());
+    }
+
+    #[test]
+    fn module_levels_override_package_levels() {
+        let table = lints(
+            &[("share_owned", LintSeverity::Deny)],
+            &[("m", "share_owned", LintSeverity::Warn)],
+        );
+        let filters = LintFilters::new(&table, Flavor::Sui).unwrap();
+        assert_ne!(filters.package, WarningFiltersBuilder::new_for_source());
+        assert_eq!(
+            filters.modules[&Symbol::from("m")],
+            WarningFiltersBuilder::new_for_source()
+        );
+    }
+
+    #[test]
+    fn sui_lints_require_sui_flavor() {
+        let table = lints(&[("share_owned", LintSeverity::Allow)], &[]);
+        assert!(LintFilters::new(&table, Flavor::Sui).is_ok());
+        let err = LintFilters::new(&table, Flavor::Core).unwrap_err();
+        assert!(err.to_string().contains("Unknown lint 'share_owned'"));
+    }
+}
@@ -12,9 +12,9 @@ use crate::{
     logging::user_note,
     package::paths::PackagePath,
     schema::{
-        DefaultDependency, Environment, ExternalDependency, LocalDepInfo, ManifestDependencyInfo,
-        ManifestGitDependency, ModeName, OnChainDepInfo, PackageMetadata, PackageName,
-        ParsedManifest, PublishAddresses, SystemDepName,
+        DefaultDependency, Environment, ExternalDependency, LintsTable, LocalDepInfo,
+        ManifestDependencyInfo, ManifestGitDependency, ModeName, OnChainDepInfo, PackageMetadata,
+        PackageName, ParsedManifest, PublishAddresses, SystemDepName,
     },
 };
 use anyhow::{Context, Result, anyhow, bail, format_err};
@@ -44,6 +44,7 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const LINTS_NAME: &str = "lints";
 
 const EXTERNAL_RESOLVER_PREFIX: &str = "r";
 
@@ -54,6 +55,7 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    LINTS_NAME,
     EXTERNAL_RESOLVER_PREFIX,
 ];
 
@@ -164,6 +166,13 @@ fn parse_source_manifest<F: MoveFlavor>(
 
             dependencies.extend(dev_dependencies);
 
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_default();
+
             let modern_name = derive_modern_name(&addresses, path)?
                 .unwrap_or(PackageName::new(NO_NAME_LEGACY_PACKAGE_NAME).expect("Cannot fail"));
             let new_name = temporary_spanned(modern_name.clone());
@@ -237,6 +246,7 @@ fn parse_source_manifest<F: MoveFlavor>(
                     legacy_publications,
                 }),
                 dep_replacements: BTreeMap::new(),
+                lints,
             })
         }
         x => {
@@ -420,6 +430,17 @@ fn parse_dependencies(
     }
 }
 
+fn parse_lints(tval: TV) -> Result<LintsTable> {
+    match tval {
+        TV::Table(_) => Ok(tval.try_into()?),
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 fn parse_build_info(tval: TV) -> Result<LegacyBuildInfo> {
     match tval {
         TV::Table(mut table) => {
@@ -831,7 +852,7 @@ fn to_modern_system_dep_name(name: &str) -> SystemDepName {
 
 #[cfg(test)]
 mod tests {
-    use crate::schema::{OriginalID, PublishedID};
+    use crate::schema::{LintSeverity, OriginalID, PublishedID};
 
     use super::*;
 
@@ -897,6 +918,41 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_legacy_lints() {
+        let lints = parse_lints(
+            parse_move_manifest_string(
+                r#"
+                share_owned = "allow"
+                self_transfer = "deny"
+
+                [modules.my_module]
+                self_transfer = "warn"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            lints.package,
+            BTreeMap::from([
+                ("share_owned".to_string(), LintSeverity::Allow),
+                ("self_transfer".to_string(), LintSeverity::Deny),
+            ])
+        );
+        assert_eq!(
+            lints.modules["my_module"],
+            BTreeMap::from([("self_transfer".to_string(), LintSeverity::Warn)])
+        );
+    }
+
+    #[test]
+    fn parse_legacy_lints_bad_level() {
+        let lints = parse_move_manifest_string(r#"share_owned = "forbid""#).unwrap();
+        assert!(parse_lints(lints).is_err());
+    }
+
     #[test]
     fn normalize_legacy_names() {
         let names = vec![
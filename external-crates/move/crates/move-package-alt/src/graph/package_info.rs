@@ -8,7 +8,7 @@ use crate::{
     errors::{PackageError, PackageResult},
     flavor::MoveFlavor,
     package::{Package, paths::PackagePath},
-    schema::{OriginalID, PackageID, PackageName, PublishAddresses},
+    schema::{LintsTable, OriginalID, PackageID, PackageName, PublishAddresses},
 };
 
 use super::PackageGraph;
@@ -100,6 +100,11 @@ impl<'graph, F: MoveFlavor> PackageInfo<'graph, F> {
         self.package().metadata().edition
     }
 
+    /// The lint levels configured in the package's manifest
+    pub fn lints(&self) -> &LintsTable {
+        self.package().lints()
+    }
+
     /// The flavor for the package
     pub fn flavor(&self) -> Option<&str> {
         // TODO: pull this from manifest
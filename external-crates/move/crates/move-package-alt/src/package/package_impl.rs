@@ -17,7 +17,8 @@ use crate::{
     dependency::Pinned,
     package::{manifest::ManifestError, package_loader::PackageLoader},
     schema::{
-        CachedPackageInfo, DefaultDependency, ManifestDependencyInfo, ParsedManifest, Publication,
+        CachedPackageInfo, DefaultDependency, LintsTable, ManifestDependencyInfo, ParsedManifest,
+        Publication,
     },
 };
 use crate::{dependency::FetchedDependency, schema::ReplacementDependency};
@@ -45,6 +46,9 @@ pub struct Package<F: MoveFlavor> {
     /// The metadata of the package.
     metadata: PackageMetadata,
 
+    /// The lint levels configured in the package's manifest.
+    lints: LintsTable,
+
     /// A [`PackagePath`] representing the canonical path to the package directory.
     path: PackagePath,
 
@@ -128,6 +132,7 @@ impl<F: MoveFlavor> Package<F> {
             env: env.name().clone(),
             digest,
             metadata: manifest.package,
+            lints: manifest.lints,
             path,
             publication,
             dep_for_self: dep,
@@ -219,6 +224,10 @@ impl<F: MoveFlavor> Package<F> {
         &self.metadata
     }
 
+    pub fn lints(&self) -> &LintsTable {
+        &self.lints
+    }
+
     /// Read the publication for the given environment from the package pubfile.
     fn load_publication(
         path: &PackagePath,
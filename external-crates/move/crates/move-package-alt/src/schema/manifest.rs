@@ -41,6 +41,10 @@ pub struct ParsedManifest {
     pub dep_replacements:
         BTreeMap<EnvironmentName, BTreeMap<PackageName, Spanned<ReplacementDependency>>>,
 
+    /// Package-wide and per-module lint levels
+    #[serde(default)]
+    pub lints: LintsTable,
+
     /// Additional information that we may need when we handle legacy packages. This data is only
     /// populated by the legacy parser
     #[serde(skip)]
@@ -67,6 +71,41 @@ fn return_true() -> bool {
     true
 }
 
+/// The `[lints]` section of a manifest. Lints are named as in `#[allow(lint(<name>))]`; the
+/// special name `all` applies to every lint.
+///
+/// ```toml
+/// [lints]
+/// share_owned = "allow"
+/// self_transfer = "deny"
+///
+/// [lints.modules.my_module]
+/// self_transfer = "warn"
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct LintsTable {
+    /// Overrides of the package-wide levels for specific modules, keyed by module name
+    #[serde(default)]
+    pub modules: BTreeMap<String, BTreeMap<String, LintSeverity>>,
+
+    /// Package-wide lint levels, keyed by lint name
+    #[serde(flatten)]
+    pub package: BTreeMap<String, LintSeverity>,
+}
+
+/// The level at which a lint is reported
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintSeverity {
+    /// Do not report the lint
+    Allow,
+    /// Report the lint as a warning (the default)
+    Warn,
+    /// Report the lint as an error, failing the build
+    Deny,
+}
+
 /// An entry in the `[dependencies]` section of a manifest
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
@@ -252,11 +291,11 @@ mod tests {
     use insta::assert_snapshot;
 
     use super::{
-        DefaultDependency, ExternalDependency, ManifestDependencyInfo, ManifestGitDependency,
-        ParsedManifest, ReplacementDependency,
+        DefaultDependency, ExternalDependency, LintSeverity, ManifestDependencyInfo,
+        ManifestGitDependency, ParsedManifest, ReplacementDependency,
     };
     use move_compiler::editions::Edition;
-    use std::str::FromStr;
+    use std::{collections::BTreeMap, str::FromStr};
 
     impl ParsedManifest {
         /// (unsafe) convenience method for pulling out a dependency having given `name`
@@ -1006,7 +1045,60 @@ mod tests {
           |
         6 |             [addresses]
           |              ^^^^^^^^^
-        unknown field `addresses`, expected one of `package`, `environments`, `dependencies`, `dep-replacements`
+        unknown field `addresses`, expected one of `package`, `environments`, `dependencies`, `dep-replacements`, `lints`
         "###);
     }
+
+    // Lints //////////////////////////////////////////////////////////////////////////////
+
+    /// Package-wide and per-module lint levels are parsed
+    #[test]
+    fn parse_lints() {
+        let manifest: ParsedManifest = toml_edit::de::from_str(
+            r#"
+            [package]
+            name = "test"
+            edition = "2024"
+
+            [lints]
+            share_owned = "allow"
+            self_transfer = "deny"
+
+            [lints.modules.my_module]
+            self_transfer = "warn"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.lints.package,
+            BTreeMap::from([
+                ("share_owned".to_string(), LintSeverity::Allow),
+                ("self_transfer".to_string(), LintSeverity::Deny),
+            ])
+        );
+        assert_eq!(
+            manifest.lints.modules["my_module"],
+            BTreeMap::from([("self_transfer".to_string(), LintSeverity::Warn)])
+        );
+    }
+
+    /// Lint levels other than `allow`, `warn`, and `deny` are rejected
+    #[test]
+    fn parse_bad_lint_level() {
+        let error = toml_edit::de::from_str::<ParsedManifest>(
+            r#"
+            [package]
+            name = "test"
+            edition = "2024"
+
+            [lints]
+            share_owned = "forbid"
+            "#,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("unknown variant `forbid`"), "{error}");
+    }
 }
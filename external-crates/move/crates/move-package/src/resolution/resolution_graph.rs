@@ -660,6 +660,7 @@ impl Package {
                 .or(config.default_edition)
                 .unwrap_or(Edition::LEGACY), // TODO require edition
             warning_filter: WarningFiltersBuilder::new_for_source(),
+            module_warning_filters: BTreeMap::new(),
         }
     }
 }
@@ -86,6 +86,7 @@ pub fn compile_packages_in_file(filename: &str, dependencies: &[&str]) -> Vec<St
     .set_default_config(PackageConfig {
         is_dependency: false,
         warning_filter: WarningFiltersBuilder::unused_warnings_filter_for_test(),
+        module_warning_filters: BTreeMap::new(),
         flavor: Flavor::Sui,
         edition: Edition::E2024_ALPHA,
     })